@@ -0,0 +1,72 @@
+use clob_rs::{ClobClient, MarketsResponse};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// about the size of a full `/markets` page
+const MARKETS: usize = 1000;
+
+fn page() -> Vec<u8> {
+    let markets: Vec<_> = (0..MARKETS)
+        .map(|i| {
+            serde_json::json!({
+                "condition_id": format!("0x{:064x}", i),
+                "question_id": format!("0x{:064x}", i + 1),
+                "question": "Will the benchmark finish before the heat death of the universe?",
+                "description": "Resolves YES if it does. ".repeat(20),
+                "market_slug": format!("benchmark-market-{}", i),
+                "tokens": [
+                    {"token_id": format!("{}1", i), "outcome": "Yes", "price": 0.5},
+                    {"token_id": format!("{}2", i), "outcome": "No", "price": 0.5}
+                ],
+                "tags": ["Benchmarks", "Sports"],
+                "active": true,
+                "closed": false,
+                "accepting_orders": true,
+                "minimum_order_size": 5,
+                "minimum_tick_size": 0.01
+            })
+        })
+        .collect();
+    serde_json::to_vec(&serde_json::json!({
+        "limit": MARKETS,
+        "count": MARKETS,
+        "next_cursor": "LTE=",
+        "data": markets
+    }))
+    .unwrap()
+}
+
+fn market_pages(c: &mut Criterion) {
+    let body = page();
+
+    // what the transport did before reading bodies as bytes
+    c.bench_function("decode_markets_page_via_text", |b| {
+        b.iter(|| {
+            let text = String::from_utf8_lossy(black_box(&body)).into_owned();
+            serde_json::from_str::<MarketsResponse>(&text).unwrap()
+        })
+    });
+    c.bench_function("decode_markets_page_from_slice", |b| {
+        b.iter(|| serde_json::from_slice::<MarketsResponse>(black_box(&body)).unwrap())
+    });
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/markets"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body.clone(), "application/json"))
+            .mount(&server)
+            .await;
+        server
+    });
+    let client = ClobClient::new(server.uri());
+    c.bench_function("get_markets_page", |b| {
+        b.to_async(&runtime)
+            .iter(|| async { client.markets().get_markets_page(None).await.unwrap() })
+    });
+}
+
+criterion_group!(benches, market_pages);
+criterion_main!(benches);
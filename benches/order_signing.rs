@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use clob_rs::{
+    CreateOrderOptions, FixedSalt, OrderArgs, OrderBuilder, Side, SignedOrder, Signer, TickSize,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const TOKEN_ID: &str =
+    "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+
+fn order_signing(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let builder = OrderBuilder::new(Signer::new(TEST_PRIVATE_KEY, 137).unwrap())
+        .with_salt_provider(Arc::new(FixedSalt(1)));
+    let args = OrderArgs::new(TOKEN_ID, 0.52, 100.0, Side::Buy);
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+
+    c.bench_function("create_order", |b| {
+        b.to_async(&runtime).iter(|| async {
+            builder
+                .create_order(black_box(&args), &options)
+                .await
+                .unwrap()
+        })
+    });
+
+    let mut reused = SignedOrder::default();
+    c.bench_function("create_order_into", |b| {
+        b.iter(|| {
+            builder
+                .create_order_into(black_box(&args), &options, &mut reused)
+                .unwrap()
+        })
+    });
+
+    let ladder: Vec<OrderArgs> = (0..50)
+        .map(|i| OrderArgs::new(TOKEN_ID, 0.40 + i as f64 * 0.002, 100.0, Side::Buy))
+        .collect();
+    c.bench_function("create_orders_50", |b| {
+        b.iter(|| builder.create_orders(black_box(&ladder), &options).unwrap())
+    });
+
+    let order = runtime
+        .block_on(builder.create_order(&args, &options))
+        .unwrap();
+    c.bench_function("signed_order_hash", |b| {
+        b.iter(|| black_box(&order).hash(137, false).unwrap())
+    });
+}
+
+criterion_group!(benches, order_signing);
+criterion_main!(benches);
@@ -0,0 +1,134 @@
+#!/usr/bin/env python3
+"""Writes order signing vectors from py-clob-client for tests/vectors_tests.rs.
+
+    pip install py-clob-client
+    python scripts/generate_vectors.py -n 2000 > tests/vectors/py_clob_client.json
+
+The format is documented in src/vectors.rs. Salts are fixed by swapping the
+py-order-utils builder for one with a constant salt generator.
+"""
+
+import argparse
+import json
+import random
+from importlib.metadata import version
+
+import py_clob_client.order_builder.builder as builder_module
+from py_clob_client.clob_types import CreateOrderOptions, MarketOrderArgs, OrderArgs
+from py_clob_client.config import get_contract_config
+from py_clob_client.order_builder.builder import OrderBuilder
+from py_clob_client.signer import Signer
+
+PRIVATE_KEY = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+CHAIN_ID = 137
+TICK_SIZES = ["0.1", "0.01", "0.001", "0.0001"]
+
+UtilsOrderBuilder = builder_module.UtilsOrderBuilder
+
+
+class FixedSaltBuilder(UtilsOrderBuilder):
+    salt = 0
+
+    def __init__(self, *args, **kwargs):
+        kwargs["salt_generator"] = lambda: FixedSaltBuilder.salt
+        super().__init__(*args, **kwargs)
+
+
+builder_module.UtilsOrderBuilder = FixedSaltBuilder
+
+
+def hex_string(value):
+    if isinstance(value, str):
+        return value if value.startswith("0x") else "0x" + value
+    return "0x" + bytes(value).hex()
+
+
+def random_vector(rng, index):
+    tick_size = rng.choice(TICK_SIZES)
+    ticks = round(1 / float(tick_size))
+    price = rng.randrange(1, ticks) / ticks
+    size = rng.choice(
+        [
+            rng.randrange(1, 100_000_000) / 100,
+            rng.randrange(1, 1_000_000_000) / 10_000,
+            float(rng.randrange(1, 10_000)),
+        ]
+    )
+    return {
+        "name": "vector {}".format(index),
+        "private_key": PRIVATE_KEY,
+        "chain_id": CHAIN_ID,
+        "signature_type": rng.choice([0, 1, 2]),
+        "tick_size": tick_size,
+        "neg_risk": rng.random() < 0.5,
+        "token_id": str(rng.getrandbits(256)),
+        "side": rng.choice(["BUY", "SELL"]),
+        "price": price,
+        "size": size,
+        "market": rng.random() < 0.25,
+        "salt": rng.getrandbits(48),
+        "nonce": rng.choice([0, rng.getrandbits(32)]),
+        "expiration": rng.choice([0, 1_700_000_000 + rng.getrandbits(24)]),
+        "fee_rate_bps": rng.choice([0, 0, 100, 1000]),
+    }
+
+
+def sign(vector):
+    signer = Signer(vector["private_key"], vector["chain_id"])
+    if vector["signature_type"] != 0:
+        # proxy and safe wallets sign for a separate funder
+        funder = random.Random(vector["salt"]).getrandbits(160)
+        vector["funder"] = "0x" + funder.to_bytes(20, "big").hex()
+    builder = OrderBuilder(signer, vector["signature_type"], vector.get("funder"))
+    options = CreateOrderOptions(tick_size=vector["tick_size"], neg_risk=vector["neg_risk"])
+    FixedSaltBuilder.salt = vector["salt"]
+    if vector["market"]:
+        vector["expiration"] = 0
+        args = MarketOrderArgs(
+            token_id=vector["token_id"],
+            amount=vector["size"],
+            side=vector["side"],
+            price=vector["price"],
+            fee_rate_bps=vector["fee_rate_bps"],
+            nonce=vector["nonce"],
+        )
+        signed = builder.create_market_order(args, options)
+    else:
+        args = OrderArgs(
+            token_id=vector["token_id"],
+            price=vector["price"],
+            size=vector["size"],
+            side=vector["side"],
+            fee_rate_bps=vector["fee_rate_bps"],
+            nonce=vector["nonce"],
+            expiration=vector["expiration"],
+        )
+        signed = builder.create_order(args, options)
+
+    exchange = get_contract_config(vector["chain_id"], vector["neg_risk"]).exchange
+    utils_builder = FixedSaltBuilder(exchange, vector["chain_id"], signer)
+    order_hash = utils_builder.build_order_hash(signed.order)
+    order = signed.dict()
+    vector["expected"] = {
+        "maker_amount": str(order["makerAmount"]),
+        "taker_amount": str(order["takerAmount"]),
+        "hash": hex_string(order_hash),
+        "signature": hex_string(order["signature"]),
+    }
+    return vector
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("-n", "--count", type=int, default=2000)
+    parser.add_argument("--seed", type=int, default=1)
+    cli = parser.parse_args()
+
+    rng = random.Random(cli.seed)
+    vectors = [sign(random_vector(rng, i)) for i in range(cli.count)]
+    source = "py-clob-client {}".format(version("py-clob-client"))
+    print(json.dumps({"source": source, "vectors": vectors}, indent=1))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,129 @@
+#!/usr/bin/env python3
+"""Writes amounts-only order vectors from a transcription of py-clob-client's
+order amount rounding, for tests/vectors_tests.rs.
+
+    python scripts/transcribed_amount_vectors.py -n 2000 > tests/vectors/py_clob_client_amounts.json
+
+Needs nothing outside the standard library, so it runs where
+scripts/generate_vectors.py can't install py-clob-client. It only covers
+maker and taker amounts; hashes and signatures still need the real client.
+The functions below follow py_clob_client.order_builder.helpers and
+OrderBuilder.get_order_amounts / get_market_order_amounts.
+"""
+
+import argparse
+import json
+import random
+from decimal import Decimal
+from math import ceil, floor
+
+PRIVATE_KEY = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+CHAIN_ID = 137
+
+# tick size: (price, size, amount) decimal places
+ROUNDING_CONFIG = {
+    "0.1": (1, 2, 3),
+    "0.01": (2, 2, 4),
+    "0.001": (3, 2, 5),
+    "0.0001": (4, 2, 6),
+}
+
+
+def round_down(x, sig_digits):
+    return floor(x * (10**sig_digits)) / (10**sig_digits)
+
+
+def round_normal(x, sig_digits):
+    return round(x * (10**sig_digits)) / (10**sig_digits)
+
+
+def round_up(x, sig_digits):
+    return ceil(x * (10**sig_digits)) / (10**sig_digits)
+
+
+def decimal_places(x):
+    return abs(Decimal(x.__str__()).as_tuple().exponent)
+
+
+def to_token_decimals(x):
+    f = (10**6) * x
+    if decimal_places(f) > 0:
+        f = round_normal(f, 0)
+    return int(f)
+
+
+def fit(raw, amount_digits):
+    if decimal_places(raw) > amount_digits:
+        raw = round_up(raw, amount_digits + 4)
+        if decimal_places(raw) > amount_digits:
+            raw = round_down(raw, amount_digits)
+    return raw
+
+
+def order_amounts(side, size, price, config):
+    price_digits, size_digits, amount_digits = config
+    raw_price = round_normal(price, price_digits)
+    if side == "BUY":
+        raw_taker = round_down(size, size_digits)
+        raw_maker = fit(raw_taker * raw_price, amount_digits)
+    else:
+        raw_maker = round_down(size, size_digits)
+        raw_taker = fit(raw_maker * raw_price, amount_digits)
+    return to_token_decimals(raw_maker), to_token_decimals(raw_taker)
+
+
+def market_order_amounts(side, amount, price, config):
+    price_digits, size_digits, amount_digits = config
+    raw_price = round_normal(price, price_digits)
+    raw_maker = round_down(amount, size_digits)
+    if side == "BUY":
+        raw_taker = fit(raw_maker / raw_price, amount_digits)
+    else:
+        raw_taker = fit(raw_maker * raw_price, amount_digits)
+    return to_token_decimals(raw_maker), to_token_decimals(raw_taker)
+
+
+def random_vector(rng, index):
+    tick_size = rng.choice(list(ROUNDING_CONFIG))
+    ticks = round(1 / float(tick_size))
+    price = rng.randrange(1, ticks) / ticks
+    size = rng.choice(
+        [
+            rng.randrange(1, 100_000_000) / 100,
+            rng.randrange(1, 1_000_000_000) / 10_000,
+            float(rng.randrange(1, 10_000)),
+        ]
+    )
+    side = rng.choice(["BUY", "SELL"])
+    market = rng.random() < 0.25
+    amounts = market_order_amounts if market else order_amounts
+    maker, taker = amounts(side, size, price, ROUNDING_CONFIG[tick_size])
+    return {
+        "name": "amounts {}".format(index),
+        "private_key": PRIVATE_KEY,
+        "chain_id": CHAIN_ID,
+        "tick_size": tick_size,
+        "token_id": str(rng.getrandbits(256)),
+        "side": side,
+        "price": price,
+        "size": size,
+        "market": market,
+        "salt": rng.getrandbits(48),
+        "expected": {"maker_amount": str(maker), "taker_amount": str(taker)},
+    }
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("-n", "--count", type=int, default=2000)
+    parser.add_argument("--seed", type=int, default=1)
+    cli = parser.parse_args()
+
+    rng = random.Random(cli.seed)
+    vectors = [random_vector(rng, i) for i in range(cli.count)]
+    source = "py-clob-client amount rounding, transcribed (amounts only)"
+    print(json.dumps({"source": source, "vectors": vectors}, indent=1))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,71 @@
+use crate::api::OrdersApi;
+use crate::config::get_contract_config;
+use crate::error::{ClobError, Result};
+use crate::types::{AssetType, BalanceAllowanceParams};
+
+/// An exchange contract that may not move one of the account's assets yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingApproval {
+    pub asset_type: AssetType,
+    /// Address of the exchange contract.
+    pub spender: String,
+    /// Whether `spender` is the neg-risk exchange.
+    pub neg_risk: bool,
+}
+
+/// Result of [`OrdersApi::ensure_allowances`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AllowanceReport {
+    pub missing: Vec<MissingApproval>,
+}
+
+impl AllowanceReport {
+    /// Both exchanges can trade both assets.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Whether orders on markets of one kind are covered, since a market
+    /// only settles through its own exchange.
+    pub fn is_complete_for(&self, neg_risk: bool) -> bool {
+        self.missing.iter().all(|m| m.neg_risk != neg_risk)
+    }
+}
+
+impl OrdersApi {
+    /// Checks that the standard and the neg-risk exchange (see
+    /// [`ContractConfig`](crate::ContractConfig)) are both approved for the
+    /// account's collateral and for `token_id`. The two need separate
+    /// approvals, which `get_balance_allowance` alone doesn't make obvious.
+    /// Approvals are on-chain transactions; with the `onchain` feature,
+    /// `OnchainClient::approve_missing` sends them.
+    pub async fn ensure_allowances(&self, token_id: &str) -> Result<AllowanceReport> {
+        let chain_id = self.order_builder().signer().chain_id();
+        let mut exchanges = Vec::with_capacity(2);
+        for neg_risk in [false, true] {
+            let config = get_contract_config(chain_id, neg_risk)
+                .ok_or_else(|| ClobError::InvalidParameter("invalid chain_id".to_string()))?;
+            exchanges.push((config.exchange, neg_risk));
+        }
+
+        let mut report = AllowanceReport::default();
+        for asset_type in [AssetType::COLLATERAL, AssetType::CONDITIONAL] {
+            let params = BalanceAllowanceParams {
+                asset_type: Some(asset_type),
+                token_id: (asset_type == AssetType::CONDITIONAL).then(|| token_id.to_string()),
+                signature_type: None,
+            };
+            let response = self.get_balance_allowance(&params).await?;
+            for (exchange, neg_risk) in &exchanges {
+                if response.allowance_raw(exchange).unwrap_or(0) == 0 {
+                    report.missing.push(MissingApproval {
+                        asset_type,
+                        spender: exchange.to_string(),
+                        neg_risk: *neg_risk,
+                    });
+                }
+            }
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+
+use crate::api::require_creds;
+use crate::endpoints;
+use crate::error::Result;
+use crate::headers::{create_level_1_headers, create_level_2_headers};
+use crate::nonce::{FixedNonce, NonceProvider};
+use crate::raw::Raw;
+use crate::signer::Signer;
+use crate::transport::Transport;
+use crate::types::{ApiCreds, ApiKeysResponse, DeleteApiKeyResponse};
+
+/// API key management. Key creation/derivation needs only the signer (L1);
+/// listing and deleting keys also needs API credentials (L2).
+#[derive(Clone)]
+pub struct AuthApi {
+    transport: Transport,
+    signer: Signer,
+    creds: Option<ApiCreds>,
+    nonce_provider: Arc<dyn NonceProvider>,
+}
+
+impl AuthApi {
+    pub fn new(transport: Transport, signer: Signer) -> Self {
+        Self {
+            transport,
+            signer,
+            creds: None,
+            nonce_provider: Arc::new(FixedNonce::default()),
+        }
+    }
+
+    /// Nonce source for key creation/derivation calls that pass `None`.
+    pub fn with_nonce_provider(mut self, provider: Arc<dyn NonceProvider>) -> Self {
+        self.nonce_provider = provider;
+        self
+    }
+
+    pub fn set_nonce_provider(&mut self, provider: Arc<dyn NonceProvider>) {
+        self.nonce_provider = provider;
+    }
+
+    pub fn with_creds(mut self, creds: ApiCreds) -> Self {
+        self.creds = Some(creds);
+        self
+    }
+
+    pub fn set_creds(&mut self, creds: ApiCreds) {
+        self.creds = Some(creds);
+    }
+
+    pub(crate) fn set_chain_id(&mut self, chain_id: u64) {
+        self.signer.set_chain_id(chain_id);
+    }
+
+    pub(crate) fn set_transport(&mut self, transport: Transport) {
+        self.transport = transport;
+    }
+
+    pub fn signer(&self) -> &Signer {
+        &self.signer
+    }
+
+    pub fn creds(&self) -> Option<&ApiCreds> {
+        self.creds.as_ref()
+    }
+
+    fn resolve_nonce(&self, nonce: Option<u64>) -> u64 {
+        nonce.unwrap_or_else(|| self.nonce_provider.next_nonce())
+    }
+
+    pub async fn create_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
+        let nonce = Some(self.resolve_nonce(nonce));
+        let headers = create_level_1_headers(&self.signer, nonce).await?;
+        self.transport
+            .post_with_l1_headers(endpoints::CREATE_API_KEY, &headers)
+            .await
+    }
+
+    pub async fn derive_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
+        let nonce = Some(self.resolve_nonce(nonce));
+        let headers = create_level_1_headers(&self.signer, nonce).await?;
+        self.transport
+            .get_with_l1_headers(endpoints::DERIVE_API_KEY, &headers)
+            .await
+    }
+
+    pub async fn create_or_derive_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
+        // both attempts must use the same nonce
+        let nonce = Some(self.resolve_nonce(nonce));
+        match self.create_api_key(nonce).await {
+            Ok(creds) => Ok(creds),
+            Err(_) => self.derive_api_key(nonce).await,
+        }
+    }
+
+    pub async fn get_api_keys(&self) -> Result<ApiKeysResponse> {
+        self.api_keys_request().await
+    }
+
+    pub async fn get_api_keys_raw(&self) -> Result<Raw<ApiKeysResponse>> {
+        self.api_keys_request().await
+    }
+
+    async fn api_keys_request<T: DeserializeOwned>(&self) -> Result<T> {
+        let creds = require_creds(&self.creds)?;
+        let headers =
+            create_level_2_headers(&self.signer, creds, "GET", endpoints::GET_API_KEYS, None)?;
+
+        self.transport
+            .get_with_l2_headers(endpoints::GET_API_KEYS, &headers)
+            .await
+    }
+
+    /// Deletes the API key the client is authenticated with.
+    pub async fn delete_api_key(&self) -> Result<DeleteApiKeyResponse> {
+        let creds = require_creds(&self.creds)?;
+        self.revoke_api_key(creds).await
+    }
+
+    /// Deletes the API key identified by `creds`. The endpoint always deletes
+    /// the key that signs the request, so another key of the same wallet can
+    /// be revoked by passing its credentials.
+    pub async fn revoke_api_key(&self, creds: &ApiCreds) -> Result<DeleteApiKeyResponse> {
+        let headers = create_level_2_headers(
+            &self.signer,
+            creds,
+            "DELETE",
+            endpoints::DELETE_API_KEY,
+            None,
+        )?;
+
+        self.transport
+            .delete_with_l2_headers(endpoints::DELETE_API_KEY, &headers)
+            .await
+    }
+}
@@ -0,0 +1,1270 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use dashmap::DashMap;
+use futures::future::join_all;
+use futures::{future, stream, Stream, TryStreamExt};
+use serde::Serialize;
+
+use crate::config::{END_CURSOR, FIRST_CURSOR, MAX_BATCH_SIZE};
+use crate::endpoints;
+use crate::error::{ClobError, Result};
+use crate::market_cache::{MarketCache, TokenInfo};
+use crate::neg_risk::{plan_complete_set_arb, yes_token_id, CompleteSetArb, NegRiskEvent};
+use crate::pair_arb::{pair_token_ids, plan_pair_arb, ArbOpportunity};
+use crate::raw::Raw;
+use crate::resolution::Resolution;
+use crate::transport::{join_segment, Transport};
+use crate::types::{
+    amount_to_f64, BatchError, BatchMidpointResponse, BatchPriceResponse, BatchResponse,
+    BatchSpreadResponse, BookParams, FeeRateResponse, LastTradePriceResponse, LastTradesPriceEntry,
+    Market, MarketFilter, MarketOrderArgs, MarketQuote, MarketTradeEvent, MarketsResponse,
+    MidpointResponse, NegRiskResponse, OrderBook, PriceHistoryInterval, PriceHistoryResponse,
+    PricePoint, PriceResponse, ServerTime, Side, SimplifiedMarket, SimplifiedMarketsResponse,
+    SpreadResponse, TickSize, TickSizeResponse,
+};
+
+#[derive(Serialize)]
+struct TokenQuery<'a> {
+    token_id: &'a str,
+}
+
+#[derive(Serialize)]
+struct PriceQuery<'a> {
+    token_id: &'a str,
+    side: Side,
+}
+
+#[derive(Serialize)]
+struct CursorQuery<'a> {
+    next_cursor: &'a str,
+}
+
+#[derive(Serialize, Default)]
+struct PriceHistoryQuery<'a> {
+    market: &'a str,
+    interval: Option<&'a str>,
+    #[serde(rename = "startTs")]
+    start_ts: Option<i64>,
+    #[serde(rename = "endTs")]
+    end_ts: Option<i64>,
+    fidelity: Option<u32>,
+}
+
+// `accepting_orders` flips when a market is paused, so unlike the market
+// index these lookups are only trusted for a short while
+const ACCEPTING_ORDERS_TTL: Duration = Duration::from_secs(30);
+
+// Follows `next_cursor` page by page until the end marker, yielding items as
+// each page arrives.
+fn paginate<'a, T, F, Fut>(fetch_page: F) -> impl Stream<Item = Result<T>> + Send + 'a
+where
+    T: Send + 'a,
+    F: Fn(String) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<(Vec<T>, String)>> + Send + 'a,
+{
+    stream::try_unfold(Some(FIRST_CURSOR.to_string()), move |cursor| {
+        let page = cursor.map(&fetch_page);
+        async move {
+            let Some(page) = page else {
+                return Result::Ok(None);
+            };
+            let (items, next_cursor) = page.await?;
+            let next =
+                (next_cursor != END_CURSOR && !next_cursor.is_empty()).then_some(next_cursor);
+            Ok(Some((items, next)))
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+// Cursors are the base64 of a row offset ("MA==" is "0"), which lets
+// `scan_pages` predict the pages after the current one.
+fn cursor_offset(cursor: &str) -> Option<usize> {
+    let decoded = STANDARD.decode(cursor).ok()?;
+    std::str::from_utf8(&decoded).ok()?.parse().ok()
+}
+
+fn offset_cursor(offset: usize) -> String {
+    STANDARD.encode(offset.to_string())
+}
+
+// Like `paginate`, but fetches up to `concurrency` pages at a time from
+// cursors predicted off the first page's size. A page whose `next_cursor`
+// breaks the prediction drops the rest of its batch and the scan carries on
+// from the cursor the server gave; failures past the last page are ignored.
+async fn scan_pages<T, F, Fut>(fetch_page: F, concurrency: usize) -> Result<Vec<T>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, String)>>,
+{
+    let (mut items, mut cursor) = fetch_page(FIRST_CURSOR.to_string()).await?;
+    let page_size = items.len();
+    while cursor != END_CURSOR && !cursor.is_empty() {
+        let cursors: Vec<String> = match cursor_offset(&cursor) {
+            Some(offset) if page_size > 0 => (0..concurrency.max(1))
+                .map(|i| offset_cursor(offset + i * page_size))
+                .collect(),
+            _ => vec![cursor.clone()],
+        };
+        let pages = join_all(cursors.iter().cloned().map(&fetch_page)).await;
+        for (i, page) in pages.into_iter().enumerate() {
+            let (page, next) = page?;
+            items.extend(page);
+            cursor = next;
+            if cursors.get(i + 1) != Some(&cursor) {
+                break;
+            }
+        }
+    }
+    Ok(items)
+}
+
+// The batch endpoints answer with a JSON object, so results come back in
+// hash order; put them back in the order the tokens were requested.
+fn in_request_order<T>(
+    params: &[BookParams],
+    mut results: Vec<T>,
+    token_id: impl Fn(&T) -> &String,
+) -> Vec<T> {
+    let position: HashMap<&str, usize> = params
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.token_id.as_str(), i))
+        .collect();
+    results.sort_by_key(|r| {
+        position
+            .get(token_id(r).as_str())
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
+    results
+}
+
+fn by_token<T>(results: Vec<T>, token_id: impl Fn(&T) -> &String) -> HashMap<String, T> {
+    results
+        .into_iter()
+        .map(|r| (token_id(&r).clone(), r))
+        .collect()
+}
+
+// best bid and ask of the last book fetched for a token
+type TopOfBook = (Instant, Option<f64>, Option<f64>);
+
+/// Public (L0) market data endpoints. Clones share the same transport and
+/// tick size / neg risk / fee rate caches.
+#[derive(Debug, Clone)]
+pub struct MarketDataApi {
+    transport: Transport,
+    tick_sizes: Arc<DashMap<String, TickSize>>,
+    neg_risk: Arc<DashMap<String, bool>>,
+    fee_rates: Arc<DashMap<String, i32>>,
+    min_order_sizes: Arc<DashMap<String, f64>>,
+    top_of_book: Arc<DashMap<String, TopOfBook>>,
+    markets_by_token: Arc<DashMap<String, Arc<Market>>>,
+    markets_by_condition: Arc<DashMap<String, Arc<Market>>>,
+    markets_by_slug: Arc<DashMap<String, Arc<Market>>>,
+    market_cache: MarketCache,
+    accepting_orders: Arc<DashMap<String, (Instant, Arc<Market>)>>,
+    include_closed: Arc<AtomicBool>,
+    batch_size: Arc<AtomicUsize>,
+}
+
+impl MarketDataApi {
+    pub fn new(transport: Transport) -> Self {
+        Self {
+            transport,
+            tick_sizes: Arc::new(DashMap::new()),
+            neg_risk: Arc::new(DashMap::new()),
+            fee_rates: Arc::new(DashMap::new()),
+            min_order_sizes: Arc::new(DashMap::new()),
+            top_of_book: Arc::new(DashMap::new()),
+            markets_by_token: Arc::new(DashMap::new()),
+            markets_by_condition: Arc::new(DashMap::new()),
+            markets_by_slug: Arc::new(DashMap::new()),
+            market_cache: MarketCache::new(),
+            accepting_orders: Arc::new(DashMap::new()),
+            include_closed: Arc::new(AtomicBool::new(false)),
+            batch_size: Arc::new(AtomicUsize::new(MAX_BATCH_SIZE)),
+        }
+    }
+
+    /// Also index closed and archived markets so token ids from old trades can
+    /// be resolved with [`MarketDataApi::market_for_token`]. Off by default,
+    /// which keeps the index to markets that are still open.
+    pub fn with_closed_markets(self, include: bool) -> Self {
+        self.set_closed_markets(include);
+        self
+    }
+
+    pub fn set_closed_markets(&self, include: bool) {
+        self.include_closed.store(include, Ordering::Relaxed);
+    }
+
+    pub fn includes_closed_markets(&self) -> bool {
+        self.include_closed.load(Ordering::Relaxed)
+    }
+
+    pub fn market_for_token(&self, token_id: &str) -> Option<Arc<Market>> {
+        self.markets_by_token.get(token_id).map(|m| m.clone())
+    }
+
+    /// Bulk-loaded market snapshot, empty until
+    /// [`MarketDataApi::refresh_market_cache`] runs.
+    pub fn market_cache(&self) -> &MarketCache {
+        &self.market_cache
+    }
+
+    /// Largest number of tokens sent in one batch request. Larger batches
+    /// are split and fetched concurrently.
+    pub fn with_batch_size(self, batch_size: usize) -> Self {
+        self.set_batch_size(batch_size);
+        self
+    }
+
+    pub fn set_batch_size(&self, batch_size: usize) {
+        self.batch_size.store(batch_size.max(1), Ordering::Relaxed);
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.load(Ordering::Relaxed)
+    }
+
+    pub fn transport(&self) -> &Transport {
+        &self.transport
+    }
+
+    pub(crate) fn set_transport(&mut self, transport: Transport) {
+        self.transport = transport;
+    }
+
+    pub async fn get_ok(&self) -> Result<serde_json::Value> {
+        self.transport.get("/").await
+    }
+
+    pub async fn get_server_time(&self) -> Result<ServerTime> {
+        self.transport.get(endpoints::TIME).await
+    }
+
+    pub async fn get_server_time_raw(&self) -> Result<Raw<ServerTime>> {
+        self.transport.get(endpoints::TIME).await
+    }
+
+    pub async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
+        let book: OrderBook = self
+            .transport
+            .get_with_query(endpoints::GET_ORDER_BOOK, &TokenQuery { token_id })
+            .await?;
+        self.cache_book(&book);
+        Ok(book)
+    }
+
+    /// [`Self::get_order_book`] with the response JSON kept.
+    pub async fn get_order_book_raw(&self, token_id: &str) -> Result<Raw<OrderBook>> {
+        let book: Raw<OrderBook> = self
+            .transport
+            .get_with_query(endpoints::GET_ORDER_BOOK, &TokenQuery { token_id })
+            .await?;
+        self.cache_book(&book.data);
+        Ok(book)
+    }
+
+    async fn fetch_order_books(&self, params: &[BookParams]) -> Result<Vec<OrderBook>> {
+        let body: Vec<_> = params
+            .iter()
+            .map(|p| serde_json::json!({"token_id": p.token_id}))
+            .collect();
+        let books: Vec<OrderBook> = self
+            .transport
+            .post(endpoints::GET_ORDER_BOOKS, &body)
+            .await?;
+        books.iter().for_each(|b| self.cache_book(b));
+        Ok(books)
+    }
+
+    pub async fn get_midpoint(&self, token_id: &str) -> Result<f64> {
+        let resp: MidpointResponse = self
+            .transport
+            .get_with_query(endpoints::MID_POINT, &TokenQuery { token_id })
+            .await?;
+        Ok(amount_to_f64(resp.mid))
+    }
+
+    pub async fn get_midpoint_raw(&self, token_id: &str) -> Result<Raw<MidpointResponse>> {
+        self.transport
+            .get_with_query(endpoints::MID_POINT, &TokenQuery { token_id })
+            .await
+    }
+
+    /// Price samples for `token_id` over `interval`. `fidelity` is the sample
+    /// resolution in minutes.
+    pub async fn get_price_history(
+        &self,
+        token_id: &str,
+        interval: PriceHistoryInterval,
+        fidelity: Option<u32>,
+    ) -> Result<Vec<PricePoint>> {
+        let query = PriceHistoryQuery {
+            market: token_id,
+            interval: Some(interval.as_str()),
+            fidelity,
+            ..PriceHistoryQuery::default()
+        };
+        let resp: PriceHistoryResponse = self
+            .transport
+            .get_with_query(endpoints::PRICES_HISTORY, &query)
+            .await?;
+        Ok(resp.history)
+    }
+
+    /// [`Self::get_price_history`] with the response JSON kept.
+    pub async fn get_price_history_raw(
+        &self,
+        token_id: &str,
+        interval: PriceHistoryInterval,
+        fidelity: Option<u32>,
+    ) -> Result<Raw<PriceHistoryResponse>> {
+        let query = PriceHistoryQuery {
+            market: token_id,
+            interval: Some(interval.as_str()),
+            fidelity,
+            ..PriceHistoryQuery::default()
+        };
+        self.transport
+            .get_with_query(endpoints::PRICES_HISTORY, &query)
+            .await
+    }
+
+    /// Price samples between two unix timestamps (seconds).
+    pub async fn get_price_history_range(
+        &self,
+        token_id: &str,
+        start_ts: i64,
+        end_ts: i64,
+        fidelity: Option<u32>,
+    ) -> Result<Vec<PricePoint>> {
+        let query = PriceHistoryQuery {
+            market: token_id,
+            start_ts: Some(start_ts),
+            end_ts: Some(end_ts),
+            fidelity,
+            ..PriceHistoryQuery::default()
+        };
+        let resp: PriceHistoryResponse = self
+            .transport
+            .get_with_query(endpoints::PRICES_HISTORY, &query)
+            .await?;
+        Ok(resp.history)
+    }
+
+    async fn fetch_midpoints(&self, params: &[BookParams]) -> Result<Vec<BatchMidpointResponse>> {
+        let body: Vec<_> = params
+            .iter()
+            .map(|p| serde_json::json!({"token_id": p.token_id}))
+            .collect();
+
+        let raw: HashMap<String, String> =
+            self.transport.post(endpoints::MID_POINTS, &body).await?;
+
+        let results = raw
+            .into_iter()
+            .map(|(token_id, mid)| BatchMidpointResponse {
+                token_id,
+                mid: mid.parse().ok(),
+            })
+            .collect();
+        Ok(in_request_order(params, results, |r| &r.token_id))
+    }
+
+    pub async fn get_price(&self, token_id: &str, side: Side) -> Result<f64> {
+        let query = PriceQuery { token_id, side };
+        let resp: PriceResponse = self
+            .transport
+            .get_with_query(endpoints::PRICE, &query)
+            .await?;
+        Ok(amount_to_f64(resp.price))
+    }
+
+    pub async fn get_price_raw(&self, token_id: &str, side: Side) -> Result<Raw<PriceResponse>> {
+        let query = PriceQuery { token_id, side };
+        self.transport
+            .get_with_query(endpoints::PRICE, &query)
+            .await
+    }
+
+    async fn fetch_prices(&self, params: &[BookParams]) -> Result<Vec<BatchPriceResponse>> {
+        let body: Vec<_> = params
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "token_id": p.token_id,
+                    "side": p.side.map(|s| s.to_string())
+                })
+            })
+            .collect();
+
+        let raw: HashMap<String, HashMap<String, String>> =
+            self.transport.post(endpoints::PRICES, &body).await?;
+
+        let results = raw
+            .into_iter()
+            .map(|(token_id, sides)| BatchPriceResponse {
+                token_id,
+                buy: sides.get("BUY").and_then(|s| s.parse().ok()),
+                sell: sides.get("SELL").and_then(|s| s.parse().ok()),
+            })
+            .collect();
+        Ok(in_request_order(params, results, |r| &r.token_id))
+    }
+
+    pub async fn get_spread(&self, token_id: &str) -> Result<f64> {
+        let resp: SpreadResponse = self
+            .transport
+            .get_with_query(endpoints::SPREAD, &TokenQuery { token_id })
+            .await?;
+        Ok(amount_to_f64(resp.spread))
+    }
+
+    pub async fn get_spread_raw(&self, token_id: &str) -> Result<Raw<SpreadResponse>> {
+        self.transport
+            .get_with_query(endpoints::SPREAD, &TokenQuery { token_id })
+            .await
+    }
+
+    async fn fetch_spreads(&self, params: &[BookParams]) -> Result<Vec<BatchSpreadResponse>> {
+        let body: Vec<_> = params
+            .iter()
+            .map(|p| serde_json::json!({"token_id": p.token_id}))
+            .collect();
+
+        let raw: HashMap<String, String> = self.transport.post(endpoints::SPREADS, &body).await?;
+
+        let results = raw
+            .into_iter()
+            .map(|(token_id, spread)| BatchSpreadResponse {
+                token_id,
+                spread: spread.parse().ok(),
+            })
+            .collect();
+        Ok(in_request_order(params, results, |r| &r.token_id))
+    }
+
+    pub async fn get_last_trade_price(&self, token_id: &str) -> Result<f64> {
+        let resp: LastTradePriceResponse = self
+            .transport
+            .get_with_query(endpoints::LAST_TRADE_PRICE, &TokenQuery { token_id })
+            .await?;
+        Ok(amount_to_f64(resp.price))
+    }
+
+    pub async fn get_last_trade_price_raw(
+        &self,
+        token_id: &str,
+    ) -> Result<Raw<LastTradePriceResponse>> {
+        self.transport
+            .get_with_query(endpoints::LAST_TRADE_PRICE, &TokenQuery { token_id })
+            .await
+    }
+
+    async fn fetch_last_trades_prices(
+        &self,
+        params: &[BookParams],
+    ) -> Result<Vec<LastTradesPriceEntry>> {
+        let body: Vec<_> = params
+            .iter()
+            .map(|p| serde_json::json!({"token_id": p.token_id}))
+            .collect();
+        self.transport
+            .post(endpoints::LAST_TRADES_PRICES, &body)
+            .await
+    }
+
+    /// What `args` would get from the current book. Fails if the book is
+    /// too thin to fill it.
+    pub async fn quote_market_order(&self, args: &MarketOrderArgs) -> Result<MarketQuote> {
+        let book = self.get_order_book(&args.token_id).await?.to_numeric()?;
+        args.quote(&book).ok_or_else(|| {
+            let unit = match args.side {
+                Side::Buy => "USDC",
+                Side::Sell => "shares",
+            };
+            ClobError::InvalidParameter(format!(
+                "book for {} is too thin for a market {} of {} {}",
+                args.token_id, args.side, args.amount, unit
+            ))
+        })
+    }
+
+    /// Fetches books in chunks of at most [`MarketDataApi::batch_size`]
+    /// tokens. Fails if any chunk fails; see
+    /// [`MarketDataApi::get_order_books_partial`] to keep partial results.
+    pub async fn get_order_books(&self, params: &[BookParams]) -> Result<Vec<OrderBook>> {
+        self.get_order_books_partial(params).await.into_result()
+    }
+
+    /// [`Self::get_order_books`] with each book's JSON kept. Fails if any
+    /// batch does.
+    pub async fn get_order_books_raw(&self, params: &[BookParams]) -> Result<Vec<Raw<OrderBook>>> {
+        self.chunked(params, |chunk| async move {
+            let body: Vec<_> = chunk
+                .iter()
+                .map(|p| serde_json::json!({"token_id": p.token_id}))
+                .collect();
+            let books: Vec<Raw<OrderBook>> = self
+                .transport
+                .post(endpoints::GET_ORDER_BOOKS, &body)
+                .await?;
+            books.iter().for_each(|b| self.cache_book(&b.data));
+            Ok(books)
+        })
+        .await
+        .into_result()
+    }
+
+    pub async fn get_order_books_partial(&self, params: &[BookParams]) -> BatchResponse<OrderBook> {
+        self.chunked(params, |chunk| self.fetch_order_books(chunk))
+            .await
+    }
+
+    pub async fn get_midpoints(&self, params: &[BookParams]) -> Result<Vec<BatchMidpointResponse>> {
+        self.get_midpoints_partial(params).await.into_result()
+    }
+
+    pub async fn get_midpoints_partial(
+        &self,
+        params: &[BookParams],
+    ) -> BatchResponse<BatchMidpointResponse> {
+        self.chunked(params, |chunk| self.fetch_midpoints(chunk))
+            .await
+    }
+
+    /// Midpoints keyed by token id.
+    pub async fn get_midpoints_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchMidpointResponse>> {
+        Ok(by_token(self.get_midpoints(params).await?, |r| &r.token_id))
+    }
+
+    pub async fn get_prices(&self, params: &[BookParams]) -> Result<Vec<BatchPriceResponse>> {
+        self.get_prices_partial(params).await.into_result()
+    }
+
+    pub async fn get_prices_partial(
+        &self,
+        params: &[BookParams],
+    ) -> BatchResponse<BatchPriceResponse> {
+        self.chunked(params, |chunk| self.fetch_prices(chunk)).await
+    }
+
+    /// Prices keyed by token id.
+    pub async fn get_prices_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchPriceResponse>> {
+        Ok(by_token(self.get_prices(params).await?, |r| &r.token_id))
+    }
+
+    pub async fn get_spreads(&self, params: &[BookParams]) -> Result<Vec<BatchSpreadResponse>> {
+        self.get_spreads_partial(params).await.into_result()
+    }
+
+    pub async fn get_spreads_partial(
+        &self,
+        params: &[BookParams],
+    ) -> BatchResponse<BatchSpreadResponse> {
+        self.chunked(params, |chunk| self.fetch_spreads(chunk))
+            .await
+    }
+
+    /// Spreads keyed by token id.
+    pub async fn get_spreads_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchSpreadResponse>> {
+        Ok(by_token(self.get_spreads(params).await?, |r| &r.token_id))
+    }
+
+    pub async fn get_last_trades_prices(
+        &self,
+        params: &[BookParams],
+    ) -> Result<Vec<LastTradesPriceEntry>> {
+        self.get_last_trades_prices_partial(params)
+            .await
+            .into_result()
+    }
+
+    pub async fn get_last_trades_prices_partial(
+        &self,
+        params: &[BookParams],
+    ) -> BatchResponse<LastTradesPriceEntry> {
+        self.chunked(params, |chunk| self.fetch_last_trades_prices(chunk))
+            .await
+    }
+
+    async fn chunked<'a, T, F, Fut>(
+        &'a self,
+        params: &'a [BookParams],
+        request: F,
+    ) -> BatchResponse<T>
+    where
+        F: Fn(&'a [BookParams]) -> Fut,
+        Fut: Future<Output = Result<Vec<T>>> + 'a,
+    {
+        let chunks: Vec<&'a [BookParams]> = params.chunks(self.batch_size()).collect();
+        let results = join_all(chunks.iter().map(|chunk| request(chunk))).await;
+
+        let mut response = BatchResponse {
+            data: Vec::new(),
+            errors: Vec::new(),
+        };
+        for (chunk, result) in chunks.into_iter().zip(results) {
+            match result {
+                Ok(items) => response.data.extend(items),
+                Err(error) => response.errors.push(BatchError {
+                    token_ids: chunk.iter().map(|p| p.token_id.clone()).collect(),
+                    error,
+                }),
+            }
+        }
+        response
+    }
+
+    pub async fn get_tick_size(&self, token_id: &str) -> Result<TickSize> {
+        if let Some(tick_size) = self.tick_sizes.get(token_id) {
+            return Ok(*tick_size);
+        }
+        if let Some(tick_size) = self.lookup_token(token_id).and_then(|t| t.tick_size) {
+            return Ok(tick_size);
+        }
+
+        let resp: TickSizeResponse = self
+            .transport
+            .get_with_query(endpoints::TICK_SIZE, &TokenQuery { token_id })
+            .await?;
+
+        self.tick_sizes
+            .insert(token_id.to_string(), resp.minimum_tick_size);
+        Ok(resp.minimum_tick_size)
+    }
+
+    /// Always asks the server, unlike [`Self::get_tick_size`], and caches
+    /// the answer.
+    pub async fn get_tick_size_raw(&self, token_id: &str) -> Result<Raw<TickSizeResponse>> {
+        let resp: Raw<TickSizeResponse> = self
+            .transport
+            .get_with_query(endpoints::TICK_SIZE, &TokenQuery { token_id })
+            .await?;
+        self.tick_sizes
+            .insert(token_id.to_string(), resp.data.minimum_tick_size);
+        Ok(resp)
+    }
+
+    pub async fn get_neg_risk(&self, token_id: &str) -> Result<bool> {
+        if let Some(neg_risk) = self.neg_risk.get(token_id) {
+            return Ok(*neg_risk);
+        }
+        if let Some(info) = self.lookup_token(token_id) {
+            return Ok(info.neg_risk);
+        }
+
+        let resp: NegRiskResponse = self
+            .transport
+            .get_with_query(endpoints::NEG_RISK, &TokenQuery { token_id })
+            .await?;
+
+        self.neg_risk.insert(token_id.to_string(), resp.neg_risk);
+        Ok(resp.neg_risk)
+    }
+
+    /// Always asks the server, unlike [`Self::get_neg_risk`], and caches the
+    /// answer.
+    pub async fn get_neg_risk_raw(&self, token_id: &str) -> Result<Raw<NegRiskResponse>> {
+        let resp: Raw<NegRiskResponse> = self
+            .transport
+            .get_with_query(endpoints::NEG_RISK, &TokenQuery { token_id })
+            .await?;
+        self.neg_risk
+            .insert(token_id.to_string(), resp.data.neg_risk);
+        Ok(resp)
+    }
+
+    pub async fn get_fee_rate_bps(&self, token_id: &str) -> Result<i32> {
+        if let Some(fee_rate) = self.fee_rates.get(token_id) {
+            return Ok(*fee_rate);
+        }
+
+        let resp: FeeRateResponse = self
+            .transport
+            .get_with_query(endpoints::FEE_RATE, &TokenQuery { token_id })
+            .await?;
+        let fee_rate = resp.base_fee.unwrap_or(0);
+
+        self.fee_rates.insert(token_id.to_string(), fee_rate);
+        Ok(fee_rate)
+    }
+
+    /// Always asks the server, unlike [`Self::get_fee_rate_bps`], and caches
+    /// the answer.
+    pub async fn get_fee_rate_raw(&self, token_id: &str) -> Result<Raw<FeeRateResponse>> {
+        let resp: Raw<FeeRateResponse> = self
+            .transport
+            .get_with_query(endpoints::FEE_RATE, &TokenQuery { token_id })
+            .await?;
+        self.fee_rates
+            .insert(token_id.to_string(), resp.data.base_fee.unwrap_or(0));
+        Ok(resp)
+    }
+
+    /// Minimum order size in shares, fetched from the order book when it has not
+    /// been seen in a previous book or market response.
+    pub async fn get_min_order_size(&self, token_id: &str) -> Result<f64> {
+        if let Some(min_size) = self.cached_min_order_size(token_id) {
+            return Ok(min_size);
+        }
+
+        let book = self.get_order_book(token_id).await?;
+        Ok(book.min_order_size_f64().unwrap_or(0.0))
+    }
+
+    pub fn cached_min_order_size(&self, token_id: &str) -> Option<f64> {
+        self.min_order_sizes.get(token_id).map(|m| *m)
+    }
+
+    /// Best bid and ask of `token_id`, from the last book fetched if it is
+    /// at most `max_age` old, otherwise from a new one.
+    pub async fn get_best_prices(
+        &self,
+        token_id: &str,
+        max_age: Duration,
+    ) -> Result<(Option<f64>, Option<f64>)> {
+        if let Some(entry) = self.top_of_book.get(token_id) {
+            let (fetched, bid, ask) = *entry;
+            if fetched.elapsed() <= max_age {
+                return Ok((bid, ask));
+            }
+        }
+        let book = self.get_order_book(token_id).await?.to_numeric()?;
+        Ok((
+            book.best_bid().map(|l| l.price),
+            book.best_ask().map(|l| l.price),
+        ))
+    }
+
+    fn cache_book(&self, book: &OrderBook) {
+        if let Some(min_size) = book.min_order_size_f64() {
+            self.min_order_sizes.insert(book.asset_id.clone(), min_size);
+        }
+        if let Ok(numeric) = book.to_numeric() {
+            self.top_of_book.insert(
+                book.asset_id.clone(),
+                (
+                    Instant::now(),
+                    numeric.best_bid().map(|l| l.price),
+                    numeric.best_ask().map(|l| l.price),
+                ),
+            );
+        }
+    }
+
+    /// `token_id` as the indexed market it was last fetched with, or the
+    /// bulk [`MarketCache`] when none was. `get_tick_size` and `get_neg_risk`
+    /// answer from here without a request.
+    pub fn lookup_token(&self, token_id: &str) -> Option<TokenInfo> {
+        match self.market_for_token(token_id) {
+            Some(market) => TokenInfo::from_market(&market).find(|t| t.token_id == token_id),
+            None => self.market_cache.lookup_token(token_id),
+        }
+    }
+
+    // simplified listings carry no tick size, only neg risk
+    fn cache_simplified_page(&self, page: &SimplifiedMarketsResponse) {
+        for market in &page.data {
+            for token in &market.tokens {
+                self.neg_risk
+                    .insert(token.token_id.clone(), market.neg_risk);
+            }
+        }
+    }
+
+    fn cache_market(&self, market: &Market) {
+        let min_size = amount_to_f64(market.minimum_order_size);
+        if min_size > 0.0 {
+            for token in &market.tokens {
+                self.min_order_sizes
+                    .insert(token.token_id.clone(), min_size);
+            }
+        }
+
+        if market.is_open() || self.includes_closed_markets() {
+            let shared = Arc::new(market.clone());
+            for token in &market.tokens {
+                self.markets_by_token
+                    .insert(token.token_id.clone(), shared.clone());
+            }
+            if let Some(slug) = &market.market_slug {
+                self.markets_by_slug.insert(slug.clone(), shared.clone());
+            }
+            self.markets_by_condition
+                .insert(market.condition_id.clone(), shared);
+        }
+    }
+
+    /// Market by condition id, served from the index when possible.
+    pub async fn market_by_condition_id(&self, condition_id: &str) -> Result<Arc<Market>> {
+        if let Some(market) = self.markets_by_condition.get(condition_id) {
+            return Ok(market.clone());
+        }
+        Ok(Arc::new(self.get_market(condition_id).await?))
+    }
+
+    /// Whether the market behind `id`, a condition id or a token id, takes
+    /// orders right now. Market state is cached for 30 seconds.
+    pub async fn is_market_accepting_orders(&self, id: &str) -> Result<bool> {
+        let market = self.accepting_orders_market(id).await?;
+        Ok(market.is_accepting_orders(Utc::now()))
+    }
+
+    /// Like [`Self::is_market_accepting_orders`], but fails with
+    /// [`ClobError::NotAcceptingOrders`] saying why the market is unavailable.
+    pub async fn ensure_accepting_orders(&self, id: &str) -> Result<()> {
+        let market = self.accepting_orders_market(id).await?;
+        let now = Utc::now();
+        let reason = if !market.is_open() {
+            "market is closed".to_string()
+        } else if !market.accepting_orders {
+            "trading is paused".to_string()
+        } else {
+            match market.accepting_orders_from() {
+                Some(from) if from > now => format!("orders are accepted from {}", from),
+                _ => return Ok(()),
+            }
+        };
+        Err(ClobError::NotAcceptingOrders {
+            market: market.condition_id.clone(),
+            reason,
+        })
+    }
+
+    async fn accepting_orders_market(&self, id: &str) -> Result<Arc<Market>> {
+        let condition_id = if id.starts_with("0x") {
+            id.to_string()
+        } else if let Some(market) = self
+            .market_for_token(id)
+            .or_else(|| self.market_cache.market_for_token(id))
+        {
+            market.condition_id.clone()
+        } else {
+            self.get_order_book(id).await?.market
+        };
+
+        if let Some(entry) = self.accepting_orders.get(&condition_id) {
+            let (fetched_at, market) = entry.value();
+            if fetched_at.elapsed() < ACCEPTING_ORDERS_TTL {
+                return Ok(market.clone());
+            }
+        }
+        let market = Arc::new(self.get_market(&condition_id).await?);
+        self.accepting_orders
+            .insert(condition_id, (Instant::now(), market.clone()));
+        Ok(market)
+    }
+
+    /// Market by its exact slug. The CLOB has no slug endpoint, so a miss in
+    /// the index scans the markets list (which indexes every page it reads).
+    pub async fn market_by_slug(&self, slug: &str) -> Result<Arc<Market>> {
+        if let Some(market) = self.markets_by_slug.get(slug) {
+            return Ok(market.clone());
+        }
+
+        let filter = MarketFilter::new().slug_contains(slug);
+        let markets = self.find_markets(filter);
+        futures::pin_mut!(markets);
+        while let Some(market) = markets.try_next().await? {
+            if market.market_slug.as_deref() == Some(slug) {
+                return Ok(Arc::new(market));
+            }
+        }
+
+        Err(ClobError::InvalidParameter(format!(
+            "no market with slug {}",
+            slug
+        )))
+    }
+
+    /// `(outcome, token_id)` pairs for a market given by condition id
+    /// (`0x...`) or slug.
+    pub async fn get_token_ids(&self, market: &str) -> Result<Vec<(String, String)>> {
+        let market = if market.starts_with("0x") {
+            self.market_by_condition_id(market).await?
+        } else {
+            self.market_by_slug(market).await?
+        };
+
+        Ok(market
+            .tokens
+            .iter()
+            .map(|t| (t.outcome.clone(), t.token_id.clone()))
+            .collect())
+    }
+
+    pub async fn get_markets_page(&self, cursor: Option<&str>) -> Result<MarketsResponse> {
+        let cursor = cursor.unwrap_or(FIRST_CURSOR);
+        let query = CursorQuery {
+            next_cursor: cursor,
+        };
+        let page: MarketsResponse = self
+            .transport
+            .get_with_query(endpoints::MARKETS, &query)
+            .await?;
+        page.data.iter().for_each(|m| self.cache_market(m));
+        Ok(page)
+    }
+
+    pub async fn get_markets_page_raw(&self, cursor: Option<&str>) -> Result<Raw<MarketsResponse>> {
+        let query = CursorQuery {
+            next_cursor: cursor.unwrap_or(FIRST_CURSOR),
+        };
+        let page: Raw<MarketsResponse> = self
+            .transport
+            .get_with_query(endpoints::MARKETS, &query)
+            .await?;
+        page.data.data.iter().for_each(|m| self.cache_market(m));
+        Ok(page)
+    }
+
+    pub async fn get_markets(&self) -> Result<Vec<Market>> {
+        let mut results = Vec::new();
+        let mut cursor = FIRST_CURSOR.to_string();
+
+        loop {
+            let response = self.get_markets_page(Some(&cursor)).await?;
+            results.extend(response.data);
+
+            if response.next_cursor == END_CURSOR {
+                break;
+            }
+            cursor = response.next_cursor;
+        }
+
+        Ok(results)
+    }
+
+    /// Every market, like [`Self::get_markets`], but with up to
+    /// `concurrency` pages in flight: the next pages are requested while
+    /// the current one is still being read.
+    pub async fn get_markets_concurrent(&self, concurrency: usize) -> Result<Vec<Market>> {
+        scan_pages(
+            |cursor| async move {
+                let page = self.get_markets_page(Some(&cursor)).await?;
+                Ok((page.data, page.next_cursor))
+            },
+            concurrency,
+        )
+        .await
+    }
+
+    /// Pages through all markets lazily and yields the ones matching `filter`.
+    pub fn find_markets(
+        &self,
+        filter: MarketFilter,
+    ) -> impl Stream<Item = Result<Market>> + Send + '_ {
+        paginate(move |cursor| async move {
+            let page = self.get_markets_page(Some(&cursor)).await?;
+            Ok((page.data, page.next_cursor))
+        })
+        .try_filter(move |market| future::ready(filter.matches(market)))
+    }
+
+    /// Open outcome markets of the neg-risk event `neg_risk_market_id`.
+    pub async fn get_neg_risk_siblings(&self, neg_risk_market_id: &str) -> Result<Vec<Market>> {
+        let filter = MarketFilter::new()
+            .neg_risk_market_id(neg_risk_market_id)
+            .active(true)
+            .closed(false);
+        self.find_markets(filter).try_collect().await
+    }
+
+    /// The open outcome markets of the event with their implied
+    /// probabilities.
+    pub async fn get_neg_risk_event(&self, neg_risk_market_id: &str) -> Result<NegRiskEvent> {
+        let siblings = self.get_neg_risk_siblings(neg_risk_market_id).await?;
+        Ok(NegRiskEvent::new(neg_risk_market_id, siblings))
+    }
+
+    /// Fetches the YES book of every open outcome in the event and plans a
+    /// complete-set arbitrage clearing `min_edge` per set, if there is one.
+    pub async fn plan_neg_risk_arb(
+        &self,
+        neg_risk_market_id: &str,
+        min_edge: f64,
+    ) -> Result<Option<CompleteSetArb>> {
+        let siblings = self.get_neg_risk_siblings(neg_risk_market_id).await?;
+        if siblings.len() < 2 {
+            return Err(ClobError::InvalidParameter(format!(
+                "neg-risk event {} has fewer than two open outcomes",
+                neg_risk_market_id
+            )));
+        }
+        let params = siblings
+            .iter()
+            .map(|m| {
+                yes_token_id(m).map(BookParams::new).ok_or_else(|| {
+                    ClobError::InvalidParameter(format!("market {} has no tokens", m.condition_id))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let books = self.get_order_books(&params).await?;
+        Ok(plan_complete_set_arb(&books, min_edge))
+    }
+
+    /// Fetches both books of every open two-outcome market in batches and
+    /// returns the pairs priced to clear `min_edge` per pair, most profitable
+    /// first. Markets whose batch failed are left out and listed in `errors`.
+    pub async fn scan_pair_arbs(
+        &self,
+        markets: &[Market],
+        min_edge: f64,
+    ) -> BatchResponse<ArbOpportunity> {
+        let pairs: Vec<(&str, &str, &str)> = markets
+            .iter()
+            .filter(|m| m.accepting_orders && !m.closed)
+            .filter_map(|m| {
+                let (yes, no) = pair_token_ids(m)?;
+                Some((m.condition_id.as_str(), yes, no))
+            })
+            .collect();
+        let params: Vec<BookParams> = pairs
+            .iter()
+            .flat_map(|(_, yes, no)| [BookParams::new(*yes), BookParams::new(*no)])
+            .collect();
+        let books = self.get_order_books_partial(&params).await;
+        let by_token: HashMap<&str, &OrderBook> = books
+            .data
+            .iter()
+            .map(|book| (book.asset_id.as_str(), book))
+            .collect();
+
+        let mut data: Vec<ArbOpportunity> = pairs
+            .iter()
+            .filter_map(|(condition_id, yes, no)| {
+                plan_pair_arb(
+                    condition_id,
+                    by_token.get(yes)?,
+                    by_token.get(no)?,
+                    min_edge,
+                )
+            })
+            .collect();
+        data.sort_by(|a, b| b.profit.total_cmp(&a.profit));
+        BatchResponse {
+            data,
+            errors: books.errors,
+        }
+    }
+
+    pub async fn get_simplified_markets_page(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<SimplifiedMarketsResponse> {
+        let cursor = cursor.unwrap_or(FIRST_CURSOR);
+        let query = CursorQuery {
+            next_cursor: cursor,
+        };
+        let page: SimplifiedMarketsResponse = self
+            .transport
+            .get_with_query(endpoints::SIMPLIFIED_MARKETS, &query)
+            .await?;
+        self.cache_simplified_page(&page);
+        Ok(page)
+    }
+
+    pub async fn get_simplified_markets_page_raw(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<Raw<SimplifiedMarketsResponse>> {
+        let query = CursorQuery {
+            next_cursor: cursor.unwrap_or(FIRST_CURSOR),
+        };
+        let page: Raw<SimplifiedMarketsResponse> = self
+            .transport
+            .get_with_query(endpoints::SIMPLIFIED_MARKETS, &query)
+            .await?;
+        self.cache_simplified_page(&page.data);
+        Ok(page)
+    }
+
+    /// Every simplified market, following the cursor to the last page.
+    pub async fn get_simplified_markets(&self) -> Result<Vec<SimplifiedMarket>> {
+        self.stream_simplified_markets().try_collect().await
+    }
+
+    /// Every simplified market, with up to `concurrency` pages in flight.
+    pub async fn get_simplified_markets_concurrent(
+        &self,
+        concurrency: usize,
+    ) -> Result<Vec<SimplifiedMarket>> {
+        scan_pages(
+            |cursor| async move {
+                let page = self.get_simplified_markets_page(Some(&cursor)).await?;
+                Ok((page.data, page.next_cursor))
+            },
+            concurrency,
+        )
+        .await
+    }
+
+    /// Pages through the simplified markets lazily.
+    pub fn stream_simplified_markets(
+        &self,
+    ) -> impl Stream<Item = Result<SimplifiedMarket>> + Send + '_ {
+        paginate(move |cursor| async move {
+            let page = self.get_simplified_markets_page(Some(&cursor)).await?;
+            Ok((page.data, page.next_cursor))
+        })
+    }
+
+    pub async fn get_sampling_markets_page(&self, cursor: Option<&str>) -> Result<MarketsResponse> {
+        let cursor = cursor.unwrap_or(FIRST_CURSOR);
+        let query = CursorQuery {
+            next_cursor: cursor,
+        };
+        let page: MarketsResponse = self
+            .transport
+            .get_with_query(endpoints::SAMPLING_MARKETS, &query)
+            .await?;
+        page.data.iter().for_each(|m| self.cache_market(m));
+        Ok(page)
+    }
+
+    pub async fn get_sampling_markets_page_raw(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<Raw<MarketsResponse>> {
+        let query = CursorQuery {
+            next_cursor: cursor.unwrap_or(FIRST_CURSOR),
+        };
+        let page: Raw<MarketsResponse> = self
+            .transport
+            .get_with_query(endpoints::SAMPLING_MARKETS, &query)
+            .await?;
+        page.data.data.iter().for_each(|m| self.cache_market(m));
+        Ok(page)
+    }
+
+    /// Every market currently eligible for rewards, following the cursor to
+    /// the last page.
+    pub async fn get_sampling_markets(&self) -> Result<Vec<Market>> {
+        self.stream_sampling_markets().try_collect().await
+    }
+
+    pub fn stream_sampling_markets(&self) -> impl Stream<Item = Result<Market>> + Send + '_ {
+        paginate(move |cursor| async move {
+            let page = self.get_sampling_markets_page(Some(&cursor)).await?;
+            Ok((page.data, page.next_cursor))
+        })
+    }
+
+    /// The sampling markets that pay rewards right now, see
+    /// [`Market::has_active_rewards`]. Sampling lists can include markets
+    /// whose rewards have ended or that stopped accepting orders.
+    pub async fn get_reward_markets(&self) -> Result<Vec<Market>> {
+        self.stream_sampling_markets()
+            .try_filter(|market| future::ready(market.has_active_rewards()))
+            .try_collect()
+            .await
+    }
+
+    pub async fn get_sampling_simplified_markets_page(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<SimplifiedMarketsResponse> {
+        let query = CursorQuery {
+            next_cursor: cursor.unwrap_or(FIRST_CURSOR),
+        };
+        let page: SimplifiedMarketsResponse = self
+            .transport
+            .get_with_query(endpoints::SAMPLING_SIMPLIFIED_MARKETS, &query)
+            .await?;
+        self.cache_simplified_page(&page);
+        Ok(page)
+    }
+
+    pub async fn get_sampling_simplified_markets_page_raw(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<Raw<SimplifiedMarketsResponse>> {
+        let query = CursorQuery {
+            next_cursor: cursor.unwrap_or(FIRST_CURSOR),
+        };
+        let page: Raw<SimplifiedMarketsResponse> = self
+            .transport
+            .get_with_query(endpoints::SAMPLING_SIMPLIFIED_MARKETS, &query)
+            .await?;
+        self.cache_simplified_page(&page.data);
+        Ok(page)
+    }
+
+    /// Every simplified market currently eligible for rewards.
+    pub async fn get_sampling_simplified_markets(&self) -> Result<Vec<SimplifiedMarket>> {
+        self.stream_sampling_simplified_markets()
+            .try_collect()
+            .await
+    }
+
+    pub fn stream_sampling_simplified_markets(
+        &self,
+    ) -> impl Stream<Item = Result<SimplifiedMarket>> + Send + '_ {
+        paginate(move |cursor| async move {
+            let page = self
+                .get_sampling_simplified_markets_page(Some(&cursor))
+                .await?;
+            Ok((page.data, page.next_cursor))
+        })
+    }
+
+    pub async fn get_market(&self, condition_id: &str) -> Result<Market> {
+        let url = join_segment(endpoints::MARKET, condition_id);
+        let market: Market = self.transport.get(&url).await?;
+        self.cache_market(&market);
+        Ok(market)
+    }
+
+    /// [`Self::get_market`] with the response JSON kept.
+    pub async fn get_market_raw(&self, condition_id: &str) -> Result<Raw<Market>> {
+        let url = join_segment(endpoints::MARKET, condition_id);
+        let market: Raw<Market> = self.transport.get(&url).await?;
+        self.cache_market(&market.data);
+        Ok(market)
+    }
+
+    /// Whether the condition has resolved and what each outcome pays, as
+    /// far as the REST API reports it. See [`Resolution::from_market`].
+    pub async fn get_resolution(&self, condition_id: &str) -> Result<Resolution> {
+        Ok(Resolution::from_market(
+            &self.get_market(condition_id).await?,
+        ))
+    }
+
+    pub async fn get_market_trades_events(
+        &self,
+        condition_id: &str,
+    ) -> Result<Vec<MarketTradeEvent>> {
+        let url = join_segment(endpoints::MARKET_TRADES_EVENTS, condition_id);
+        self.transport.get(&url).await
+    }
+
+    pub async fn get_market_trades_events_raw(
+        &self,
+        condition_id: &str,
+    ) -> Result<Vec<Raw<MarketTradeEvent>>> {
+        let url = join_segment(endpoints::MARKET_TRADES_EVENTS, condition_id);
+        self.transport.get(&url).await
+    }
+}
@@ -0,0 +1,18 @@
+mod auth;
+mod market_data;
+mod orders;
+mod scoped;
+
+pub use auth::AuthApi;
+pub use market_data::MarketDataApi;
+pub use orders::OrdersApi;
+pub use scoped::{AccountApi, MarketsApi, PricesApi};
+
+use crate::error::{ClobError, Result};
+use crate::types::ApiCreds;
+
+pub(crate) fn require_creds(creds: &Option<ApiCreds>) -> Result<&ApiCreds> {
+    creds.as_ref().ok_or_else(|| {
+        ClobError::AuthRequired("L2 auth required (provide API credentials)".to_string())
+    })
+}
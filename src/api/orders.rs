@@ -0,0 +1,938 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::api::{require_creds, MarketDataApi};
+use crate::audit_log::AuditLog;
+use crate::config::{collateral_decimals, get_contract_config, END_CURSOR, FIRST_CURSOR};
+use crate::deadline::with_deadline;
+use crate::dry_run::DryRunLog;
+use crate::endpoints;
+use crate::error::{ClobError, Result};
+use crate::headers::create_level_2_headers;
+use crate::ladder::{diff_ladder, LadderUpdate, QuoteLadder, RestingOrder};
+use crate::order_builder::{OrderBuilder, SignedOrder};
+use crate::portfolio::Portfolio;
+use crate::raw::Raw;
+use crate::risk::{OrderTerms, RiskConfig, SpreadGuard};
+use crate::sweep::{check_limit, plan_sweep, SweepLimit, SweepResult};
+use crate::transport::{join_segment, Transport};
+use crate::types::{
+    amount_to_f64, ApiCreds, AssetType, BalanceAllowanceParams, BalanceAllowanceResponse,
+    BookParams, CreateOrderOptions, MarketOrderArgs, OpenOrderParams, OpenOrdersResponse,
+    OrderArgs, OrderStatus, OrderType, PartialCreateOrderOptions, PostOrderResponse, Side,
+    TradeParams,
+};
+
+fn order_body(order: &SignedOrder, owner: &str, order_type: OrderType) -> serde_json::Value {
+    serde_json::json!({
+        "order": order,
+        "owner": owner,
+        "orderType": order_type.to_string()
+    })
+}
+
+#[derive(Serialize)]
+struct OrdersQuery<'a> {
+    #[serde(flatten)]
+    params: Option<&'a OpenOrderParams>,
+    next_cursor: &'a str,
+}
+
+fn is_last_page(next_cursor: &str) -> bool {
+    next_cursor == END_CURSOR || next_cursor.is_empty()
+}
+
+fn dry_run_cancel_response(order_ids: &[String]) -> serde_json::Value {
+    serde_json::json!({"canceled": order_ids, "not_canceled": {}})
+}
+
+/// Order creation, submission and account endpoints. Creating orders needs
+/// only the signer (L1); everything that talks to the exchange on behalf of
+/// the account needs API credentials (L2).
+#[derive(Clone)]
+pub struct OrdersApi {
+    transport: Transport,
+    market_data: MarketDataApi,
+    order_builder: OrderBuilder,
+    creds: Option<ApiCreds>,
+    auto_fee_rate: bool,
+    dry_run: Option<DryRunLog>,
+    risk: Option<RiskConfig>,
+    spread_guard: Option<SpreadGuard>,
+    check_accepting_orders: bool,
+    check_balance: bool,
+    audit: Option<AuditLog>,
+}
+
+impl OrdersApi {
+    pub fn new(market_data: MarketDataApi, order_builder: OrderBuilder) -> Self {
+        Self {
+            transport: market_data.transport().clone(),
+            market_data,
+            order_builder,
+            creds: None,
+            auto_fee_rate: true,
+            dry_run: None,
+            risk: None,
+            spread_guard: None,
+            check_accepting_orders: false,
+            check_balance: false,
+            audit: None,
+        }
+    }
+
+    /// Record posts, cancels and allowance updates in `log` instead of
+    /// sending them, and answer with synthetic responses. Reads still hit the
+    /// network, and API credentials are optional.
+    pub fn with_dry_run(mut self, log: DryRunLog) -> Self {
+        self.dry_run = Some(log);
+        self
+    }
+
+    pub fn set_dry_run(&mut self, log: Option<DryRunLog>) {
+        self.dry_run = log;
+    }
+
+    pub fn dry_run(&self) -> Option<&DryRunLog> {
+        self.dry_run.as_ref()
+    }
+
+    /// When enabled (the default) orders created with `fee_rate_bps == 0` get
+    /// the market's fee rate filled in before signing.
+    pub fn with_auto_fee_rate(mut self, enabled: bool) -> Self {
+        self.auto_fee_rate = enabled;
+        self
+    }
+
+    pub fn set_auto_fee_rate(&mut self, enabled: bool) {
+        self.auto_fee_rate = enabled;
+    }
+
+    /// Checks every order against `risk` before it is posted.
+    pub fn with_risk_config(mut self, risk: RiskConfig) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    pub fn set_risk_config(&mut self, risk: Option<RiskConfig>) {
+        self.risk = risk;
+    }
+
+    pub fn risk_config(&self) -> Option<&RiskConfig> {
+        self.risk.as_ref()
+    }
+
+    /// Checks GTC and GTD orders against the best bid and ask before they
+    /// are posted. FOK and FAK orders are meant to cross and are let through.
+    pub fn with_spread_guard(mut self, guard: SpreadGuard) -> Self {
+        self.spread_guard = Some(guard);
+        self
+    }
+
+    pub fn set_spread_guard(&mut self, guard: Option<SpreadGuard>) {
+        self.spread_guard = guard;
+    }
+
+    pub fn spread_guard(&self) -> Option<&SpreadGuard> {
+        self.spread_guard.as_ref()
+    }
+
+    /// Makes `post_order` fail with [`crate::ClobError::NotAcceptingOrders`]
+    /// before sending when the market is closed, paused or still in its
+    /// delay window. Off by default.
+    pub fn with_accepting_orders_check(mut self, enabled: bool) -> Self {
+        self.check_accepting_orders = enabled;
+        self
+    }
+
+    pub fn set_accepting_orders_check(&mut self, enabled: bool) {
+        self.check_accepting_orders = enabled;
+    }
+
+    /// Makes `post_order` check the account's balance and exchange allowance
+    /// first, failing with [`ClobError::InsufficientBalance`] or
+    /// [`ClobError::InsufficientAllowance`] instead of sending an order the
+    /// exchange would reject. Off by default.
+    pub fn with_balance_check(mut self, enabled: bool) -> Self {
+        self.check_balance = enabled;
+        self
+    }
+
+    pub fn set_balance_check(&mut self, enabled: bool) {
+        self.check_balance = enabled;
+    }
+
+    /// Records every signed order, and every post and cancel with the
+    /// server's answer, in `log`. Dry-run calls aren't sent and aren't
+    /// recorded. A failed write aborts the call.
+    pub fn with_audit_log(mut self, log: AuditLog) -> Self {
+        self.audit = Some(log);
+        self
+    }
+
+    pub fn set_audit_log(&mut self, log: Option<AuditLog>) {
+        self.audit = log;
+    }
+
+    pub fn audit_log(&self) -> Option<&AuditLog> {
+        self.audit.as_ref()
+    }
+
+    fn audit(&self, event: &str, data: impl Serialize) -> Result<Option<u64>> {
+        let Some(log) = &self.audit else {
+            return Ok(None);
+        };
+        let data = serde_json::to_value(data).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })?;
+        log.record(event, &data).map(Some)
+    }
+
+    // The request has already reached the exchange, so a failed write must
+    // not turn its answer into an error the caller would retry; it is logged
+    // and flagged on the log instead (see `AuditLog::has_failed`).
+    fn audit_response<T: Serialize>(&self, request: Option<u64>, result: &Result<T>) {
+        let Some(request_seq) = request else {
+            return;
+        };
+        let data = match result {
+            Ok(response) => serde_json::json!({"request_seq": request_seq, "response": response}),
+            Err(e) => serde_json::json!({"request_seq": request_seq, "error": e.to_string()}),
+        };
+        if let Err(e) = self.audit("response", data) {
+            tracing::error!(request_seq, error = %e, "failed to audit a response");
+        }
+    }
+
+    pub fn with_creds(mut self, creds: ApiCreds) -> Self {
+        self.creds = Some(creds);
+        self
+    }
+
+    pub fn set_creds(&mut self, creds: ApiCreds) {
+        self.creds = Some(creds);
+    }
+
+    pub(crate) fn set_transport(&mut self, transport: Transport) {
+        self.market_data.set_transport(transport.clone());
+        self.transport = transport;
+    }
+
+    pub fn creds(&self) -> Option<&ApiCreds> {
+        self.creds.as_ref()
+    }
+
+    pub fn order_builder(&self) -> &OrderBuilder {
+        &self.order_builder
+    }
+
+    pub(crate) fn order_builder_mut(&mut self) -> &mut OrderBuilder {
+        &mut self.order_builder
+    }
+
+    pub fn market_data(&self) -> &MarketDataApi {
+        &self.market_data
+    }
+
+    async fn resolve_options(
+        &self,
+        token_id: &str,
+        options: Option<PartialCreateOrderOptions>,
+    ) -> Result<CreateOrderOptions> {
+        let tick_size = match options.as_ref().and_then(|o| o.tick_size) {
+            Some(ts) => ts,
+            None => self.market_data.get_tick_size(token_id).await?,
+        };
+
+        let neg_risk = match options.as_ref().and_then(|o| o.neg_risk) {
+            Some(nr) => nr,
+            None => self.market_data.get_neg_risk(token_id).await?,
+        };
+
+        Ok(CreateOrderOptions {
+            tick_size,
+            neg_risk,
+            salt: options.and_then(|o| o.salt),
+        })
+    }
+
+    pub async fn create_order(
+        &self,
+        order_args: &OrderArgs,
+        options: Option<PartialCreateOrderOptions>,
+    ) -> Result<SignedOrder> {
+        let create_options = self.resolve_options(&order_args.token_id, options).await?;
+        self.check_min_order_size(&order_args.token_id, order_args.size)?;
+
+        let fee_rate_bps = self
+            .resolve_fee_rate(&order_args.token_id, order_args.fee_rate_bps)
+            .await?;
+        let order_args = if fee_rate_bps != order_args.fee_rate_bps {
+            Cow::Owned(OrderArgs {
+                fee_rate_bps,
+                ..order_args.clone()
+            })
+        } else {
+            Cow::Borrowed(order_args)
+        };
+
+        let order = self
+            .order_builder
+            .create_order(&order_args, &create_options)
+            .await?;
+        self.audit("sign_order", &order)?;
+        Ok(order)
+    }
+
+    async fn resolve_fee_rate(&self, token_id: &str, fee_rate_bps: i32) -> Result<i32> {
+        if !self.auto_fee_rate || fee_rate_bps != 0 {
+            return Ok(fee_rate_bps);
+        }
+        self.market_data.get_fee_rate_bps(token_id).await
+    }
+
+    /// Rejects sizes below the market minimum when it is already cached from a
+    /// book or market response. Does not hit the network.
+    pub fn check_min_order_size(&self, token_id: &str, size: f64) -> Result<()> {
+        match self.market_data.cached_min_order_size(token_id) {
+            Some(min_size) if size < min_size => {
+                Err(ClobError::BelowMinimumSize { size, min_size })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn create_market_order(
+        &self,
+        order_args: &MarketOrderArgs,
+        options: Option<PartialCreateOrderOptions>,
+    ) -> Result<SignedOrder> {
+        let create_options = self.resolve_options(&order_args.token_id, options).await?;
+
+        let fee_rate_bps = self
+            .resolve_fee_rate(&order_args.token_id, order_args.fee_rate_bps)
+            .await?;
+        // without a worst price, take the one that fills the order on the
+        // current book
+        let price = if order_args.price > 0.0 {
+            order_args.price
+        } else {
+            self.market_data
+                .quote_market_order(order_args)
+                .await?
+                .worst_price
+        };
+        let order_args = if fee_rate_bps != order_args.fee_rate_bps || price != order_args.price {
+            Cow::Owned(MarketOrderArgs {
+                fee_rate_bps,
+                price,
+                ..order_args.clone()
+            })
+        } else {
+            Cow::Borrowed(order_args)
+        };
+
+        let order = self
+            .order_builder
+            .create_market_order(&order_args, &create_options)
+            .await?;
+        self.audit("sign_order", &order)?;
+        Ok(order)
+    }
+
+    pub async fn create_and_post_order(
+        &self,
+        order_args: &OrderArgs,
+        options: Option<PartialCreateOrderOptions>,
+        order_type: OrderType,
+    ) -> Result<PostOrderResponse> {
+        let order = self.create_order(order_args, options).await?;
+        self.post_order(&order, order_type).await
+    }
+
+    /// [`Self::post_order`] that fails with [`ClobError::Timeout`] once
+    /// `deadline` passes, risk checks included, so the caller can reprice.
+    pub async fn post_order_with_deadline(
+        &self,
+        order: &SignedOrder,
+        order_type: OrderType,
+        deadline: Duration,
+    ) -> Result<PostOrderResponse> {
+        with_deadline(deadline, self.post_order(order, order_type)).await
+    }
+
+    pub async fn create_and_post_order_with_deadline(
+        &self,
+        order_args: &OrderArgs,
+        options: Option<PartialCreateOrderOptions>,
+        order_type: OrderType,
+        deadline: Duration,
+    ) -> Result<PostOrderResponse> {
+        with_deadline(
+            deadline,
+            self.create_and_post_order(order_args, options, order_type),
+        )
+        .await
+    }
+
+    fn order_terms(&self, order: &SignedOrder) -> Result<OrderTerms> {
+        let decimals = collateral_decimals(self.order_builder.signer().chain_id());
+        OrderTerms::from_order_with_decimals(order, decimals)
+    }
+
+    // one whole collateral token or share in base units
+    fn collateral_unit(&self) -> f64 {
+        10f64.powi(collateral_decimals(self.order_builder.signer().chain_id()) as i32)
+    }
+
+    /// Runs the [`RiskConfig`] checks for `order`. Limits that need the
+    /// account's state (position, open orders) query the exchange.
+    pub async fn check_risk(&self, order: &SignedOrder) -> Result<()> {
+        let Some(risk) = &self.risk else {
+            return Ok(());
+        };
+        let terms = self.order_terms(order)?;
+        risk.check_notional(&terms)?;
+
+        if risk.max_price_deviation.is_some() {
+            let mid = self.market_data.get_midpoint(&terms.token_id).await?;
+            risk.check_price(&terms, mid)?;
+        }
+        if risk.max_position.is_some() && terms.side == Side::Buy {
+            let params = BalanceAllowanceParams {
+                asset_type: Some(AssetType::CONDITIONAL),
+                token_id: Some(terms.token_id.clone()),
+                signature_type: None,
+            };
+            let held = self.get_balance_allowance(&params).await?.balance_raw() as f64
+                / self.collateral_unit();
+            risk.check_position(&terms, held)?;
+        }
+        if risk.max_open_orders.is_some() {
+            let open = RestingOrder::from_open_orders(&self.get_orders(None).await?).len();
+            risk.check_open_orders(open)?;
+        }
+        Ok(())
+    }
+
+    /// Runs the [`SpreadGuard`] check for `order`, if one is set.
+    pub async fn check_spread(&self, order: &SignedOrder) -> Result<()> {
+        let Some(guard) = &self.spread_guard else {
+            return Ok(());
+        };
+        let terms = self.order_terms(order)?;
+        let (bid, ask) = self
+            .market_data
+            .get_best_prices(&terms.token_id, guard.max_book_age)
+            .await?;
+        match guard.check(&terms, bid, ask) {
+            Err(e) if guard.warn_only => {
+                tracing::warn!(token_id = %terms.token_id, "{}", e);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Checks that the account holds what `order` sells (collateral for a
+    /// buy, outcome tokens for a sell) and has approved it to the exchange.
+    /// Funds reserved by other open orders are not subtracted.
+    pub async fn check_balance(&self, order: &SignedOrder) -> Result<()> {
+        let terms = self.order_terms(order)?;
+        let needed: u128 = order.maker_amount.parse().map_err(|_| {
+            ClobError::InvalidParameter(format!("invalid maker_amount: {}", order.maker_amount))
+        })?;
+        let (asset_type, token_id, asset) = match terms.side {
+            Side::Buy => (AssetType::COLLATERAL, None, "USDC".to_string()),
+            Side::Sell => (
+                AssetType::CONDITIONAL,
+                Some(terms.token_id.clone()),
+                format!("token {}", terms.token_id),
+            ),
+        };
+        let params = BalanceAllowanceParams {
+            asset_type: Some(asset_type),
+            token_id,
+            signature_type: Some(order.signature_type as i32),
+        };
+        let response = self.get_balance_allowance(&params).await?;
+
+        let unit = self.collateral_unit();
+        let to_units = |raw: u128| raw as f64 / unit;
+        let available = response.balance_raw();
+        if available < needed {
+            return Err(ClobError::InsufficientBalance {
+                asset,
+                needed: to_units(needed),
+                available: to_units(available),
+            });
+        }
+
+        let neg_risk = self.market_data.get_neg_risk(&terms.token_id).await?;
+        let chain_id = self.order_builder.signer().chain_id();
+        let exchange = get_contract_config(chain_id, neg_risk)
+            .ok_or_else(|| ClobError::InvalidParameter("invalid chain_id".to_string()))?
+            .exchange;
+        // an account that has never approved anything gets no entry at all
+        if let Some(approved) = response.allowance_raw(exchange) {
+            if approved < needed {
+                return Err(ClobError::InsufficientAllowance {
+                    asset,
+                    spender: exchange.to_string(),
+                    needed: to_units(needed),
+                    available: to_units(approved),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn post_order(
+        &self,
+        order: &SignedOrder,
+        order_type: OrderType,
+    ) -> Result<PostOrderResponse> {
+        self.post_order_raw(order, order_type)
+            .await
+            .map(|response| response.data)
+    }
+
+    /// [`Self::post_order`] with the response JSON kept. A dry run's JSON is
+    /// the simulated response.
+    pub async fn post_order_raw(
+        &self,
+        order: &SignedOrder,
+        order_type: OrderType,
+    ) -> Result<Raw<PostOrderResponse>> {
+        if self.check_accepting_orders {
+            self.market_data
+                .ensure_accepting_orders(&order.token_id)
+                .await?;
+        }
+        self.check_risk(order).await?;
+        if matches!(order_type, OrderType::GTC | OrderType::GTD) {
+            self.check_spread(order).await?;
+        }
+        if self.check_balance {
+            self.check_balance(order).await?;
+        }
+
+        if let Some(log) = &self.dry_run {
+            let owner = self.creds.as_ref().map(|c| c.api_key.as_str());
+            let body = order_body(order, owner.unwrap_or_default(), order_type);
+            let seq = log.record("POST", endpoints::POST_ORDER, Some(body));
+            let response = PostOrderResponse {
+                success: true,
+                error_msg: String::new(),
+                order_id: format!("dry-run-{}", seq),
+                making_amount: String::new(),
+                status: "live".to_string(),
+                taking_amount: String::new(),
+                order_hashes: Vec::new(),
+            };
+            let json = serde_json::to_value(&response).map_err(|e| ClobError::Json {
+                message: e.to_string(),
+            })?;
+            return Ok(Raw::new(response, json));
+        }
+
+        let creds = require_creds(&self.creds)?;
+
+        let body = order_body(order, &creds.api_key, order_type);
+        let body_str = serde_json::to_string(&body).unwrap();
+
+        let headers = create_level_2_headers(
+            self.order_builder.signer(),
+            creds,
+            "POST",
+            endpoints::POST_ORDER,
+            Some(&body_str),
+        )?;
+
+        let request = self.audit("post_order", &body)?;
+        let result = self
+            .transport
+            .post_with_l2_headers(endpoints::POST_ORDER, &headers, &body)
+            .await;
+        self.audit_response(request, &result);
+        result
+    }
+
+    /// Takes liquidity from the live book with a FAK order priced at the worst
+    /// level that `limit` and `limit_price` allow.
+    pub async fn sweep(
+        &self,
+        token_id: &str,
+        side: Side,
+        limit: SweepLimit,
+        limit_price: f64,
+    ) -> Result<SweepResult> {
+        if self.dry_run.is_none() {
+            require_creds(&self.creds)?;
+        }
+        check_limit(limit)?;
+
+        let book = self.market_data.get_order_book(token_id).await?;
+        let plan = plan_sweep(&book, side, limit, limit_price)?.ok_or_else(|| {
+            ClobError::InvalidParameter(format!(
+                "no {} liquidity within limit price {}",
+                side, limit_price
+            ))
+        })?;
+
+        let options = PartialCreateOrderOptions {
+            tick_size: book.tick_size.as_deref().and_then(|ts| ts.parse().ok()),
+            neg_risk: book.neg_risk,
+            salt: None,
+        };
+        let order_args = OrderArgs::new(token_id, plan.price, plan.size, side);
+        let order = self.create_order(&order_args, Some(options)).await?;
+        let response = self.post_order(&order, OrderType::FAK).await?;
+
+        Ok(SweepResult::new(plan, order, response))
+    }
+
+    /// Moves the open orders on `ladder.token_id` onto the ladder with as few
+    /// requests as possible: one cancel for the levels that no longer match,
+    /// then a post per missing level.
+    pub async fn replace_ladder(
+        &self,
+        ladder: &QuoteLadder,
+        order_type: OrderType,
+    ) -> Result<LadderUpdate> {
+        let params = OpenOrderParams {
+            asset_id: Some(ladder.token_id.clone()),
+            ..Default::default()
+        };
+        let resting = RestingOrder::from_open_orders(&self.get_orders(Some(&params)).await?);
+        self.replace_ladder_from(ladder, &resting, order_type).await
+    }
+
+    /// Like [`Self::replace_ladder`], for callers that already track their
+    /// resting orders.
+    pub async fn replace_ladder_from(
+        &self,
+        ladder: &QuoteLadder,
+        resting: &[RestingOrder],
+        order_type: OrderType,
+    ) -> Result<LadderUpdate> {
+        let diff = diff_ladder(&ladder.orders(), resting);
+
+        let canceled = if diff.cancel.is_empty() {
+            None
+        } else {
+            Some(self.cancel_orders(&diff.cancel).await?)
+        };
+
+        let options = PartialCreateOrderOptions {
+            tick_size: Some(ladder.tick_size),
+            neg_risk: None,
+            salt: None,
+        };
+        let mut posted = Vec::with_capacity(diff.post.len());
+        for order_args in &diff.post {
+            let order = self.create_order(order_args, Some(options.clone())).await?;
+            posted.push(self.post_order(&order, order_type).await?);
+        }
+
+        Ok(LadderUpdate {
+            diff,
+            canceled,
+            posted,
+        })
+    }
+
+    pub async fn cancel(&self, order_id: &str) -> Result<serde_json::Value> {
+        let body = serde_json::json!({"orderID": order_id});
+        if let Some(log) = &self.dry_run {
+            log.record("DELETE", endpoints::CANCEL, Some(body));
+            return Ok(dry_run_cancel_response(&[order_id.to_string()]));
+        }
+
+        let creds = require_creds(&self.creds)?;
+        let body_str = serde_json::to_string(&body).unwrap();
+
+        let headers = create_level_2_headers(
+            self.order_builder.signer(),
+            creds,
+            "DELETE",
+            endpoints::CANCEL,
+            Some(&body_str),
+        )?;
+
+        let request = self.audit("cancel", &body)?;
+        let result = self
+            .transport
+            .delete_with_l2_headers_and_body(endpoints::CANCEL, &headers, &body)
+            .await;
+        self.audit_response(request, &result);
+        result
+    }
+
+    pub async fn cancel_with_deadline(
+        &self,
+        order_id: &str,
+        deadline: Duration,
+    ) -> Result<serde_json::Value> {
+        with_deadline(deadline, self.cancel(order_id)).await
+    }
+
+    pub async fn cancel_orders(&self, order_ids: &[String]) -> Result<serde_json::Value> {
+        let body = serde_json::json!(order_ids);
+        if let Some(log) = &self.dry_run {
+            log.record("DELETE", endpoints::CANCEL_ORDERS, Some(body));
+            return Ok(dry_run_cancel_response(order_ids));
+        }
+
+        let creds = require_creds(&self.creds)?;
+        let body_str = serde_json::to_string(&body).unwrap();
+
+        let headers = create_level_2_headers(
+            self.order_builder.signer(),
+            creds,
+            "DELETE",
+            endpoints::CANCEL_ORDERS,
+            Some(&body_str),
+        )?;
+
+        let request = self.audit("cancel_orders", &body)?;
+        let result = self
+            .transport
+            .delete_with_l2_headers_and_body(endpoints::CANCEL_ORDERS, &headers, &body)
+            .await;
+        self.audit_response(request, &result);
+        result
+    }
+
+    pub async fn cancel_all(&self) -> Result<serde_json::Value> {
+        if let Some(log) = &self.dry_run {
+            log.record("DELETE", endpoints::CANCEL_ALL, None);
+            return Ok(dry_run_cancel_response(&[]));
+        }
+
+        let creds = require_creds(&self.creds)?;
+        let headers = create_level_2_headers(
+            self.order_builder.signer(),
+            creds,
+            "DELETE",
+            endpoints::CANCEL_ALL,
+            None,
+        )?;
+
+        let request = self.audit("cancel_all", serde_json::Value::Null)?;
+        let result = self
+            .transport
+            .delete_with_l2_headers(endpoints::CANCEL_ALL, &headers)
+            .await;
+        self.audit_response(request, &result);
+        result
+    }
+
+    /// Raw open orders matching `params`, following `next_cursor` so every
+    /// page's `data` ends up in the returned response.
+    pub async fn get_orders(&self, params: Option<&OpenOrderParams>) -> Result<serde_json::Value> {
+        let mut response: serde_json::Value = self.orders_request(params, FIRST_CURSOR).await?;
+        loop {
+            let cursor = match response.get("next_cursor").and_then(|c| c.as_str()) {
+                Some(cursor) if !is_last_page(cursor) => cursor.to_string(),
+                _ => return Ok(response),
+            };
+            let mut page: serde_json::Value = self.orders_request(params, &cursor).await?;
+            let orders = match page.get_mut("data").map(serde_json::Value::take) {
+                Some(serde_json::Value::Array(orders)) => orders,
+                _ => Vec::new(),
+            };
+            if let Some(data) = response.get_mut("data").and_then(|d| d.as_array_mut()) {
+                data.extend(orders);
+            }
+            // a server handing back the same cursor would loop forever
+            let next = match page.get("next_cursor") {
+                Some(next) if next.as_str() != Some(&cursor) => next.clone(),
+                _ => END_CURSOR.into(),
+            };
+            response["next_cursor"] = next;
+        }
+    }
+
+    /// One page of open orders, starting from the first without a cursor.
+    pub async fn get_orders_page(
+        &self,
+        params: Option<&OpenOrderParams>,
+        cursor: Option<&str>,
+    ) -> Result<OpenOrdersResponse> {
+        self.orders_request(params, cursor.unwrap_or(FIRST_CURSOR))
+            .await
+    }
+
+    pub async fn get_orders_page_raw(
+        &self,
+        params: Option<&OpenOrderParams>,
+        cursor: Option<&str>,
+    ) -> Result<Raw<OpenOrdersResponse>> {
+        self.orders_request(params, cursor.unwrap_or(FIRST_CURSOR))
+            .await
+    }
+
+    /// Every open order matching `params`, page by page.
+    pub async fn get_all_orders(
+        &self,
+        params: Option<&OpenOrderParams>,
+    ) -> Result<Vec<OrderStatus>> {
+        let mut orders = Vec::new();
+        let mut cursor = FIRST_CURSOR.to_string();
+        loop {
+            let page = self.get_orders_page(params, Some(&cursor)).await?;
+            orders.extend(page.data);
+            if is_last_page(&page.next_cursor) || page.next_cursor == cursor {
+                return Ok(orders);
+            }
+            cursor = page.next_cursor;
+        }
+    }
+
+    async fn orders_request<T: serde::de::DeserializeOwned>(
+        &self,
+        params: Option<&OpenOrderParams>,
+        cursor: &str,
+    ) -> Result<T> {
+        let creds = require_creds(&self.creds)?;
+        let headers = create_level_2_headers(
+            self.order_builder.signer(),
+            creds,
+            "GET",
+            endpoints::ORDERS,
+            None,
+        )?;
+        let query = OrdersQuery {
+            params,
+            next_cursor: cursor,
+        };
+        self.transport
+            .get_with_l2_headers_and_query(endpoints::ORDERS, &headers, &query)
+            .await
+    }
+
+    pub async fn get_order(&self, order_id: &str) -> Result<OrderStatus> {
+        self.order_request(order_id).await
+    }
+
+    pub async fn get_order_raw(&self, order_id: &str) -> Result<Raw<OrderStatus>> {
+        self.order_request(order_id).await
+    }
+
+    async fn order_request<T: serde::de::DeserializeOwned>(&self, order_id: &str) -> Result<T> {
+        let creds = require_creds(&self.creds)?;
+        let path = join_segment(endpoints::GET_ORDER, order_id);
+        let headers =
+            create_level_2_headers(self.order_builder.signer(), creds, "GET", &path, None)?;
+
+        self.transport.get_with_l2_headers(&path, &headers).await
+    }
+
+    /// Polls [`Self::get_order`] every `poll_interval` until the order is
+    /// matched, canceled or unmatched, and returns that final state. Fails
+    /// with [`ClobError::Timeout`] after `timeout`.
+    pub async fn wait_for_order_terminal(
+        &self,
+        order_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<OrderStatus> {
+        with_deadline(timeout, async {
+            loop {
+                let order = self.get_order(order_id).await?;
+                if order.status.is_terminal() {
+                    return Ok(order);
+                }
+                tokio::time::sleep(poll_interval).await;
+            }
+        })
+        .await
+    }
+
+    pub async fn get_trades(&self, params: Option<&TradeParams>) -> Result<serde_json::Value> {
+        let creds = require_creds(&self.creds)?;
+        let headers = create_level_2_headers(
+            self.order_builder.signer(),
+            creds,
+            "GET",
+            endpoints::TRADES,
+            None,
+        )?;
+
+        self.transport
+            .get_with_l2_headers_and_query(endpoints::TRADES, &headers, &params)
+            .await
+    }
+
+    /// Applies our trades matching `params` to `portfolio`, then marks every
+    /// open position to its current midpoint. Returns how many fills were
+    /// new.
+    pub async fn update_portfolio(
+        &self,
+        portfolio: &mut Portfolio,
+        params: Option<&TradeParams>,
+    ) -> Result<usize> {
+        let trades = self.get_trades(params).await?;
+        let maker = self.order_builder.funder().to_checksum(None);
+        let applied = portfolio.apply_trades(&trades, &maker);
+
+        let params: Vec<BookParams> = portfolio
+            .open_tokens()
+            .into_iter()
+            .map(BookParams::new)
+            .collect();
+        if !params.is_empty() {
+            for entry in self.market_data.get_midpoints(&params).await? {
+                if let Some(mid) = entry.mid {
+                    portfolio.set_mark(entry.token_id, amount_to_f64(mid));
+                }
+            }
+        }
+        Ok(applied)
+    }
+
+    pub async fn get_balance_allowance(
+        &self,
+        params: &BalanceAllowanceParams,
+    ) -> Result<BalanceAllowanceResponse> {
+        self.balance_allowance_request(endpoints::GET_BALANCE_ALLOWANCE, params)
+            .await
+    }
+
+    pub async fn get_balance_allowance_raw(
+        &self,
+        params: &BalanceAllowanceParams,
+    ) -> Result<Raw<BalanceAllowanceResponse>> {
+        self.balance_allowance_request(endpoints::GET_BALANCE_ALLOWANCE, params)
+            .await
+    }
+
+    pub async fn update_balance_allowance(
+        &self,
+        params: &BalanceAllowanceParams,
+    ) -> Result<BalanceAllowanceResponse> {
+        if let Some(log) = &self.dry_run {
+            log.record("GET", endpoints::UPDATE_BALANCE_ALLOWANCE, None);
+            return self.get_balance_allowance(params).await;
+        }
+        self.balance_allowance_request(endpoints::UPDATE_BALANCE_ALLOWANCE, params)
+            .await
+    }
+
+    async fn balance_allowance_request<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &BalanceAllowanceParams,
+    ) -> Result<T> {
+        let creds = require_creds(&self.creds)?;
+        let headers =
+            create_level_2_headers(self.order_builder.signer(), creds, "GET", endpoint, None)?;
+
+        self.transport
+            .get_with_l2_headers_and_query(endpoint, &headers, params)
+            .await
+    }
+}
@@ -0,0 +1,509 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::Stream;
+
+use super::{AuthApi, MarketDataApi, OrdersApi};
+use crate::error::Result;
+use crate::market_cache::{MarketCache, MarketSource, TokenInfo};
+use crate::neg_risk::{CompleteSetArb, NegRiskEvent};
+use crate::pair_arb::ArbOpportunity;
+use crate::raw::Raw;
+use crate::resolution::Resolution;
+use crate::types::{
+    ApiCreds, ApiKeysResponse, BalanceAllowanceParams, BalanceAllowanceResponse,
+    BatchMidpointResponse, BatchPriceResponse, BatchResponse, BatchSpreadResponse, BookParams,
+    DeleteApiKeyResponse, FeeRateResponse, LastTradePriceResponse, LastTradesPriceEntry, Market,
+    MarketFilter, MarketTradeEvent, MarketsResponse, MidpointResponse, NegRiskResponse, OrderBook,
+    PriceHistoryInterval, PriceHistoryResponse, PricePoint, PriceResponse, Side, SimplifiedMarket,
+    SimplifiedMarketsResponse, SpreadResponse, TickSize, TickSizeResponse,
+};
+
+/// Market listings and per-market metadata, from
+/// [`ClobClient::markets`](crate::ClobClient::markets).
+#[derive(Debug, Clone, Copy)]
+pub struct MarketsApi<'a> {
+    market_data: &'a MarketDataApi,
+}
+
+impl<'a> MarketsApi<'a> {
+    pub(crate) fn new(market_data: &'a MarketDataApi) -> Self {
+        Self { market_data }
+    }
+
+    pub async fn get_markets_page(&self, cursor: Option<&str>) -> Result<MarketsResponse> {
+        self.market_data.get_markets_page(cursor).await
+    }
+
+    pub async fn get_markets_page_raw(&self, cursor: Option<&str>) -> Result<Raw<MarketsResponse>> {
+        self.market_data.get_markets_page_raw(cursor).await
+    }
+
+    pub async fn get_markets(&self) -> Result<Vec<Market>> {
+        self.market_data.get_markets().await
+    }
+
+    pub async fn get_markets_concurrent(&self, concurrency: usize) -> Result<Vec<Market>> {
+        self.market_data.get_markets_concurrent(concurrency).await
+    }
+
+    pub fn find_markets(
+        &self,
+        filter: MarketFilter,
+    ) -> impl Stream<Item = Result<Market>> + Send + 'a {
+        self.market_data.find_markets(filter)
+    }
+
+    pub async fn get_neg_risk_siblings(&self, neg_risk_market_id: &str) -> Result<Vec<Market>> {
+        self.market_data
+            .get_neg_risk_siblings(neg_risk_market_id)
+            .await
+    }
+
+    pub async fn get_neg_risk_event(&self, neg_risk_market_id: &str) -> Result<NegRiskEvent> {
+        self.market_data
+            .get_neg_risk_event(neg_risk_market_id)
+            .await
+    }
+
+    pub async fn get_market(&self, condition_id: &str) -> Result<Market> {
+        self.market_data.get_market(condition_id).await
+    }
+
+    pub async fn get_market_raw(&self, condition_id: &str) -> Result<Raw<Market>> {
+        self.market_data.get_market_raw(condition_id).await
+    }
+
+    pub async fn get_resolution(&self, condition_id: &str) -> Result<Resolution> {
+        self.market_data.get_resolution(condition_id).await
+    }
+
+    pub fn lookup_token(&self, token_id: &str) -> Option<TokenInfo> {
+        self.market_data.lookup_token(token_id)
+    }
+
+    pub fn market_cache(&self) -> &'a MarketCache {
+        self.market_data.market_cache()
+    }
+
+    pub async fn refresh_market_cache(&self, source: MarketSource) -> Result<usize> {
+        self.market_data.refresh_market_cache(source).await
+    }
+
+    pub async fn is_market_accepting_orders(&self, id: &str) -> Result<bool> {
+        self.market_data.is_market_accepting_orders(id).await
+    }
+
+    pub async fn ensure_accepting_orders(&self, id: &str) -> Result<()> {
+        self.market_data.ensure_accepting_orders(id).await
+    }
+
+    pub async fn market_by_condition_id(&self, condition_id: &str) -> Result<Arc<Market>> {
+        self.market_data.market_by_condition_id(condition_id).await
+    }
+
+    pub async fn market_by_slug(&self, slug: &str) -> Result<Arc<Market>> {
+        self.market_data.market_by_slug(slug).await
+    }
+
+    pub fn market_for_token(&self, token_id: &str) -> Option<Arc<Market>> {
+        self.market_data.market_for_token(token_id)
+    }
+
+    pub async fn get_token_ids(&self, market: &str) -> Result<Vec<(String, String)>> {
+        self.market_data.get_token_ids(market).await
+    }
+
+    pub async fn get_simplified_markets_page(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<SimplifiedMarketsResponse> {
+        self.market_data.get_simplified_markets_page(cursor).await
+    }
+
+    pub async fn get_simplified_markets_page_raw(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<Raw<SimplifiedMarketsResponse>> {
+        self.market_data
+            .get_simplified_markets_page_raw(cursor)
+            .await
+    }
+
+    pub async fn get_simplified_markets(&self) -> Result<Vec<SimplifiedMarket>> {
+        self.market_data.get_simplified_markets().await
+    }
+
+    pub async fn get_simplified_markets_concurrent(
+        &self,
+        concurrency: usize,
+    ) -> Result<Vec<SimplifiedMarket>> {
+        self.market_data
+            .get_simplified_markets_concurrent(concurrency)
+            .await
+    }
+
+    pub fn stream_simplified_markets(
+        &self,
+    ) -> impl Stream<Item = Result<SimplifiedMarket>> + Send + 'a {
+        self.market_data.stream_simplified_markets()
+    }
+
+    pub async fn get_sampling_markets_page(&self, cursor: Option<&str>) -> Result<MarketsResponse> {
+        self.market_data.get_sampling_markets_page(cursor).await
+    }
+
+    pub async fn get_sampling_markets_page_raw(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<Raw<MarketsResponse>> {
+        self.market_data.get_sampling_markets_page_raw(cursor).await
+    }
+
+    pub async fn get_sampling_markets(&self) -> Result<Vec<Market>> {
+        self.market_data.get_sampling_markets().await
+    }
+
+    pub fn stream_sampling_markets(&self) -> impl Stream<Item = Result<Market>> + Send + 'a {
+        self.market_data.stream_sampling_markets()
+    }
+
+    pub async fn get_reward_markets(&self) -> Result<Vec<Market>> {
+        self.market_data.get_reward_markets().await
+    }
+
+    pub async fn get_sampling_simplified_markets_page(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<SimplifiedMarketsResponse> {
+        self.market_data
+            .get_sampling_simplified_markets_page(cursor)
+            .await
+    }
+
+    pub async fn get_sampling_simplified_markets_page_raw(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<Raw<SimplifiedMarketsResponse>> {
+        self.market_data
+            .get_sampling_simplified_markets_page_raw(cursor)
+            .await
+    }
+
+    pub async fn get_sampling_simplified_markets(&self) -> Result<Vec<SimplifiedMarket>> {
+        self.market_data.get_sampling_simplified_markets().await
+    }
+
+    pub fn stream_sampling_simplified_markets(
+        &self,
+    ) -> impl Stream<Item = Result<SimplifiedMarket>> + Send + 'a {
+        self.market_data.stream_sampling_simplified_markets()
+    }
+
+    pub async fn get_market_trades_events(
+        &self,
+        condition_id: &str,
+    ) -> Result<Vec<MarketTradeEvent>> {
+        self.market_data
+            .get_market_trades_events(condition_id)
+            .await
+    }
+
+    pub async fn get_market_trades_events_raw(
+        &self,
+        condition_id: &str,
+    ) -> Result<Vec<Raw<MarketTradeEvent>>> {
+        self.market_data
+            .get_market_trades_events_raw(condition_id)
+            .await
+    }
+
+    pub async fn get_tick_size(&self, token_id: &str) -> Result<TickSize> {
+        self.market_data.get_tick_size(token_id).await
+    }
+
+    pub async fn get_tick_size_raw(&self, token_id: &str) -> Result<Raw<TickSizeResponse>> {
+        self.market_data.get_tick_size_raw(token_id).await
+    }
+
+    pub async fn get_neg_risk(&self, token_id: &str) -> Result<bool> {
+        self.market_data.get_neg_risk(token_id).await
+    }
+
+    pub async fn get_neg_risk_raw(&self, token_id: &str) -> Result<Raw<NegRiskResponse>> {
+        self.market_data.get_neg_risk_raw(token_id).await
+    }
+
+    pub async fn get_fee_rate_bps(&self, token_id: &str) -> Result<i32> {
+        self.market_data.get_fee_rate_bps(token_id).await
+    }
+
+    pub async fn get_fee_rate_raw(&self, token_id: &str) -> Result<Raw<FeeRateResponse>> {
+        self.market_data.get_fee_rate_raw(token_id).await
+    }
+
+    pub async fn get_min_order_size(&self, token_id: &str) -> Result<f64> {
+        self.market_data.get_min_order_size(token_id).await
+    }
+}
+
+/// Books, quotes and price history, from
+/// [`ClobClient::prices`](crate::ClobClient::prices).
+#[derive(Debug, Clone, Copy)]
+pub struct PricesApi<'a> {
+    market_data: &'a MarketDataApi,
+}
+
+impl<'a> PricesApi<'a> {
+    pub(crate) fn new(market_data: &'a MarketDataApi) -> Self {
+        Self { market_data }
+    }
+
+    pub async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
+        self.market_data.get_order_book(token_id).await
+    }
+
+    pub async fn get_order_book_raw(&self, token_id: &str) -> Result<Raw<OrderBook>> {
+        self.market_data.get_order_book_raw(token_id).await
+    }
+
+    pub async fn get_order_books(&self, params: &[BookParams]) -> Result<Vec<OrderBook>> {
+        self.market_data.get_order_books(params).await
+    }
+
+    pub async fn get_order_books_raw(&self, params: &[BookParams]) -> Result<Vec<Raw<OrderBook>>> {
+        self.market_data.get_order_books_raw(params).await
+    }
+
+    pub async fn get_order_books_partial(&self, params: &[BookParams]) -> BatchResponse<OrderBook> {
+        self.market_data.get_order_books_partial(params).await
+    }
+
+    pub async fn get_midpoint(&self, token_id: &str) -> Result<f64> {
+        self.market_data.get_midpoint(token_id).await
+    }
+
+    pub async fn get_midpoint_raw(&self, token_id: &str) -> Result<Raw<MidpointResponse>> {
+        self.market_data.get_midpoint_raw(token_id).await
+    }
+
+    pub async fn get_midpoints(&self, params: &[BookParams]) -> Result<Vec<BatchMidpointResponse>> {
+        self.market_data.get_midpoints(params).await
+    }
+
+    pub async fn get_midpoints_partial(
+        &self,
+        params: &[BookParams],
+    ) -> BatchResponse<BatchMidpointResponse> {
+        self.market_data.get_midpoints_partial(params).await
+    }
+
+    pub async fn get_midpoints_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchMidpointResponse>> {
+        self.market_data.get_midpoints_by_token(params).await
+    }
+
+    pub async fn get_price(&self, token_id: &str, side: Side) -> Result<f64> {
+        self.market_data.get_price(token_id, side).await
+    }
+
+    pub async fn get_price_raw(&self, token_id: &str, side: Side) -> Result<Raw<PriceResponse>> {
+        self.market_data.get_price_raw(token_id, side).await
+    }
+
+    pub async fn get_prices(&self, params: &[BookParams]) -> Result<Vec<BatchPriceResponse>> {
+        self.market_data.get_prices(params).await
+    }
+
+    pub async fn get_prices_partial(
+        &self,
+        params: &[BookParams],
+    ) -> BatchResponse<BatchPriceResponse> {
+        self.market_data.get_prices_partial(params).await
+    }
+
+    pub async fn get_prices_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchPriceResponse>> {
+        self.market_data.get_prices_by_token(params).await
+    }
+
+    pub async fn get_spread(&self, token_id: &str) -> Result<f64> {
+        self.market_data.get_spread(token_id).await
+    }
+
+    pub async fn get_spread_raw(&self, token_id: &str) -> Result<Raw<SpreadResponse>> {
+        self.market_data.get_spread_raw(token_id).await
+    }
+
+    pub async fn get_spreads(&self, params: &[BookParams]) -> Result<Vec<BatchSpreadResponse>> {
+        self.market_data.get_spreads(params).await
+    }
+
+    pub async fn get_spreads_partial(
+        &self,
+        params: &[BookParams],
+    ) -> BatchResponse<BatchSpreadResponse> {
+        self.market_data.get_spreads_partial(params).await
+    }
+
+    pub async fn get_spreads_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchSpreadResponse>> {
+        self.market_data.get_spreads_by_token(params).await
+    }
+
+    pub async fn plan_neg_risk_arb(
+        &self,
+        neg_risk_market_id: &str,
+        min_edge: f64,
+    ) -> Result<Option<CompleteSetArb>> {
+        self.market_data
+            .plan_neg_risk_arb(neg_risk_market_id, min_edge)
+            .await
+    }
+
+    pub async fn scan_pair_arbs(
+        &self,
+        markets: &[Market],
+        min_edge: f64,
+    ) -> BatchResponse<ArbOpportunity> {
+        self.market_data.scan_pair_arbs(markets, min_edge).await
+    }
+
+    pub async fn get_last_trade_price(&self, token_id: &str) -> Result<f64> {
+        self.market_data.get_last_trade_price(token_id).await
+    }
+
+    pub async fn get_last_trade_price_raw(
+        &self,
+        token_id: &str,
+    ) -> Result<Raw<LastTradePriceResponse>> {
+        self.market_data.get_last_trade_price_raw(token_id).await
+    }
+
+    pub async fn get_last_trades_prices(
+        &self,
+        params: &[BookParams],
+    ) -> Result<Vec<LastTradesPriceEntry>> {
+        self.market_data.get_last_trades_prices(params).await
+    }
+
+    pub async fn get_last_trades_prices_partial(
+        &self,
+        params: &[BookParams],
+    ) -> BatchResponse<LastTradesPriceEntry> {
+        self.market_data
+            .get_last_trades_prices_partial(params)
+            .await
+    }
+
+    pub async fn get_price_history(
+        &self,
+        token_id: &str,
+        interval: PriceHistoryInterval,
+        fidelity: Option<u32>,
+    ) -> Result<Vec<PricePoint>> {
+        self.market_data
+            .get_price_history(token_id, interval, fidelity)
+            .await
+    }
+
+    pub async fn get_price_history_raw(
+        &self,
+        token_id: &str,
+        interval: PriceHistoryInterval,
+        fidelity: Option<u32>,
+    ) -> Result<Raw<PriceHistoryResponse>> {
+        self.market_data
+            .get_price_history_raw(token_id, interval, fidelity)
+            .await
+    }
+
+    pub async fn get_price_history_range(
+        &self,
+        token_id: &str,
+        start_ts: i64,
+        end_ts: i64,
+        fidelity: Option<u32>,
+    ) -> Result<Vec<PricePoint>> {
+        self.market_data
+            .get_price_history_range(token_id, start_ts, end_ts, fidelity)
+            .await
+    }
+}
+
+/// API keys and collateral balances for the signing wallet, from
+/// [`ClobClient::account`](crate::ClobClient::account).
+#[derive(Clone, Copy)]
+pub struct AccountApi<'a> {
+    auth: &'a AuthApi,
+    orders: &'a OrdersApi,
+}
+
+impl<'a> AccountApi<'a> {
+    pub(crate) fn new(auth: &'a AuthApi, orders: &'a OrdersApi) -> Self {
+        Self { auth, orders }
+    }
+
+    pub fn address(&self) -> String {
+        self.auth.signer().address_string()
+    }
+
+    pub fn creds(&self) -> Option<&'a ApiCreds> {
+        self.auth.creds()
+    }
+
+    pub async fn create_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
+        self.auth.create_api_key(nonce).await
+    }
+
+    pub async fn derive_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
+        self.auth.derive_api_key(nonce).await
+    }
+
+    pub async fn create_or_derive_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
+        self.auth.create_or_derive_api_key(nonce).await
+    }
+
+    pub async fn get_api_keys(&self) -> Result<ApiKeysResponse> {
+        self.auth.get_api_keys().await
+    }
+
+    pub async fn get_api_keys_raw(&self) -> Result<Raw<ApiKeysResponse>> {
+        self.auth.get_api_keys_raw().await
+    }
+
+    pub async fn delete_api_key(&self) -> Result<DeleteApiKeyResponse> {
+        self.auth.delete_api_key().await
+    }
+
+    pub async fn revoke_api_key(&self, creds: &ApiCreds) -> Result<DeleteApiKeyResponse> {
+        self.auth.revoke_api_key(creds).await
+    }
+
+    pub async fn get_balance_allowance(
+        &self,
+        params: &BalanceAllowanceParams,
+    ) -> Result<BalanceAllowanceResponse> {
+        self.orders.get_balance_allowance(params).await
+    }
+
+    pub async fn get_balance_allowance_raw(
+        &self,
+        params: &BalanceAllowanceParams,
+    ) -> Result<Raw<BalanceAllowanceResponse>> {
+        self.orders.get_balance_allowance_raw(params).await
+    }
+
+    pub async fn update_balance_allowance(
+        &self,
+        params: &BalanceAllowanceParams,
+    ) -> Result<BalanceAllowanceResponse> {
+        self.orders.update_balance_allowance(params).await
+    }
+}
@@ -0,0 +1,212 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{ClobError, Result};
+
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const REDACTED: &str = "[redacted]";
+
+/// The hashed part of an audit line.
+#[derive(Serialize)]
+struct EntryBody<'a> {
+    seq: u64,
+    ts: i64,
+    event: &'a str,
+    data: &'a serde_json::Value,
+    prev_hash: &'a str,
+}
+
+/// One line of an audit file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    /// Unix milliseconds.
+    pub ts: i64,
+    pub event: String,
+    pub data: serde_json::Value,
+    /// `hash` of the previous line, zeros for the first.
+    pub prev_hash: String,
+    /// SHA-256 over the other fields.
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> Result<String> {
+        entry_hash(&EntryBody {
+            seq: self.seq,
+            ts: self.ts,
+            event: &self.event,
+            data: &self.data,
+            prev_hash: &self.prev_hash,
+        })
+    }
+}
+
+fn entry_hash(body: &EntryBody<'_>) -> Result<String> {
+    let json = serde_json::to_vec(body).map_err(|e| ClobError::Json {
+        message: e.to_string(),
+    })?;
+    Ok(hex::encode(Sha256::digest(&json)))
+}
+
+fn parse_line(line: &str, line_no: usize) -> Result<AuditEntry> {
+    serde_json::from_str(line).map_err(|e| ClobError::Json {
+        message: format!("audit log line {}: {}", line_no, e),
+    })
+}
+
+struct Writer {
+    file: File,
+    seq: u64,
+    last_hash: String,
+    failed: bool,
+}
+
+/// Append-only JSONL record of signed orders, posts, cancels and the
+/// server's answers. Each line carries the hash of the previous one, so
+/// [`verify`](Self::verify) detects edited, dropped or reordered lines.
+/// Clones share the file.
+#[derive(Clone)]
+pub struct AuditLog {
+    writer: Arc<Mutex<Writer>>,
+    redact_signatures: bool,
+}
+
+impl AuditLog {
+    /// Opens `path` for appending, continuing the hash chain of any lines
+    /// already in it. Signatures are redacted unless
+    /// [`with_signatures`](Self::with_signatures) says otherwise.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut seq = 0;
+        let mut last_hash = GENESIS_HASH.to_string();
+        if path.exists() {
+            for (i, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry = parse_line(&line, i + 1)?;
+                seq = entry.seq;
+                last_hash = entry.hash;
+            }
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: Arc::new(Mutex::new(Writer {
+                file,
+                seq,
+                last_hash,
+                failed: false,
+            })),
+            redact_signatures: true,
+        })
+    }
+
+    /// Keep order and request signatures in the log instead of redacting
+    /// them.
+    pub fn with_signatures(mut self, keep: bool) -> Self {
+        self.redact_signatures = !keep;
+        self
+    }
+
+    /// `true` once a write has failed, after which the log may be missing
+    /// entries for requests that were sent.
+    pub fn has_failed(&self) -> bool {
+        self.writer.lock().unwrap().failed
+    }
+
+    /// Forces everything written so far to disk.
+    pub fn sync(&self) -> Result<()> {
+        self.writer.lock().unwrap().file.sync_all()?;
+        Ok(())
+    }
+
+    /// Appends and flushes one entry, returning its sequence number.
+    pub fn record(&self, event: &str, data: &serde_json::Value) -> Result<u64> {
+        let mut data = data.clone();
+        if self.redact_signatures {
+            redact(&mut data);
+        }
+
+        let mut writer = self.writer.lock().unwrap();
+        let seq = writer.seq + 1;
+        let body = EntryBody {
+            seq,
+            ts: chrono::Utc::now().timestamp_millis(),
+            event,
+            data: &data,
+            prev_hash: &writer.last_hash,
+        };
+        let entry = AuditEntry {
+            seq,
+            ts: body.ts,
+            event: event.to_string(),
+            hash: entry_hash(&body)?,
+            prev_hash: writer.last_hash.clone(),
+            data,
+        };
+        let mut line = serde_json::to_vec(&entry).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })?;
+        line.push(b'\n');
+        let written = writer
+            .file
+            .write_all(&line)
+            .and_then(|_| writer.file.flush());
+        if let Err(e) = written {
+            writer.failed = true;
+            return Err(e.into());
+        }
+
+        writer.seq = seq;
+        writer.last_hash = entry.hash;
+        Ok(seq)
+    }
+
+    /// Checks the hash chain of the file at `path` and returns its entries.
+    pub fn verify(path: impl AsRef<Path>) -> Result<Vec<AuditEntry>> {
+        let mut entries = Vec::new();
+        let mut prev_hash = GENESIS_HASH.to_string();
+        for (i, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = parse_line(&line, i + 1)?;
+            let tampered = entry.prev_hash != prev_hash
+                || entry.seq != entries.len() as u64 + 1
+                || entry.compute_hash()? != entry.hash;
+            if tampered {
+                return Err(ClobError::InvalidParameter(format!(
+                    "audit log chain broken at line {}",
+                    i + 1
+                )));
+            }
+            prev_hash = entry.hash.clone();
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+fn redact(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if key.eq_ignore_ascii_case("signature") {
+                    *value = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
@@ -0,0 +1,443 @@
+//! Replays captured market data through a paper-trading engine.
+//!
+//! Captures are JSON lines, one [`CaptureEvent`] per line, ordered by
+//! timestamp. The engine keeps a virtual clock that follows the events, so
+//! strategies see the same time they would have seen live.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ClobError, Result};
+use crate::types::{NumericOrderBook, OrderBook, Side};
+
+/// One line of a capture file. Timestamps are unix milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CaptureEvent {
+    /// Full book snapshot for `book.asset_id`.
+    Book {
+        #[serde(deserialize_with = "deserialize_number")]
+        ts: u64,
+        book: OrderBook,
+    },
+    /// A print on the tape; `side` is the aggressor's side.
+    Trade {
+        #[serde(deserialize_with = "deserialize_number")]
+        ts: u64,
+        token_id: String,
+        side: Side,
+        #[serde(deserialize_with = "deserialize_number")]
+        price: f64,
+        #[serde(deserialize_with = "deserialize_number")]
+        size: f64,
+    },
+}
+
+// Tagged enums buffer their fields, which loses numbers under serde_json's
+// arbitrary_precision; going through `Value` keeps them readable.
+fn deserialize_number<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Number(n) => n.to_string().parse().map_err(serde::de::Error::custom),
+        other => Err(serde::de::Error::custom(format!(
+            "invalid number: {}",
+            other
+        ))),
+    }
+}
+
+impl CaptureEvent {
+    pub fn ts(&self) -> u64 {
+        match self {
+            CaptureEvent::Book { ts, .. } | CaptureEvent::Trade { ts, .. } => *ts,
+        }
+    }
+
+    pub fn token_id(&self) -> &str {
+        match self {
+            CaptureEvent::Book { book, .. } => &book.asset_id,
+            CaptureEvent::Trade { token_id, .. } => token_id,
+        }
+    }
+}
+
+pub fn write_capture_event<W: Write>(writer: &mut W, event: &CaptureEvent) -> Result<()> {
+    serde_json::to_writer(&mut *writer, event).map_err(|e| ClobError::Json {
+        message: e.to_string(),
+    })?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Reads a capture lazily. Blank lines are skipped.
+pub fn read_capture<R: BufRead>(reader: R) -> impl Iterator<Item = Result<CaptureEvent>> {
+    reader.lines().filter_map(|line| match line {
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(line) => Some(serde_json::from_str(&line).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })),
+        Err(e) => Some(Err(e.into())),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaperOrder {
+    pub id: u64,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    pub size: f64,
+    pub remaining: f64,
+    pub created_ts: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaperFill {
+    pub order_id: u64,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    pub size: f64,
+    pub ts: u64,
+    /// Filled on arrival against the book rather than while resting.
+    pub taker: bool,
+}
+
+/// Simulated exchange. Orders that cross the current book fill immediately
+/// against its levels; the rest rest at their limit price and fill when the
+/// book or a trade print moves through them.
+#[derive(Debug, Default)]
+pub struct PaperEngine {
+    now: u64,
+    next_id: u64,
+    books: HashMap<String, NumericOrderBook>,
+    last_trade: HashMap<String, f64>,
+    orders: Vec<PaperOrder>,
+    fills: Vec<PaperFill>,
+    positions: HashMap<String, f64>,
+    cash: f64,
+    orders_placed: u64,
+}
+
+impl PaperEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Virtual time of the last applied event, in unix milliseconds.
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    pub fn book(&self, token_id: &str) -> Option<&NumericOrderBook> {
+        self.books.get(token_id)
+    }
+
+    pub fn open_orders(&self) -> &[PaperOrder] {
+        &self.orders
+    }
+
+    pub fn fills(&self) -> &[PaperFill] {
+        &self.fills
+    }
+
+    pub fn position(&self, token_id: &str) -> f64 {
+        self.positions.get(token_id).copied().unwrap_or(0.0)
+    }
+
+    /// USDC balance relative to the start of the run.
+    pub fn cash(&self) -> f64 {
+        self.cash
+    }
+
+    /// Cash plus open positions marked at the midpoint (or last trade).
+    pub fn equity(&self) -> f64 {
+        self.cash
+            + self
+                .positions
+                .iter()
+                .map(|(token_id, shares)| shares * self.mark(token_id).unwrap_or(0.0))
+                .sum::<f64>()
+    }
+
+    fn mark(&self, token_id: &str) -> Option<f64> {
+        self.books
+            .get(token_id)
+            .and_then(|b| b.midpoint())
+            .or_else(|| self.last_trade.get(token_id).copied())
+    }
+
+    pub fn place_limit(
+        &mut self,
+        token_id: &str,
+        side: Side,
+        price: f64,
+        size: f64,
+    ) -> Result<u64> {
+        if !(price > 0.0 && price < 1.0 && size > 0.0) {
+            return Err(ClobError::InvalidParameter(format!(
+                "invalid paper order: {} @ {}",
+                size, price
+            )));
+        }
+
+        self.next_id += 1;
+        self.orders_placed += 1;
+        let mut order = PaperOrder {
+            id: self.next_id,
+            token_id: token_id.to_string(),
+            side,
+            price,
+            size,
+            remaining: size,
+            created_ts: self.now,
+        };
+
+        // take whatever the current book offers through our limit
+        if let Some(book) = self.books.get(token_id) {
+            let levels = match side {
+                Side::Buy => &book.asks,
+                Side::Sell => &book.bids,
+            };
+            let crossing: Vec<_> = levels
+                .iter()
+                .take_while(|l| crosses(side, price, l.price))
+                .map(|l| (l.price, l.size))
+                .collect();
+            let mut taken = Vec::new();
+            for (level_price, level_size) in crossing {
+                let size = order.remaining.min(level_size);
+                self.fill(&mut order, level_price, size, true);
+                taken.push(size);
+                if order.remaining <= 0.0 {
+                    break;
+                }
+            }
+
+            // consume the liquidity until the next snapshot replaces the book
+            if let Some(book) = self.books.get_mut(token_id) {
+                let levels = match side {
+                    Side::Buy => &mut book.asks,
+                    Side::Sell => &mut book.bids,
+                };
+                for (level, size) in levels.iter_mut().zip(&taken) {
+                    level.size -= size;
+                }
+                levels.retain(|l| l.size > 0.0);
+            }
+        }
+
+        if order.remaining > 0.0 {
+            self.orders.push(order);
+        }
+        Ok(self.next_id)
+    }
+
+    pub fn cancel(&mut self, order_id: u64) -> bool {
+        let before = self.orders.len();
+        self.orders.retain(|o| o.id != order_id);
+        self.orders.len() != before
+    }
+
+    pub fn cancel_all(&mut self) {
+        self.orders.clear();
+    }
+
+    fn fill(&mut self, order: &mut PaperOrder, price: f64, size: f64, taker: bool) {
+        if size <= 0.0 {
+            return;
+        }
+        order.remaining -= size;
+        let signed = match order.side {
+            Side::Buy => size,
+            Side::Sell => -size,
+        };
+        *self.positions.entry(order.token_id.clone()).or_default() += signed;
+        self.cash -= signed * price;
+        self.fills.push(PaperFill {
+            order_id: order.id,
+            token_id: order.token_id.clone(),
+            side: order.side,
+            price,
+            size,
+            ts: self.now,
+            taker,
+        });
+    }
+
+    /// Advances the clock to the event and fills resting orders it trades
+    /// through. Out-of-order events do not move the clock backwards.
+    pub fn apply(&mut self, event: &CaptureEvent) -> Result<()> {
+        self.now = self.now.max(event.ts());
+
+        match event {
+            CaptureEvent::Book { book, .. } => {
+                let book = book.to_numeric()?;
+                let token_id = book.asset_id.clone();
+                let mut orders = std::mem::take(&mut self.orders);
+                // size already handed to earlier orders, per side
+                let (mut bought, mut sold) = (0.0, 0.0);
+                for order in orders.iter_mut().filter(|o| o.token_id == token_id) {
+                    let (levels, used) = match order.side {
+                        Side::Buy => (&book.asks, &mut bought),
+                        Side::Sell => (&book.bids, &mut sold),
+                    };
+                    let available: f64 = levels
+                        .iter()
+                        .take_while(|l| crosses(order.side, order.price, l.price))
+                        .map(|l| l.size)
+                        .sum();
+                    let size = order.remaining.min((available - *used).max(0.0));
+                    *used += size;
+                    let price = order.price;
+                    self.fill(order, price, size, false);
+                }
+                orders.retain(|o| o.remaining > 0.0);
+                self.orders = orders;
+                self.books.insert(token_id, book);
+            }
+            CaptureEvent::Trade {
+                token_id,
+                price,
+                size,
+                ..
+            } => {
+                self.last_trade.insert(token_id.clone(), *price);
+                let mut remaining = *size;
+                let mut orders = std::mem::take(&mut self.orders);
+                for order in orders.iter_mut().filter(|o| &o.token_id == token_id) {
+                    if remaining <= 0.0 || !crosses(order.side, order.price, *price) {
+                        continue;
+                    }
+                    let size = order.remaining.min(remaining);
+                    remaining -= size;
+                    let price = order.price;
+                    self.fill(order, price, size, false);
+                }
+                orders.retain(|o| o.remaining > 0.0);
+                self.orders = orders;
+            }
+        }
+        Ok(())
+    }
+}
+
+// a buy crosses anything offered at or below its limit, a sell anything bid
+// at or above
+fn crosses(side: Side, limit: f64, price: f64) -> bool {
+    match side {
+        Side::Buy => price <= limit + f64::EPSILON,
+        Side::Sell => price >= limit - f64::EPSILON,
+    }
+}
+
+/// Reacts to replayed events by placing and cancelling paper orders.
+pub trait Strategy {
+    fn on_event(&mut self, event: &CaptureEvent, engine: &mut PaperEngine);
+}
+
+impl<F> Strategy for F
+where
+    F: FnMut(&CaptureEvent, &mut PaperEngine),
+{
+    fn on_event(&mut self, event: &CaptureEvent, engine: &mut PaperEngine) {
+        self(event, engine)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestReport {
+    pub events: u64,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    pub orders_placed: u64,
+    pub fills: Vec<PaperFill>,
+    /// Shares traded across all fills.
+    pub volume: f64,
+    pub cash: f64,
+    pub positions: HashMap<String, f64>,
+    /// Final equity, i.e. P&L with open positions marked to market.
+    pub pnl: f64,
+    /// Largest peak-to-trough drop in equity seen after any event.
+    pub max_drawdown: f64,
+}
+
+impl BacktestReport {
+    pub fn taker_fills(&self) -> usize {
+        self.fills.iter().filter(|f| f.taker).count()
+    }
+
+    pub fn maker_fills(&self) -> usize {
+        self.fills.len() - self.taker_fills()
+    }
+}
+
+pub struct Backtest<S> {
+    strategy: S,
+    engine: PaperEngine,
+}
+
+impl<S: Strategy> Backtest<S> {
+    pub fn new(strategy: S) -> Self {
+        Self {
+            strategy,
+            engine: PaperEngine::new(),
+        }
+    }
+
+    pub fn engine(&self) -> &PaperEngine {
+        &self.engine
+    }
+
+    pub fn into_strategy(self) -> S {
+        self.strategy
+    }
+
+    /// Feeds every event to the engine, then to the strategy.
+    pub fn run<I>(&mut self, events: I) -> Result<BacktestReport>
+    where
+        I: IntoIterator<Item = Result<CaptureEvent>>,
+    {
+        let mut count = 0;
+        let mut start_ts = None;
+        let mut peak = 0.0_f64;
+        let mut max_drawdown = 0.0_f64;
+
+        for event in events {
+            let event = event?;
+            count += 1;
+            start_ts.get_or_insert(event.ts());
+
+            self.engine.apply(&event)?;
+            self.strategy.on_event(&event, &mut self.engine);
+
+            let equity = self.engine.equity();
+            peak = peak.max(equity);
+            max_drawdown = max_drawdown.max(peak - equity);
+        }
+
+        let fills = self.engine.fills.clone();
+        Ok(BacktestReport {
+            events: count,
+            start_ts,
+            end_ts: start_ts.map(|_| self.engine.now()),
+            orders_placed: self.engine.orders_placed,
+            volume: fills.iter().map(|f| f.size).sum(),
+            fills,
+            cash: self.engine.cash(),
+            positions: self.engine.positions.clone(),
+            pnl: self.engine.equity(),
+            max_drawdown,
+        })
+    }
+
+    pub fn run_capture<R: BufRead>(&mut self, reader: R) -> Result<BacktestReport> {
+        self.run(read_capture(reader))
+    }
+}
@@ -0,0 +1,57 @@
+use sha1::{Digest, Sha1};
+
+use crate::types::{OrderBook, OrderSummary};
+
+fn json_str(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn json_opt_str(value: Option<&str>) -> String {
+    value.map(json_str).unwrap_or_else(|| "null".to_string())
+}
+
+fn json_levels(levels: &[OrderSummary]) -> String {
+    let levels: Vec<String> = levels
+        .iter()
+        .map(|l| {
+            format!(
+                "{{\"price\":{},\"size\":{}}}",
+                json_str(&l.price),
+                json_str(&l.size)
+            )
+        })
+        .collect();
+    format!("[{}]", levels.join(","))
+}
+
+impl OrderBook {
+    /// Recomputes the book checksum the way the CLOB does: SHA-1 over the
+    /// compact JSON of the summary with an empty `hash` field, fields in the
+    /// reference client's order.
+    pub fn compute_hash(&self) -> String {
+        let neg_risk = match self.neg_risk {
+            Some(v) => v.to_string(),
+            None => "null".to_string(),
+        };
+        let json = format!(
+            "{{\"market\":{},\"asset_id\":{},\"timestamp\":{},\"bids\":{},\"asks\":{},\"min_order_size\":{},\"neg_risk\":{},\"tick_size\":{},\"hash\":\"\"}}",
+            json_str(&self.market),
+            json_str(&self.asset_id),
+            json_str(&self.timestamp),
+            json_levels(&self.bids),
+            json_levels(&self.asks),
+            json_opt_str(self.min_order_size.as_deref()),
+            neg_risk,
+            json_opt_str(self.tick_size.as_deref()),
+        );
+
+        hex::encode(Sha1::digest(json.as_bytes()))
+    }
+
+    /// False when the snapshot does not match its `hash`, e.g. because it was
+    /// corrupted or levels were applied out of order. Books without a hash
+    /// never verify.
+    pub fn verify_hash(&self) -> bool {
+        !self.hash.is_empty() && self.hash.eq_ignore_ascii_case(&self.compute_hash())
+    }
+}
@@ -0,0 +1,152 @@
+//! OHLCV candles from trade prints or price history.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[cfg(feature = "http")]
+use futures::{future, stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::trade_prints::TradePrint;
+use crate::types::{amount_to_f64, PricePoint};
+
+/// One interval of a token's trading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    pub token_id: String,
+    /// Interval start, unix milliseconds.
+    pub start: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Traded size. Zero for candles built from price history.
+    pub volume: f64,
+    pub trades: u32,
+}
+
+impl Candle {
+    fn new(token_id: &str, start: u64, price: f64, size: f64) -> Self {
+        Self {
+            token_id: token_id.to_string(),
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: size,
+            trades: 1,
+        }
+    }
+
+    fn add(&mut self, price: f64, size: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += size;
+        self.trades += 1;
+    }
+}
+
+/// Folds prices into per-token candles aligned to multiples of the
+/// interval. A candle is complete once a price from a later interval
+/// arrives; intervals without trades produce no candle. Prices older than
+/// a token's open candle are dropped.
+#[derive(Debug, Clone)]
+pub struct CandleAggregator {
+    interval_ms: u64,
+    open: HashMap<String, Candle>,
+}
+
+impl CandleAggregator {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval_ms: (interval.as_millis() as u64).max(1),
+            open: HashMap::new(),
+        }
+    }
+
+    /// Adds a print, returning the token's completed candle if the print
+    /// opens a new one.
+    pub fn push(&mut self, print: &TradePrint) -> Option<Candle> {
+        self.push_price(&print.token_id, print.timestamp, print.price, print.size)
+    }
+
+    /// [`push`](Self::push) for a bare price at `timestamp` (unix ms).
+    pub fn push_price(
+        &mut self,
+        token_id: &str,
+        timestamp: u64,
+        price: f64,
+        size: f64,
+    ) -> Option<Candle> {
+        let start = timestamp - timestamp % self.interval_ms;
+        match self.open.get_mut(token_id) {
+            Some(candle) if start == candle.start => {
+                candle.add(price, size);
+                None
+            }
+            Some(candle) if start < candle.start => None,
+            Some(candle) => Some(std::mem::replace(
+                candle,
+                Candle::new(token_id, start, price, size),
+            )),
+            None => {
+                self.open.insert(
+                    token_id.to_string(),
+                    Candle::new(token_id, start, price, size),
+                );
+                None
+            }
+        }
+    }
+
+    /// Candles still open, oldest first, leaving the aggregator empty.
+    pub fn flush(&mut self) -> Vec<Candle> {
+        let mut candles: Vec<Candle> = self.open.drain().map(|(_, c)| c).collect();
+        candles.sort_by(|a, b| (a.start, &a.token_id).cmp(&(b.start, &b.token_id)));
+        candles
+    }
+}
+
+/// Candles from a stream of prints, e.g. [`crate::TradePrints::subscribe`]
+/// with the errors filtered out. Open candles are emitted when `prints` ends.
+#[cfg(feature = "http")]
+pub fn candles<S>(prints: S, interval: Duration) -> impl Stream<Item = Candle>
+where
+    S: Stream<Item = TradePrint>,
+{
+    prints
+        .map(Some)
+        .chain(stream::once(future::ready(None)))
+        .scan(CandleAggregator::new(interval), |aggregator, print| {
+            let done = match print {
+                Some(print) => aggregator.push(&print).into_iter().collect(),
+                None => aggregator.flush(),
+            };
+            future::ready(Some(stream::iter(done)))
+        })
+        .flatten()
+}
+
+/// Candles from [`crate::MarketDataApi::get_price_history`] samples. Each
+/// sample counts as one trade of zero size.
+pub fn candles_from_history(
+    token_id: &str,
+    history: &[PricePoint],
+    interval: Duration,
+) -> Vec<Candle> {
+    let mut points: Vec<&PricePoint> = history.iter().collect();
+    points.sort_by_key(|p| p.t);
+
+    let mut aggregator = CandleAggregator::new(interval);
+    let mut candles: Vec<Candle> = points
+        .into_iter()
+        .filter_map(|p| {
+            let timestamp = p.t.max(0) as u64 * 1000;
+            aggregator.push_price(token_id, timestamp, amount_to_f64(p.p), 0.0)
+        })
+        .collect();
+    candles.extend(aggregator.flush());
+    candles
+}
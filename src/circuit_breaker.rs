@@ -0,0 +1,118 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::error::{ClobError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests go through.
+    Closed,
+    /// Requests fail fast with [`ClobError::CircuitOpen`].
+    Open,
+    /// A health probe is in flight; other requests still fail fast.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+enum Inner {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen,
+}
+
+/// Stops requests to a degraded API instead of piling onto it. After
+/// `failure_threshold` failures in a row (connection errors, 5xx responses
+/// and, if set, calls slower than the slow-call limit) the breaker opens and
+/// requests fail with [`ClobError::CircuitOpen`]. Once `cool_down` has
+/// passed, the next request first probes `GET /` (as `get_ok` does) and the
+/// breaker closes if the probe is healthy, or stays open for another
+/// `cool_down` if not. Cancels (`DELETE` requests) always go out and don't
+/// count either way. Clones share state.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cool_down: Duration,
+    slow_call: Option<Duration>,
+    inner: Arc<Mutex<Inner>>,
+}
+
+pub(crate) enum Admission {
+    Allowed,
+    /// The caller must probe before sending, then call
+    /// [`CircuitBreaker::record`] with the probe's outcome.
+    Probe,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cool_down: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cool_down,
+            slow_call: None,
+            inner: Arc::new(Mutex::new(Inner::Closed { failures: 0 })),
+        }
+    }
+
+    /// Counts calls that take longer than `limit` as failures.
+    pub fn with_slow_call(mut self, limit: Duration) -> Self {
+        self.slow_call = Some(limit);
+        self
+    }
+
+    pub fn state(&self) -> CircuitState {
+        match *self.inner.lock().unwrap() {
+            Inner::Closed { .. } => CircuitState::Closed,
+            Inner::Open { .. } => CircuitState::Open,
+            Inner::HalfOpen => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Closes the breaker and forgets past failures.
+    pub fn reset(&self) {
+        *self.inner.lock().unwrap() = Inner::Closed { failures: 0 };
+    }
+
+    pub(crate) fn admit(&self) -> Result<Admission> {
+        let mut inner = self.inner.lock().unwrap();
+        match *inner {
+            Inner::Closed { .. } => Ok(Admission::Allowed),
+            Inner::Open { until } => {
+                let now = Instant::now();
+                if now < until {
+                    return Err(ClobError::CircuitOpen {
+                        retry_in: until - now,
+                    });
+                }
+                *inner = Inner::HalfOpen;
+                Ok(Admission::Probe)
+            }
+            Inner::HalfOpen => Err(ClobError::CircuitOpen {
+                retry_in: Duration::ZERO,
+            }),
+        }
+    }
+
+    /// Records a finished call. `healthy` is whether the API answered at all
+    /// without a server error; slowness is judged here.
+    pub(crate) fn record(&self, healthy: bool, elapsed: Duration) {
+        let ok = healthy && self.slow_call.is_none_or(|limit| elapsed <= limit);
+        let mut inner = self.inner.lock().unwrap();
+        let failures = match *inner {
+            _ if ok => {
+                *inner = Inner::Closed { failures: 0 };
+                return;
+            }
+            Inner::Closed { failures } => failures + 1,
+            // a failed probe, or a late answer to a request sent before
+            // the breaker opened
+            Inner::HalfOpen | Inner::Open { .. } => self.failure_threshold,
+        };
+        *inner = if failures >= self.failure_threshold {
+            Inner::Open {
+                until: Instant::now() + self.cool_down,
+            }
+        } else {
+            Inner::Closed { failures }
+        };
+    }
+}
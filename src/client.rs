@@ -1,63 +1,84 @@
-use reqwest::Client;
-use serde::Serialize;
-use std::collections::HashMap;
-use dashmap::DashMap;
-
-use crate::config::{END_CURSOR, FIRST_CURSOR};
-use crate::endpoints;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::Stream;
+use tokio::task::JoinHandle;
+
+use crate::api::{AccountApi, AuthApi, MarketDataApi, MarketsApi, OrdersApi, PricesApi};
+use crate::audit_log::AuditLog;
+use crate::circuit_breaker::CircuitBreaker;
+use crate::client_config::ClobConfig;
+use crate::drift::DriftMonitor;
+use crate::dry_run::DryRunLog;
 use crate::error::{ClobError, Result};
-use crate::headers::{
-    create_level_1_headers, create_level_2_headers, L2Headers, POLY_ADDRESS, POLY_API_KEY,
-    POLY_NONCE, POLY_PASSPHRASE, POLY_SIGNATURE, POLY_TIMESTAMP,
-};
+use crate::fixtures::Cassette;
+use crate::headers::Clock;
+use crate::latency::{LatencyStats, LatencyTracker};
+use crate::market_cache::TokenInfo;
+use crate::nonce::{NonceProvider, SaltProvider};
 use crate::order_builder::{OrderBuilder, SignedOrder};
+use crate::risk::{RiskConfig, SpreadGuard};
+use crate::shutdown::{ShutdownPolicy, ShutdownReport};
 use crate::signer::Signer;
+use crate::strict::StrictDecoding;
+use crate::sweep::{SweepLimit, SweepResult};
+use crate::transport::Transport;
 use crate::types::{
-    ApiCreds, BalanceAllowanceParams, BalanceAllowanceResponse, BatchMidpointResponse,
-    BatchPriceResponse, BatchSpreadResponse, BookParams, CreateOrderOptions, FeeRateResponse,
-    LastTradePriceResponse, LastTradesPriceEntry, Market, MarketOrderArgs, MarketTradeEvent,
-    MarketsResponse, MidpointResponse, NegRiskResponse, OpenOrderParams, OrderArgs, OrderBook,
-    OrderType, PartialCreateOrderOptions, PriceResponse, ServerTime, Side,
-    SimplifiedMarketsResponse, SpreadResponse, TickSize, TickSizeResponse, TradeParams, PostOrderResponse
+    ApiCreds, ApiKeysResponse, BalanceAllowanceParams, BalanceAllowanceResponse,
+    BatchMidpointResponse, BatchPriceResponse, BatchSpreadResponse, BookParams,
+    DeleteApiKeyResponse, LastTradesPriceEntry, Market, MarketFilter, MarketOrderArgs,
+    MarketTradeEvent, MarketsResponse, OpenOrderParams, OrderArgs, OrderBook, OrderStatus,
+    OrderType, PartialCreateOrderOptions, PostOrderResponse, PriceHistoryInterval, PricePoint,
+    ServerTime, Side, SimplifiedMarketsResponse, TickSize, TradeParams,
 };
 
-const L0: u8 = 0;
-const L1: u8 = 1;
-const L2: u8 = 2;
-
+/// Facade over [`MarketDataApi`], [`AuthApi`] and [`OrdersApi`]. The services
+/// are created as auth material is attached and can also be used directly.
 pub struct ClobClient {
-    host: String,
     chain_id: u64,
-    http: Client,
-    signer: Option<Signer>,
+    transport: Transport,
+    clock: Clock,
+    market_data: MarketDataApi,
     creds: Option<ApiCreds>,
-    order_builder: Option<OrderBuilder>,
-    mode: u8,
-    tick_sizes: DashMap<String, TickSize>,
-    neg_risk: DashMap<String, bool>,
-    fee_rates: DashMap<String, i32>,
+    auto_fee_rate: bool,
+    nonce_provider: Option<Arc<dyn NonceProvider>>,
+    salt_provider: Option<Arc<dyn SaltProvider>>,
+    dry_run: Option<DryRunLog>,
+    audit: Option<AuditLog>,
+    risk: Option<RiskConfig>,
+    spread_guard: Option<SpreadGuard>,
+    check_accepting_orders: bool,
+    check_balance: bool,
+    funder: Option<alloy_primitives::Address>,
+    signature_type: Option<u8>,
+    auth: Option<AuthApi>,
+    orders: Option<OrdersApi>,
 }
 
 impl ClobClient {
     pub fn new(host: impl Into<String>) -> Self {
-        let host = host.into();
-        let host = if host.ends_with('/') {
-            host[..host.len() - 1].to_string()
-        } else {
-            host
-        };
+        let transport = Transport::new(host);
 
         Self {
-            host,
             chain_id: crate::config::CHAIN_ID,
-            http: Client::new(), //TODO: connection pool?
-            signer: None,
+            clock: Clock::default(),
+            market_data: MarketDataApi::new(transport.clone()),
+            transport,
             creds: None,
-            order_builder: None,
-            mode: L0,
-            tick_sizes: DashMap::new(),
-            neg_risk: DashMap::new(),
-            fee_rates: DashMap::new(),
+            auto_fee_rate: true,
+            nonce_provider: None,
+            salt_provider: None,
+            dry_run: None,
+            audit: None,
+            risk: None,
+            spread_guard: None,
+            check_accepting_orders: false,
+            check_balance: false,
+            funder: None,
+            signature_type: None,
+            auth: None,
+            orders: None,
         }
     }
 
@@ -65,37 +86,441 @@ impl ClobClient {
         Self::new(crate::config::HOST)
     }
 
+    /// Builds a client with everything `config` provides attached.
+    pub fn from_config(config: &ClobConfig) -> Result<Self> {
+        let mut client = Self::new(&config.host)
+            .with_fallback_hosts(&config.fallback_hosts)
+            .with_chain_id(config.chain_id);
+        if let Some(creds) = config.resolve_creds()? {
+            client = client.with_creds(creds);
+        }
+        if let Some(funder) = &config.funder {
+            client = client.with_funder(funder)?;
+        }
+        if let Some(sig_type) = config.signature_type {
+            client = client.with_signature_type(sig_type);
+        }
+        if let Some(private_key) = &config.private_key {
+            client = client.with_signer(private_key.expose())?;
+        }
+        Ok(client)
+    }
+
+    /// Chain the signer signs for. Like the funder, it can be set before or
+    /// after [`Self::with_signer`].
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.set_chain_id(chain_id);
+        self
+    }
+
+    pub fn set_chain_id(&mut self, chain_id: u64) {
+        if let Some(auth) = self.auth.as_mut() {
+            auth.set_chain_id(chain_id);
+        }
+        if let Some(orders) = self.orders.as_mut() {
+            orders.order_builder_mut().set_chain_id(chain_id);
+        }
+        self.chain_id = chain_id;
+    }
+
     pub fn with_signer(mut self, private_key: &str) -> Result<Self> {
-        let signer = Signer::new(private_key, self.chain_id)?;
-        let order_builder = OrderBuilder::new(Signer::new(private_key, self.chain_id)?);
-        self.signer = Some(signer);
-        self.order_builder = Some(order_builder);
-        self.mode = L1;
+        let signer = Signer::new(private_key, self.chain_id)?.with_clock(self.clock.clone());
+        let mut auth = AuthApi::new(self.transport.clone(), signer.clone());
+        let mut orders = OrdersApi::new(self.market_data.clone(), OrderBuilder::new(signer))
+            .with_auto_fee_rate(self.auto_fee_rate);
+
+        if let Some(creds) = self.creds.clone() {
+            auth.set_creds(creds.clone());
+            orders.set_creds(creds);
+        }
+        if let Some(provider) = self.nonce_provider.clone() {
+            auth.set_nonce_provider(provider.clone());
+            orders.order_builder_mut().set_nonce_provider(provider);
+        }
+        if let Some(provider) = self.salt_provider.clone() {
+            orders.order_builder_mut().set_salt_provider(provider);
+        }
+        orders.set_dry_run(self.dry_run.clone());
+        orders.set_audit_log(self.audit.clone());
+        orders.set_risk_config(self.risk.clone());
+        orders.set_spread_guard(self.spread_guard.clone());
+        orders.set_accepting_orders_check(self.check_accepting_orders);
+        orders.set_balance_check(self.check_balance);
+        if let Some(funder) = self.funder {
+            orders.order_builder_mut().set_funder(funder);
+        }
+        if let Some(sig_type) = self.signature_type {
+            orders.order_builder_mut().set_sig_type(sig_type);
+        }
+
+        self.auth = Some(auth);
+        self.orders = Some(orders);
         Ok(self)
     }
 
-    pub fn with_creds(mut self, creds: ApiCreds) -> Self {
-        self.creds = Some(creds);
-        if self.signer.is_some() {
-            self.mode = L2;
+    /// Records mutating order calls in `log` instead of sending them. See
+    /// [`OrdersApi::with_dry_run`].
+    pub fn with_dry_run(mut self, log: DryRunLog) -> Self {
+        self.set_dry_run(Some(log));
+        self
+    }
+
+    pub fn set_dry_run(&mut self, log: Option<DryRunLog>) {
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_dry_run(log.clone());
         }
+        self.dry_run = log;
+    }
+
+    /// Records signed orders, posts and cancels in `log`. See
+    /// [`OrdersApi::with_audit_log`].
+    pub fn with_audit_log(mut self, log: AuditLog) -> Self {
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_audit_log(Some(log.clone()));
+        }
+        self.audit = Some(log);
+        self
+    }
+
+    /// Rejects orders that break `risk` before they are posted. See
+    /// [`OrdersApi::check_risk`].
+    pub fn with_risk_config(mut self, risk: RiskConfig) -> Self {
+        self.set_risk_config(Some(risk));
         self
     }
 
+    pub fn set_risk_config(&mut self, risk: Option<RiskConfig>) {
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_risk_config(risk.clone());
+        }
+        self.risk = risk;
+    }
+
+    /// Rejects resting orders that cross the spread. See
+    /// [`OrdersApi::with_spread_guard`].
+    pub fn with_spread_guard(mut self, guard: SpreadGuard) -> Self {
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_spread_guard(Some(guard.clone()));
+        }
+        self.spread_guard = Some(guard);
+        self
+    }
+
+    /// Checks that the market is accepting orders before posting. See
+    /// [`OrdersApi::with_accepting_orders_check`].
+    pub fn with_accepting_orders_check(mut self, enabled: bool) -> Self {
+        self.check_accepting_orders = enabled;
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_accepting_orders_check(enabled);
+        }
+        self
+    }
+
+    /// Checks balance and allowance before posting. See
+    /// [`OrdersApi::with_balance_check`].
+    pub fn with_balance_check(mut self, enabled: bool) -> Self {
+        self.check_balance = enabled;
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_balance_check(enabled);
+        }
+        self
+    }
+
+    /// Nonce source for API-key derivation and for orders created with
+    /// `nonce: 0`. Defaults to a fixed nonce of 0.
+    pub fn with_nonce_provider(mut self, provider: impl NonceProvider + 'static) -> Self {
+        self.set_nonce_provider(provider);
+        self
+    }
+
+    pub fn set_nonce_provider(&mut self, provider: impl NonceProvider + 'static) {
+        let provider: Arc<dyn NonceProvider> = Arc::new(provider);
+        if let Some(auth) = self.auth.as_mut() {
+            auth.set_nonce_provider(provider.clone());
+        }
+        if let Some(orders) = self.orders.as_mut() {
+            orders
+                .order_builder_mut()
+                .set_nonce_provider(provider.clone());
+        }
+        self.nonce_provider = Some(provider);
+    }
+
+    /// Salt source for orders created without an explicit salt. Defaults to
+    /// [`RandomSalt`](crate::RandomSalt).
+    pub fn with_salt_provider(mut self, provider: impl SaltProvider + 'static) -> Self {
+        self.set_salt_provider(provider);
+        self
+    }
+
+    pub fn set_salt_provider(&mut self, provider: impl SaltProvider + 'static) {
+        let provider: Arc<dyn SaltProvider> = Arc::new(provider);
+        if let Some(orders) = self.orders.as_mut() {
+            orders
+                .order_builder_mut()
+                .set_salt_provider(provider.clone());
+        }
+        self.salt_provider = Some(provider);
+    }
+
+    /// See [`Transport::with_proxy`] for the supported schemes.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+        let transport = self.transport.clone().with_proxy(proxy_url)?;
+        self.set_transport(transport);
+        Ok(self)
+    }
+
+    /// See [`Transport::with_user_agent`].
+    pub fn with_user_agent(mut self, user_agent: &str) -> Result<Self> {
+        let transport = self.transport.clone().with_user_agent(user_agent)?;
+        self.set_transport(transport);
+        Ok(self)
+    }
+
+    /// See [`Transport::with_header`].
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<Self> {
+        let transport = self.transport.clone().with_header(name, value)?;
+        self.set_transport(transport);
+        Ok(self)
+    }
+
+    pub fn with_root_certificate_pem(mut self, pem: &[u8]) -> Result<Self> {
+        let transport = self.transport.clone().with_root_certificate_pem(pem)?;
+        self.set_transport(transport);
+        Ok(self)
+    }
+
+    pub fn with_client_identity_pem(mut self, cert_pem: &[u8], key_pem: &[u8]) -> Result<Self> {
+        let transport = self
+            .transport
+            .clone()
+            .with_client_identity_pem(cert_pem, key_pem)?;
+        self.set_transport(transport);
+        Ok(self)
+    }
+
+    pub fn with_client_identity_pkcs12(mut self, der: &[u8], password: &str) -> Result<Self> {
+        let transport = self
+            .transport
+            .clone()
+            .with_client_identity_pkcs12(der, password)?;
+        self.set_transport(transport);
+        Ok(self)
+    }
+
+    /// See [`Transport::with_cassette`].
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        let transport = self.transport.clone().with_cassette(cassette);
+        self.set_transport(transport);
+        self
+    }
+
+    /// See [`Transport::with_fallback_hosts`].
+    pub fn with_fallback_hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let transport = self.transport.clone().with_fallback_hosts(hosts);
+        self.set_transport(transport);
+        self
+    }
+
+    /// The host requests currently go to, which changes on failover.
+    pub fn host(&self) -> &str {
+        self.transport.host()
+    }
+
+    /// See [`Transport::with_circuit_breaker`].
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        let transport = self.transport.clone().with_circuit_breaker(breaker);
+        self.set_transport(transport);
+        self
+    }
+
+    /// See [`Transport::with_latency_tracker`].
+    pub fn with_latency_tracker(mut self, tracker: LatencyTracker) -> Self {
+        let transport = self.transport.clone().with_latency_tracker(tracker);
+        self.set_transport(transport);
+        self
+    }
+
+    /// See [`Transport::with_strict_decoding`].
+    pub fn with_strict_decoding(mut self, mode: StrictDecoding) -> Self {
+        let transport = self.transport.clone().with_strict_decoding(mode);
+        self.set_transport(transport);
+        self
+    }
+
+    /// Per-endpoint round-trip times, empty unless a tracker was attached
+    /// with [`Self::with_latency_tracker`].
+    pub fn latency_stats(&self) -> BTreeMap<String, LatencyStats> {
+        self.transport
+            .latency_tracker()
+            .map(LatencyTracker::stats)
+            .unwrap_or_default()
+    }
+
+    /// Clock used for `POLY_TIMESTAMP` on every signed request.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Corrects auth timestamps by the offset to the server's clock, measured
+    /// with `GET /time`. Returns the offset in seconds.
+    pub async fn sync_time(&self) -> Result<i64> {
+        self.clock.sync(&self.transport).await
+    }
+
+    /// Re-syncs the clock whenever a signed request is rejected over its
+    /// timestamp, so the next request goes out with a corrected one.
+    pub fn with_time_sync(mut self) -> Self {
+        let transport = self.transport.clone().with_clock_sync(self.clock.clone());
+        self.set_transport(transport);
+        self
+    }
+
+    /// Calls [`Self::sync_time`] every `interval` on a background task.
+    /// Failed syncs keep the previous offset.
+    pub fn spawn_time_sync(&self, interval: Duration) -> JoinHandle<()> {
+        let clock = self.clock.clone();
+        let transport = self.transport.clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let _ = clock.sync(&transport).await;
+            }
+        });
+        self.track_task(&task);
+        task
+    }
+
+    /// Samples the server's clock every `interval` and tracks the skew of
+    /// auth timestamps to it, warning via `tracing` when it exceeds
+    /// `max_skew` (see [`DriftMonitor::DEFAULT_MAX_SKEW`]).
+    pub fn spawn_drift_monitor(&self, interval: Duration, max_skew: Duration) -> DriftMonitor {
+        DriftMonitor::spawn(
+            self.transport.clone(),
+            self.clock.clone(),
+            interval,
+            max_skew,
+        )
+    }
+
+    /// Sets up a connection to the host before it is needed, so the first
+    /// order of a session doesn't pay for the TCP and TLS handshakes.
+    /// Returns the round trip, handshakes included.
+    pub async fn warm_up(&self) -> Result<Duration> {
+        self.transport.warm_up(1).await
+    }
+
+    /// Like [`Self::warm_up`] with `connections` parallel connections, for
+    /// sessions that post several orders at once.
+    pub async fn warm_up_with(&self, connections: usize) -> Result<Duration> {
+        self.transport.warm_up(connections).await
+    }
+
+    /// Fetches `/time` every `interval` on a background task so pooled
+    /// connections aren't closed as idle. Errors are ignored.
+    pub fn spawn_keep_alive(&self, interval: Duration) -> JoinHandle<()> {
+        let transport = self.transport.clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let _ = transport.warm_up(1).await;
+            }
+        });
+        self.track_task(&task);
+        task
+    }
+
+    /// Has [`Self::shutdown`] stop a task the client didn't start itself,
+    /// such as an [`OrderManager`](crate::OrderManager) expiry sweeper or a
+    /// websocket reader. Tasks spawned by the client and its services are
+    /// tracked already.
+    pub fn track_task<T>(&self, task: &JoinHandle<T>) {
+        self.transport.tasks().track(task);
+    }
+
+    /// Stops every tracked background task and waits for them to finish,
+    /// then cancels open orders if `policy` says to and syncs the audit log
+    /// to disk. Meant for SIGTERM handling: once it resolves nothing the
+    /// client started is still running.
+    pub async fn shutdown(&self, policy: ShutdownPolicy) -> Result<ShutdownReport> {
+        // checked first so a misconfigured policy stops nothing
+        let orders = match policy.cancel_orders {
+            Some(_) => Some(self.orders()?),
+            None => None,
+        };
+        let (tasks_stopped, drained) = self.transport.tasks().stop_all(policy.timeout).await;
+        let cancelled = match (orders, policy.cancel_orders) {
+            (Some(orders), Some(kill_switch)) => Some(orders.kill_switch_with(kill_switch).await),
+            _ => None,
+        };
+        if let Some(audit) = &self.audit {
+            audit.sync()?;
+        }
+        Ok(ShutdownReport {
+            tasks_stopped,
+            drained,
+            cancelled,
+        })
+    }
+
+    fn set_transport(&mut self, transport: Transport) {
+        self.market_data.set_transport(transport.clone());
+        if let Some(auth) = self.auth.as_mut() {
+            auth.set_transport(transport.clone());
+        }
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_transport(transport.clone());
+        }
+        self.transport = transport;
+    }
+
+    pub fn with_auto_fee_rate(mut self, enabled: bool) -> Self {
+        self.auto_fee_rate = enabled;
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_auto_fee_rate(enabled);
+        }
+        self
+    }
+
+    pub fn with_closed_markets(self, include: bool) -> Self {
+        self.market_data.set_closed_markets(include);
+        self
+    }
+
+    pub fn with_creds(mut self, creds: ApiCreds) -> Self {
+        self.set_creds(creds);
+        self
+    }
+
+    /// Loads credentials previously stored with [`ApiCreds::save`].
+    pub fn with_creds_file(
+        self,
+        path: impl AsRef<std::path::Path>,
+        passphrase: Option<&str>,
+    ) -> Result<Self> {
+        Ok(self.with_creds(ApiCreds::load(path, passphrase)?))
+    }
+
     pub fn set_creds(&mut self, creds: ApiCreds) {
-        self.creds = Some(creds);
-        if self.signer.is_some() {
-            self.mode = L2;
+        self.creds = Some(creds.clone());
+        if let Some(auth) = self.auth.as_mut() {
+            auth.set_creds(creds.clone());
+        }
+        if let Some(orders) = self.orders.as_mut() {
+            orders.set_creds(creds);
         }
     }
 
+    /// Wallet that holds the funds and makes the orders. Like the signature
+    /// type, it can be set before or after [`Self::with_signer`].
     pub fn with_funder(mut self, funder: &str) -> Result<Self> {
-        let funder_addr: alloy_primitives::Address = funder
-            .parse()
-            .map_err(|_| ClobError::InvalidParameter("invalid funder address".to_string()))?;
-        if let Some(builder) = self.order_builder.take() {
-            self.order_builder = Some(builder.with_funder(funder_addr));
-        }
+        self.set_funder(funder)?;
         Ok(self)
     }
 
@@ -103,802 +528,394 @@ impl ClobClient {
         let funder_addr: alloy_primitives::Address = funder
             .parse()
             .map_err(|_| ClobError::InvalidParameter("invalid funder address".to_string()))?;
-        if let Some(builder) = self.order_builder.take() {
-            self.order_builder = Some(builder.with_funder(funder_addr));
+        if let Some(orders) = self.orders.as_mut() {
+            orders.order_builder_mut().set_funder(funder_addr);
         }
+        self.funder = Some(funder_addr);
         Ok(())
     }
 
     pub fn with_signature_type(mut self, sig_type: u8) -> Self {
-        if let Some(builder) = self.order_builder.take() {
-            self.order_builder = Some(builder.with_sig_type(sig_type));
-        }
+        self.set_signature_type(sig_type);
         self
     }
 
     pub fn set_signature_type(&mut self, sig_type: u8) {
-        if let Some(builder) = self.order_builder.take() {
-            self.order_builder = Some(builder.with_sig_type(sig_type));
+        if let Some(orders) = self.orders.as_mut() {
+            orders.order_builder_mut().set_sig_type(sig_type);
         }
+        self.signature_type = Some(sig_type);
     }
 
     pub fn address(&self) -> Option<String> {
-        self.signer.as_ref().map(|s| s.address_string())
+        self.auth.as_ref().map(|a| a.signer().address_string())
     }
 
-    fn assert_l1(&self) -> Result<&Signer> {
-        if self.mode < L1 {
-            return Err(ClobError::AuthRequired(
-                "L1 auth required (provide private key)".to_string(),
-            ));
-        }
-        self.signer.as_ref().ok_or_else(|| {
+    pub fn market_data(&self) -> &MarketDataApi {
+        &self.market_data
+    }
+
+    pub fn auth(&self) -> Result<&AuthApi> {
+        self.auth.as_ref().ok_or_else(|| {
             ClobError::AuthRequired("L1 auth required (provide private key)".to_string())
         })
     }
 
-    fn assert_l2(&self) -> Result<(&Signer, &ApiCreds)> {
-        if self.mode < L2 {
-            return Err(ClobError::AuthRequired(
-                "L2 auth required (provide API credentials)".to_string(),
-            ));
-        }
-        let signer = self
-            .signer
-            .as_ref()
-            .ok_or_else(|| ClobError::AuthRequired("L2 auth required".to_string()))?;
-        let creds = self.creds.as_ref().ok_or_else(|| {
-            ClobError::AuthRequired("L2 auth required (provide API credentials)".to_string())
-        })?;
-        Ok((signer, creds))
+    pub fn orders(&self) -> Result<&OrdersApi> {
+        self.orders.as_ref().ok_or_else(|| {
+            ClobError::AuthRequired("L1 auth required (provide private key)".to_string())
+        })
+    }
+
+    pub fn markets(&self) -> MarketsApi<'_> {
+        MarketsApi::new(&self.market_data)
+    }
+
+    pub fn prices(&self) -> PricesApi<'_> {
+        PricesApi::new(&self.market_data)
+    }
+
+    /// API keys and balances; requires a signer.
+    pub fn account(&self) -> Result<AccountApi<'_>> {
+        Ok(AccountApi::new(self.auth()?, self.orders()?))
     }
 
     // ========== L0 Endpoints (public) ==========
+    //
+    // The flat methods below are kept for compatibility; new code should go
+    // through the scoped accessors above.
 
     pub async fn get_ok(&self) -> Result<serde_json::Value> {
-        self.get("/").await
+        self.market_data.get_ok().await
     }
 
     pub async fn get_server_time(&self) -> Result<ServerTime> {
-        self.get(endpoints::TIME).await
+        self.market_data.get_server_time().await
     }
 
+    #[deprecated(note = "use `prices().get_order_book()`")]
     pub async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
-        let url = format!("{}?token_id={}", endpoints::GET_ORDER_BOOK, token_id);
-        self.get(&url).await
+        self.market_data.get_order_book(token_id).await
     }
 
+    #[deprecated(note = "use `prices().get_order_books()`")]
     pub async fn get_order_books(&self, params: &[BookParams]) -> Result<Vec<OrderBook>> {
-        let body: Vec<_> = params
-            .iter()
-            .map(|p| serde_json::json!({"token_id": p.token_id}))
-            .collect();
-        self.post(endpoints::GET_ORDER_BOOKS, &body).await
+        self.market_data.get_order_books(params).await
     }
 
+    #[deprecated(note = "use `prices().get_midpoint()`")]
     pub async fn get_midpoint(&self, token_id: &str) -> Result<f64> {
-        let url = format!("{}?token_id={}", endpoints::MID_POINT, token_id);
-        let resp: MidpointResponse = self.get(&url).await?;
-        Ok(resp.mid)
+        self.market_data.get_midpoint(token_id).await
     }
 
-    pub async fn get_midpoints(&self, params: &[BookParams]) -> Result<Vec<BatchMidpointResponse>> {
-        let body: Vec<_> = params
-            .iter()
-            .map(|p| serde_json::json!({"token_id": p.token_id}))
-            .collect();
+    #[deprecated(note = "use `prices().get_price_history()`")]
+    pub async fn get_price_history(
+        &self,
+        token_id: &str,
+        interval: PriceHistoryInterval,
+        fidelity: Option<u32>,
+    ) -> Result<Vec<PricePoint>> {
+        self.market_data
+            .get_price_history(token_id, interval, fidelity)
+            .await
+    }
 
-        let raw: HashMap<String, String> = self.post(endpoints::MID_POINTS, &body).await?;
+    #[deprecated(note = "use `prices().get_price_history_range()`")]
+    pub async fn get_price_history_range(
+        &self,
+        token_id: &str,
+        start_ts: i64,
+        end_ts: i64,
+        fidelity: Option<u32>,
+    ) -> Result<Vec<PricePoint>> {
+        self.market_data
+            .get_price_history_range(token_id, start_ts, end_ts, fidelity)
+            .await
+    }
 
-        Ok(raw
-            .into_iter()
-            .map(|(token_id, mid)| BatchMidpointResponse {
-                token_id,
-                mid: mid.parse().ok(),
-            })
-            .collect())
+    #[deprecated(note = "use `prices().get_midpoints()`")]
+    pub async fn get_midpoints(&self, params: &[BookParams]) -> Result<Vec<BatchMidpointResponse>> {
+        self.market_data.get_midpoints(params).await
     }
 
+    #[deprecated(note = "use `prices().get_midpoints_by_token()`")]
+    pub async fn get_midpoints_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchMidpointResponse>> {
+        self.market_data.get_midpoints_by_token(params).await
+    }
+
+    #[deprecated(note = "use `prices().get_price()`")]
     pub async fn get_price(&self, token_id: &str, side: Side) -> Result<f64> {
-        let url = format!("{}?token_id={}&side={}", endpoints::PRICE, token_id, side);
-        let resp: PriceResponse = self.get(&url).await?;
-        Ok(resp.price)
+        self.market_data.get_price(token_id, side).await
     }
 
+    #[deprecated(note = "use `prices().get_prices()`")]
     pub async fn get_prices(&self, params: &[BookParams]) -> Result<Vec<BatchPriceResponse>> {
-        let body: Vec<_> = params
-            .iter()
-            .map(|p| {
-                serde_json::json!({
-                    "token_id": p.token_id,
-                    "side": p.side.map(|s| s.to_string())
-                })
-            })
-            .collect();
-
-        let raw: HashMap<String, HashMap<String, String>> =
-            self.post(endpoints::PRICES, &body).await?;
-
-        Ok(raw
-            .into_iter()
-            .map(|(token_id, sides)| BatchPriceResponse {
-                token_id,
-                buy: sides.get("BUY").and_then(|s| s.parse().ok()),
-                sell: sides.get("SELL").and_then(|s| s.parse().ok()),
-            })
-            .collect())
+        self.market_data.get_prices(params).await
     }
 
+    #[deprecated(note = "use `prices().get_prices_by_token()`")]
+    pub async fn get_prices_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchPriceResponse>> {
+        self.market_data.get_prices_by_token(params).await
+    }
+
+    #[deprecated(note = "use `prices().get_spread()`")]
     pub async fn get_spread(&self, token_id: &str) -> Result<f64> {
-        let url = format!("{}?token_id={}", endpoints::SPREAD, token_id);
-        let resp: SpreadResponse = self.get(&url).await?;
-        Ok(resp.spread)
+        self.market_data.get_spread(token_id).await
     }
 
+    #[deprecated(note = "use `prices().get_spreads()`")]
     pub async fn get_spreads(&self, params: &[BookParams]) -> Result<Vec<BatchSpreadResponse>> {
-        let body: Vec<_> = params
-            .iter()
-            .map(|p| serde_json::json!({"token_id": p.token_id}))
-            .collect();
-
-        let raw: HashMap<String, String> = self.post(endpoints::SPREADS, &body).await?;
+        self.market_data.get_spreads(params).await
+    }
 
-        Ok(raw
-            .into_iter()
-            .map(|(token_id, spread)| BatchSpreadResponse {
-                token_id,
-                spread: spread.parse().ok(),
-            })
-            .collect())
+    #[deprecated(note = "use `prices().get_spreads_by_token()`")]
+    pub async fn get_spreads_by_token(
+        &self,
+        params: &[BookParams],
+    ) -> Result<HashMap<String, BatchSpreadResponse>> {
+        self.market_data.get_spreads_by_token(params).await
     }
 
+    #[deprecated(note = "use `prices().get_last_trade_price()`")]
     pub async fn get_last_trade_price(&self, token_id: &str) -> Result<f64> {
-        let url = format!("{}?token_id={}", endpoints::LAST_TRADE_PRICE, token_id);
-        let resp: LastTradePriceResponse = self.get(&url).await?;
-        Ok(resp.price)
+        self.market_data.get_last_trade_price(token_id).await
     }
 
+    #[deprecated(note = "use `prices().get_last_trades_prices()`")]
     pub async fn get_last_trades_prices(
         &self,
         params: &[BookParams],
     ) -> Result<Vec<LastTradesPriceEntry>> {
-        let body: Vec<_> = params
-            .iter()
-            .map(|p| serde_json::json!({"token_id": p.token_id}))
-            .collect();
-        self.post(endpoints::LAST_TRADES_PRICES, &body).await
+        self.market_data.get_last_trades_prices(params).await
     }
 
+    #[deprecated(note = "use `markets().get_tick_size()`")]
     pub async fn get_tick_size(&self, token_id: &str) -> Result<TickSize> {
-        if let Some(tick_size) = self.tick_sizes.get(token_id) {
-            return Ok(*tick_size);
-        }
-
-        let url = format!("{}?token_id={}", endpoints::TICK_SIZE, token_id);
-        let resp: TickSizeResponse = self.get(&url).await?;
-
-        self.tick_sizes
-            .insert(token_id.to_string(), resp.minimum_tick_size);
-        Ok(resp.minimum_tick_size)
+        self.market_data.get_tick_size(token_id).await
     }
 
+    #[deprecated(note = "use `markets().get_neg_risk()`")]
     pub async fn get_neg_risk(&self, token_id: &str) -> Result<bool> {
-        if let Some(neg_risk) = self.neg_risk.get(token_id) {
-            return Ok(*neg_risk);
-        }
-
-        let url = format!("{}?token_id={}", endpoints::NEG_RISK, token_id);
-        let resp: NegRiskResponse = self.get(&url).await?;
-
-        self.neg_risk.insert(token_id.to_string(), resp.neg_risk);
-        Ok(resp.neg_risk)
+        self.market_data.get_neg_risk(token_id).await
     }
 
+    #[deprecated(note = "use `markets().get_fee_rate_bps()`")]
     pub async fn get_fee_rate_bps(&self, token_id: &str) -> Result<i32> {
-        if let Some(fee_rate) = self.fee_rates.get(token_id) {
-            return Ok(*fee_rate);
-        }
-
-        let url = format!("{}?token_id={}", endpoints::FEE_RATE, token_id);
-        let resp: FeeRateResponse = self.get(&url).await?;
-        let fee_rate = resp.base_fee.unwrap_or(0);
+        self.market_data.get_fee_rate_bps(token_id).await
+    }
 
-        self.fee_rates.insert(token_id.to_string(), fee_rate);
-        Ok(fee_rate)
+    #[deprecated(note = "use `markets().get_min_order_size()`")]
+    pub async fn get_min_order_size(&self, token_id: &str) -> Result<f64> {
+        self.market_data.get_min_order_size(token_id).await
     }
 
+    #[deprecated(note = "use `markets().get_markets_page()`")]
     pub async fn get_markets_page(&self, cursor: Option<&str>) -> Result<MarketsResponse> {
-        let cursor = cursor.unwrap_or(FIRST_CURSOR);
-        let url = format!("{}?next_cursor={}", endpoints::MARKETS, cursor);
-        self.get(&url).await
+        self.market_data.get_markets_page(cursor).await
     }
 
+    #[deprecated(note = "use `markets().get_markets()`")]
     pub async fn get_markets(&self) -> Result<Vec<Market>> {
-        let mut results = Vec::new();
-        let mut cursor = FIRST_CURSOR.to_string();
+        self.market_data.get_markets().await
+    }
 
-        loop {
-            let response = self.get_markets_page(Some(&cursor)).await?;
-            results.extend(response.data);
+    #[deprecated(note = "use `markets().market_by_condition_id()`")]
+    pub async fn market_by_condition_id(&self, condition_id: &str) -> Result<Arc<Market>> {
+        self.market_data.market_by_condition_id(condition_id).await
+    }
 
-            if response.next_cursor == END_CURSOR {
-                break;
-            }
-            cursor = response.next_cursor;
-        }
+    #[deprecated(note = "use `markets().market_by_slug()`")]
+    pub async fn market_by_slug(&self, slug: &str) -> Result<Arc<Market>> {
+        self.market_data.market_by_slug(slug).await
+    }
+
+    #[deprecated(note = "use `markets().get_token_ids()`")]
+    pub async fn get_token_ids(&self, market: &str) -> Result<Vec<(String, String)>> {
+        self.market_data.get_token_ids(market).await
+    }
 
-        Ok(results)
+    #[deprecated(note = "use `markets().find_markets()`")]
+    pub fn find_markets(
+        &self,
+        filter: MarketFilter,
+    ) -> impl Stream<Item = Result<Market>> + Send + '_ {
+        self.market_data.find_markets(filter)
     }
 
+    #[deprecated(note = "use `markets().get_simplified_markets_page()`")]
     pub async fn get_simplified_markets_page(
         &self,
         cursor: Option<&str>,
     ) -> Result<SimplifiedMarketsResponse> {
-        let cursor = cursor.unwrap_or(FIRST_CURSOR);
-        let url = format!("{}?next_cursor={}", endpoints::SIMPLIFIED_MARKETS, cursor);
-        self.get(&url).await
+        self.market_data.get_simplified_markets_page(cursor).await
     }
 
+    #[deprecated(note = "use `markets().get_sampling_markets_page()`")]
     pub async fn get_sampling_markets_page(&self, cursor: Option<&str>) -> Result<MarketsResponse> {
-        let cursor = cursor.unwrap_or(FIRST_CURSOR);
-        let url = format!("{}?next_cursor={}", endpoints::SAMPLING_MARKETS, cursor);
-        self.get(&url).await
+        self.market_data.get_sampling_markets_page(cursor).await
     }
 
+    #[deprecated(note = "use `markets().get_sampling_simplified_markets_page()`")]
     pub async fn get_sampling_simplified_markets_page(
         &self,
         cursor: Option<&str>,
     ) -> Result<SimplifiedMarketsResponse> {
-        let cursor = cursor.unwrap_or(FIRST_CURSOR);
-        let url = format!(
-            "{}?next_cursor={}",
-            endpoints::SAMPLING_SIMPLIFIED_MARKETS,
-            cursor
-        );
-        self.get(&url).await
+        self.market_data
+            .get_sampling_simplified_markets_page(cursor)
+            .await
     }
 
+    #[deprecated(note = "use `markets().get_market()`")]
     pub async fn get_market(&self, condition_id: &str) -> Result<Market> {
-        let url = format!("{}{}", endpoints::MARKET, condition_id);
-        self.get(&url).await
+        self.market_data.get_market(condition_id).await
+    }
+
+    /// Condition id, outcome, tick size and neg risk of a token in an indexed
+    /// market or the market cache, see [`MarketDataApi::lookup_token`].
+    pub fn lookup_token(&self, token_id: &str) -> Option<TokenInfo> {
+        self.market_data.lookup_token(token_id)
     }
 
+    #[deprecated(note = "use `markets().market_for_token()`")]
+    pub fn market_for_token(&self, token_id: &str) -> Option<Arc<Market>> {
+        self.market_data.market_for_token(token_id)
+    }
+
+    #[deprecated(note = "use `markets().get_market_trades_events()`")]
     pub async fn get_market_trades_events(
         &self,
         condition_id: &str,
     ) -> Result<Vec<MarketTradeEvent>> {
-        let url = format!("{}{}", endpoints::MARKET_TRADES_EVENTS, condition_id);
-        self.get(&url).await
+        self.market_data
+            .get_market_trades_events(condition_id)
+            .await
     }
 
     // ========== L1 Endpoints (requires signer) ==========
 
+    #[deprecated(note = "use `account()?.create_api_key()`")]
     pub async fn create_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
-        let signer = self.assert_l1()?;
-        let headers = create_level_1_headers(signer, nonce).await?;
-
-        let url = format!("{}{}", self.host, endpoints::CREATE_API_KEY);
-        let response = self
-            .http
-            .post(&url)
-            .header("User-Agent", "clob-rs")
-            .header("Accept", "application/json")
-            .header(POLY_ADDRESS, &headers.address)
-            .header(POLY_SIGNATURE, &headers.signature)
-            .header(POLY_TIMESTAMP, &headers.timestamp)
-            .header(POLY_NONCE, &headers.nonce)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClobError::Api {
-                message: format!("HTTP {}: {}", status, body),
-            });
-        }
-
-        response.json().await.map_err(|e| ClobError::Json {
-            message: e.to_string(),
-        })
+        self.auth()?.create_api_key(nonce).await
     }
 
+    #[deprecated(note = "use `account()?.derive_api_key()`")]
     pub async fn derive_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
-        let signer = self.assert_l1()?;
-        let headers = create_level_1_headers(signer, nonce).await?;
-
-        let url = format!("{}{}", self.host, endpoints::DERIVE_API_KEY);
-        let response = self
-            .http
-            .get(&url)
-            .header("User-Agent", "clob-rs")
-            .header("Accept", "application/json")
-            .header(POLY_ADDRESS, &headers.address)
-            .header(POLY_SIGNATURE, &headers.signature)
-            .header(POLY_TIMESTAMP, &headers.timestamp)
-            .header(POLY_NONCE, &headers.nonce)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClobError::Api {
-                message: format!("HTTP {}: {}", status, body),
-            });
-        }
-
-        response.json().await.map_err(|e| ClobError::Json {
-            message: e.to_string(),
-        })
+        self.auth()?.derive_api_key(nonce).await
     }
 
+    #[deprecated(note = "use `account()?.create_or_derive_api_key()`")]
     pub async fn create_or_derive_api_key(&self, nonce: Option<u64>) -> Result<ApiCreds> {
-        match self.create_api_key(nonce).await {
-            Ok(creds) => Ok(creds),
-            Err(_) => self.derive_api_key(nonce).await,
-        }
+        self.auth()?.create_or_derive_api_key(nonce).await
     }
 
+    #[deprecated(note = "use `orders()?.create_order()`")]
     pub async fn create_order(
         &self,
         order_args: &OrderArgs,
         options: Option<PartialCreateOrderOptions>,
     ) -> Result<SignedOrder> {
-        self.assert_l1()?;
-
-        let tick_size = match options.as_ref().and_then(|o| o.tick_size) {
-            Some(ts) => ts,
-            None => self.get_tick_size(&order_args.token_id).await?,
-        };
-
-        let neg_risk = match options.as_ref().and_then(|o| o.neg_risk) {
-            Some(nr) => nr,
-            None => self.get_neg_risk(&order_args.token_id).await?,
-        };
-
-        let create_options = CreateOrderOptions {
-            tick_size,
-            neg_risk,
-        };
-
-        self.order_builder
-            .as_ref()
-            .ok_or_else(|| ClobError::AuthRequired("order builder not initialized".to_string()))?
-            .create_order(order_args, &create_options)
-            .await
+        self.orders()?.create_order(order_args, options).await
     }
 
+    #[deprecated(note = "use `orders()?.create_market_order()`")]
     pub async fn create_market_order(
         &mut self,
         order_args: &MarketOrderArgs,
         options: Option<PartialCreateOrderOptions>,
     ) -> Result<SignedOrder> {
-        self.assert_l1()?;
-
-        let tick_size = match options.as_ref().and_then(|o| o.tick_size) {
-            Some(ts) => ts,
-            None => self.get_tick_size(&order_args.token_id).await?,
-        };
-
-        let neg_risk = match options.as_ref().and_then(|o| o.neg_risk) {
-            Some(nr) => nr,
-            None => self.get_neg_risk(&order_args.token_id).await?,
-        };
-
-        let create_options = CreateOrderOptions {
-            tick_size,
-            neg_risk,
-        };
-
-        self.order_builder
-            .as_ref()
-            .ok_or_else(|| ClobError::AuthRequired("order builder not initialized".to_string()))?
-            .create_market_order(order_args, &create_options)
+        self.orders()?
+            .create_market_order(order_args, options)
             .await
     }
 
     // ========== L2 Endpoints (requires API credentials) ==========
 
-    pub async fn get_api_keys(&self) -> Result<serde_json::Value> {
-        let (signer, creds) = self.assert_l2()?;
-        let headers = create_level_2_headers(signer, creds, "GET", endpoints::GET_API_KEYS, None);
-
-        let url = format!("{}{}", self.host, endpoints::GET_API_KEYS);
-        self.get_with_l2_headers(&url, &headers).await
+    #[deprecated(note = "use `account()?.get_api_keys()`")]
+    pub async fn get_api_keys(&self) -> Result<ApiKeysResponse> {
+        self.auth()?.get_api_keys().await
     }
 
-    pub async fn delete_api_key(&self) -> Result<serde_json::Value> {
-        let (signer, creds) = self.assert_l2()?;
-        let headers =
-            create_level_2_headers(signer, creds, "DELETE", endpoints::DELETE_API_KEY, None);
+    #[deprecated(note = "use `account()?.delete_api_key()`")]
+    pub async fn delete_api_key(&self) -> Result<DeleteApiKeyResponse> {
+        self.auth()?.delete_api_key().await
+    }
 
-        let url = format!("{}{}", self.host, endpoints::DELETE_API_KEY);
-        self.delete_with_l2_headers(&url, &headers).await
+    #[deprecated(note = "use `account()?.revoke_api_key()`")]
+    pub async fn revoke_api_key(&self, creds: &ApiCreds) -> Result<DeleteApiKeyResponse> {
+        self.auth()?.revoke_api_key(creds).await
     }
 
+    #[deprecated(note = "use `orders()?.post_order()`")]
     pub async fn post_order(
         &self,
         order: &SignedOrder,
         order_type: OrderType,
     ) -> Result<PostOrderResponse> {
-        let (signer, creds) = self.assert_l2()?;
-
-        let body = serde_json::json!({
-            "order": order,
-            "owner": creds.api_key,
-            "orderType": order_type.to_string()
-        });
-        let body_str = serde_json::to_string(&body).unwrap();
-
-        let headers = create_level_2_headers(
-            signer,
-            creds,
-            "POST",
-            endpoints::POST_ORDER,
-            Some(&body_str),
-        );
+        self.orders()?.post_order(order, order_type).await
+    }
 
-        let url = format!("{}{}", self.host, endpoints::POST_ORDER);
-        self.post_with_l2_headers(&url, &headers, &body).await
+    #[deprecated(note = "use `orders()?.sweep()`")]
+    pub async fn sweep(
+        &self,
+        token_id: &str,
+        side: Side,
+        limit: SweepLimit,
+        limit_price: f64,
+    ) -> Result<SweepResult> {
+        self.orders()?
+            .sweep(token_id, side, limit, limit_price)
+            .await
     }
 
+    #[deprecated(note = "use `orders()?.cancel()`")]
     pub async fn cancel(&self, order_id: &str) -> Result<serde_json::Value> {
-        let (signer, creds) = self.assert_l2()?;
-
-        let body = serde_json::json!({"orderID": order_id});
-        let body_str = serde_json::to_string(&body).unwrap();
-
-        let headers =
-            create_level_2_headers(signer, creds, "DELETE", endpoints::CANCEL, Some(&body_str));
-
-        let url = format!("{}{}", self.host, endpoints::CANCEL);
-        self.delete_with_l2_headers_and_body(&url, &headers, &body)
-            .await
+        self.orders()?.cancel(order_id).await
     }
 
+    #[deprecated(note = "use `orders()?.cancel_orders()`")]
     pub async fn cancel_orders(&self, order_ids: &[String]) -> Result<serde_json::Value> {
-        let (signer, creds) = self.assert_l2()?;
-
-        let body = serde_json::json!(order_ids);
-        let body_str = serde_json::to_string(&body).unwrap();
-
-        let headers = create_level_2_headers(
-            signer,
-            creds,
-            "DELETE",
-            endpoints::CANCEL_ORDERS,
-            Some(&body_str),
-        );
-
-        let url = format!("{}{}", self.host, endpoints::CANCEL_ORDERS);
-        self.delete_with_l2_headers_and_body(&url, &headers, &body)
-            .await
+        self.orders()?.cancel_orders(order_ids).await
     }
 
+    #[deprecated(note = "use `orders()?.cancel_all()`")]
     pub async fn cancel_all(&self) -> Result<serde_json::Value> {
-        let (signer, creds) = self.assert_l2()?;
-        let headers = create_level_2_headers(signer, creds, "DELETE", endpoints::CANCEL_ALL, None);
-
-        let url = format!("{}{}", self.host, endpoints::CANCEL_ALL);
-        self.delete_with_l2_headers(&url, &headers).await
+        self.orders()?.cancel_all().await
     }
 
+    #[deprecated(note = "use `orders()?.get_orders()`")]
     pub async fn get_orders(&self, params: Option<&OpenOrderParams>) -> Result<serde_json::Value> {
-        let (signer, creds) = self.assert_l2()?;
-        let headers = create_level_2_headers(signer, creds, "GET", endpoints::ORDERS, None);
-
-        let mut url = format!("{}{}", self.host, endpoints::ORDERS);
-
-        // TODO: this is veryhacky and we'd rather use serde to searlize these
-        let mut query_parts = Vec::new();
-        if let Some(p) = params {
-            if let Some(id) = &p.id {
-                query_parts.push(format!("id={}", id));
-            }
-            if let Some(market) = &p.market {
-                query_parts.push(format!("market={}", market));
-            }
-            if let Some(asset_id) = &p.asset_id {
-                query_parts.push(format!("asset_id={}", asset_id));
-            }
-        }
-        if !query_parts.is_empty() {
-            url = format!("{}?{}", url, query_parts.join("&"));
-        }
-
-        self.get_with_l2_headers(&url, &headers).await
+        self.orders()?.get_orders(params).await
     }
 
-    pub async fn get_order(&self, order_id: &str) -> Result<serde_json::Value> {
-        let (signer, creds) = self.assert_l2()?;
-        let path = format!("{}{}", endpoints::GET_ORDER, order_id);
-        let headers = create_level_2_headers(signer, creds, "GET", &path, None);
-
-        let url = format!("{}{}", self.host, path);
-        self.get_with_l2_headers(&url, &headers).await
+    #[deprecated(note = "use `orders()?.get_order()`")]
+    pub async fn get_order(&self, order_id: &str) -> Result<OrderStatus> {
+        self.orders()?.get_order(order_id).await
     }
 
+    #[deprecated(note = "use `orders()?.get_trades()`")]
     pub async fn get_trades(&self, params: Option<&TradeParams>) -> Result<serde_json::Value> {
-        let (signer, creds) = self.assert_l2()?;
-        let headers = create_level_2_headers(signer, creds, "GET", endpoints::TRADES, None);
-
-        let mut url = format!("{}{}", self.host, endpoints::TRADES);
-
-        let mut query_parts = Vec::new();
-        if let Some(p) = params {
-            if let Some(id) = &p.id {
-                query_parts.push(format!("id={}", id));
-            }
-            if let Some(maker) = &p.maker_address {
-                query_parts.push(format!("maker_address={}", maker));
-            }
-            if let Some(market) = &p.market {
-                query_parts.push(format!("market={}", market));
-            }
-            if let Some(asset_id) = &p.asset_id {
-                query_parts.push(format!("asset_id={}", asset_id));
-            }
-            if let Some(before) = p.before {
-                query_parts.push(format!("before={}", before));
-            }
-            if let Some(after) = p.after {
-                query_parts.push(format!("after={}", after));
-            }
-        }
-        if !query_parts.is_empty() {
-            url = format!("{}?{}", url, query_parts.join("&"));
-        }
-
-        self.get_with_l2_headers(&url, &headers).await
+        self.orders()?.get_trades(params).await
     }
 
+    #[deprecated(note = "use `account()?.get_balance_allowance()`")]
     pub async fn get_balance_allowance(
         &self,
         params: &BalanceAllowanceParams,
     ) -> Result<BalanceAllowanceResponse> {
-        let (signer, creds) = self.assert_l2()?;
-        let headers =
-            create_level_2_headers(signer, creds, "GET", endpoints::GET_BALANCE_ALLOWANCE, None);
-
-        let mut url = format!("{}{}", self.host, endpoints::GET_BALANCE_ALLOWANCE);
-
-        // Add query params
-        let mut query_parts = Vec::new();
-        if let Some(asset_type) = &params.asset_type {
-            query_parts.push(format!("asset_type={}", asset_type));
-        }
-        if let Some(token_id) = &params.token_id {
-            query_parts.push(format!("token_id={}", token_id));
-        }
-        if let Some(sig_type) = params.signature_type {
-            query_parts.push(format!("signature_type={}", sig_type));
-        }
-        if !query_parts.is_empty() {
-            url = format!("{}?{}", url, query_parts.join("&"));
-        }
-
-        self.get_with_l2_headers(&url, &headers).await
+        self.orders()?.get_balance_allowance(params).await
     }
 
+    #[deprecated(note = "use `account()?.update_balance_allowance()`")]
     pub async fn update_balance_allowance(
         &self,
         params: &BalanceAllowanceParams,
     ) -> Result<BalanceAllowanceResponse> {
-        let (signer, creds) = self.assert_l2()?;
-        let headers = create_level_2_headers(
-            signer,
-            creds,
-            "GET",
-            endpoints::UPDATE_BALANCE_ALLOWANCE,
-            None,
-        );
-
-        let mut url = format!("{}{}", self.host, endpoints::UPDATE_BALANCE_ALLOWANCE);
-
-        // Add query params
-        let mut query_parts = Vec::new();
-        if let Some(asset_type) = &params.asset_type {
-            query_parts.push(format!("asset_type={}", asset_type));
-        }
-        if let Some(token_id) = &params.token_id {
-            query_parts.push(format!("token_id={}", token_id));
-        }
-        if let Some(sig_type) = params.signature_type {
-            query_parts.push(format!("signature_type={}", sig_type));
-        }
-        if !query_parts.is_empty() {
-            url = format!("{}?{}", url, query_parts.join("&"));
-        }
-
-        self.get_with_l2_headers(&url, &headers).await
-    }
-
-    // ========== Internal HTTP helpers ==========
-    // TODO: refactor out all these different gets / puts / deletes method into one
-    // there would just be one request() where it takes in authlevel and you have a match
-    // case to write specific headers based on the auth
-
-    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
-        let url = format!("{}{}", self.host, path);
-        let response = self
-            .http
-            .get(&url)
-            .header("User-Agent", "clob-rs")
-            .header("Accept", "application/json")
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            // TODO: some better error handling would be nice instead of plain string
-            return Err(ClobError::Api {
-                message: format!("HTTP {}: {}", status, body),
-            });
-        }
-
-        let text = response.text().await?;
-        serde_json::from_str(&text).map_err(|e| ClobError::Json {
-            message: e.to_string(),
-        })
-    }
-
-    async fn post<T: serde::de::DeserializeOwned, B: Serialize>(
-        &self,
-        path: &str,
-        body: &B,
-    ) -> Result<T> {
-        let url = format!("{}{}", self.host, path);
-        let response = self
-            .http
-            .post(&url)
-            .header("User-Agent", "clob-rs")
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .json(body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClobError::Api {
-                message: format!("HTTP {}: {}", status, body),
-            });
-        }
-
-        let body = response.json().await?;
-        Ok(body)
-    }
-
-    async fn get_with_l2_headers<T: serde::de::DeserializeOwned>(
-        &self,
-        url: &str,
-        headers: &L2Headers,
-    ) -> Result<T> {
-        let response = self
-            .http
-            .get(url)
-            .header("User-Agent", "clob-rs")
-            .header("Accept", "application/json")
-            .header(POLY_ADDRESS, &headers.address)
-            .header(POLY_SIGNATURE, &headers.signature)
-            .header(POLY_TIMESTAMP, &headers.timestamp)
-            .header(POLY_API_KEY, &headers.api_key)
-            .header(POLY_PASSPHRASE, &headers.passphrase)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClobError::Api {
-                message: format!("HTTP {}: {}", status, body),
-            });
-        }
-
-        response.json().await.map_err(|e| ClobError::Json {
-            message: e.to_string(),
-        })
-    }
-
-    async fn post_with_l2_headers<T: serde::de::DeserializeOwned, B: Serialize>(
-        &self,
-        url: &str,
-        headers: &L2Headers,
-        body: &B,
-    ) -> Result<T> {
-        let response = self
-            .http
-            .post(url)
-            .header("User-Agent", "clob-rs")
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .header(POLY_ADDRESS, &headers.address)
-            .header(POLY_SIGNATURE, &headers.signature)
-            .header(POLY_TIMESTAMP, &headers.timestamp)
-            .header(POLY_API_KEY, &headers.api_key)
-            .header(POLY_PASSPHRASE, &headers.passphrase)
-            .json(body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClobError::Api {
-                message: format!("HTTP {}: {}", status, body),
-            });
-        }
-
-        response.json().await.map_err(|e| ClobError::Json {
-            message: e.to_string(),
-        })
-    }
-
-    async fn delete_with_l2_headers<T: serde::de::DeserializeOwned>(
-        &self,
-        url: &str,
-        headers: &L2Headers,
-    ) -> Result<T> {
-        let response = self
-            .http
-            .delete(url)
-            .header("User-Agent", "clob-rs")
-            .header("Accept", "application/json")
-            .header(POLY_ADDRESS, &headers.address)
-            .header(POLY_SIGNATURE, &headers.signature)
-            .header(POLY_TIMESTAMP, &headers.timestamp)
-            .header(POLY_API_KEY, &headers.api_key)
-            .header(POLY_PASSPHRASE, &headers.passphrase)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClobError::Api {
-                message: format!("HTTP {}: {}", status, body),
-            });
-        }
-
-        response.json().await.map_err(|e| ClobError::Json {
-            message: e.to_string(),
-        })
-    }
-
-    async fn delete_with_l2_headers_and_body<T: serde::de::DeserializeOwned, B: Serialize>(
-        &self,
-        url: &str,
-        headers: &L2Headers,
-        body: &B,
-    ) -> Result<T> {
-        let response = self
-            .http
-            .delete(url)
-            .header("User-Agent", "clob-rs")
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .header(POLY_ADDRESS, &headers.address)
-            .header(POLY_SIGNATURE, &headers.signature)
-            .header(POLY_TIMESTAMP, &headers.timestamp)
-            .header(POLY_API_KEY, &headers.api_key)
-            .header(POLY_PASSPHRASE, &headers.passphrase)
-            .json(body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(ClobError::Api {
-                message: format!("HTTP {}: {}", status, body),
-            });
-        }
-
-        response.json().await.map_err(|e| ClobError::Json {
-            message: e.to_string(),
-        })
+        self.orders()?.update_balance_allowance(params).await
     }
 }
@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::{ClobError, Result};
+use crate::secret::SecretString;
+use crate::types::ApiCreds;
+
+/// Everything needed to build a [`ClobClient`](crate::ClobClient), loaded from the environment
+/// or a TOML file.
+///
+/// ```toml
+/// host = "https://clob.polymarket.com"
+/// fallback_hosts = ["https://backup.example.com"]
+/// chain_id = 137
+/// private_key = "0x..."
+/// funder = "0x..."
+/// signature_type = 1
+///
+/// [creds]
+/// api_key = "..."
+/// api_secret = "..."
+/// api_passphrase = "..."
+/// ```
+///
+/// Instead of `[creds]`, `creds_file` (and `creds_passphrase` if it is
+/// encrypted) can point at a file written by [`ApiCreds::save`].
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClobConfig {
+    pub host: String,
+    /// Tried in order when `host` fails, see
+    /// [`Transport::with_fallback_hosts`](crate::Transport::with_fallback_hosts).
+    pub fallback_hosts: Vec<String>,
+    pub chain_id: u64,
+    pub private_key: Option<SecretString>,
+    pub funder: Option<String>,
+    pub signature_type: Option<u8>,
+    pub creds: Option<ApiCreds>,
+    pub creds_file: Option<PathBuf>,
+    pub creds_passphrase: Option<SecretString>,
+}
+
+impl Default for ClobConfig {
+    fn default() -> Self {
+        Self {
+            host: crate::config::HOST.to_string(),
+            fallback_hosts: Vec::new(),
+            chain_id: crate::config::CHAIN_ID,
+            private_key: None,
+            funder: None,
+            signature_type: None,
+            creds: None,
+            creds_file: None,
+            creds_passphrase: None,
+        }
+    }
+}
+
+// keys and secrets stay out of logs
+impl std::fmt::Debug for ClobConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let redact = |set: bool| if set { Some("<redacted>") } else { None };
+        f.debug_struct("ClobConfig")
+            .field("host", &self.host)
+            .field("fallback_hosts", &self.fallback_hosts)
+            .field("chain_id", &self.chain_id)
+            .field("private_key", &redact(self.private_key.is_some()))
+            .field("funder", &self.funder)
+            .field("signature_type", &self.signature_type)
+            .field("creds", &self.creds.as_ref().map(|c| &c.api_key))
+            .field("creds_file", &self.creds_file)
+            .field("creds_passphrase", &redact(self.creds_passphrase.is_some()))
+            .finish()
+    }
+}
+
+impl ClobConfig {
+    /// Reads `CLOB_HOST`, `CLOB_FALLBACK_HOSTS` (comma-separated), `CLOB_CHAIN_ID`, `CLOB_PRIVATE_KEY`, `CLOB_FUNDER`,
+    /// `CLOB_SIGNATURE_TYPE`, `CLOB_API_KEY`, `CLOB_API_SECRET`,
+    /// `CLOB_API_PASSPHRASE`, `CLOB_CREDS_FILE` and `CLOB_CREDS_PASSPHRASE`.
+    /// Unset variables keep their defaults.
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    }
+
+    /// Loads a TOML file. A relative `creds_file` is resolved against the
+    /// file's directory.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        let mut config: ClobConfig = toml::from_str(&contents).map_err(|e| {
+            ClobError::InvalidParameter(format!("invalid config file {}: {}", path.display(), e))
+        })?;
+
+        if let (Some(creds_file), Some(dir)) = (&config.creds_file, path.parent()) {
+            if creds_file.is_relative() {
+                config.creds_file = Some(dir.join(creds_file));
+            }
+        }
+        Ok(config)
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut config = Self::default();
+        if let Some(host) = var("CLOB_HOST") {
+            config.host = host;
+        }
+        if let Some(hosts) = var("CLOB_FALLBACK_HOSTS") {
+            config.fallback_hosts = hosts
+                .split(',')
+                .map(str::trim)
+                .filter(|host| !host.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(chain_id) = var("CLOB_CHAIN_ID") {
+            config.chain_id = chain_id
+                .parse()
+                .map_err(|_| ClobError::InvalidParameter("invalid CLOB_CHAIN_ID".to_string()))?;
+        }
+        config.private_key = var("CLOB_PRIVATE_KEY").map(SecretString::from);
+        config.funder = var("CLOB_FUNDER");
+        if let Some(sig_type) = var("CLOB_SIGNATURE_TYPE") {
+            config.signature_type = Some(sig_type.parse().map_err(|_| {
+                ClobError::InvalidParameter("invalid CLOB_SIGNATURE_TYPE".to_string())
+            })?);
+        }
+        config.creds = creds_from_parts(
+            var("CLOB_API_KEY"),
+            var("CLOB_API_SECRET"),
+            var("CLOB_API_PASSPHRASE"),
+        )?;
+        config.creds_file = var("CLOB_CREDS_FILE").map(PathBuf::from);
+        config.creds_passphrase = var("CLOB_CREDS_PASSPHRASE").map(SecretString::from);
+        Ok(config)
+    }
+
+    /// Inline credentials, or those in `creds_file`.
+    pub fn resolve_creds(&self) -> Result<Option<ApiCreds>> {
+        match (&self.creds, &self.creds_file) {
+            (Some(creds), _) => Ok(Some(creds.clone())),
+            (None, Some(path)) => ApiCreds::load(
+                path,
+                self.creds_passphrase.as_ref().map(SecretString::expose),
+            )
+            .map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+// the three parts must be all set or all missing
+fn creds_from_parts(
+    api_key: Option<String>,
+    api_secret: Option<String>,
+    api_passphrase: Option<String>,
+) -> Result<Option<ApiCreds>> {
+    match (api_key, api_secret, api_passphrase) {
+        (Some(api_key), Some(api_secret), Some(api_passphrase)) => Ok(Some(ApiCreds {
+            api_key,
+            api_secret: api_secret.into(),
+            api_passphrase: api_passphrase.into(),
+        })),
+        (None, None, None) => Ok(None),
+        _ => Err(ClobError::InvalidParameter(
+            "api key, secret and passphrase must be set together".to_string(),
+        )),
+    }
+}
@@ -1,4 +1,10 @@
 // https://github.com/Polymarket/py-clob-client/blob/main/py_clob_client/config.py
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use alloy_primitives::Address;
+
+use crate::error::{ClobError, Result};
 use crate::types::ContractConfig;
 
 pub const HOST: &str = "https://clob.polymarket.com";
@@ -7,22 +13,104 @@ pub const CHAIN_ID: u64 = 137;
 pub const END_CURSOR: &str = "LTE=";
 pub const FIRST_CURSOR: &str = "MA==";
 
+/// Most tokens the batch endpoints (`/books`, `/prices`, ...) accept per call.
+pub const MAX_BATCH_SIZE: usize = 500;
+
+/// Decimals of USDC, the collateral on Polygon.
+pub const COLLATERAL_DECIMALS: u32 = 6;
+
 const POLYGON_CONFIG: ContractConfig = ContractConfig {
     exchange: "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E",
     collateral: "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174",
     conditional_tokens: "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045",
+    collateral_decimals: COLLATERAL_DECIMALS,
 };
 
 const POLYGON_NEG_RISK_CONFIG: ContractConfig = ContractConfig {
     exchange: "0xC5d563A36AE78145C45a50134d48A1215220f80a",
     collateral: "0x2791bca1f2de4661ed88a30c99a7a9449aa84174",
     conditional_tokens: "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045",
+    collateral_decimals: COLLATERAL_DECIMALS,
 };
 
+// (chain_id, neg_risk) -> config; entries are leaked so lookups stay 'static
+static CONTRACT_CONFIGS: LazyLock<RwLock<HashMap<(u64, bool), &'static ContractConfig>>> =
+    LazyLock::new(|| {
+        RwLock::new(HashMap::from([
+            ((CHAIN_ID, false), &POLYGON_CONFIG),
+            ((CHAIN_ID, true), &POLYGON_NEG_RISK_CONFIG),
+        ]))
+    });
+
+static NEG_RISK_ADAPTERS: LazyLock<RwLock<HashMap<u64, &'static str>>> = LazyLock::new(|| {
+    RwLock::new(HashMap::from([(
+        CHAIN_ID,
+        "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296",
+    )]))
+});
+
 pub fn get_contract_config(chain_id: u64, neg_risk: bool) -> Option<&'static ContractConfig> {
-    match (chain_id, neg_risk) {
-        (137, false) => Some(&POLYGON_CONFIG),
-        (137, true) => Some(&POLYGON_NEG_RISK_CONFIG),
-        _ => None,
+    CONTRACT_CONFIGS
+        .read()
+        .unwrap()
+        .get(&(chain_id, neg_risk))
+        .copied()
+}
+
+/// Adds or replaces the contracts used for `chain_id`, e.g. for a testnet or
+/// a fork of the exchange. Every address must parse and the collateral must
+/// have between 6 and 18 decimals. Configs registered here
+/// live for the rest of the process, so register once at startup; runtime
+/// strings can be turned into `&'static str` with [`String::leak`].
+pub fn register_contract_config(
+    chain_id: u64,
+    neg_risk: bool,
+    config: ContractConfig,
+) -> Result<()> {
+    for (name, address) in [
+        ("exchange", config.exchange),
+        ("collateral", config.collateral),
+        ("conditional_tokens", config.conditional_tokens),
+    ] {
+        check_address(name, address)?;
+    }
+    if !(COLLATERAL_DECIMALS..=18).contains(&config.collateral_decimals) {
+        return Err(ClobError::InvalidParameter(format!(
+            "collateral_decimals must be between {} and 18, got {}",
+            COLLATERAL_DECIMALS, config.collateral_decimals
+        )));
     }
+    let config: &'static ContractConfig = Box::leak(Box::new(config));
+    CONTRACT_CONFIGS
+        .write()
+        .unwrap()
+        .insert((chain_id, neg_risk), config);
+    Ok(())
+}
+
+/// Collateral decimals on `chain_id`, [`COLLATERAL_DECIMALS`] if the chain
+/// has no config.
+pub fn collateral_decimals(chain_id: u64) -> u32 {
+    get_contract_config(chain_id, false)
+        .or_else(|| get_contract_config(chain_id, true))
+        .map_or(COLLATERAL_DECIMALS, |config| config.collateral_decimals)
+}
+
+/// NegRiskAdapter, which converts NO positions of a neg-risk event.
+pub fn get_neg_risk_adapter(chain_id: u64) -> Option<&'static str> {
+    NEG_RISK_ADAPTERS.read().unwrap().get(&chain_id).copied()
+}
+
+/// Adds or replaces the NegRiskAdapter used for `chain_id`.
+pub fn register_neg_risk_adapter(chain_id: u64, adapter: &'static str) -> Result<()> {
+    check_address("neg_risk_adapter", adapter)?;
+    NEG_RISK_ADAPTERS.write().unwrap().insert(chain_id, adapter);
+    Ok(())
+}
+
+fn check_address(name: &str, address: &str) -> Result<()> {
+    address
+        .parse::<Address>()
+        .map(|_| ())
+        .map_err(|_| ClobError::InvalidParameter(format!("invalid {} address: {}", name, address)))
 }
@@ -0,0 +1,170 @@
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::error::{ClobError, Result};
+use crate::types::ApiCreds;
+
+const KDF: &str = "pbkdf2-hmac-sha256";
+const CIPHER: &str = "chacha20poly1305";
+const ITERATIONS: u32 = 100_000;
+// a file asking for more rounds than this could stall `load`
+const ITERATION_RANGE: RangeInclusive<u32> = 10_000..=10_000_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// On-disk layout of a passphrase-protected credentials file: the key is
+/// stretched with PBKDF2 and the payload sealed with ChaCha20-Poly1305.
+#[derive(Serialize, Deserialize)]
+struct EncryptedCreds {
+    kdf: String,
+    cipher: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CredsFile {
+    Encrypted(EncryptedCreds),
+    Plain(ApiCreds),
+}
+
+fn cipher(passphrase: &str, salt: &[u8], iterations: u32) -> ChaCha20Poly1305 {
+    let mut key = Zeroizing::new([0u8; 32]);
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, key.as_mut_slice());
+    ChaCha20Poly1305::new_from_slice(key.as_slice()).expect("key is 32 bytes")
+}
+
+fn decode(field: &str, value: &str) -> Result<Vec<u8>> {
+    STANDARD
+        .decode(value)
+        .map_err(|_| ClobError::InvalidParameter(format!("invalid {} in credentials file", field)))
+}
+
+fn encrypt(creds: &ApiCreds, passphrase: &str) -> Result<EncryptedCreds> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let data = Zeroizing::new(serde_json::to_vec(creds).map_err(|e| ClobError::Json {
+        message: e.to_string(),
+    })?);
+    let ciphertext = cipher(passphrase, &salt, ITERATIONS)
+        .encrypt(&Nonce::from(nonce), data.as_slice())
+        .map_err(|_| ClobError::InvalidParameter("failed to encrypt credentials".to_string()))?;
+
+    Ok(EncryptedCreds {
+        kdf: KDF.to_string(),
+        cipher: CIPHER.to_string(),
+        iterations: ITERATIONS,
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    })
+}
+
+fn decrypt(file: &EncryptedCreds, passphrase: &str) -> Result<ApiCreds> {
+    if file.kdf != KDF || file.cipher != CIPHER {
+        return Err(ClobError::InvalidParameter(format!(
+            "unsupported credentials encryption: {}/{}",
+            file.kdf, file.cipher
+        )));
+    }
+    if !ITERATION_RANGE.contains(&file.iterations) {
+        return Err(ClobError::InvalidParameter(format!(
+            "credentials file iterations must be between {} and {}, got {}",
+            ITERATION_RANGE.start(),
+            ITERATION_RANGE.end(),
+            file.iterations
+        )));
+    }
+
+    let salt = decode("salt", &file.salt)?;
+    let nonce: [u8; NONCE_LEN] = decode("nonce", &file.nonce)?.try_into().map_err(|_| {
+        ClobError::InvalidParameter("invalid nonce in credentials file".to_string())
+    })?;
+    let ciphertext = decode("ciphertext", &file.ciphertext)?;
+
+    let data = cipher(passphrase, &salt, file.iterations)
+        .decrypt(&Nonce::from(nonce), ciphertext.as_slice())
+        .map(Zeroizing::new)
+        .map_err(|_| {
+            ClobError::InvalidParameter(
+                "wrong passphrase or corrupted credentials file".to_string(),
+            )
+        })?;
+    serde_json::from_slice(&data).map_err(|e| ClobError::Json {
+        message: e.to_string(),
+    })
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(contents)
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    fs::write(path, contents)
+}
+
+impl ApiCreds {
+    /// Writes the credentials to `path` as JSON, encrypted with a key derived
+    /// from `passphrase` when one is given. On unix the file is created 0600.
+    pub fn save(&self, path: impl AsRef<Path>, passphrase: Option<&str>) -> Result<()> {
+        let contents = match passphrase {
+            Some(passphrase) => serde_json::to_vec_pretty(&encrypt(self, passphrase)?),
+            None => serde_json::to_vec_pretty(self),
+        }
+        .map(Zeroizing::new)
+        .map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })?;
+
+        write_private(path.as_ref(), &contents)?;
+        Ok(())
+    }
+
+    /// Reads credentials written by [`ApiCreds::save`]. Encrypted files need
+    /// the passphrase they were saved with, and a passphrase only opens an
+    /// encrypted file.
+    pub fn load(path: impl AsRef<Path>, passphrase: Option<&str>) -> Result<Self> {
+        let contents = Zeroizing::new(fs::read(path.as_ref())?);
+        let file: CredsFile = serde_json::from_slice(&contents).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })?;
+
+        match (file, passphrase) {
+            (CredsFile::Plain(creds), None) => Ok(creds),
+            // never fall back to plaintext when encryption was expected
+            (CredsFile::Plain(_), Some(_)) => Err(ClobError::InvalidParameter(
+                "credentials file is not encrypted, but a passphrase was given".to_string(),
+            )),
+            (CredsFile::Encrypted(file), Some(passphrase)) => decrypt(&file, passphrase),
+            (CredsFile::Encrypted(_), None) => Err(ClobError::InvalidParameter(
+                "credentials file is encrypted; a passphrase is required".to_string(),
+            )),
+        }
+    }
+}
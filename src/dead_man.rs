@@ -0,0 +1,175 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinHandle;
+
+use crate::api::OrdersApi;
+use crate::kill_switch::{KillSwitch, KillSwitchReport};
+
+/// Settings for [`OrdersApi::spawn_dead_man_switch`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeadManSwitch {
+    /// Longest gap allowed between [`DeadManHandle::heartbeat`] calls.
+    pub heartbeat_timeout: Duration,
+    /// Longest the feed may stay disconnected, if it is tracked at all.
+    pub disconnect_timeout: Option<Duration>,
+    /// How often the deadlines are checked.
+    pub check_interval: Duration,
+    /// How the orders are cancelled once tripped.
+    pub kill_switch: KillSwitch,
+}
+
+impl DeadManSwitch {
+    pub fn new(heartbeat_timeout: Duration) -> Self {
+        Self {
+            heartbeat_timeout,
+            disconnect_timeout: None,
+            check_interval: (heartbeat_timeout / 10).max(Duration::from_millis(10)),
+            kill_switch: KillSwitch::default(),
+        }
+    }
+
+    pub fn with_disconnect_timeout(mut self, timeout: Duration) -> Self {
+        self.disconnect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_check_interval(mut self, interval: Duration) -> Self {
+        self.check_interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    pub fn with_kill_switch(mut self, kill_switch: KillSwitch) -> Self {
+        self.kill_switch = kill_switch;
+        self
+    }
+}
+
+/// Why a [`DeadManSwitch`] cancelled everything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadManTrigger {
+    MissedHeartbeat,
+    Disconnected,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadManTrip {
+    pub trigger: DeadManTrigger,
+    pub report: KillSwitchReport,
+}
+
+#[derive(Debug)]
+struct DeadManState {
+    last_heartbeat: Instant,
+    disconnected_since: Option<Instant>,
+    // cleared by a trip, set again by the next heartbeat or reconnect
+    armed: bool,
+    trips: Vec<DeadManTrip>,
+}
+
+impl DeadManState {
+    fn due(&self, settings: &DeadManSwitch, now: Instant) -> Option<DeadManTrigger> {
+        if !self.armed {
+            return None;
+        }
+        if now.duration_since(self.last_heartbeat) > settings.heartbeat_timeout {
+            return Some(DeadManTrigger::MissedHeartbeat);
+        }
+        match (self.disconnected_since, settings.disconnect_timeout) {
+            (Some(since), Some(timeout)) if now.duration_since(since) > timeout => {
+                Some(DeadManTrigger::Disconnected)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Background task started by [`OrdersApi::spawn_dead_man_switch`]. Once it
+/// trips it stays quiet until the next heartbeat or reconnect. Stops when
+/// dropped.
+#[derive(Debug)]
+pub struct DeadManHandle {
+    state: Arc<Mutex<DeadManState>>,
+    task: JoinHandle<()>,
+}
+
+impl DeadManHandle {
+    /// Proves the process is alive and pushes the deadline back.
+    pub fn heartbeat(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.last_heartbeat = Instant::now();
+        state.armed = true;
+    }
+
+    /// Reports the state of the caller's websocket or user channel. Only
+    /// counts with a disconnect timeout set.
+    pub fn set_connected(&self, connected: bool) {
+        let mut state = self.state.lock().unwrap();
+        if connected {
+            if state.disconnected_since.take().is_some() {
+                state.armed = true;
+            }
+        } else if state.disconnected_since.is_none() {
+            state.disconnected_since = Some(Instant::now());
+        }
+    }
+
+    /// Every time the switch fired, oldest first.
+    pub fn trips(&self) -> Vec<DeadManTrip> {
+        self.state.lock().unwrap().trips.clone()
+    }
+}
+
+impl Drop for DeadManHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl OrdersApi {
+    /// Emulates cancel-on-disconnect: runs the kill switch if
+    /// [`DeadManHandle::heartbeat`] isn't called within the timeout, or if
+    /// the feed reported through [`DeadManHandle::set_connected`] stays down
+    /// too long. The clock starts at spawn.
+    pub fn spawn_dead_man_switch(&self, settings: DeadManSwitch) -> DeadManHandle {
+        let state = Arc::new(Mutex::new(DeadManState {
+            last_heartbeat: Instant::now(),
+            disconnected_since: None,
+            armed: true,
+            trips: Vec::new(),
+        }));
+        let shared = state.clone();
+        let orders = self.clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(settings.check_interval);
+            loop {
+                ticker.tick().await;
+                let trigger = {
+                    let mut state = shared.lock().unwrap();
+                    let trigger = state.due(&settings, Instant::now());
+                    if trigger.is_some() {
+                        state.armed = false;
+                    }
+                    trigger
+                };
+                let Some(trigger) = trigger else { continue };
+                tracing::warn!(?trigger, "dead man's switch tripped, cancelling all orders");
+                let report = orders.kill_switch_with(settings.kill_switch).await;
+                if !report.is_flat() {
+                    tracing::warn!(
+                        remaining = report.remaining.len(),
+                        errors = report.errors.len(),
+                        "dead man's switch could not confirm all orders cancelled"
+                    );
+                }
+                shared
+                    .lock()
+                    .unwrap()
+                    .trips
+                    .push(DeadManTrip { trigger, report });
+            }
+        });
+        self.market_data().transport().tasks().track(&task);
+        DeadManHandle { state, task }
+    }
+}
@@ -0,0 +1,16 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::{ClobError, Result};
+
+/// Runs `call`, giving up with [`ClobError::Timeout`] once `deadline` has
+/// passed. The call is dropped, which aborts its HTTP request; a request
+/// that was already sent may still have reached the exchange.
+pub async fn with_deadline<T>(
+    deadline: Duration,
+    call: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::time::timeout(deadline, call)
+        .await
+        .map_err(|_| ClobError::Timeout { deadline })?
+}
@@ -0,0 +1,246 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::api::MarketDataApi;
+use crate::error::Result;
+use crate::types::{BookParams, OrderBook, OrderSummary};
+
+/// Anything that maintains order books locally (e.g. from a streaming feed)
+/// and can hand out its current view of a token's book.
+pub trait BookSource: Send + Sync + 'static {
+    fn book(&self, token_id: &str) -> Option<OrderBook>;
+
+    /// Price of the token's latest trade, if the feed tracks trades.
+    fn last_trade_price(&self, _token_id: &str) -> Option<f64> {
+        None
+    }
+}
+
+impl<F> BookSource for F
+where
+    F: Fn(&str) -> Option<OrderBook> + Send + Sync + 'static,
+{
+    fn book(&self, token_id: &str) -> Option<OrderBook> {
+        self(token_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookDivergence {
+    pub token_id: String,
+    /// Local book had nothing for this token.
+    pub missing_local: bool,
+    /// Price levels present on only one side of the comparison.
+    pub missing_levels: usize,
+    /// Price levels present in both books with different sizes.
+    pub size_mismatches: usize,
+    pub best_bid_diff: Option<f64>,
+    pub best_ask_diff: Option<f64>,
+}
+
+impl BookDivergence {
+    pub fn is_consistent(&self) -> bool {
+        !self.missing_local && self.missing_levels == 0 && self.size_mismatches == 0
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DivergenceStats {
+    pub samples: u64,
+    pub divergent: u64,
+    pub missing_local: u64,
+    pub max_best_price_diff: f64,
+    pub last: HashMap<String, BookDivergence>,
+}
+
+impl DivergenceStats {
+    pub fn record(&mut self, divergence: BookDivergence) {
+        self.samples += 1;
+        if !divergence.is_consistent() {
+            self.divergent += 1;
+        }
+        if divergence.missing_local {
+            self.missing_local += 1;
+        }
+        for diff in [divergence.best_bid_diff, divergence.best_ask_diff]
+            .into_iter()
+            .flatten()
+        {
+            self.max_best_price_diff = self.max_best_price_diff.max(diff.abs());
+        }
+        self.last.insert(divergence.token_id.clone(), divergence);
+    }
+
+    pub fn divergence_rate(&self) -> f64 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        self.divergent as f64 / self.samples as f64
+    }
+}
+
+fn levels(side: &[OrderSummary]) -> BTreeMap<String, f64> {
+    side.iter()
+        .map(|l| (l.price.clone(), l.size_f64()))
+        .collect()
+}
+
+fn best(side: &[OrderSummary], highest: bool) -> Option<f64> {
+    let prices = side.iter().map(|l| l.price_f64());
+    if highest {
+        prices.reduce(f64::max)
+    } else {
+        prices.reduce(f64::min)
+    }
+}
+
+/// Level-by-level comparison of a locally maintained book against a REST
+/// snapshot of the same token.
+pub fn compare_books(local: &OrderBook, rest: &OrderBook) -> BookDivergence {
+    let mut missing_levels = 0;
+    let mut size_mismatches = 0;
+
+    for (local_side, rest_side) in [(&local.bids, &rest.bids), (&local.asks, &rest.asks)] {
+        let local_levels = levels(local_side);
+        let rest_levels = levels(rest_side);
+
+        for (price, size) in &rest_levels {
+            match local_levels.get(price) {
+                None => missing_levels += 1,
+                Some(local_size) if (local_size - size).abs() > f64::EPSILON => {
+                    size_mismatches += 1
+                }
+                Some(_) => {}
+            }
+        }
+        missing_levels += local_levels
+            .keys()
+            .filter(|p| !rest_levels.contains_key(*p))
+            .count();
+    }
+
+    let diff = |a: Option<f64>, b: Option<f64>| match (a, b) {
+        (Some(a), Some(b)) => Some(a - b),
+        _ => None,
+    };
+
+    BookDivergence {
+        token_id: rest.asset_id.clone(),
+        missing_local: false,
+        missing_levels,
+        size_mismatches,
+        best_bid_diff: diff(best(&local.bids, true), best(&rest.bids, true)),
+        best_ask_diff: diff(best(&local.asks, false), best(&rest.asks, false)),
+    }
+}
+
+/// Periodically compares a [`BookSource`] against fresh REST snapshots for a
+/// rotating sample of tokens.
+pub struct BookCrossCheck<S> {
+    market_data: MarketDataApi,
+    source: S,
+    tokens: Vec<String>,
+    sample_size: usize,
+    cursor: usize,
+    stats: Arc<Mutex<DivergenceStats>>,
+}
+
+impl<S: BookSource> BookCrossCheck<S> {
+    pub fn new(market_data: MarketDataApi, source: S, tokens: Vec<String>) -> Self {
+        let sample_size = tokens.len();
+        Self {
+            market_data,
+            source,
+            tokens,
+            sample_size,
+            cursor: 0,
+            stats: Arc::new(Mutex::new(DivergenceStats::default())),
+        }
+    }
+
+    /// Number of tokens checked per round, rotating through the token list.
+    pub fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = sample_size.max(1);
+        self
+    }
+
+    pub fn stats(&self) -> DivergenceStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    fn next_sample(&mut self) -> Vec<String> {
+        let n = self.sample_size.min(self.tokens.len());
+        let sample = (0..n)
+            .map(|i| self.tokens[(self.cursor + i) % self.tokens.len()].clone())
+            .collect();
+        self.cursor = (self.cursor + n) % self.tokens.len().max(1);
+        sample
+    }
+
+    pub async fn check_once(&mut self) -> Result<Vec<BookDivergence>> {
+        let sample = self.next_sample();
+        if sample.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let params: Vec<_> = sample.iter().map(BookParams::new).collect();
+        let rest_books = self.market_data.get_order_books(&params).await?;
+
+        let divergences: Vec<_> = rest_books
+            .iter()
+            .map(|rest| match self.source.book(&rest.asset_id) {
+                Some(local) => compare_books(&local, rest),
+                None => BookDivergence {
+                    token_id: rest.asset_id.clone(),
+                    missing_local: true,
+                    missing_levels: rest.bids.len() + rest.asks.len(),
+                    size_mismatches: 0,
+                    best_bid_diff: None,
+                    best_ask_diff: None,
+                },
+            })
+            .collect();
+
+        let mut stats = self.stats.lock().unwrap();
+        for divergence in &divergences {
+            stats.record(divergence.clone());
+        }
+
+        Ok(divergences)
+    }
+
+    /// Runs [`BookCrossCheck::check_once`] every `interval` on a background
+    /// task. Failed REST snapshots are skipped.
+    pub fn spawn(mut self, interval: Duration) -> CrossCheckHandle {
+        let stats = self.stats.clone();
+        let tasks = self.market_data.transport().tasks().clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let _ = self.check_once().await;
+            }
+        });
+
+        tasks.track(&task);
+        CrossCheckHandle { stats, task }
+    }
+}
+
+pub struct CrossCheckHandle {
+    stats: Arc<Mutex<DivergenceStats>>,
+    task: JoinHandle<()>,
+}
+
+impl CrossCheckHandle {
+    pub fn stats(&self) -> DivergenceStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
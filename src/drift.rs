@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::headers::{measure_offset, Clock};
+use crate::transport::Transport;
+
+// samples kept for the jitter estimate
+const WINDOW: usize = 20;
+
+/// Clock drift as seen by the last samples of a [`DriftMonitor`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DriftStats {
+    /// Seconds the server is ahead of the timestamps we sign with, i.e. the
+    /// local clock corrected by the client's synced offset.
+    pub skew_secs: f64,
+    /// Standard deviation of the skew over the recent samples.
+    pub jitter_secs: f64,
+    /// Samples taken so far, failed requests excluded.
+    pub samples: usize,
+}
+
+impl DriftStats {
+    pub fn exceeds(&self, max_skew: Duration) -> bool {
+        self.skew_secs.abs() > max_skew.as_secs_f64()
+    }
+}
+
+#[derive(Debug, Default)]
+struct DriftState {
+    window: VecDeque<f64>,
+    stats: Option<DriftStats>,
+}
+
+impl DriftState {
+    fn record(&mut self, skew: f64) -> DriftStats {
+        if self.window.len() == WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(skew);
+        let n = self.window.len() as f64;
+        let mean = self.window.iter().sum::<f64>() / n;
+        let variance = self.window.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+        let stats = DriftStats {
+            skew_secs: skew,
+            jitter_secs: variance.sqrt(),
+            samples: self.stats.map_or(0, |s| s.samples) + 1,
+        };
+        self.stats = Some(stats);
+        stats
+    }
+}
+
+/// Background task comparing `GET /time` with the signing clock, started with
+/// `ClobClient::spawn_drift_monitor`. Logs a `tracing` warning whenever the
+/// skew is over the threshold, since the server rejects L1/L2 headers with
+/// stale timestamps. Stops when dropped.
+#[derive(Debug)]
+pub struct DriftMonitor {
+    state: Arc<Mutex<DriftState>>,
+    task: JoinHandle<()>,
+}
+
+impl DriftMonitor {
+    /// Warning threshold used when none is given.
+    pub const DEFAULT_MAX_SKEW: Duration = Duration::from_secs(5);
+
+    pub(crate) fn spawn(
+        transport: Transport,
+        clock: Clock,
+        interval: Duration,
+        max_skew: Duration,
+    ) -> Self {
+        let state = Arc::new(Mutex::new(DriftState::default()));
+        let shared = state.clone();
+        let tasks = transport.tasks().clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let offset = match measure_offset(&transport).await {
+                    Ok(offset) => offset,
+                    Err(e) => {
+                        tracing::debug!(error = %e, "server time sample failed");
+                        continue;
+                    }
+                };
+                let stats = shared
+                    .lock()
+                    .unwrap()
+                    .record(offset - clock.offset() as f64);
+                if stats.exceeds(max_skew) {
+                    tracing::warn!(
+                        skew_secs = stats.skew_secs,
+                        jitter_secs = stats.jitter_secs,
+                        max_skew_secs = max_skew.as_secs_f64(),
+                        "clock skew to the CLOB server may get signed requests rejected"
+                    );
+                }
+            }
+        });
+        tasks.track(&task);
+        Self { state, task }
+    }
+
+    /// `None` until the first sample succeeds.
+    pub fn stats(&self) -> Option<DriftStats> {
+        self.state.lock().unwrap().stats
+    }
+}
+
+impl Drop for DriftMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::{Arc, Mutex};
+
+/// A mutating request that was recorded instead of sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunRequest {
+    pub method: String,
+    pub path: String,
+    pub body: Option<serde_json::Value>,
+}
+
+/// Shared record of everything a dry-run client would have sent. Clones
+/// share the same log.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunLog {
+    requests: Arc<Mutex<Vec<DryRunRequest>>>,
+}
+
+impl DryRunLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn requests(&self) -> Vec<DryRunRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.requests.lock().unwrap().clear();
+    }
+
+    /// Records a request and returns its sequence number, starting at 1.
+    #[cfg(feature = "http")]
+    pub(crate) fn record(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> usize {
+        let mut requests = self.requests.lock().unwrap();
+        requests.push(DryRunRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            body,
+        });
+        requests.len()
+    }
+}
@@ -15,6 +15,7 @@ pub const SPREAD: &str = "/spread";
 pub const SPREADS: &str = "/spreads";
 pub const LAST_TRADE_PRICE: &str = "/last-trade-price";
 pub const LAST_TRADES_PRICES: &str = "/last-trades-prices";
+pub const PRICES_HISTORY: &str = "/prices-history";
 pub const TICK_SIZE: &str = "/tick-size";
 pub const NEG_RISK: &str = "/neg-risk";
 pub const FEE_RATE: &str = "/fee-rate";
@@ -2,23 +2,85 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum ClobError {
+    #[cfg(feature = "http")]
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
 
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("JSON error: {message}")]
     Json { message: String },
 
+    /// A response that didn't match the expected schema. `path` is where in
+    /// the body decoding failed, e.g. `bids[0].price`.
+    #[error("Invalid JSON from {endpoint} (HTTP {status}) at {path}: {message}; body: {snippet}")]
+    Decode {
+        endpoint: String,
+        status: u16,
+        path: String,
+        message: String,
+        /// Start of the response body.
+        snippet: String,
+    },
+
+    /// A response that decoded but had unmodeled or missing fields, under
+    /// [`StrictDecoding::Fail`](crate::StrictDecoding::Fail).
+    #[error("Response from {endpoint} doesn't match the modeled schema: unknown fields {unknown:?}, missing fields {missing:?}")]
+    SchemaDrift {
+        endpoint: String,
+        unknown: Vec<String>,
+        missing: Vec<String>,
+    },
+
     #[error("API error: {message}")]
     Api { message: String },
 
     #[error("Invalid parameter: {0}")]
     InvalidParameter(String),
 
+    #[error("Order size {size} is below the market minimum of {min_size}")]
+    BelowMinimumSize { size: f64, min_size: f64 },
+
     #[error("Signing error: {message}")]
     Signing { message: String },
 
     #[error("Authentication required: {0}")]
     AuthRequired(String),
+
+    #[error("Blocked: {message} (the CLOB rejects this region or network; route requests through an allowed location with `with_proxy`)")]
+    Blocked { message: String },
+
+    #[error("Forbidden: {message}")]
+    Forbidden { message: String },
+
+    #[error("Risk limit: {message}")]
+    RiskLimit { message: String },
+
+    #[error("Insufficient {asset} balance: order needs {needed} but {available} is available")]
+    InsufficientBalance {
+        asset: String,
+        needed: f64,
+        available: f64,
+    },
+
+    #[error("Insufficient {asset} allowance for {spender}: order needs {needed} but {available} is approved")]
+    InsufficientAllowance {
+        asset: String,
+        spender: String,
+        needed: f64,
+        available: f64,
+    },
+
+    #[error("Market {market} is not accepting orders: {reason}")]
+    NotAcceptingOrders { market: String, reason: String },
+
+    #[error("Deadline of {deadline:?} exceeded")]
+    Timeout { deadline: std::time::Duration },
+
+    /// Refused without a request by an open [`CircuitBreaker`](crate::CircuitBreaker).
+    #[error("Circuit breaker open after repeated API failures; next probe in {retry_in:?}")]
+    CircuitOpen { retry_in: std::time::Duration },
 }
 
 pub type Result<T> = std::result::Result<T, ClobError>;
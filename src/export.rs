@@ -0,0 +1,284 @@
+//! CSV and Parquet export of fills, trade prints and position history.
+//!
+//! Each record type has a fixed column list ([`Record::COLUMNS`]); columns
+//! are only ever appended, so files written by older versions keep their
+//! meaning. Parquet needs the `parquet` feature.
+
+use std::io::Write;
+
+use crate::error::Result;
+use crate::portfolio::{Fill, PortfolioSnapshot, PositionSnapshot};
+use crate::trade_prints::TradePrint;
+use crate::types::Side;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Text,
+    Float,
+    Int,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub name: &'static str,
+    pub kind: ColumnType,
+    pub nullable: bool,
+}
+
+const fn column(name: &'static str, kind: ColumnType) -> Column {
+    Column {
+        name,
+        kind,
+        nullable: false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    Text(&'a str),
+    Float(f64),
+    Int(i64),
+    Null,
+}
+
+/// A row type with a stable export schema.
+pub trait Record {
+    const COLUMNS: &'static [Column];
+
+    /// One value per column, in [`COLUMNS`](Self::COLUMNS) order.
+    fn values(&self) -> Vec<Value<'_>>;
+}
+
+fn side(side: Side) -> Value<'static> {
+    match side {
+        Side::Buy => Value::Text("BUY"),
+        Side::Sell => Value::Text("SELL"),
+    }
+}
+
+impl Record for Fill {
+    const COLUMNS: &'static [Column] = &[
+        column("id", ColumnType::Text),
+        column("token_id", ColumnType::Text),
+        column("side", ColumnType::Text),
+        column("price", ColumnType::Float),
+        column("size", ColumnType::Float),
+        column("fee", ColumnType::Float),
+    ];
+
+    fn values(&self) -> Vec<Value<'_>> {
+        vec![
+            Value::Text(&self.id),
+            Value::Text(&self.token_id),
+            side(self.side),
+            Value::Float(self.price),
+            Value::Float(self.size),
+            Value::Float(self.fee),
+        ]
+    }
+}
+
+impl Record for TradePrint {
+    const COLUMNS: &'static [Column] = &[
+        column("timestamp_ms", ColumnType::Int),
+        column("token_id", ColumnType::Text),
+        column("side", ColumnType::Text),
+        column("price", ColumnType::Float),
+        column("size", ColumnType::Float),
+    ];
+
+    fn values(&self) -> Vec<Value<'_>> {
+        vec![
+            Value::Int(self.timestamp as i64),
+            Value::Text(&self.token_id),
+            side(self.side),
+            Value::Float(self.price),
+            Value::Float(self.size),
+        ]
+    }
+}
+
+/// One position at one point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionRecord {
+    /// Unix milliseconds.
+    pub timestamp: u64,
+    pub position: PositionSnapshot,
+}
+
+impl PositionRecord {
+    /// A row per position in `snapshot`, all stamped `timestamp`.
+    pub fn from_snapshot(timestamp: u64, snapshot: &PortfolioSnapshot) -> Vec<PositionRecord> {
+        snapshot
+            .positions
+            .iter()
+            .map(|position| PositionRecord {
+                timestamp,
+                position: position.clone(),
+            })
+            .collect()
+    }
+}
+
+impl Record for PositionRecord {
+    const COLUMNS: &'static [Column] = &[
+        column("timestamp_ms", ColumnType::Int),
+        column("token_id", ColumnType::Text),
+        column("size", ColumnType::Float),
+        column("avg_cost", ColumnType::Float),
+        Column {
+            name: "mark",
+            kind: ColumnType::Float,
+            nullable: true,
+        },
+        column("realized_pnl", ColumnType::Float),
+        column("unrealized_pnl", ColumnType::Float),
+        column("fees", ColumnType::Float),
+    ];
+
+    fn values(&self) -> Vec<Value<'_>> {
+        let p = &self.position;
+        vec![
+            Value::Int(self.timestamp as i64),
+            Value::Text(&p.token_id),
+            Value::Float(p.size),
+            Value::Float(p.avg_cost),
+            p.mark.map_or(Value::Null, Value::Float),
+            Value::Float(p.realized_pnl),
+            Value::Float(p.unrealized_pnl),
+            Value::Float(p.fees),
+        ]
+    }
+}
+
+fn write_csv_field<W: Write>(writer: &mut W, value: &Value<'_>) -> std::io::Result<()> {
+    match value {
+        Value::Text(s) if s.contains([',', '"', '\n', '\r']) => {
+            write!(writer, "\"{}\"", s.replace('"', "\"\""))
+        }
+        Value::Text(s) => writer.write_all(s.as_bytes()),
+        Value::Float(f) => write!(writer, "{}", f),
+        Value::Int(i) => write!(writer, "{}", i),
+        Value::Null => Ok(()),
+    }
+}
+
+/// Writes a header row and one row per record. Nulls are empty fields.
+pub fn write_csv<R: Record, W: Write>(mut writer: W, records: &[R]) -> Result<()> {
+    let header: Vec<&str> = R::COLUMNS.iter().map(|c| c.name).collect();
+    writeln!(writer, "{}", header.join(","))?;
+    for record in records {
+        for (i, value) in record.values().iter().enumerate() {
+            if i > 0 {
+                writer.write_all(b",")?;
+            }
+            write_csv_field(&mut writer, value)?;
+        }
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+pub use self::parquet_export::write_parquet;
+
+#[cfg(feature = "parquet")]
+mod parquet_export {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    use super::{ColumnType, Record, Value};
+    use crate::error::{ClobError, Result};
+
+    fn parquet_error(e: parquet::errors::ParquetError) -> ClobError {
+        ClobError::Io(std::io::Error::other(e))
+    }
+
+    fn schema<R: Record>() -> String {
+        let fields: String = R::COLUMNS
+            .iter()
+            .map(|c| {
+                let repetition = if c.nullable { "OPTIONAL" } else { "REQUIRED" };
+                let kind = match c.kind {
+                    ColumnType::Text => "BYTE_ARRAY",
+                    ColumnType::Float => "DOUBLE",
+                    ColumnType::Int => "INT64",
+                };
+                let annotation = if c.kind == ColumnType::Text {
+                    " (UTF8)"
+                } else {
+                    ""
+                };
+                format!("{} {} {}{}; ", repetition, kind, c.name, annotation)
+            })
+            .collect();
+        format!("message record {{ {}}}", fields)
+    }
+
+    /// Writes the records as a single row group.
+    pub fn write_parquet<R: Record, W: Write + Send>(writer: W, records: &[R]) -> Result<()> {
+        let schema = Arc::new(parse_message_type(&schema::<R>()).map_err(parquet_error)?);
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut file = SerializedFileWriter::new(writer, schema, props).map_err(parquet_error)?;
+        let rows: Vec<Vec<Value<'_>>> = records.iter().map(Record::values).collect();
+
+        let mut row_group = file.next_row_group().map_err(parquet_error)?;
+        let mut index = 0;
+        while let Some(mut column) = row_group.next_column().map_err(parquet_error)? {
+            let cells = rows.iter().map(|row| &row[index]);
+            let def_levels: Vec<i16> = cells
+                .clone()
+                .map(|v| i16::from(*v != Value::Null))
+                .collect();
+            let def_levels = R::COLUMNS[index].nullable.then_some(def_levels.as_slice());
+
+            match R::COLUMNS[index].kind {
+                ColumnType::Text => {
+                    let values: Vec<ByteArray> = cells
+                        .filter_map(|v| match v {
+                            Value::Text(s) => Some(ByteArray::from(*s)),
+                            _ => None,
+                        })
+                        .collect();
+                    column
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, def_levels, None)
+                }
+                ColumnType::Float => {
+                    let values: Vec<f64> = cells
+                        .filter_map(|v| match v {
+                            Value::Float(f) => Some(*f),
+                            _ => None,
+                        })
+                        .collect();
+                    column
+                        .typed::<DoubleType>()
+                        .write_batch(&values, def_levels, None)
+                }
+                ColumnType::Int => {
+                    let values: Vec<i64> = cells
+                        .filter_map(|v| match v {
+                            Value::Int(i) => Some(*i),
+                            _ => None,
+                        })
+                        .collect();
+                    column
+                        .typed::<Int64Type>()
+                        .write_batch(&values, def_levels, None)
+                }
+            }
+            .map_err(parquet_error)?;
+            column.close().map_err(parquet_error)?;
+            index += 1;
+        }
+        row_group.close().map_err(parquet_error)?;
+        file.close().map_err(parquet_error)?;
+        Ok(())
+    }
+}
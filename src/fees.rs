@@ -0,0 +1,49 @@
+//! Trading fees. The CLOB charges `rate × min(p, 1 − p)` per share, so fees
+//! peak at 50¢ and vanish towards 0 and 1. Buyers pay in shares and sellers
+//! in collateral; amounts here are the collateral value either way.
+
+use crate::order_builder::SignedOrder;
+use crate::risk::OrderTerms;
+use crate::types::{Market, OrderArgs};
+
+/// Fee on `size` shares traded at `price` with a rate of `fee_rate_bps`.
+pub fn fee(fee_rate_bps: i32, price: f64, size: f64) -> f64 {
+    let rate = f64::from(fee_rate_bps.max(0)) / 10_000.0;
+    rate * price.min(1.0 - price).max(0.0) * size
+}
+
+/// The fee as shares, which is how a buy pays it.
+pub fn fee_in_shares(fee_rate_bps: i32, price: f64, size: f64) -> f64 {
+    if price <= 0.0 {
+        return 0.0;
+    }
+    fee(fee_rate_bps, price, size) / price
+}
+
+/// Fee for taking liquidity in `market`.
+pub fn taker_fee(market: &Market, price: f64, size: f64) -> f64 {
+    fee(market.taker_base_fee, price, size)
+}
+
+/// Fee for a resting order in `market` that gets filled.
+pub fn maker_fee(market: &Market, price: f64, size: f64) -> f64 {
+    fee(market.maker_base_fee, price, size)
+}
+
+impl OrderArgs {
+    /// Fee if the whole order fills at its limit price and its
+    /// `fee_rate_bps`.
+    pub fn fee(&self) -> f64 {
+        fee(self.fee_rate_bps, self.price, self.size)
+    }
+}
+
+impl SignedOrder {
+    /// Fee if the whole order fills at its limit price, or `None` if the
+    /// amounts or `fee_rate_bps` don't parse.
+    pub fn fee(&self) -> Option<f64> {
+        let terms = OrderTerms::from_order(self).ok()?;
+        let rate = self.fee_rate_bps.parse().ok()?;
+        Some(fee(rate, terms.price, terms.size))
+    }
+}
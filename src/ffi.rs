@@ -0,0 +1,228 @@
+//! C ABI for order signing and L2 auth headers, behind the `ffi` feature.
+//!
+//! Build a shared or static library with
+//! `cargo rustc --release --lib --features ffi --crate-type cdylib` (or
+//! `staticlib`). Strings cross the boundary as NUL-terminated UTF-8; every
+//! string returned by this module must be released with
+//! [`clob_string_free`]. Functions return NULL on failure and leave the
+//! reason in [`clob_last_error`].
+//!
+//! ```c
+//! char *clob_sign_order(const char *private_key, uint64_t chain_id,
+//!                       const char *request_json);
+//! char *clob_l2_headers(const char *address, const char *api_key,
+//!                       const char *api_secret, const char *api_passphrase,
+//!                       uint64_t timestamp, const char *method,
+//!                       const char *request_path, const char *body);
+//! const char *clob_last_error(void);
+//! void clob_string_free(char *s);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use alloy_primitives::Address;
+use serde::Deserialize;
+
+use crate::error::{ClobError, Result};
+use crate::headers::{POLY_ADDRESS, POLY_API_KEY, POLY_PASSPHRASE, POLY_SIGNATURE, POLY_TIMESTAMP};
+use crate::order_builder::{OrderBuilder, SignedOrder, EOA};
+use crate::signer::Signer;
+use crate::signing::build_hmac_signature;
+use crate::types::{CreateOrderOptions, OrderArgs, Side, TickSize};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// `request_json` of [`clob_sign_order`].
+#[derive(Deserialize)]
+struct SignOrderRequest {
+    token_id: String,
+    price: f64,
+    size: f64,
+    side: Side,
+    tick_size: String,
+    neg_risk: bool,
+    #[serde(default)]
+    fee_rate_bps: i32,
+    #[serde(default)]
+    nonce: u64,
+    #[serde(default)]
+    expiration: u64,
+    #[serde(default)]
+    taker: Option<String>,
+    #[serde(default)]
+    salt: Option<u64>,
+    #[serde(default)]
+    signature_type: Option<u8>,
+    #[serde(default)]
+    funder: Option<String>,
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Runs `f`, turning errors and panics into NULL plus a last error.
+fn ffi_call(f: impl FnOnce() -> Result<String>) -> *mut c_char {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(s)) => into_c_string(s),
+        Ok(Err(e)) => {
+            set_last_error(e.to_string());
+            ptr::null_mut()
+        }
+        Err(_) => {
+            set_last_error("panic in clob-rs".to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+/// `s` must be NULL or a valid NUL-terminated string.
+unsafe fn arg<'a>(s: *const c_char, name: &str) -> Result<&'a str> {
+    if s.is_null() {
+        return Err(ClobError::InvalidParameter(format!("{} is NULL", name)));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| ClobError::InvalidParameter(format!("{} is not UTF-8", name)))
+}
+
+fn sign_order(private_key: &str, chain_id: u64, request: &str) -> Result<String> {
+    let request: SignOrderRequest = serde_json::from_str(request).map_err(|e| ClobError::Json {
+        message: format!("invalid sign order request: {}", e),
+    })?;
+    let tick_size: TickSize = request.tick_size.parse().map_err(|_| {
+        ClobError::InvalidParameter(format!("invalid tick size {}", request.tick_size))
+    })?;
+
+    let mut builder = OrderBuilder::new(Signer::new(private_key, chain_id)?)
+        .with_sig_type(request.signature_type.unwrap_or(EOA));
+    if let Some(funder) = &request.funder {
+        let funder: Address = funder
+            .parse()
+            .map_err(|_| ClobError::InvalidParameter(format!("invalid funder {}", funder)))?;
+        builder = builder.with_funder(funder);
+    }
+
+    let mut args = OrderArgs::new(request.token_id, request.price, request.size, request.side);
+    args.fee_rate_bps = request.fee_rate_bps;
+    args.nonce = request.nonce;
+    args.expiration = request.expiration;
+    if let Some(taker) = request.taker {
+        args.taker = taker;
+    }
+    let options = CreateOrderOptions {
+        tick_size,
+        neg_risk: request.neg_risk,
+        salt: request.salt,
+    };
+
+    let mut order = SignedOrder::default();
+    builder.create_order_into(&args, &options, &mut order)?;
+    serde_json::to_string(&order).map_err(|e| ClobError::Json {
+        message: e.to_string(),
+    })
+}
+
+/// Signs a limit order with the crate's rounding and EIP-712 logic and
+/// returns the signed order as JSON, ready for the `order` field of a
+/// `POST /order` body.
+///
+/// `request_json` holds `token_id`, `price`, `size`, `side` (`"BUY"` or
+/// `"SELL"`), `tick_size` (e.g. `"0.01"`) and `neg_risk`, plus the optional
+/// `fee_rate_bps`, `nonce`, `expiration`, `taker`, `salt`,
+/// `signature_type` and `funder`.
+///
+/// # Safety
+/// The string arguments must be NULL or valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn clob_sign_order(
+    private_key: *const c_char,
+    chain_id: u64,
+    request_json: *const c_char,
+) -> *mut c_char {
+    ffi_call(|| {
+        let private_key = arg(private_key, "private_key")?;
+        let request = arg(request_json, "request_json")?;
+        sign_order(private_key, chain_id, request)
+    })
+}
+
+/// L2 auth headers for one request, as a JSON object of header names to
+/// values. `timestamp` is unix seconds, 0 for now; `body` may be NULL.
+///
+/// # Safety
+/// The string arguments must be NULL (only allowed for `body`) or valid
+/// NUL-terminated strings.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn clob_l2_headers(
+    address: *const c_char,
+    api_key: *const c_char,
+    api_secret: *const c_char,
+    api_passphrase: *const c_char,
+    timestamp: u64,
+    method: *const c_char,
+    request_path: *const c_char,
+    body: *const c_char,
+) -> *mut c_char {
+    ffi_call(|| {
+        let address = arg(address, "address")?;
+        let api_key = arg(api_key, "api_key")?;
+        let api_secret = arg(api_secret, "api_secret")?;
+        let api_passphrase = arg(api_passphrase, "api_passphrase")?;
+        let method = arg(method, "method")?;
+        let request_path = arg(request_path, "request_path")?;
+        let body = if body.is_null() {
+            None
+        } else {
+            Some(arg(body, "body")?)
+        };
+
+        let timestamp = match timestamp {
+            0 => chrono::Utc::now().timestamp() as u64,
+            ts => ts,
+        };
+        let signature = build_hmac_signature(api_secret, timestamp, method, request_path, body)?;
+        let headers = BTreeMap::from([
+            (POLY_ADDRESS, address.to_string()),
+            (POLY_SIGNATURE, signature),
+            (POLY_TIMESTAMP, timestamp.to_string()),
+            (POLY_API_KEY, api_key.to_string()),
+            (POLY_PASSPHRASE, api_passphrase.to_string()),
+        ]);
+        serde_json::to_string(&headers).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })
+    })
+}
+
+/// Message of the last failure on this thread, or NULL. Valid until the
+/// next call into this module on the same thread; do not free it.
+#[no_mangle]
+pub extern "C" fn clob_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Frees a string returned by this module. NULL is ignored.
+///
+/// # Safety
+/// `s` must be NULL or a pointer returned by this module that hasn't been
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn clob_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -0,0 +1,151 @@
+//! Recorded HTTP responses for tests that use a [`ClobClient`](crate::ClobClient).
+//!
+//! Record a cassette once against the real API, commit the file, and replay
+//! it in tests without a network or mock server:
+//!
+//! ```no_run
+//! # async fn run() -> clob_rs::Result<()> {
+//! use clob_rs::fixtures::Cassette;
+//! use clob_rs::ClobClient;
+//!
+//! // CLOB_RECORD=1 cargo test refreshes the file
+//! let cassette = if std::env::var("CLOB_RECORD").is_ok() {
+//!     Cassette::record("tests/fixtures/book.json")
+//! } else {
+//!     Cassette::replay("tests/fixtures/book.json")?
+//! };
+//! let client = ClobClient::new("https://clob.polymarket.com").with_cassette(cassette);
+//! let book = client.prices().get_order_book("123").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Responses are matched by method and path including the query string;
+//! hosts, headers and request bodies are ignored, so signed requests replay
+//! even though their signatures change. Only response bodies are stored, but
+//! check recordings of auth endpoints for keys before committing them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ClobError, Result};
+
+/// One recorded request and the server's answer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub method: String,
+    /// Path and query, without the host.
+    pub path: String,
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    path: PathBuf,
+    recording: bool,
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+/// A file of recorded interactions, either being written by live requests
+/// or served back instead of them. Clones share the same cassette.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Cassette {
+    /// Sends requests for real and writes every response to `path`,
+    /// replacing whatever the file held.
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self::with_inner(Inner {
+            path: path.as_ref().to_path_buf(),
+            recording: true,
+            interactions: Vec::new(),
+            used: Vec::new(),
+        })
+    }
+
+    /// Serves the responses recorded in `path` without touching the network.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file: CassetteFile =
+            serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| ClobError::Json {
+                message: format!("invalid cassette {}: {}", path.display(), e),
+            })?;
+        let used = vec![false; file.interactions.len()];
+        Ok(Self::with_inner(Inner {
+            path,
+            recording: false,
+            interactions: file.interactions,
+            used,
+        }))
+    }
+
+    fn with_inner(inner: Inner) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.inner.lock().unwrap().recording
+    }
+
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.inner.lock().unwrap().interactions.clone()
+    }
+
+    /// The first unplayed response for `method` and `path`. Once all of them
+    /// have been played the last one repeats, so polling loops keep working.
+    pub(crate) fn play(&self, method: &str, path: &str) -> Result<Interaction> {
+        let mut inner = self.inner.lock().unwrap();
+        let matches: Vec<usize> = (0..inner.interactions.len())
+            .filter(|&i| {
+                inner.interactions[i].method == method && inner.interactions[i].path == path
+            })
+            .collect();
+        let index = matches
+            .iter()
+            .copied()
+            .find(|&i| !inner.used[i])
+            .or(matches.last().copied())
+            .ok_or_else(|| {
+                ClobError::InvalidParameter(format!(
+                    "no recorded response for {} {} in {}",
+                    method,
+                    path,
+                    inner.path.display()
+                ))
+            })?;
+        inner.used[index] = true;
+        Ok(inner.interactions[index].clone())
+    }
+
+    /// Appends an interaction and rewrites the file.
+    pub(crate) fn save(&self, interaction: Interaction) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.interactions.push(interaction);
+        inner.used.push(true);
+        let file = CassetteFile {
+            interactions: inner.interactions.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })?;
+        if let Some(dir) = inner.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&inner.path, json)?;
+        Ok(())
+    }
+}
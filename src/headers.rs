@@ -1,11 +1,19 @@
 // https://github.com/Polymarket/py-clob-client/blob/main/py_clob_client/headers/headers.py
 
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::error::Result;
+#[cfg(feature = "http")]
+use crate::endpoints;
+use crate::error::{ClobError, Result};
 use crate::signer::Signer;
 use crate::signing::{build_hmac_signature, sign_clob_auth_message};
+#[cfg(feature = "http")]
+use crate::transport::Transport;
 use crate::types::ApiCreds;
+#[cfg(feature = "http")]
+use crate::types::ServerTime;
 
 pub const POLY_ADDRESS: &str = "POLY_ADDRESS";
 pub const POLY_SIGNATURE: &str = "POLY_SIGNATURE";
@@ -29,15 +37,66 @@ pub struct L2Headers {
     pub passphrase: String,
 }
 
-fn current_timestamp() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("time went backwards")
-        .as_secs()
+fn unix_time(time: SystemTime) -> Result<std::time::Duration> {
+    time.duration_since(UNIX_EPOCH)
+        .map_err(|_| ClobError::Signing {
+            message: "system clock is set before 1970".to_string(),
+        })
+}
+
+fn current_timestamp() -> Result<u64> {
+    Ok(unix_time(SystemTime::now())?.as_secs())
+}
+
+/// Local time corrected by an offset to the server's clock, used for
+/// `POLY_TIMESTAMP`. Clones share the offset.
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    offset_secs: Arc<AtomicI64>,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Unix seconds on the server's clock, as far as we know it.
+    pub fn now(&self) -> Result<u64> {
+        Ok(current_timestamp()?.saturating_add_signed(self.offset()))
+    }
+
+    /// Seconds the server is ahead of the local clock.
+    pub fn offset(&self) -> i64 {
+        self.offset_secs.load(Ordering::Relaxed)
+    }
+
+    pub fn set_offset(&self, offset_secs: i64) {
+        self.offset_secs.store(offset_secs, Ordering::Relaxed);
+    }
+
+    /// Measures the offset against `GET /time`, assuming the server read its
+    /// clock halfway through the round trip. Returns the new offset.
+    #[cfg(feature = "http")]
+    pub async fn sync(&self, transport: &Transport) -> Result<i64> {
+        let offset = measure_offset(transport).await?.round() as i64;
+        self.set_offset(offset);
+        Ok(offset)
+    }
+}
+
+/// Seconds the server's clock is ahead of the local one, from one `GET /time`
+/// read halfway through the round trip.
+#[cfg(feature = "http")]
+pub(crate) async fn measure_offset(transport: &Transport) -> Result<f64> {
+    let before = SystemTime::now();
+    let server: ServerTime = transport.get(endpoints::TIME).await?;
+    let elapsed = before.elapsed().unwrap_or_default();
+    let local = unix_time(before + elapsed / 2)?.as_secs_f64();
+    Ok(server as f64 - local)
 }
 
 pub async fn create_level_1_headers(signer: &Signer, nonce: Option<u64>) -> Result<L1Headers> {
-    let timestamp = current_timestamp();
+    let timestamp = signer.clock().now()?;
     let n = nonce.unwrap_or(0);
     let signature = sign_clob_auth_message(signer, timestamp, n).await?;
 
@@ -55,15 +114,21 @@ pub fn create_level_2_headers(
     method: &str,
     request_path: &str,
     body: Option<&str>,
-) -> L2Headers {
-    let timestamp = current_timestamp();
-    let signature = build_hmac_signature(&creds.api_secret, timestamp, method, request_path, body);
+) -> Result<L2Headers> {
+    let timestamp = signer.clock().now()?;
+    let signature = build_hmac_signature(
+        creds.api_secret.expose(),
+        timestamp,
+        method,
+        request_path,
+        body,
+    )?;
 
-    L2Headers {
+    Ok(L2Headers {
         address: signer.address_string(),
         signature,
         timestamp: timestamp.to_string(),
         api_key: creds.api_key.clone(),
-        passphrase: creds.api_passphrase.clone(),
-    }
+        passphrase: creds.api_passphrase.expose().to_string(),
+    })
 }
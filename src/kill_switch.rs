@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use futures::future::join_all;
+
+use crate::api::OrdersApi;
+use crate::ladder::RestingOrder;
+
+/// How hard [`OrdersApi::kill_switch_with`] tries before giving up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KillSwitch {
+    /// Rounds of cancel-then-verify.
+    pub attempts: usize,
+    /// Pause between rounds, doubled after each one.
+    pub retry_delay: Duration,
+}
+
+impl Default for KillSwitch {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            retry_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+impl KillSwitch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KillSwitchReport {
+    /// Rounds actually run.
+    pub attempts: usize,
+    /// Ids the exchange reported as cancelled.
+    pub canceled: Vec<String>,
+    /// Orders still open on the last successful check.
+    pub remaining: Vec<RestingOrder>,
+    /// `false` if the open orders could never be listed, in which case
+    /// `remaining` says nothing.
+    pub verified: bool,
+    /// Every failed request, in order.
+    pub errors: Vec<String>,
+}
+
+impl KillSwitchReport {
+    /// Verified with nothing left open.
+    pub fn is_flat(&self) -> bool {
+        self.verified && self.remaining.is_empty()
+    }
+}
+
+pub(crate) fn canceled_ids(response: &serde_json::Value) -> impl Iterator<Item = String> + '_ {
+    response
+        .get("canceled")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|id| id.as_str().map(str::to_string))
+}
+
+impl OrdersApi {
+    /// Emergency stop with the default [`KillSwitch`] settings.
+    pub async fn kill_switch(&self) -> KillSwitchReport {
+        self.kill_switch_with(KillSwitch::default()).await
+    }
+
+    /// Cancels everything, then keeps listing open orders and cancelling
+    /// what survived, one request per token in parallel, until the book is
+    /// flat or the attempts run out. Never fails; errors are collected in the
+    /// report.
+    pub async fn kill_switch_with(&self, settings: KillSwitch) -> KillSwitchReport {
+        let mut report = KillSwitchReport::default();
+        let mut delay = settings.retry_delay;
+
+        for attempt in 0..settings.attempts.max(1) {
+            if attempt > 0 {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            report.attempts += 1;
+
+            if report.remaining.is_empty() {
+                match self.cancel_all().await {
+                    Ok(response) => report.canceled.extend(canceled_ids(&response)),
+                    Err(e) => report.errors.push(format!("cancel all: {}", e)),
+                }
+            } else {
+                let mut by_token: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+                for order in &report.remaining {
+                    by_token
+                        .entry(order.token_id.as_str())
+                        .or_default()
+                        .push(order.id.clone());
+                }
+                let cancels = by_token.into_iter().map(|(token_id, ids)| async move {
+                    (token_id.to_string(), self.cancel_orders(&ids).await)
+                });
+                for (token_id, result) in join_all(cancels).await {
+                    match result {
+                        Ok(response) => report.canceled.extend(canceled_ids(&response)),
+                        Err(e) => report.errors.push(format!("cancel {}: {}", token_id, e)),
+                    }
+                }
+            }
+
+            match self.get_orders(None).await {
+                Ok(open) => {
+                    report.remaining = RestingOrder::from_open_orders(&open);
+                    report.verified = true;
+                    if report.remaining.is_empty() {
+                        break;
+                    }
+                }
+                Err(e) => report.errors.push(format!("list open orders: {}", e)),
+            }
+        }
+        report
+    }
+}
+
+/// Runs [`OrdersApi::kill_switch_with`] on every account at once.
+pub async fn kill_switch_all(
+    accounts: &[OrdersApi],
+    settings: KillSwitch,
+) -> Vec<KillSwitchReport> {
+    join_all(
+        accounts
+            .iter()
+            .map(|orders| orders.kill_switch_with(settings)),
+    )
+    .await
+}
@@ -0,0 +1,202 @@
+use crate::types::{OrderArgs, PostOrderResponse, Side, TickSize};
+use crate::units::Price;
+
+/// Symmetric quotes around a midpoint. Level `i` (from 0) sits at
+/// `half_spread + i * level_spacing` from the mid, rounded away from it to the
+/// tick so a ladder never quotes tighter than asked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteLadder {
+    pub token_id: String,
+    pub midpoint: f64,
+    pub half_spread: f64,
+    pub tick_size: TickSize,
+    pub levels: usize,
+    /// Distance between levels; defaults to one tick.
+    pub level_spacing: f64,
+    /// Size per level, innermost first. The last entry repeats for deeper
+    /// levels.
+    pub sizes: Vec<f64>,
+}
+
+impl QuoteLadder {
+    pub fn new(
+        token_id: impl Into<String>,
+        midpoint: f64,
+        half_spread: f64,
+        tick_size: TickSize,
+    ) -> Self {
+        Self {
+            token_id: token_id.into(),
+            midpoint,
+            half_spread,
+            tick_size,
+            levels: 1,
+            level_spacing: tick_size.as_f64(),
+            sizes: vec![0.0],
+        }
+    }
+
+    pub fn with_levels(mut self, levels: usize) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    pub fn with_level_spacing(mut self, spacing: f64) -> Self {
+        self.level_spacing = spacing;
+        self
+    }
+
+    pub fn with_sizes(mut self, sizes: Vec<f64>) -> Self {
+        self.sizes = sizes;
+        self
+    }
+
+    /// Same size on every level.
+    pub fn with_size(self, size: f64) -> Self {
+        self.with_sizes(vec![size])
+    }
+
+    fn size_at(&self, level: usize) -> f64 {
+        self.sizes
+            .get(level)
+            .or(self.sizes.last())
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Bids then asks, each innermost first. Levels that fall outside the
+    /// valid price range, round onto an earlier level or have no size are
+    /// skipped.
+    pub fn orders(&self) -> Vec<OrderArgs> {
+        let ticks_per_unit = (1.0 / self.tick_size.as_f64()).round();
+        let mut orders = Vec::new();
+
+        for side in [Side::Buy, Side::Sell] {
+            let mut last_ticks = None;
+            for level in 0..self.levels {
+                let size = self.size_at(level);
+                let offset = self.half_spread + level as f64 * self.level_spacing;
+                // the epsilon keeps prices already on the tick where they are
+                let ticks = match side {
+                    Side::Buy => ((self.midpoint - offset) * ticks_per_unit + 1e-9).floor(),
+                    Side::Sell => ((self.midpoint + offset) * ticks_per_unit - 1e-9).ceil(),
+                } as i64;
+
+                let price = match Price::from_ticks(ticks, self.tick_size) {
+                    Ok(price) if size > 0.0 && last_ticks != Some(ticks) => price,
+                    _ => continue,
+                };
+                last_ticks = Some(ticks);
+                orders.push(OrderArgs::new(
+                    self.token_id.clone(),
+                    price.value(),
+                    size,
+                    side,
+                ));
+            }
+        }
+        orders
+    }
+}
+
+/// An order currently on the book, as far as a ladder diff is concerned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestingOrder {
+    pub id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    /// Unfilled size.
+    pub size: f64,
+}
+
+impl RestingOrder {
+    /// Parses the open orders returned by `get_orders`, either a bare array or
+    /// a page with a `data` array. Entries that do not look like orders are
+    /// skipped.
+    pub fn from_open_orders(value: &serde_json::Value) -> Vec<RestingOrder> {
+        let orders = match value {
+            serde_json::Value::Array(orders) => orders,
+            other => match other.get("data").and_then(|d| d.as_array()) {
+                Some(orders) => orders,
+                None => return Vec::new(),
+            },
+        };
+
+        let number = |order: &serde_json::Value, key: &str| -> Option<f64> {
+            match order.get(key)? {
+                serde_json::Value::String(s) => s.parse().ok(),
+                serde_json::Value::Number(n) => n.to_string().parse().ok(),
+                _ => None,
+            }
+        };
+
+        orders
+            .iter()
+            .filter_map(|order| {
+                let side = match order.get("side")?.as_str()?.to_uppercase().as_str() {
+                    "BUY" => Side::Buy,
+                    "SELL" => Side::Sell,
+                    _ => return None,
+                };
+                let original = number(order, "original_size")?;
+                let matched = number(order, "size_matched").unwrap_or(0.0);
+                Some(RestingOrder {
+                    id: order.get("id")?.as_str()?.to_string(),
+                    token_id: order.get("asset_id")?.as_str()?.to_string(),
+                    side,
+                    price: number(order, "price")?,
+                    size: original - matched,
+                })
+            })
+            .collect()
+    }
+}
+
+/// What has to change to turn the resting orders into the target ladder.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LadderDiff {
+    pub cancel: Vec<String>,
+    pub post: Vec<OrderArgs>,
+    /// Resting orders that already match a target level.
+    pub keep: Vec<String>,
+}
+
+impl LadderDiff {
+    pub fn is_empty(&self) -> bool {
+        self.cancel.is_empty() && self.post.is_empty()
+    }
+}
+
+/// Pairs each target with a resting order on the same token, side and price
+/// whose remaining size matches, and cancels or posts the rest. Every
+/// unmatched resting order is cancelled, so pass only the orders the ladder
+/// owns.
+pub fn diff_ladder(target: &[OrderArgs], resting: &[RestingOrder]) -> LadderDiff {
+    const EPSILON: f64 = 1e-9;
+    let mut unmatched: Vec<&RestingOrder> = resting.iter().collect();
+    let mut diff = LadderDiff::default();
+
+    for order in target {
+        let found = unmatched.iter().position(|r| {
+            r.token_id == order.token_id
+                && r.side == order.side
+                && (r.price - order.price).abs() < EPSILON
+                && (r.size - order.size).abs() < EPSILON
+        });
+        match found {
+            Some(i) => diff.keep.push(unmatched.remove(i).id.clone()),
+            None => diff.post.push(order.clone()),
+        }
+    }
+    diff.cancel = unmatched.into_iter().map(|r| r.id.clone()).collect();
+    diff
+}
+
+#[derive(Debug, Clone)]
+pub struct LadderUpdate {
+    pub diff: LadderDiff,
+    /// Raw response of the cancel request, if anything was cancelled.
+    pub canceled: Option<serde_json::Value>,
+    pub posted: Vec<PostOrderResponse>,
+}
@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::endpoints;
+
+// paths ending in an id, recorded under one key each
+const ID_PREFIXES: [&str; 3] = [
+    endpoints::MARKET,
+    endpoints::MARKET_TRADES_EVENTS,
+    endpoints::GET_ORDER,
+];
+
+// exact below 16µs, then 8 buckets per power of two (at most 12.5% wide)
+const LINEAR: u64 = 16;
+const SUB_BUCKETS: u64 = 8;
+const BUCKETS: usize = (LINEAR + (64 - 4) * SUB_BUCKETS) as usize;
+
+fn bucket(micros: u64) -> usize {
+    if micros < LINEAR {
+        return micros as usize;
+    }
+    let exp = 63 - micros.leading_zeros() as u64;
+    let sub = (micros >> (exp - 3)) & (SUB_BUCKETS - 1);
+    (LINEAR + (exp - 4) * SUB_BUCKETS + sub) as usize
+}
+
+// largest value that lands in `index`
+fn bucket_max(index: usize) -> u64 {
+    let index = index as u64;
+    if index < LINEAR {
+        return index;
+    }
+    let exp = (index - LINEAR) / SUB_BUCKETS + 4;
+    let sub = (index - LINEAR) % SUB_BUCKETS;
+    let width = 1u64 << (exp - 3);
+    (1u64 << exp) - 1 + (sub + 1) * width
+}
+
+/// Latency percentiles of one endpoint. Percentiles are bucketed, so they
+/// can overstate the true value by up to 12.5%, but never exceed `max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+}
+
+#[derive(Debug)]
+struct Histogram {
+    buckets: Vec<u64>,
+    count: u64,
+    total_micros: u128,
+    min_micros: u64,
+    max_micros: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            buckets: vec![0; BUCKETS],
+            count: 0,
+            total_micros: 0,
+            min_micros: u64::MAX,
+            max_micros: 0,
+        }
+    }
+
+    fn record(&mut self, micros: u64) {
+        self.buckets[bucket(micros)] += 1;
+        self.count += 1;
+        self.total_micros += micros as u128;
+        self.min_micros = self.min_micros.min(micros);
+        self.max_micros = self.max_micros.max(micros);
+    }
+
+    fn percentile(&self, q: f64) -> u64 {
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, n) in self.buckets.iter().enumerate() {
+            seen += n;
+            if seen >= rank {
+                return bucket_max(index).min(self.max_micros);
+            }
+        }
+        self.max_micros
+    }
+
+    fn stats(&self) -> LatencyStats {
+        LatencyStats {
+            count: self.count,
+            mean: Duration::from_micros((self.total_micros / self.count as u128) as u64),
+            min: Duration::from_micros(self.min_micros),
+            max: Duration::from_micros(self.max_micros),
+            p50: Duration::from_micros(self.percentile(0.50)),
+            p95: Duration::from_micros(self.percentile(0.95)),
+            p99: Duration::from_micros(self.percentile(0.99)),
+        }
+    }
+}
+
+/// Per-endpoint histograms of round-trip times, keyed like `"POST /order"`.
+/// Query strings are dropped and ids in paths replaced with `{id}`. Times
+/// cover the whole exchange, failover and body included, for requests that
+/// got an HTTP response or failed in transport; replayed cassette requests
+/// aren't timed. Clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyTracker {
+    endpoints: Arc<Mutex<HashMap<String, Histogram>>>,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record(&self, method: &str, path: &str, elapsed: Duration) {
+        let key = endpoint_key(method, path);
+        self.endpoints
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(Histogram::new)
+            .record(elapsed.as_micros().min(u64::MAX as u128) as u64);
+    }
+
+    /// Every endpoint called so far.
+    pub fn stats(&self) -> BTreeMap<String, LatencyStats> {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, histogram)| (key.clone(), histogram.stats()))
+            .collect()
+    }
+
+    /// `endpoint` as in the keys of [`Self::stats`], e.g. `"GET /book"`.
+    pub fn endpoint(&self, endpoint: &str) -> Option<LatencyStats> {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .get(endpoint)
+            .map(Histogram::stats)
+    }
+
+    pub fn reset(&self) {
+        self.endpoints.lock().unwrap().clear();
+    }
+}
+
+fn endpoint_key(method: &str, path: &str) -> String {
+    for prefix in ID_PREFIXES {
+        if path.len() > prefix.len() && path.starts_with(prefix) {
+            return format!("{} {}{{id}}", method, prefix);
+        }
+    }
+    format!("{} {}", method, path)
+}
@@ -1,24 +1,169 @@
+#[cfg(feature = "http")]
+mod allowances;
+#[cfg(feature = "http")]
+mod api;
+mod audit_log;
+pub mod backtest;
+mod book_hash;
+mod candles;
+#[cfg(feature = "http")]
+mod circuit_breaker;
+#[cfg(feature = "http")]
 mod client;
+mod client_config;
 pub mod config;
+mod creds_store;
+#[cfg(feature = "http")]
+mod dead_man;
+#[cfg(feature = "http")]
+mod deadline;
+#[cfg(feature = "http")]
+pub mod diagnostics;
+#[cfg(feature = "http")]
+mod drift;
+mod dry_run;
 pub mod endpoints;
 mod error;
+pub mod export;
+pub mod fees;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "http")]
+pub mod fixtures;
 pub mod headers;
+#[cfg(feature = "http")]
+mod kill_switch;
+mod ladder;
+#[cfg(feature = "http")]
+mod latency;
+#[cfg(feature = "http")]
+mod market_cache;
+#[cfg(feature = "http")]
+mod market_data_service;
+mod neg_risk;
+mod nonce;
+#[cfg(feature = "onchain")]
+pub mod onchain;
 pub mod order_builder;
+#[cfg(feature = "http")]
+mod order_manager;
+mod pair_arb;
+mod portfolio;
+#[cfg(feature = "http")]
+mod presign;
+#[cfg(feature = "http")]
+mod price_watcher;
+pub mod pricing;
+#[cfg(feature = "http")]
+mod raw;
+#[cfg(feature = "http")]
+pub mod recorder;
+#[cfg(feature = "http")]
+mod replace;
+mod resolution;
+mod rewards;
+mod risk;
+mod secret;
+#[cfg(feature = "http")]
+mod shutdown;
 mod signer;
 pub mod signing;
+#[cfg(feature = "binary")]
+pub mod snapshot;
+mod state_store;
+#[cfg(feature = "http")]
+pub mod strategy;
+#[cfg(feature = "http")]
+mod strict;
+mod sweep;
+mod trade_prints;
+#[cfg(feature = "http")]
+mod transport;
 mod types;
+mod units;
+pub mod vectors;
 
+#[cfg(feature = "http")]
+pub use allowances::{AllowanceReport, MissingApproval};
+#[cfg(feature = "http")]
+pub use api::{AccountApi, AuthApi, MarketDataApi, MarketsApi, OrdersApi, PricesApi};
+pub use audit_log::{AuditEntry, AuditLog};
+#[cfg(feature = "http")]
+pub use candles::candles;
+pub use candles::{candles_from_history, Candle, CandleAggregator};
+#[cfg(feature = "http")]
+pub use circuit_breaker::{CircuitBreaker, CircuitState};
+#[cfg(feature = "http")]
 pub use client::ClobClient;
+pub use client_config::ClobConfig;
+#[cfg(feature = "http")]
+pub use dead_man::{DeadManHandle, DeadManSwitch, DeadManTrigger, DeadManTrip};
+#[cfg(feature = "http")]
+pub use deadline::with_deadline;
+#[cfg(feature = "http")]
+pub use drift::{DriftMonitor, DriftStats};
+pub use dry_run::{DryRunLog, DryRunRequest};
 pub use error::{ClobError, Result};
+#[cfg(feature = "http")]
+pub use kill_switch::{kill_switch_all, KillSwitch, KillSwitchReport};
+pub use ladder::{diff_ladder, LadderDiff, LadderUpdate, QuoteLadder, RestingOrder};
+#[cfg(feature = "http")]
+pub use latency::{LatencyStats, LatencyTracker};
+#[cfg(feature = "http")]
+pub use market_cache::{MarketCache, MarketSource, TokenInfo};
+#[cfg(feature = "http")]
+pub use market_data_service::{MarketDataService, Quote, QuoteSource};
+pub use neg_risk::{
+    group_neg_risk_events, normalize_probabilities, plan_complete_set_arb, yes_token_id,
+    CompleteSetArb, CompleteSetQuote, NegRiskEvent,
+};
+pub use nonce::{
+    FixedNonce, FixedSalt, IncrementingNonce, NonceProvider, RandomSalt, SaltProvider,
+    TimestampNonce,
+};
 pub use order_builder::{OrderBuilder, SignedOrder, EOA, POLY_GNOSIS_SAFE, POLY_PROXY};
+#[cfg(feature = "http")]
+pub use order_manager::{ExpirySweep, FillEvent, OrderManager, OrphanPolicy, ReconcileReport};
+pub use pair_arb::{pair_token_ids, plan_pair_arb, ArbOpportunity};
+pub use portfolio::{Fill, Portfolio, PortfolioSnapshot, Position, PositionSnapshot};
+#[cfg(feature = "http")]
+pub use presign::{PresignedOrder, PresignedPool};
+#[cfg(feature = "http")]
+pub use price_watcher::{PriceChange, PriceSnapshot, PriceWatcher};
+#[cfg(feature = "http")]
+pub use raw::Raw;
+#[cfg(feature = "http")]
+pub use replace::{ReplaceResult, ReplaceStrategy};
+pub use resolution::{Payout, Resolution};
+pub use rewards::RewardQuoter;
+pub use risk::{OrderTerms, RiskConfig, SpreadGuard};
+pub use secret::SecretString;
+#[cfg(feature = "http")]
+pub use shutdown::{ShutdownPolicy, ShutdownReport};
 pub use signer::Signer;
+#[cfg(feature = "sqlite")]
+pub use state_store::SqliteStore;
+pub use state_store::{JsonFileStore, ManagedOrder, MemoryStore, StateStore};
+#[cfg(feature = "http")]
+pub use strict::{SchemaDrift, StrictDecoding};
+pub use sweep::{plan_sweep, SweepLimit, SweepPlan, SweepResult};
+pub use trade_prints::TradePrint;
+#[cfg(feature = "http")]
+pub use trade_prints::TradePrints;
+#[cfg(feature = "http")]
+pub use transport::Transport;
 pub use types::{
-    ApiCreds, AssetType, BalanceAllowanceParams, BalanceAllowanceResponse, BatchMidpointResponse,
-    BatchPriceResponse, BatchSpreadResponse, BookParams, ContractConfig, CreateOrderOptions,
-    DropNotificationParams, FeeRateResponse, LastTradePriceResponse, LastTradesPriceEntry, Market,
-    MarketOrderArgs, MarketRewards, MarketTradeEvent, MarketsResponse, MidpointResponse,
-    NegRiskResponse, OpenOrderParams, OrderArgs, OrderBook, OrderScoringParams, OrderSummary,
-    OrderType, OrdersScoringParams, PartialCreateOrderOptions, PriceResponse, RoundConfig,
-    ServerTime, Side, SimplifiedMarket, SimplifiedMarketsResponse, SpreadResponse, TickSize,
-    TickSizeResponse, Token, TradeParams,
+    amount_to_f64, round_up_to_min_size, Amount, ApiCreds, ApiKeysResponse, AssetType,
+    BalanceAllowanceParams, BalanceAllowanceResponse, BatchError, BatchMidpointResponse,
+    BatchPriceResponse, BatchResponse, BatchSpreadResponse, BookParams, ContractConfig,
+    CreateOrderOptions, CumulativeDepth, DeleteApiKeyResponse, DropNotificationParams,
+    FeeRateResponse, LastTradePriceResponse, LastTradesPriceEntry, Level, Market, MarketFilter,
+    MarketOrderArgs, MarketQuote, MarketRewards, MarketTradeEvent, MarketsResponse,
+    MidpointResponse, NegRiskResponse, NumericOrderBook, OpenOrderParams, OpenOrdersResponse,
+    OrderArgs, OrderArgsBuilder, OrderBook, OrderScoringParams, OrderState, OrderStatus,
+    OrderSummary, OrderType, OrdersScoringParams, PartialCreateOrderOptions, PostOrderResponse,
+    PriceHistoryInterval, PriceHistoryResponse, PricePoint, PriceResponse, RoundConfig, ServerTime,
+    Side, SimplifiedMarket, SimplifiedMarketsResponse, SpreadResponse, TickSize, TickSizeResponse,
+    Token, TradeParams,
 };
+pub use units::{Price, Size};
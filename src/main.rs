@@ -0,0 +1,310 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use clob_rs::{
+    ApiCreds, ClobClient, ClobConfig, ClobError, DryRunLog, MarketFilter, OrderArgs, OrderType,
+    Result, SecretString, Side,
+};
+use futures::{StreamExt, TryStreamExt};
+
+/// Command line access to the Polymarket CLOB.
+///
+/// Settings come from `--config`, then `CLOB_*` environment variables, then
+/// flags, each overriding the last. See `ClobConfig` for the file format.
+#[derive(Parser)]
+#[command(name = "clob", version)]
+struct Cli {
+    /// TOML config file.
+    #[arg(long, env = "CLOB_CONFIG", global = true)]
+    config: Option<PathBuf>,
+
+    #[arg(long, env = "CLOB_HOST", global = true)]
+    host: Option<String>,
+
+    #[arg(long, env = "CLOB_CHAIN_ID", global = true)]
+    chain_id: Option<u64>,
+
+    #[arg(long, env = "CLOB_PRIVATE_KEY", hide_env_values = true, global = true)]
+    private_key: Option<SecretString>,
+
+    #[arg(long, env = "CLOB_FUNDER", global = true)]
+    funder: Option<String>,
+
+    /// 0 = EOA, 1 = Polymarket proxy, 2 = Gnosis safe.
+    #[arg(long, env = "CLOB_SIGNATURE_TYPE", global = true)]
+    signature_type: Option<u8>,
+
+    #[arg(long, env = "CLOB_API_KEY", global = true)]
+    api_key: Option<String>,
+
+    #[arg(long, env = "CLOB_API_SECRET", hide_env_values = true, global = true)]
+    api_secret: Option<String>,
+
+    #[arg(
+        long,
+        env = "CLOB_API_PASSPHRASE",
+        hide_env_values = true,
+        global = true
+    )]
+    api_passphrase: Option<String>,
+
+    /// Credentials file, used when the API key flags are not set.
+    #[arg(long, env = "CLOB_CREDS_FILE", global = true)]
+    creds_file: Option<PathBuf>,
+
+    /// Passphrase for an encrypted credentials file.
+    #[arg(
+        long,
+        env = "CLOB_CREDS_PASSPHRASE",
+        hide_env_values = true,
+        global = true
+    )]
+    creds_passphrase: Option<SecretString>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the order book for a token.
+    Book {
+        token_id: String,
+        /// Levels shown per side.
+        #[arg(long, default_value_t = 10)]
+        depth: usize,
+    },
+    /// Place a limit buy.
+    Buy(OrderCommand),
+    /// Place a limit sell.
+    Sell(OrderCommand),
+    /// Cancel every open order.
+    CancelAll,
+    /// List markets.
+    Markets {
+        /// Only markets that are active and not closed.
+        #[arg(long)]
+        active: bool,
+        #[arg(long)]
+        slug: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Manage API credentials.
+    Creds {
+        #[command(subcommand)]
+        command: CredsCommand,
+    },
+}
+
+#[derive(Args)]
+struct OrderCommand {
+    token_id: String,
+    price: f64,
+    size: f64,
+    #[arg(long = "type", default_value = "GTC", value_parser = parse_order_type)]
+    order_type: OrderType,
+    /// Sign the order and print the request instead of sending it.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Subcommand)]
+enum CredsCommand {
+    /// Create or derive the API key for the private key.
+    Derive {
+        /// Write the credentials here instead of printing them.
+        #[arg(long)]
+        save: Option<PathBuf>,
+    },
+}
+
+fn parse_order_type(s: &str) -> std::result::Result<OrderType, String> {
+    match s.to_uppercase().as_str() {
+        "GTC" => Ok(OrderType::GTC),
+        "FOK" => Ok(OrderType::FOK),
+        "GTD" => Ok(OrderType::GTD),
+        "FAK" => Ok(OrderType::FAK),
+        other => Err(format!("unknown order type {}", other)),
+    }
+}
+
+impl Cli {
+    fn config(&self) -> Result<ClobConfig> {
+        let mut config = match &self.config {
+            Some(path) => ClobConfig::from_file(path)?,
+            None => ClobConfig::default(),
+        };
+
+        if let Some(host) = &self.host {
+            config.host = host.clone();
+        }
+        if let Some(chain_id) = self.chain_id {
+            config.chain_id = chain_id;
+        }
+        if self.private_key.is_some() {
+            config.private_key = self.private_key.clone();
+        }
+        if self.funder.is_some() {
+            config.funder = self.funder.clone();
+        }
+        if self.signature_type.is_some() {
+            config.signature_type = self.signature_type;
+        }
+        if self.creds_file.is_some() {
+            config.creds = None;
+            config.creds_file = self.creds_file.clone();
+        }
+        match (&self.api_key, &self.api_secret, &self.api_passphrase) {
+            (Some(key), Some(secret), Some(passphrase)) => {
+                config.creds = Some(ApiCreds {
+                    api_key: key.clone(),
+                    api_secret: secret.as_str().into(),
+                    api_passphrase: passphrase.as_str().into(),
+                });
+            }
+            (None, None, None) => {}
+            _ => {
+                return Err(ClobError::InvalidParameter(
+                    "--api-key, --api-secret and --api-passphrase must be set together".to_string(),
+                ))
+            }
+        }
+        if self.creds_passphrase.is_some() {
+            config.creds_passphrase = self.creds_passphrase.clone();
+        }
+        Ok(config)
+    }
+
+    fn client(&self, dry_run: Option<DryRunLog>) -> Result<ClobClient> {
+        let client = ClobClient::from_config(&self.config()?)?;
+        Ok(match dry_run {
+            Some(log) => client.with_dry_run(log),
+            None => client,
+        })
+    }
+}
+
+fn print_json(value: &impl serde::Serialize) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| ClobError::Json {
+        message: e.to_string(),
+    })?;
+    println!("{}", json);
+    Ok(())
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    match &cli.command {
+        Command::Book { token_id, depth } => {
+            let book = cli
+                .client(None)?
+                .prices()
+                .get_order_book(token_id)
+                .await?
+                .to_numeric()?;
+            println!("{:>10} {:>12}", "price", "size");
+            for level in book.asks.iter().take(*depth).rev() {
+                println!("{:>10.4} {:>12.2}  ask", level.price, level.size);
+            }
+            println!("{:-<23}", "");
+            for level in book.bids.iter().take(*depth) {
+                println!("{:>10.4} {:>12.2}  bid", level.price, level.size);
+            }
+            if let Some(spread) = book.spread() {
+                println!("spread {:.4}", spread);
+            }
+        }
+        Command::Buy(order) | Command::Sell(order) => {
+            let side = match cli.command {
+                Command::Buy(_) => Side::Buy,
+                _ => Side::Sell,
+            };
+            let log = order.dry_run.then(DryRunLog::new);
+            let client = cli.client(log.clone())?;
+            let args = OrderArgs::new(&order.token_id, order.price, order.size, side);
+            let signed = client.orders()?.create_order(&args, None).await?;
+            let response = client
+                .orders()?
+                .post_order(&signed, order.order_type)
+                .await?;
+            match log {
+                Some(log) => {
+                    for request in log.requests() {
+                        println!("{} {}", request.method, request.path);
+                        if let Some(body) = &request.body {
+                            print_json(body)?;
+                        }
+                    }
+                }
+                None => print_json(&response)?,
+            }
+        }
+        Command::CancelAll => {
+            print_json(&cli.client(None)?.orders()?.cancel_all().await?)?;
+        }
+        Command::Markets {
+            active,
+            slug,
+            limit,
+        } => {
+            let mut filter = MarketFilter::new();
+            if *active {
+                filter = filter.active(true).closed(false);
+            }
+            if let Some(slug) = slug {
+                filter = filter.slug_contains(slug);
+            }
+            let client = cli.client(None)?;
+            let markets: Vec<_> = client
+                .markets()
+                .find_markets(filter)
+                .take(*limit)
+                .try_collect()
+                .await?;
+            for market in markets {
+                println!(
+                    "{}  {}",
+                    market.condition_id,
+                    market
+                        .market_slug
+                        .as_deref()
+                        .or(market.question.as_deref())
+                        .unwrap_or("")
+                );
+                for token in &market.tokens {
+                    println!("    {:<8} {}", token.outcome, token.token_id);
+                }
+            }
+        }
+        Command::Creds {
+            command: CredsCommand::Derive { save },
+        } => {
+            let creds = cli
+                .client(None)?
+                .account()?
+                .create_or_derive_api_key(None)
+                .await?;
+            match save {
+                Some(path) => {
+                    let passphrase = cli.config()?.creds_passphrase;
+                    creds.save(path, passphrase.as_ref().map(SecretString::expose))?;
+                    println!(
+                        "saved credentials for {} to {}",
+                        creds.api_key,
+                        path.display()
+                    );
+                }
+                None => print_json(&creds)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run(Cli::parse()).await {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinHandle;
+
+use crate::api::MarketDataApi;
+use crate::error::Result;
+use crate::types::{amount_to_f64, Market, SimplifiedMarket, TickSize};
+
+const TICK_SIZES: [TickSize; 4] = [
+    TickSize::Size0_1,
+    TickSize::Size0_01,
+    TickSize::Size0_001,
+    TickSize::Size0_0001,
+];
+
+/// What [`MarketDataApi::refresh_market_cache`] loads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarketSource {
+    /// `/markets`: full metadata, including tick sizes and slugs.
+    #[default]
+    Markets,
+    /// `/simplified-markets`: a lighter scan that only knows neg risk.
+    SimplifiedMarkets,
+}
+
+/// What order creation needs to know about a token, from whichever market
+/// listing it was last seen in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenInfo {
+    pub token_id: String,
+    pub condition_id: String,
+    pub outcome: String,
+    /// `None` when only seen in a simplified market listing.
+    pub tick_size: Option<TickSize>,
+    pub neg_risk: bool,
+}
+
+impl TokenInfo {
+    /// One per token of `market`.
+    pub fn from_market(market: &Market) -> impl Iterator<Item = TokenInfo> + '_ {
+        let min_tick = amount_to_f64(market.minimum_tick_size);
+        let tick_size = TICK_SIZES
+            .into_iter()
+            .find(|t| (t.as_f64() - min_tick).abs() < 1e-9);
+        market.tokens.iter().map(move |token| TokenInfo {
+            token_id: token.token_id.clone(),
+            condition_id: market.condition_id.clone(),
+            outcome: token.outcome.clone(),
+            tick_size,
+            neg_risk: market.neg_risk,
+        })
+    }
+
+    pub fn from_simplified(market: &SimplifiedMarket) -> impl Iterator<Item = TokenInfo> + '_ {
+        market.tokens.iter().map(|token| TokenInfo {
+            token_id: token.token_id.clone(),
+            condition_id: market.condition_id.clone(),
+            outcome: token.outcome.clone(),
+            tick_size: None,
+            neg_risk: market.neg_risk,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct MarketIndex {
+    markets: HashMap<String, Arc<Market>>,
+    by_token: HashMap<String, Arc<Market>>,
+    by_slug: HashMap<String, Arc<Market>>,
+    tokens: HashMap<String, TokenInfo>,
+    condition_ids: usize,
+    loaded_at: Option<Instant>,
+}
+
+impl MarketIndex {
+    fn from_markets(markets: Vec<Market>) -> Self {
+        let mut index = Self {
+            condition_ids: markets.len(),
+            loaded_at: Some(Instant::now()),
+            ..Self::default()
+        };
+        for market in markets {
+            let market = Arc::new(market);
+            for info in TokenInfo::from_market(&market) {
+                index.by_token.insert(info.token_id.clone(), market.clone());
+                index.tokens.insert(info.token_id.clone(), info);
+            }
+            if let Some(slug) = &market.market_slug {
+                index.by_slug.insert(slug.clone(), market.clone());
+            }
+            index.markets.insert(market.condition_id.clone(), market);
+        }
+        index
+    }
+
+    fn from_simplified(markets: Vec<SimplifiedMarket>) -> Self {
+        let mut index = Self {
+            condition_ids: markets.len(),
+            loaded_at: Some(Instant::now()),
+            ..Self::default()
+        };
+        for market in &markets {
+            for info in TokenInfo::from_simplified(market) {
+                index.tokens.insert(info.token_id.clone(), info);
+            }
+        }
+        index
+    }
+}
+
+/// Snapshot of every market, indexed by condition id, token id and slug.
+/// Clones share the snapshot, and a refresh swaps it in whole so lookups
+/// never see a half-loaded index.
+#[derive(Debug, Clone, Default)]
+pub struct MarketCache {
+    index: Arc<RwLock<Arc<MarketIndex>>>,
+}
+
+impl MarketCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn snapshot(&self) -> Arc<MarketIndex> {
+        self.index.read().unwrap().clone()
+    }
+
+    fn replace(&self, index: MarketIndex) {
+        *self.index.write().unwrap() = Arc::new(index);
+    }
+
+    /// Markets in the last load.
+    pub fn len(&self) -> usize {
+        self.snapshot().condition_ids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// When the snapshot was loaded, `None` before the first refresh.
+    pub fn loaded_at(&self) -> Option<Instant> {
+        self.snapshot().loaded_at
+    }
+
+    pub fn market(&self, condition_id: &str) -> Option<Arc<Market>> {
+        self.snapshot().markets.get(condition_id).cloned()
+    }
+
+    pub fn market_for_token(&self, token_id: &str) -> Option<Arc<Market>> {
+        self.snapshot().by_token.get(token_id).cloned()
+    }
+
+    pub fn market_by_slug(&self, slug: &str) -> Option<Arc<Market>> {
+        self.snapshot().by_slug.get(slug).cloned()
+    }
+
+    /// Only known after loading [`MarketSource::Markets`].
+    pub fn tick_size(&self, token_id: &str) -> Option<TickSize> {
+        self.snapshot().tokens.get(token_id)?.tick_size
+    }
+
+    pub fn neg_risk(&self, token_id: &str) -> Option<bool> {
+        self.snapshot().tokens.get(token_id).map(|t| t.neg_risk)
+    }
+
+    pub fn lookup_token(&self, token_id: &str) -> Option<TokenInfo> {
+        self.snapshot().tokens.get(token_id).cloned()
+    }
+}
+
+impl MarketDataApi {
+    /// Scans every market from `source` into [`MarketDataApi::market_cache`],
+    /// which then answers `get_tick_size` and `get_neg_risk` without a
+    /// request per token. Returns the number of markets loaded.
+    pub async fn refresh_market_cache(&self, source: MarketSource) -> Result<usize> {
+        let index = match source {
+            MarketSource::Markets => MarketIndex::from_markets(self.get_markets().await?),
+            MarketSource::SimplifiedMarkets => {
+                MarketIndex::from_simplified(self.get_simplified_markets().await?)
+            }
+        };
+        let loaded = index.condition_ids;
+        self.market_cache().replace(index);
+        Ok(loaded)
+    }
+
+    /// Calls [`Self::refresh_market_cache`] every `interval` on a background
+    /// task. A failed refresh keeps the previous snapshot.
+    pub fn spawn_market_cache_refresh(
+        &self,
+        source: MarketSource,
+        interval: Duration,
+    ) -> JoinHandle<()> {
+        let market_data = self.clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let _ = market_data.refresh_market_cache(source).await;
+            }
+        });
+        self.transport().tasks().track(&task);
+        task
+    }
+}
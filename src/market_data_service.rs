@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::api::MarketDataApi;
+use crate::diagnostics::BookSource;
+use crate::error::Result;
+use crate::types::OrderBook;
+
+/// Where a [`MarketDataService`] answer came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteSource {
+    Live,
+    Rest,
+}
+
+/// A price and the source that served it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    pub price: f64,
+    pub source: QuoteSource,
+}
+
+/// One price source for strategy code: answers from a locally maintained
+/// feed (see [`BookSource`]) when it has a fresh book for the token, and
+/// from REST otherwise.
+#[derive(Clone)]
+pub struct MarketDataService {
+    market_data: MarketDataApi,
+    live: Option<Arc<dyn BookSource>>,
+    max_age: Option<Duration>,
+}
+
+impl MarketDataService {
+    /// REST only until a live source is attached.
+    pub fn new(market_data: MarketDataApi) -> Self {
+        Self {
+            market_data,
+            live: None,
+            max_age: None,
+        }
+    }
+
+    pub fn with_live_source(mut self, source: impl BookSource) -> Self {
+        self.set_live_source(source);
+        self
+    }
+
+    pub fn set_live_source(&mut self, source: impl BookSource) {
+        self.live = Some(Arc::new(source));
+    }
+
+    /// Treats live books whose `timestamp` (unix ms) is older than `max_age`
+    /// as unavailable. Unset, any live book is used.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn market_data(&self) -> &MarketDataApi {
+        &self.market_data
+    }
+
+    fn live_book(&self, token_id: &str) -> Option<OrderBook> {
+        let book = self.live.as_ref()?.book(token_id)?;
+        match self.max_age {
+            Some(max_age) if !is_fresh(&book, max_age) => None,
+            _ => Some(book),
+        }
+    }
+
+    /// The token's book, live if available.
+    pub async fn book(&self, token_id: &str) -> Result<(OrderBook, QuoteSource)> {
+        if let Some(book) = self.live_book(token_id) {
+            return Ok((book, QuoteSource::Live));
+        }
+        let book = self.market_data.get_order_book(token_id).await?;
+        Ok((book, QuoteSource::Rest))
+    }
+
+    /// Highest bid, `None` if that side of the book is empty.
+    pub async fn best_bid(&self, token_id: &str) -> Result<Option<Quote>> {
+        let (book, source) = self.book(token_id).await?;
+        let best = book.to_numeric()?.best_bid();
+        Ok(best.map(|level| Quote {
+            price: level.price,
+            source,
+        }))
+    }
+
+    /// Lowest ask, `None` if that side of the book is empty.
+    pub async fn best_ask(&self, token_id: &str) -> Result<Option<Quote>> {
+        let (book, source) = self.book(token_id).await?;
+        let best = book.to_numeric()?.best_ask();
+        Ok(best.map(|level| Quote {
+            price: level.price,
+            source,
+        }))
+    }
+
+    /// Live book midpoint, or `/midpoint` when the live book is missing,
+    /// stale or one-sided.
+    pub async fn midpoint(&self, token_id: &str) -> Result<Quote> {
+        let live = self
+            .live_book(token_id)
+            .and_then(|book| book.to_numeric().ok()?.midpoint());
+        if let Some(price) = live {
+            return Ok(Quote {
+                price,
+                source: QuoteSource::Live,
+            });
+        }
+        Ok(Quote {
+            price: self.market_data.get_midpoint(token_id).await?,
+            source: QuoteSource::Rest,
+        })
+    }
+
+    pub async fn last_trade(&self, token_id: &str) -> Result<Quote> {
+        let live = self
+            .live
+            .as_ref()
+            .and_then(|live| live.last_trade_price(token_id));
+        if let Some(price) = live {
+            return Ok(Quote {
+                price,
+                source: QuoteSource::Live,
+            });
+        }
+        Ok(Quote {
+            price: self.market_data.get_last_trade_price(token_id).await?,
+            source: QuoteSource::Rest,
+        })
+    }
+}
+
+fn is_fresh(book: &OrderBook, max_age: Duration) -> bool {
+    let Ok(stamp_ms) = book.timestamp.parse::<u64>() else {
+        return false;
+    };
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64);
+    now_ms.saturating_sub(stamp_ms) <= max_age.as_millis() as u64
+}
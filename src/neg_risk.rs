@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use crate::types::{amount_to_f64, Market, OrderArgs, OrderBook, Side};
+
+/// The YES token of a neg-risk outcome market. Falls back to the first token
+/// when no outcome is labelled "Yes".
+pub fn yes_token_id(market: &Market) -> Option<&str> {
+    market
+        .tokens
+        .iter()
+        .find(|t| t.outcome.eq_ignore_ascii_case("yes"))
+        .or(market.tokens.first())
+        .map(|t| t.token_id.as_str())
+}
+
+/// Top of book summed over the YES books of every outcome in an event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompleteSetQuote {
+    /// Cost of buying one full set at the best asks. `None` if any outcome
+    /// has no asks.
+    pub ask_sum: Option<f64>,
+    /// Proceeds of selling one full set at the best bids. `None` if any
+    /// outcome has no bids.
+    pub bid_sum: Option<f64>,
+    /// Sets available at the best asks (smallest best-ask size).
+    pub ask_size: f64,
+    /// Sets available at the best bids (smallest best-bid size).
+    pub bid_size: f64,
+}
+
+// levels are not guaranteed best-first
+fn best_level(book: &OrderBook, side: Side) -> Option<(f64, f64)> {
+    let levels = match side {
+        Side::Buy => &book.asks,
+        Side::Sell => &book.bids,
+    };
+    levels
+        .iter()
+        .map(|l| (l.price_f64(), l.size_f64()))
+        .reduce(|best, level| {
+            let better = match side {
+                Side::Buy => level.0 < best.0,
+                Side::Sell => level.0 > best.0,
+            };
+            if better {
+                level
+            } else {
+                best
+            }
+        })
+}
+
+fn sum_side(books: &[OrderBook], side: Side) -> (Option<f64>, f64) {
+    let levels: Option<Vec<(f64, f64)>> = books.iter().map(|b| best_level(b, side)).collect();
+    match levels {
+        Some(levels) if !levels.is_empty() => (
+            Some(levels.iter().map(|l| l.0).sum()),
+            levels.iter().map(|l| l.1).fold(f64::INFINITY, f64::min),
+        ),
+        _ => (None, 0.0),
+    }
+}
+
+impl CompleteSetQuote {
+    /// Pass the YES book of every outcome in the event.
+    pub fn from_books(books: &[OrderBook]) -> Self {
+        let (ask_sum, ask_size) = sum_side(books, Side::Buy);
+        let (bid_sum, bid_size) = sum_side(books, Side::Sell);
+        Self {
+            ask_sum,
+            bid_sum,
+            ask_size,
+            bid_size,
+        }
+    }
+}
+
+/// A complete-set trade across every outcome of a neg-risk event. Exactly one
+/// outcome resolves YES, so a full set is worth $1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompleteSetArb {
+    /// `Buy` when the asks sum below 1, `Sell` when the bids sum above 1.
+    pub side: Side,
+    /// Sum of the leg prices.
+    pub price_sum: f64,
+    /// Profit per set before fees.
+    pub edge: f64,
+    /// Sets to trade, limited by the thinnest top-of-book level.
+    pub size: f64,
+    /// One order per outcome at its best price. Selling requires holding the
+    /// YES shares of every outcome.
+    pub orders: Vec<OrderArgs>,
+}
+
+/// Looks for a complete-set arbitrage worth more than `min_edge` per set.
+/// Buying is preferred when both directions qualify. `None` if the books
+/// are empty or no direction clears the edge.
+pub fn plan_complete_set_arb(books: &[OrderBook], min_edge: f64) -> Option<CompleteSetArb> {
+    let quote = CompleteSetQuote::from_books(books);
+
+    let (side, price_sum, edge, size) = match (quote.ask_sum, quote.bid_sum) {
+        (Some(ask_sum), _) if 1.0 - ask_sum > min_edge && quote.ask_size > 0.0 => {
+            (Side::Buy, ask_sum, 1.0 - ask_sum, quote.ask_size)
+        }
+        (_, Some(bid_sum)) if bid_sum - 1.0 > min_edge && quote.bid_size > 0.0 => {
+            (Side::Sell, bid_sum, bid_sum - 1.0, quote.bid_size)
+        }
+        _ => return None,
+    };
+
+    let orders = books
+        .iter()
+        .map(|book| {
+            let (price, _) = best_level(book, side)?;
+            Some(OrderArgs::new(book.asset_id.clone(), price, size, side))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(CompleteSetArb {
+        side,
+        price_sum,
+        edge,
+        size,
+        orders,
+    })
+}
+
+/// The outcome markets of one neg-risk event, with each outcome's implied
+/// probability: the last price of its YES token. The raw probabilities
+/// rarely sum to exactly one; see [`Self::normalized`].
+#[derive(Debug, Clone)]
+pub struct NegRiskEvent {
+    pub neg_risk_market_id: String,
+    pub markets: Vec<Market>,
+    /// One per market, in the same order.
+    pub probabilities: Vec<f64>,
+}
+
+impl NegRiskEvent {
+    pub fn new(neg_risk_market_id: impl Into<String>, markets: Vec<Market>) -> Self {
+        let probabilities = markets.iter().map(implied_probability).collect();
+        Self {
+            neg_risk_market_id: neg_risk_market_id.into(),
+            markets,
+            probabilities,
+        }
+    }
+
+    /// Sum of the raw probabilities. Above one is the overround.
+    pub fn total(&self) -> f64 {
+        self.probabilities.iter().sum()
+    }
+
+    /// The probabilities scaled to sum to one. `None` if they are all zero.
+    pub fn normalized(&self) -> Option<Vec<f64>> {
+        normalize_probabilities(&self.probabilities)
+    }
+
+    /// Raw implied probability of the outcome market `condition_id`.
+    pub fn probability(&self, condition_id: &str) -> Option<f64> {
+        let index = self
+            .markets
+            .iter()
+            .position(|m| m.condition_id == condition_id)?;
+        Some(self.probabilities[index])
+    }
+}
+
+fn implied_probability(market: &Market) -> f64 {
+    market
+        .tokens
+        .iter()
+        .find(|t| t.outcome.eq_ignore_ascii_case("yes"))
+        .or(market.tokens.first())
+        .map_or(0.0, |t| amount_to_f64(t.price))
+}
+
+/// Groups neg-risk markets by event, ordered by `neg_risk_market_id`.
+/// Markets outside a neg-risk event are skipped.
+pub fn group_neg_risk_events(markets: impl IntoIterator<Item = Market>) -> Vec<NegRiskEvent> {
+    let mut events: BTreeMap<String, Vec<Market>> = BTreeMap::new();
+    for market in markets {
+        if let Some(id) = market.neg_risk_market_id.clone() {
+            events.entry(id).or_default().push(market);
+        }
+    }
+    events
+        .into_iter()
+        .map(|(id, markets)| NegRiskEvent::new(id, markets))
+        .collect()
+}
+
+/// Scales `probabilities` to sum to one, treating negative or non-finite
+/// entries as zero. `None` if nothing is left to scale.
+pub fn normalize_probabilities(probabilities: &[f64]) -> Option<Vec<f64>> {
+    let clean = |p: f64| if p.is_finite() && p > 0.0 { p } else { 0.0 };
+    let total: f64 = probabilities.iter().map(|p| clean(*p)).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    Some(probabilities.iter().map(|p| clean(*p) / total).collect())
+}
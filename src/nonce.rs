@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of nonces for API-key derivation and orders that leave their nonce
+/// unset. Closures `Fn() -> u64` work as custom providers.
+pub trait NonceProvider: Send + Sync {
+    fn next_nonce(&self) -> u64;
+}
+
+impl<F> NonceProvider for F
+where
+    F: Fn() -> u64 + Send + Sync,
+{
+    fn next_nonce(&self) -> u64 {
+        self()
+    }
+}
+
+/// Always hands out the same nonce. `FixedNonce(0)` is the default and
+/// matches the reference clients.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixedNonce(pub u64);
+
+impl NonceProvider for FixedNonce {
+    fn next_nonce(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Counts up from a starting value. Clones share the counter.
+#[derive(Debug, Clone, Default)]
+pub struct IncrementingNonce {
+    next: Arc<AtomicU64>,
+}
+
+impl IncrementingNonce {
+    pub fn new(start: u64) -> Self {
+        Self {
+            next: Arc::new(AtomicU64::new(start)),
+        }
+    }
+}
+
+impl NonceProvider for IncrementingNonce {
+    fn next_nonce(&self) -> u64 {
+        self.next.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+/// Millisecond timestamps, bumped by one when two calls land in the same
+/// millisecond so values are strictly increasing.
+#[derive(Debug, Clone, Default)]
+pub struct TimestampNonce {
+    last: Arc<AtomicU64>,
+}
+
+impl TimestampNonce {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NonceProvider for TimestampNonce {
+    fn next_nonce(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        let mut last = self.last.load(Ordering::SeqCst);
+        loop {
+            let next = now.max(last + 1);
+            match self
+                .last
+                .compare_exchange(last, next, Ordering::SeqCst, Ordering::SeqCst)
+            {
+                Ok(_) => return next,
+                Err(actual) => last = actual,
+            }
+        }
+    }
+}
+
+/// Source of order salts. The salt only makes otherwise identical orders
+/// hash differently, so a fixed or counting provider gives reproducible
+/// signatures. Closures `Fn() -> u64` work as custom providers.
+pub trait SaltProvider: Send + Sync {
+    fn next_salt(&self) -> u64;
+}
+
+impl<F> SaltProvider for F
+where
+    F: Fn() -> u64 + Send + Sync,
+{
+    fn next_salt(&self) -> u64 {
+        self()
+    }
+}
+
+/// `round(now_secs * random())`, as the reference clients do. The default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomSalt;
+
+impl SaltProvider for RandomSalt {
+    fn next_salt(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+        (now * rand::random::<f64>()).round() as u64
+    }
+}
+
+/// Always the same salt.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixedSalt(pub u64);
+
+impl SaltProvider for FixedSalt {
+    fn next_salt(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,362 @@
+//! Transactions against the Polymarket contracts, sent through a plain
+//! JSON-RPC endpoint. Enabled with the `onchain` feature.
+//!
+//! Covers the NegRiskAdapter `convertPositions` flow, where a holder of NO
+//! shares on several outcomes of a neg-risk event converts them into
+//! collateral plus YES shares on the remaining outcomes, and the exchange
+//! approvals trading needs.
+
+use alloy_consensus::{SignableTransaction, TxEip1559, TxEnvelope};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, Bytes, TxKind, B256, U256};
+use alloy_sol_types::{sol, SolCall};
+use serde::Deserialize;
+
+use crate::allowances::AllowanceReport;
+use crate::config::{get_contract_config, get_neg_risk_adapter};
+use crate::error::{ClobError, Result};
+use crate::resolution::{Payout, Resolution};
+use crate::signer::Signer;
+use crate::transport::Transport;
+use crate::types::{AssetType, Market};
+
+sol! {
+    function convertPositions(bytes32 marketId, uint256 indexSet, uint256 amount);
+    function setApprovalForAll(address operator, bool approved);
+    function approve(address spender, uint256 amount) returns (bool);
+    function isApprovedForAll(address owner, address operator) returns (bool);
+    function payoutDenominator(bytes32 conditionId) returns (uint256);
+    function payoutNumerators(bytes32 conditionId, uint256 index) returns (uint256);
+}
+
+/// Calldata for `NegRiskAdapter.convertPositions`. `amount` is in share
+/// base units, which have the collateral's decimals.
+pub fn convert_positions_calldata(market_id: B256, index_set: U256, amount: U256) -> Bytes {
+    convertPositionsCall {
+        marketId: market_id,
+        indexSet: index_set,
+        amount,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Bitmask of the outcomes whose NO shares are converted. The index of a
+/// neg-risk question is the last byte of its question id.
+pub fn neg_risk_index_set<S: AsRef<str>>(question_ids: &[S]) -> Result<U256> {
+    let mut index_set = U256::ZERO;
+    for question_id in question_ids {
+        let id = parse_b256(question_id.as_ref(), "question id")?;
+        index_set |= U256::from(1u8) << id[31] as usize;
+    }
+    Ok(index_set)
+}
+
+fn parse_b256(value: &str, what: &str) -> Result<B256> {
+    value
+        .parse()
+        .map_err(|_| ClobError::InvalidParameter(format!("invalid {}: {}", what, value)))
+}
+
+fn parse_address(value: &str) -> Result<Address> {
+    value
+        .parse()
+        .map_err(|_| ClobError::InvalidParameter(format!("invalid address: {}", value)))
+}
+
+// shares to base units
+fn to_amount(size: f64, decimals: u32) -> Result<U256> {
+    if !size.is_finite() || size <= 0.0 {
+        return Err(ClobError::InvalidParameter(format!(
+            "size must be positive, got {}",
+            size
+        )));
+    }
+    let micros = U256::from((size * 1_000_000.0).round() as u128);
+    Ok(match decimals.checked_sub(6) {
+        Some(extra) => micros * U256::from(10u8).pow(U256::from(extra)),
+        None => micros / U256::from(10u8).pow(U256::from(6 - decimals)),
+    })
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<serde_json::Value>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+fn parse_quantity(value: &serde_json::Value) -> Result<u128> {
+    value
+        .as_str()
+        .and_then(|s| u128::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| ClobError::Json {
+            message: format!("expected a hex quantity, got {}", value),
+        })
+}
+
+/// Signs and sends transactions for the wallet behind `signer`. Gas, fees
+/// and nonce are filled in from the node.
+#[derive(Clone)]
+pub struct OnchainClient {
+    rpc: Transport,
+    signer: Signer,
+    adapter: Address,
+    collateral: Address,
+    conditional_tokens: Address,
+    decimals: u32,
+}
+
+impl OnchainClient {
+    pub fn new(rpc_url: impl Into<String>, signer: Signer) -> Result<Self> {
+        let chain_id = signer.chain_id();
+        let adapter = get_neg_risk_adapter(chain_id).ok_or_else(|| {
+            ClobError::InvalidParameter(format!("no neg risk adapter for chain {}", chain_id))
+        })?;
+        let config = get_contract_config(chain_id, true).ok_or_else(|| {
+            ClobError::InvalidParameter(format!("no contract config for chain {}", chain_id))
+        })?;
+
+        Ok(Self {
+            rpc: Transport::new(rpc_url),
+            signer,
+            adapter: parse_address(adapter)?,
+            collateral: parse_address(config.collateral)?,
+            conditional_tokens: parse_address(config.conditional_tokens)?,
+            decimals: config.collateral_decimals,
+        })
+    }
+
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// Whether the adapter may move this wallet's conditional tokens, which
+    /// `convertPositions` needs.
+    pub async fn is_neg_risk_adapter_approved(&self) -> Result<bool> {
+        let call = isApprovedForAllCall {
+            owner: self.address(),
+            operator: self.adapter,
+        };
+        let bytes = self
+            .eth_call(self.conditional_tokens, call.abi_encode())
+            .await?;
+        Ok(bytes.last().is_some_and(|b| *b == 1))
+    }
+
+    /// Reads the payouts the oracle reported to the conditional tokens
+    /// contract, which is authoritative where the REST `winner` flags are
+    /// missing. Payouts follow the order of `market.tokens`.
+    pub async fn resolution(&self, market: &Market) -> Result<Resolution> {
+        let condition_id = parse_b256(&market.condition_id, "condition id")?;
+        let denominator = self
+            .read_uint(payoutDenominatorCall {
+                conditionId: condition_id,
+            })
+            .await?;
+        if denominator.is_zero() {
+            return Ok(if market.closed {
+                Resolution::Pending
+            } else {
+                Resolution::Open
+            });
+        }
+
+        let mut payouts = Vec::with_capacity(market.tokens.len());
+        for (index, token) in market.tokens.iter().enumerate() {
+            let numerator = self
+                .read_uint(payoutNumeratorsCall {
+                    conditionId: condition_id,
+                    index: U256::from(index),
+                })
+                .await?;
+            payouts.push(Payout {
+                token_id: token.token_id.clone(),
+                outcome: token.outcome.clone(),
+                value: f64::from(numerator) / f64::from(denominator),
+            });
+        }
+        Ok(Resolution::Resolved(payouts))
+    }
+
+    /// Sends `setApprovalForAll(adapter, true)` on the conditional tokens
+    /// contract and returns the transaction hash.
+    pub async fn approve_neg_risk_adapter(&self) -> Result<B256> {
+        let data = setApprovalForAllCall {
+            operator: self.adapter,
+            approved: true,
+        }
+        .abi_encode();
+        self.send(self.conditional_tokens, data.into()).await
+    }
+
+    /// Sends one approval per entry of `report`: an unlimited USDC allowance
+    /// or `setApprovalForAll` on the conditional tokens. Returns the
+    /// transaction hashes in the same order. The approvals come from this
+    /// wallet, so this only helps accounts that trade from it directly
+    /// (EOA signature type). Call `update_balance_allowance` once they are
+    /// mined so the exchange sees them.
+    pub async fn approve_missing(&self, report: &AllowanceReport) -> Result<Vec<B256>> {
+        let mut hashes = Vec::with_capacity(report.missing.len());
+        for missing in &report.missing {
+            let spender = parse_address(&missing.spender)?;
+            let (to, data) = match missing.asset_type {
+                AssetType::COLLATERAL => (
+                    self.collateral,
+                    approveCall {
+                        spender,
+                        amount: U256::MAX,
+                    }
+                    .abi_encode(),
+                ),
+                AssetType::CONDITIONAL => (
+                    self.conditional_tokens,
+                    setApprovalForAllCall {
+                        operator: spender,
+                        approved: true,
+                    }
+                    .abi_encode(),
+                ),
+            };
+            hashes.push(self.send(to, data.into()).await?);
+        }
+        Ok(hashes)
+    }
+
+    /// Converts `size` NO shares on each outcome in `index_set` and returns
+    /// the transaction hash.
+    pub async fn convert_positions(
+        &self,
+        market_id: B256,
+        index_set: U256,
+        size: f64,
+    ) -> Result<B256> {
+        if index_set.is_zero() {
+            return Err(ClobError::InvalidParameter(
+                "index set selects no outcomes".to_string(),
+            ));
+        }
+        let data =
+            convert_positions_calldata(market_id, index_set, to_amount(size, self.decimals)?);
+        self.send(self.adapter, data).await
+    }
+
+    /// Converts `size` NO shares on each of `markets`, which must be outcomes
+    /// of the same neg-risk event.
+    pub async fn convert_no_positions(&self, markets: &[Market], size: f64) -> Result<B256> {
+        let mut market_ids = markets.iter().map(|m| m.neg_risk_market_id.as_deref());
+        let market_id = match market_ids.next() {
+            Some(Some(id)) if market_ids.all(|other| other == Some(id)) => id,
+            _ => {
+                return Err(ClobError::InvalidParameter(
+                    "markets must belong to one neg-risk event".to_string(),
+                ))
+            }
+        };
+        let question_ids: Vec<&str> = markets.iter().map(|m| m.question_id.as_str()).collect();
+        let index_set = neg_risk_index_set(&question_ids)?;
+        self.convert_positions(
+            parse_b256(market_id, "neg risk market id")?,
+            index_set,
+            size,
+        )
+        .await
+    }
+
+    async fn eth_call(&self, to: Address, data: Vec<u8>) -> Result<Bytes> {
+        let result = self
+            .call(
+                "eth_call",
+                serde_json::json!([{"to": to, "data": Bytes::from(data)}, "latest"]),
+            )
+            .await?;
+        serde_json::from_value(result).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })
+    }
+
+    // for view functions returning a single uint256
+    async fn read_uint<C: SolCall>(&self, call: C) -> Result<U256> {
+        let bytes = self
+            .eth_call(self.conditional_tokens, call.abi_encode())
+            .await?;
+        if bytes.len() < 32 {
+            return Err(ClobError::Json {
+                message: format!("expected a uint256, got {}", bytes),
+            });
+        }
+        Ok(U256::from_be_slice(&bytes[..32]))
+    }
+
+    async fn send(&self, to: Address, input: Bytes) -> Result<B256> {
+        let from = self.address();
+        let nonce = self
+            .call(
+                "eth_getTransactionCount",
+                serde_json::json!([from, "pending"]),
+            )
+            .await?;
+        let gas = self
+            .call(
+                "eth_estimateGas",
+                serde_json::json!([{"from": from, "to": to, "data": input}]),
+            )
+            .await?;
+        let gas_price = parse_quantity(&self.call("eth_gasPrice", serde_json::json!([])).await?)?;
+        let priority_fee = parse_quantity(
+            &self
+                .call("eth_maxPriorityFeePerGas", serde_json::json!([]))
+                .await?,
+        )?;
+
+        // 20% headroom on the estimate, 2x on the fee so a rising base fee
+        // does not strand the transaction
+        let max_fee_per_gas = gas_price * 2;
+        let tx = TxEip1559 {
+            chain_id: self.signer.chain_id(),
+            nonce: parse_quantity(&nonce)? as u64,
+            gas_limit: (parse_quantity(&gas)? as u64).saturating_mul(6) / 5,
+            max_fee_per_gas,
+            max_priority_fee_per_gas: priority_fee.min(max_fee_per_gas),
+            to: TxKind::Call(to),
+            value: U256::ZERO,
+            access_list: Default::default(),
+            input,
+        };
+
+        let signature = self.signer.sign_hash_raw(tx.signature_hash()).await?;
+        let envelope = TxEnvelope::from(tx.into_signed(signature));
+        let raw = Bytes::from(envelope.encoded_2718());
+
+        let hash = self
+            .call("eth_sendRawTransaction", serde_json::json!([raw]))
+            .await?;
+        serde_json::from_value(hash).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })
+    }
+
+    async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response: RpcResponse = self.rpc.post("", &request).await?;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(ClobError::Api {
+                message: format!("{} failed ({}): {}", method, error.code, error.message),
+            }),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(ClobError::Api {
+                message: format!("{} returned no result", method),
+            }),
+        }
+    }
+}
@@ -1,8 +1,12 @@
-use alloy_primitives::{keccak256, Address, B256, U256};
-use alloy_sol_types::sol;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use alloy_primitives::{keccak256, Address, PrimitiveSignature, B256, U256};
+use alloy_sol_types::{eip712_domain, sol, Eip712Domain};
 
 use crate::config::get_contract_config;
 use crate::error::{ClobError, Result};
+use crate::nonce::{FixedNonce, NonceProvider, RandomSalt, SaltProvider};
 use crate::signer::Signer;
 use crate::types::{CreateOrderOptions, MarketOrderArgs, OrderArgs, RoundConfig, Side, TickSize};
 
@@ -13,6 +17,9 @@ pub const POLY_PROXY: u8 = 1;
 pub const POLY_GNOSIS_SAFE: u8 = 2;
 
 sol! {
+    /// EIP-712 typed data of an exchange order. Hashing and signing in
+    /// [`OrderBuilder`] encode the same struct by hand, without allocating.
+    #[derive(Debug, PartialEq, Eq)]
     struct Order {
         uint256 salt;
         address maker;
@@ -29,9 +36,12 @@ sol! {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SignedOrder {
-    #[serde(serialize_with = "serialize_salt_as_int")]
+    #[serde(
+        serialize_with = "serialize_salt_as_int",
+        deserialize_with = "deserialize_salt"
+    )]
     pub salt: String,
     pub maker: String,
     pub signer: String,
@@ -46,7 +56,10 @@ pub struct SignedOrder {
     pub nonce: String,
     #[serde(rename = "feeRateBps")]
     pub fee_rate_bps: String,
-    #[serde(serialize_with = "serialize_side")]
+    #[serde(
+        serialize_with = "serialize_side",
+        deserialize_with = "deserialize_side"
+    )]
     pub side: u8,
     #[serde(rename = "signatureType")]
     pub signature_type: u8,
@@ -74,6 +87,136 @@ where
     }
 }
 
+fn deserialize_side<'de, D>(deserializer: D) -> std::result::Result<u8, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) if s == "BUY" => Ok(0),
+        serde_json::Value::String(s) if s == "SELL" => Ok(1),
+        serde_json::Value::Number(n) if n.as_u64() == Some(0) => Ok(0),
+        serde_json::Value::Number(n) if n.as_u64() == Some(1) => Ok(1),
+        other => Err(serde::de::Error::custom(format!("invalid side: {}", other))),
+    }
+}
+
+fn deserialize_salt<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!("invalid salt: {}", other))),
+    }
+}
+
+impl SignedOrder {
+    /// The order as EIP-712 typed data, e.g. to check it with
+    /// [`alloy_sol_types::SolStruct::eip712_signing_hash`] or show it in a wallet.
+    pub fn typed_data(&self) -> Result<Order> {
+        let uint = |value: &str, name: &str| {
+            U256::from_str_radix(value, 10)
+                .map_err(|_| ClobError::InvalidParameter(format!("invalid {}", name)))
+        };
+        let address = |value: &str, name: &str| {
+            value
+                .parse::<Address>()
+                .map_err(|_| ClobError::InvalidParameter(format!("invalid {} address", name)))
+        };
+
+        Ok(Order {
+            salt: uint(&self.salt, "salt")?,
+            maker: address(&self.maker, "maker")?,
+            signer: address(&self.signer, "signer")?,
+            taker: address(&self.taker, "taker")?,
+            tokenId: parse_token_id(&self.token_id)?,
+            makerAmount: uint(&self.maker_amount, "maker_amount")?,
+            takerAmount: uint(&self.taker_amount, "taker_amount")?,
+            expiration: uint(&self.expiration, "expiration")?,
+            nonce: uint(&self.nonce, "nonce")?,
+            feeRateBps: uint(&self.fee_rate_bps, "fee_rate_bps")?,
+            side: self.side,
+            signatureType: self.signature_type,
+        })
+    }
+
+    /// EIP-712 digest of the order, as signed by the maker and reported by the
+    /// exchange in `order_hashes`.
+    pub fn hash(&self, chain_id: u64, neg_risk: bool) -> Result<B256> {
+        let order = self.typed_data()?;
+        let struct_hash = order_struct_hash(
+            order.salt,
+            order.maker,
+            order.signer,
+            order.taker,
+            order.tokenId,
+            order.makerAmount,
+            order.takerAmount,
+            order.expiration,
+            order.nonce,
+            order.feeRateBps,
+            order.side,
+            order.signatureType,
+        );
+
+        Ok(eip712_digest(
+            exchange_domain_separator(chain_id, neg_risk)?,
+            struct_hash,
+        ))
+    }
+}
+
+/// EIP-712 domain of the exchange that settles orders on `chain_id`.
+pub fn exchange_domain(chain_id: u64, neg_risk: bool) -> Result<Eip712Domain> {
+    let contract_config = get_contract_config(chain_id, neg_risk)
+        .ok_or_else(|| ClobError::InvalidParameter("invalid chain_id".to_string()))?;
+    let exchange: Address = contract_config
+        .exchange
+        .parse()
+        .expect("invalid exchange address");
+    Ok(eip712_domain! {
+        name: "Polymarket CTF Exchange",
+        version: "1",
+        chain_id: chain_id,
+        verifying_contract: exchange,
+    })
+}
+
+/// Signs `order` with any alloy signer through its `sign_typed_data`, for
+/// wallets and remote signers that never expose a private key.
+pub async fn sign_typed_order<S>(
+    signer: &S,
+    order: &Order,
+    chain_id: u64,
+    neg_risk: bool,
+) -> Result<SignedOrder>
+where
+    S: alloy_signer::Signer<PrimitiveSignature> + Send + Sync,
+{
+    let signature = signer
+        .sign_typed_data(order, &exchange_domain(chain_id, neg_risk)?)
+        .await
+        .map_err(|e| ClobError::Signing {
+            message: format!("failed to sign: {}", e),
+        })?;
+    Ok(SignedOrder {
+        salt: order.salt.to_string(),
+        maker: order.maker.to_checksum(None),
+        signer: order.signer.to_checksum(None),
+        taker: order.taker.to_checksum(None),
+        token_id: order.tokenId.to_string(),
+        maker_amount: order.makerAmount.to_string(),
+        taker_amount: order.takerAmount.to_string(),
+        expiration: order.expiration.to_string(),
+        nonce: order.nonce.to_string(),
+        fee_rate_bps: order.feeRateBps.to_string(),
+        side: order.side,
+        signature_type: order.signatureType,
+        signature: format!("0x{}", hex::encode(signature.as_bytes())),
+    })
+}
+
 use serde::{Deserialize, Serialize};
 
 const ROUNDING_CONFIG: [(TickSize, RoundConfig); 4] = [
@@ -138,26 +281,237 @@ fn round_up(x: f64, decimals: u32) -> f64 {
     (x * factor).ceil() / factor
 }
 
-fn to_token_decimals(x: f64) -> u64 {
-    let scaled = x * 1_000_000.0;
-    scaled.round() as u64
+/// `x` in base units of a token with `decimals` decimals. Rounded amounts
+/// never have more than 6 decimals, so `x` is scaled in exact integer steps
+/// past that instead of losing precision in one large float product.
+fn to_token_decimals(x: f64, decimals: u32) -> u128 {
+    let micros = (x * 1_000_000.0).round() as u128;
+    match decimals.checked_sub(6) {
+        Some(extra) => micros * 10u128.pow(extra),
+        None => {
+            let factor = 10u128.pow(6 - decimals);
+            (micros + factor / 2) / factor
+        }
+    }
 }
 
-// TODO: this is in the order path as well which is called everytime
-// there might be optimizations here since it allocates a string
+// places past which decimal_places stops counting; 10^21 times a 53-bit
+// mantissa leaves headroom in a u128
+const MAX_DECIMAL_PLACES: u32 = 21;
+
+/// Decimal places of the shortest decimal that parses back to `x`, as in
+/// `0.1 + 0.2 -> 17`. Found with exact integer math on the float's bits, so
+/// it never allocates and doesn't depend on how floats are printed. Values
+/// that need more than [`MAX_DECIMAL_PLACES`] places report one more.
 fn decimal_places(x: f64) -> u32 {
-    let s = format!("{}", x);
-    if let Some(pos) = s.find('.') {
-        (s.len() - pos - 1) as u32
-    } else {
-        0
+    if !x.is_finite() || x == 0.0 {
+        return 0;
     }
+    // |x| = mantissa / 2^shift
+    let bits = x.abs().to_bits();
+    let biased_exponent = (bits >> 52) as i32;
+    let fraction = (bits & ((1 << 52) - 1)) as u128;
+    let (mantissa, shift) = match biased_exponent {
+        0 => (fraction, 1074),
+        _ => (fraction | (1 << 52), 1075 - biased_exponent),
+    };
+    if shift <= 0 {
+        return 0;
+    }
+    if shift >= 128 {
+        return MAX_DECIMAL_PLACES + 1;
+    }
+    // the float next below is closer when the mantissa is a power of two
+    let narrow_below = fraction == 0 && biased_exponent > 1;
+    for places in 0..=MAX_DECIMAL_PLACES {
+        let pow10 = 10u128.pow(places);
+        // |x| * 10^places * 2^shift, as an integer
+        let target = mantissa * pow10;
+        let floor = target >> shift;
+        for candidate in [floor, floor + 1] {
+            // whether candidate / 10^places parses back to |x|: within half
+            // a gap of it, ties going to the even mantissa. Both sides are
+            // scaled by 4 so a quarter gap is an integer too.
+            let scaled = candidate << shift;
+            let (distance, limit) = if scaled >= target {
+                (scaled - target, 2 * pow10)
+            } else if narrow_below {
+                (target - scaled, pow10)
+            } else {
+                (target - scaled, 2 * pow10)
+            };
+            let distance = distance.saturating_mul(4);
+            if distance < limit || (distance == limit && mantissa % 2 == 0) {
+                return places;
+            }
+        }
+    }
+    MAX_DECIMAL_PLACES + 1
+}
+
+static EXCHANGE_DOMAIN_TYPE_HASH: LazyLock<B256> = LazyLock::new(|| {
+    keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
+});
+static EXCHANGE_NAME_HASH: LazyLock<B256> = LazyLock::new(|| keccak256("Polymarket CTF Exchange"));
+static EXCHANGE_VERSION_HASH: LazyLock<B256> = LazyLock::new(|| keccak256("1"));
+static ORDER_TYPE_HASH: LazyLock<B256> = LazyLock::new(|| {
+    keccak256(
+        "Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,uint8 signatureType)",
+    )
+});
+
+/// What signing needs from the contract config of a (chain_id, neg_risk).
+#[derive(Clone, Copy)]
+struct Exchange {
+    domain_separator: B256,
+    decimals: u32,
+}
+
+// keyed by everything an entry is derived from, so a replaced config never
+// reads a stale entry and needs no invalidation
+type ExchangeKey = (u64, bool, &'static str, u32);
+
+static EXCHANGES: LazyLock<RwLock<HashMap<ExchangeKey, Exchange>>> =
+    LazyLock::new(Default::default);
+
+fn exchange(chain_id: u64, neg_risk: bool) -> Result<Exchange> {
+    let contract_config = get_contract_config(chain_id, neg_risk)
+        .ok_or_else(|| ClobError::InvalidParameter("invalid chain_id".to_string()))?;
+    let key = (
+        chain_id,
+        neg_risk,
+        contract_config.exchange,
+        contract_config.collateral_decimals,
+    );
+    if let Some(exchange) = EXCHANGES.read().unwrap().get(&key) {
+        return Ok(*exchange);
+    }
+    let exchange = Exchange {
+        domain_separator: domain_separator(contract_config.exchange, chain_id),
+        decimals: contract_config.collateral_decimals,
+    };
+    EXCHANGES.write().unwrap().insert(key, exchange);
+    Ok(exchange)
+}
+
+fn exchange_domain_separator(chain_id: u64, neg_risk: bool) -> Result<B256> {
+    exchange(chain_id, neg_risk).map(|exchange| exchange.domain_separator)
+}
+
+fn domain_separator(exchange: &str, chain_id: u64) -> B256 {
+    let exchange_addr: Address = exchange.parse().expect("invalid exchange address");
+
+    let mut encoded = [0u8; 5 * 32];
+    encoded[..32].copy_from_slice(EXCHANGE_DOMAIN_TYPE_HASH.as_slice());
+    encoded[32..64].copy_from_slice(EXCHANGE_NAME_HASH.as_slice());
+    encoded[64..96].copy_from_slice(EXCHANGE_VERSION_HASH.as_slice());
+    encoded[96..128].copy_from_slice(&U256::from(chain_id).to_be_bytes::<32>());
+    encoded[140..].copy_from_slice(exchange_addr.as_slice());
+
+    keccak256(encoded)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn order_struct_hash(
+    salt: U256,
+    maker: Address,
+    signer_addr: Address,
+    taker: Address,
+    token_id: U256,
+    maker_amount: U256,
+    taker_amount: U256,
+    expiration: U256,
+    nonce: U256,
+    fee_rate_bps: U256,
+    side: u8,
+    signature_type: u8,
+) -> B256 {
+    // ABI encoding of the 12 fields after the type hash, one 32-byte word each
+    let mut encoded = [0u8; 13 * 32];
+    let mut words = encoded.chunks_exact_mut(32);
+    let mut put_word = |word: [u8; 32]| words.next().unwrap().copy_from_slice(&word);
+    let address_word = |addr: Address| {
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(addr.as_slice());
+        word
+    };
+    let small_word = |value: u8| {
+        let mut word = [0u8; 32];
+        word[31] = value;
+        word
+    };
+
+    put_word(ORDER_TYPE_HASH.0);
+    put_word(salt.to_be_bytes());
+    put_word(address_word(maker));
+    put_word(address_word(signer_addr));
+    put_word(address_word(taker));
+    put_word(token_id.to_be_bytes());
+    put_word(maker_amount.to_be_bytes());
+    put_word(taker_amount.to_be_bytes());
+    put_word(expiration.to_be_bytes());
+    put_word(nonce.to_be_bytes());
+    put_word(fee_rate_bps.to_be_bytes());
+    put_word(small_word(side));
+    put_word(small_word(signature_type));
+
+    keccak256(encoded)
 }
 
+fn eip712_digest(domain_sep: B256, struct_hash: B256) -> B256 {
+    let mut message = [0u8; 66];
+    message[..2].copy_from_slice(&[0x19, 0x01]);
+    message[2..34].copy_from_slice(domain_sep.as_slice());
+    message[34..].copy_from_slice(struct_hash.as_slice());
+
+    keccak256(message)
+}
+
+// Overwrites `field` in place, reusing its allocation.
+fn set_field(field: &mut String, value: impl std::fmt::Display) {
+    use std::fmt::Write;
+    field.clear();
+    write!(field, "{}", value).expect("writing to a String cannot fail");
+}
+
+fn parse_token_id(token_id: &str) -> Result<U256> {
+    U256::from_str_radix(
+        token_id.strip_prefix("0x").unwrap_or(token_id),
+        if token_id.starts_with("0x") { 16 } else { 10 },
+    )
+    .map_err(|_| ClobError::InvalidParameter("invalid token_id".to_string()))
+}
+
+fn parse_taker(taker: &str) -> Result<Address> {
+    taker
+        .parse()
+        .map_err(|_| ClobError::InvalidParameter(format!("invalid taker address {}", taker)))
+}
+
+// below this, spawning threads costs more than signing on one
+const PARALLEL_SIGNING_MIN_ORDERS: usize = 16;
+
+// What an order commits to besides the builder's own maker, signer and
+// signature type. Strings are borrowed from the caller's args.
+struct OrderFields<'a> {
+    salt: U256,
+    taker: &'a str,
+    token_id: &'a str,
+    maker_amount: u128,
+    taker_amount: u128,
+    expiration: u64,
+    nonce: u64,
+    fee_rate_bps: i32,
+    side: u8,
+}
+
+#[derive(Clone)]
 pub struct OrderBuilder {
     signer: Signer,
     sig_type: u8,
     funder: Address,
+    nonce_provider: Arc<dyn NonceProvider>,
+    salt_provider: Arc<dyn SaltProvider>,
 }
 
 impl OrderBuilder {
@@ -167,6 +521,40 @@ impl OrderBuilder {
             signer,
             sig_type: EOA,
             funder,
+            nonce_provider: Arc::new(FixedNonce::default()),
+            salt_provider: Arc::new(RandomSalt),
+        }
+    }
+
+    /// Nonce source for orders whose args leave `nonce` at 0.
+    pub fn with_nonce_provider(mut self, provider: Arc<dyn NonceProvider>) -> Self {
+        self.nonce_provider = provider;
+        self
+    }
+
+    pub fn set_nonce_provider(&mut self, provider: Arc<dyn NonceProvider>) {
+        self.nonce_provider = provider;
+    }
+
+    /// Salt source for orders whose options carry no salt.
+    pub fn with_salt_provider(mut self, provider: Arc<dyn SaltProvider>) -> Self {
+        self.salt_provider = provider;
+        self
+    }
+
+    pub fn set_salt_provider(&mut self, provider: Arc<dyn SaltProvider>) {
+        self.salt_provider = provider;
+    }
+
+    fn resolve_salt(&self, salt: Option<u64>) -> U256 {
+        U256::from(salt.unwrap_or_else(|| self.salt_provider.next_salt()))
+    }
+
+    fn resolve_nonce(&self, nonce: u64) -> u64 {
+        if nonce == 0 {
+            self.nonce_provider.next_nonce()
+        } else {
+            nonce
         }
     }
 
@@ -180,13 +568,35 @@ impl OrderBuilder {
         self
     }
 
+    pub fn set_sig_type(&mut self, sig_type: u8) {
+        self.sig_type = sig_type;
+    }
+
+    pub fn set_funder(&mut self, funder: Address) {
+        self.funder = funder;
+    }
+
+    pub fn set_chain_id(&mut self, chain_id: u64) {
+        self.signer.set_chain_id(chain_id);
+    }
+
+    pub fn signer(&self) -> &Signer {
+        &self.signer
+    }
+
+    /// Address that holds the funds and makes the orders.
+    pub fn funder(&self) -> Address {
+        self.funder
+    }
+
     fn get_order_amounts(
         &self,
         side: Side,
         size: f64,
         price: f64,
         round_config: RoundConfig,
-    ) -> (u8, u64, u64) {
+        decimals: u32,
+    ) -> (u8, u128, u128) {
         let raw_price = round_normal(price, round_config.price);
 
         match side {
@@ -201,8 +611,8 @@ impl OrderBuilder {
                     }
                 }
 
-                let maker_amount = to_token_decimals(raw_maker_amt);
-                let taker_amount = to_token_decimals(raw_taker_amt);
+                let maker_amount = to_token_decimals(raw_maker_amt, decimals);
+                let taker_amount = to_token_decimals(raw_taker_amt, decimals);
                 (0, maker_amount, taker_amount) // 0 = BUY
             }
             Side::Sell => {
@@ -216,8 +626,8 @@ impl OrderBuilder {
                     }
                 }
 
-                let maker_amount = to_token_decimals(raw_maker_amt);
-                let taker_amount = to_token_decimals(raw_taker_amt);
+                let maker_amount = to_token_decimals(raw_maker_amt, decimals);
+                let taker_amount = to_token_decimals(raw_taker_amt, decimals);
                 (1, maker_amount, taker_amount) // 1 = SELL
             }
         }
@@ -229,7 +639,8 @@ impl OrderBuilder {
         amount: f64,
         price: f64,
         round_config: RoundConfig,
-    ) -> (u8, u64, u64) {
+        decimals: u32,
+    ) -> (u8, u128, u128) {
         let raw_price = round_normal(price, round_config.price);
 
         match side {
@@ -244,8 +655,8 @@ impl OrderBuilder {
                     }
                 }
 
-                let maker_amount = to_token_decimals(raw_maker_amt);
-                let taker_amount = to_token_decimals(raw_taker_amt);
+                let maker_amount = to_token_decimals(raw_maker_amt, decimals);
+                let taker_amount = to_token_decimals(raw_taker_amt, decimals);
                 (0, maker_amount, taker_amount)
             }
             Side::Sell => {
@@ -259,164 +670,102 @@ impl OrderBuilder {
                     }
                 }
 
-                let maker_amount = to_token_decimals(raw_maker_amt);
-                let taker_amount = to_token_decimals(raw_taker_amt);
+                let maker_amount = to_token_decimals(raw_maker_amt, decimals);
+                let taker_amount = to_token_decimals(raw_taker_amt, decimals);
                 (1, maker_amount, taker_amount)
             }
         }
     }
 
-    //TODO: domain separators are computed on every order/signature
-    //these are computed on every order creation and uncessary maybe some type of lazylock
-    fn domain_separator(&self, exchange: &str, chain_id: u64) -> B256 {
-        let type_hash = keccak256(
-            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
-        );
-
-        let name_hash = keccak256("Polymarket CTF Exchange");
-        let version_hash = keccak256("1");
-        let chain_id_bytes = U256::from(chain_id);
-
-        let exchange_addr: Address = exchange.parse().expect("invalid exchange address");
-        let mut exchange_padded = [0u8; 32];
-        exchange_padded[12..].copy_from_slice(exchange_addr.as_slice());
-
-        let encoded = [
-            type_hash.as_slice(),
-            name_hash.as_slice(),
-            version_hash.as_slice(),
-            &chain_id_bytes.to_be_bytes::<32>(),
-            &exchange_padded,
-        ]
-        .concat();
-
-        keccak256(&encoded)
+    pub async fn create_order(
+        &self,
+        order_args: &OrderArgs,
+        options: &CreateOrderOptions,
+    ) -> Result<SignedOrder> {
+        let mut order = SignedOrder::default();
+        self.create_order_into(order_args, options, &mut order)?;
+        Ok(order)
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn order_struct_hash(
+    /// [`Self::create_order`] that writes into `order`, reusing its string
+    /// buffers. Signing a stream of orders into the same `SignedOrder` does
+    /// no heap allocation once the buffers have grown.
+    pub fn create_order_into(
         &self,
-        salt: U256,
-        maker: Address,
-        signer_addr: Address,
-        taker: Address,
-        token_id: U256,
-        maker_amount: U256,
-        taker_amount: U256,
-        expiration: U256,
-        nonce: U256,
-        fee_rate_bps: U256,
-        side: u8,
-        signature_type: u8,
-    ) -> B256 {
-        let type_hash = keccak256(
-            "Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,uint8 signatureType)",
-        );
+        order_args: &OrderArgs,
+        options: &CreateOrderOptions,
+        order: &mut SignedOrder,
+    ) -> Result<()> {
+        let exchange = exchange(self.signer.chain_id(), options.neg_risk)?;
+        self.limit_order_into(order_args, options, exchange, order)
+    }
 
-        fn pad_address(addr: Address) -> [u8; 32] {
-            let mut padded = [0u8; 32];
-            padded[12..].copy_from_slice(addr.as_slice());
-            padded
+    /// Signs every order in `orders` with the same options, in order. Large
+    /// batches (e.g. a quote ladder) are split across threads. This blocks
+    /// while signing, so async callers with big batches may want
+    /// `spawn_blocking`.
+    pub fn create_orders(
+        &self,
+        orders: &[OrderArgs],
+        options: &CreateOrderOptions,
+    ) -> Result<Vec<SignedOrder>> {
+        let exchange = exchange(self.signer.chain_id(), options.neg_risk)?;
+        let sign_chunk = |chunk: &[OrderArgs]| -> Result<Vec<SignedOrder>> {
+            chunk
+                .iter()
+                .map(|args| {
+                    let mut order = SignedOrder::default();
+                    self.limit_order_into(args, options, exchange, &mut order)?;
+                    Ok(order)
+                })
+                .collect()
+        };
+
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        if orders.len() < PARALLEL_SIGNING_MIN_ORDERS || threads == 1 {
+            return sign_chunk(orders);
         }
-
-        let encoded = [
-            type_hash.as_slice(),
-            &salt.to_be_bytes::<32>(),
-            &pad_address(maker),
-            &pad_address(signer_addr),
-            &pad_address(taker),
-            &token_id.to_be_bytes::<32>(),
-            &maker_amount.to_be_bytes::<32>(),
-            &taker_amount.to_be_bytes::<32>(),
-            &expiration.to_be_bytes::<32>(),
-            &nonce.to_be_bytes::<32>(),
-            &fee_rate_bps.to_be_bytes::<32>(),
-            &U256::from(side).to_be_bytes::<32>(),
-            &U256::from(signature_type).to_be_bytes::<32>(),
-        ]
-        .concat();
-
-        keccak256(&encoded)
+        let chunk_size = orders.len().div_ceil(threads);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = orders
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || sign_chunk(chunk)))
+                .collect();
+            let mut signed = Vec::with_capacity(orders.len());
+            for handle in handles {
+                signed.extend(handle.join().expect("order signing thread panicked")?);
+            }
+            Ok(signed)
+        })
     }
 
-    pub async fn create_order(
+    fn limit_order_into(
         &self,
         order_args: &OrderArgs,
         options: &CreateOrderOptions,
-    ) -> Result<SignedOrder> {
+        exchange: Exchange,
+        order: &mut SignedOrder,
+    ) -> Result<()> {
         let round_config = get_round_config(options.tick_size);
         let (side, maker_amount, taker_amount) = self.get_order_amounts(
             order_args.side,
             order_args.size,
             order_args.price,
             round_config,
+            exchange.decimals,
         );
-
-        let contract_config = get_contract_config(self.signer.chain_id(), options.neg_risk)
-            .ok_or_else(|| ClobError::InvalidParameter("invalid chain_id".to_string()))?;
-
-        // Generate salt similar to Python: round(timestamp * random())
-        // This gives a value in the range of 0 to ~current_timestamp
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs_f64();
-        let salt = U256::from((now * rand::random::<f64>()).round() as u64);
-        let taker_addr: Address = order_args.taker.parse().unwrap_or_default();
-        let token_id = U256::from_str_radix(
-            order_args
-                .token_id
-                .strip_prefix("0x")
-                .unwrap_or(&order_args.token_id),
-            if order_args.token_id.starts_with("0x") {
-                16
-            } else {
-                10
-            },
-        )
-        .map_err(|_| ClobError::InvalidParameter("invalid token_id".to_string()))?;
-
-        let domain_sep = self.domain_separator(contract_config.exchange, self.signer.chain_id());
-        let struct_hash = self.order_struct_hash(
-            salt,
-            self.funder,
-            self.signer.address(),
-            taker_addr,
-            token_id,
-            U256::from(maker_amount),
-            U256::from(taker_amount),
-            U256::from(order_args.expiration),
-            U256::from(order_args.nonce),
-            U256::from(order_args.fee_rate_bps as u64),
+        let terms = OrderFields {
+            salt: self.resolve_salt(options.salt),
+            taker: &order_args.taker,
+            token_id: &order_args.token_id,
+            maker_amount,
+            taker_amount,
+            expiration: order_args.expiration,
+            nonce: self.resolve_nonce(order_args.nonce),
+            fee_rate_bps: order_args.fee_rate_bps,
             side,
-            self.sig_type,
-        );
-
-        let mut message = Vec::with_capacity(66);
-        message.extend_from_slice(&[0x19, 0x01]);
-        message.extend_from_slice(domain_sep.as_slice());
-        message.extend_from_slice(struct_hash.as_slice());
-
-        let hash = keccak256(&message);
-        let signature = self.signer.sign_hash(hash).await?;
-
-        //TODO: there are to many to_string or even clone calls here, could be room to optimize
-        // this is in the order path
-        Ok(SignedOrder {
-            salt: salt.to_string(),
-            maker: self.funder.to_checksum(None),
-            signer: self.signer.address_string(),
-            taker: order_args.taker.clone(),
-            token_id: order_args.token_id.clone(),
-            maker_amount: maker_amount.to_string(),
-            taker_amount: taker_amount.to_string(),
-            expiration: order_args.expiration.to_string(),
-            nonce: order_args.nonce.to_string(),
-            fee_rate_bps: order_args.fee_rate_bps.to_string(),
-            side,
-            signature_type: self.sig_type,
-            signature,
-        })
+        };
+        self.sign_into(&terms, exchange.domain_separator, order)
     }
 
     pub async fn create_market_order(
@@ -424,75 +773,79 @@ impl OrderBuilder {
         order_args: &MarketOrderArgs,
         options: &CreateOrderOptions,
     ) -> Result<SignedOrder> {
+        let exchange = exchange(self.signer.chain_id(), options.neg_risk)?;
         let round_config = get_round_config(options.tick_size);
         let (side, maker_amount, taker_amount) = self.get_market_order_amounts(
             order_args.side,
             order_args.amount,
             order_args.price,
             round_config,
+            exchange.decimals,
         );
+        let terms = OrderFields {
+            salt: self.resolve_salt(options.salt),
+            taker: &order_args.taker,
+            token_id: &order_args.token_id,
+            maker_amount,
+            taker_amount,
+            expiration: 0, // market orders have no expiration
+            nonce: self.resolve_nonce(order_args.nonce),
+            fee_rate_bps: order_args.fee_rate_bps,
+            side,
+        };
+        let mut order = SignedOrder::default();
+        self.sign_into(&terms, exchange.domain_separator, &mut order)?;
+        Ok(order)
+    }
 
-        let contract_config = get_contract_config(self.signer.chain_id(), options.neg_risk)
-            .ok_or_else(|| ClobError::InvalidParameter("invalid chain_id".to_string()))?;
-
-        // Generate salt similar to Python: round(timestamp * random())
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs_f64();
-        let salt = U256::from((now * rand::random::<f64>()).round() as u64);
-        let taker_addr: Address = order_args.taker.parse().unwrap_or_default();
-        let token_id = U256::from_str_radix(
-            order_args
-                .token_id
-                .strip_prefix("0x")
-                .unwrap_or(&order_args.token_id),
-            if order_args.token_id.starts_with("0x") {
-                16
-            } else {
-                10
-            },
-        )
-        .map_err(|_| ClobError::InvalidParameter("invalid token_id".to_string()))?;
-
-        let domain_sep = self.domain_separator(contract_config.exchange, self.signer.chain_id());
-        let struct_hash = self.order_struct_hash(
-            salt,
+    fn sign_into(
+        &self,
+        terms: &OrderFields,
+        domain_sep: B256,
+        order: &mut SignedOrder,
+    ) -> Result<()> {
+        let struct_hash = order_struct_hash(
+            terms.salt,
             self.funder,
             self.signer.address(),
-            taker_addr,
-            token_id,
-            U256::from(maker_amount),
-            U256::from(taker_amount),
-            U256::from(0u64), // market orders have no expiration
-            U256::from(order_args.nonce),
-            U256::from(order_args.fee_rate_bps as u64),
-            side,
+            parse_taker(terms.taker)?,
+            parse_token_id(terms.token_id)?,
+            U256::from(terms.maker_amount),
+            U256::from(terms.taker_amount),
+            U256::from(terms.expiration),
+            U256::from(terms.nonce),
+            U256::from(terms.fee_rate_bps as u64),
+            terms.side,
             self.sig_type,
         );
-
-        let mut message = Vec::with_capacity(66);
-        message.extend_from_slice(&[0x19, 0x01]);
-        message.extend_from_slice(domain_sep.as_slice());
-        message.extend_from_slice(struct_hash.as_slice());
-
-        let hash = keccak256(&message);
-        let signature = self.signer.sign_hash(hash).await?;
-
-        Ok(SignedOrder {
-            salt: salt.to_string(),
-            maker: self.funder.to_checksum(None),
-            signer: self.signer.address_string(),
-            taker: order_args.taker.clone(),
-            token_id: order_args.token_id.clone(),
-            maker_amount: maker_amount.to_string(),
-            taker_amount: taker_amount.to_string(),
-            expiration: "0".to_string(),
-            nonce: order_args.nonce.to_string(),
-            fee_rate_bps: order_args.fee_rate_bps.to_string(),
-            side,
-            signature_type: self.sig_type,
-            signature,
-        })
+        let signature = self
+            .signer
+            .sign_hash_bytes(eip712_digest(domain_sep, struct_hash))?;
+
+        let mut signature_hex = [0u8; 2 + 65 * 2];
+        signature_hex[..2].copy_from_slice(b"0x");
+        hex::encode_to_slice(signature, &mut signature_hex[2..])
+            .expect("buffer fits a 65-byte signature");
+
+        set_field(&mut order.salt, terms.salt);
+        set_field(&mut order.maker, self.funder.to_checksum_buffer(None));
+        set_field(
+            &mut order.signer,
+            self.signer.address().to_checksum_buffer(None),
+        );
+        set_field(&mut order.taker, terms.taker);
+        set_field(&mut order.token_id, terms.token_id);
+        set_field(&mut order.maker_amount, terms.maker_amount);
+        set_field(&mut order.taker_amount, terms.taker_amount);
+        set_field(&mut order.expiration, terms.expiration);
+        set_field(&mut order.nonce, terms.nonce);
+        set_field(&mut order.fee_rate_bps, terms.fee_rate_bps);
+        order.side = terms.side;
+        order.signature_type = self.sig_type;
+        set_field(
+            &mut order.signature,
+            std::str::from_utf8(&signature_hex).expect("hex is ascii"),
+        );
+        Ok(())
     }
 }
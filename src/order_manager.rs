@@ -0,0 +1,429 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::api::OrdersApi;
+use crate::error::Result;
+use crate::fees;
+use crate::ladder::RestingOrder;
+use crate::state_store::{JsonFileStore, ManagedOrder, StateStore};
+use crate::types::{amount_to_f64, OrderArgs, OrderStatus, OrderType, PostOrderResponse, Side};
+
+// matched sizes closer than this are the same
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// What [`OrderManager::reconcile`] does with open orders it has no record of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrphanPolicy {
+    /// Track them from now on.
+    Adopt,
+    /// Cancel them, so a restart leaves no quotes the bot doesn't know about.
+    #[default]
+    Cancel,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReconcileReport {
+    /// Open on the exchange and known locally.
+    pub matched: Vec<String>,
+    pub adopted: Vec<String>,
+    pub cancelled: Vec<String>,
+    /// Known locally but no longer open, i.e. filled, cancelled or never
+    /// accepted. Dropped from the local state.
+    pub dropped: Vec<String>,
+}
+
+/// Shares of a tracked order that matched since the last update, found by
+/// [`OrderManager::poll_fills`] or [`OrderManager::apply_order_status`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillEvent {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    /// The order's limit price. A taker fill can get a better one; see
+    /// `get_trades` for exact prices.
+    pub price: f64,
+    pub size: f64,
+    /// Estimated at the token's fee rate.
+    pub fee: f64,
+}
+
+/// Orders [`OrderManager::sweep_expired`] dropped, and the outcome of
+/// posting their replacements.
+#[derive(Debug, Default)]
+pub struct ExpirySweep {
+    pub expired: Vec<ManagedOrder>,
+    /// Id of the expired order and the post of the order replacing it.
+    pub requoted: Vec<(String, Result<PostOrderResponse>)>,
+}
+
+type Requote = Box<dyn Fn(&ManagedOrder) -> Option<OrderArgs> + Send>;
+
+/// Tracks the orders a bot placed, persisted to a [`StateStore`] so a
+/// restarted process can [`reconcile`](Self::reconcile) them with the
+/// exchange.
+pub struct OrderManager {
+    orders: OrdersApi,
+    local: HashMap<String, ManagedOrder>,
+    store: Option<Box<dyn StateStore>>,
+    orphan_policy: OrphanPolicy,
+    // last seen `size_matched` per order id
+    matched: HashMap<String, f64>,
+    fills: Option<UnboundedSender<FillEvent>>,
+    requote: Option<Requote>,
+}
+
+impl OrderManager {
+    pub fn new(orders: OrdersApi) -> Self {
+        Self {
+            orders,
+            local: HashMap::new(),
+            store: None,
+            orphan_policy: OrphanPolicy::default(),
+            matched: HashMap::new(),
+            fills: None,
+            requote: None,
+        }
+    }
+
+    /// Loads the orders saved in `store` and saves every change to it.
+    pub fn with_store(mut self, store: impl StateStore + 'static) -> Result<Self> {
+        self.local = store
+            .load_orders()?
+            .into_iter()
+            .map(|order| (order.order_id.to_lowercase(), order))
+            .collect();
+        self.store = Some(Box::new(store));
+        Ok(self)
+    }
+
+    /// [`with_store`](Self::with_store) with a [`JsonFileStore`] at `path`.
+    pub fn with_state_file(self, path: impl AsRef<Path>) -> Result<Self> {
+        self.with_store(JsonFileStore::open(path)?)
+    }
+
+    pub fn with_orphan_policy(mut self, policy: OrphanPolicy) -> Self {
+        self.orphan_policy = policy;
+        self
+    }
+
+    /// Re-quotes GTD orders as [`sweep_expired`](Self::sweep_expired) drops
+    /// them: `requote` gets the expired order and returns the order to post
+    /// in its place, typically the same terms with a later expiration, or
+    /// `None` to let it go.
+    pub fn with_expiry_requote(
+        mut self,
+        requote: impl Fn(&ManagedOrder) -> Option<OrderArgs> + Send + 'static,
+    ) -> Self {
+        self.requote = Some(Box::new(requote));
+        self
+    }
+
+    /// Channel receiving every [`FillEvent`], replacing any earlier one.
+    pub fn fill_events(&mut self) -> UnboundedReceiver<FillEvent> {
+        let (tx, rx) = unbounded_channel();
+        self.fills = Some(tx);
+        rx
+    }
+
+    pub fn orders_api(&self) -> &OrdersApi {
+        &self.orders
+    }
+
+    /// Tracked orders, in no particular order.
+    pub fn orders(&self) -> impl Iterator<Item = &ManagedOrder> {
+        self.local.values()
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<&ManagedOrder> {
+        self.local.get(&order_id.to_lowercase())
+    }
+
+    /// Signs and posts an order, recording it before it is sent so a crash
+    /// mid-request still leaves a record to reconcile.
+    pub async fn place(
+        &mut self,
+        args: &OrderArgs,
+        order_type: OrderType,
+    ) -> Result<PostOrderResponse> {
+        let order = self.orders.create_order(args, None).await?;
+        let neg_risk = self
+            .orders
+            .market_data()
+            .get_neg_risk(&args.token_id)
+            .await?;
+        let chain_id = self.orders.order_builder().signer().chain_id();
+        let order_id = format!("{:#x}", order.hash(chain_id, neg_risk)?);
+
+        self.insert(ManagedOrder {
+            order_id: order_id.clone(),
+            salt: Some(order.salt.clone()),
+            token_id: args.token_id.clone(),
+            side: args.side,
+            price: args.price,
+            size: args.size,
+            order_type: Some(order_type),
+            expiration: (args.expiration > 0).then_some(args.expiration),
+        })?;
+
+        let response = self.orders.post_order(&order, order_type).await?;
+        let posted_id = response.order_id.to_lowercase();
+        if !response.success {
+            self.remove(&order_id)?;
+        } else if !posted_id.is_empty() && posted_id != order_id {
+            if let Some(mut record) = self.remove(&order_id)? {
+                record.order_id = posted_id;
+                self.insert(record)?;
+            }
+        }
+        Ok(response)
+    }
+
+    pub async fn cancel(&mut self, order_id: &str) -> Result<serde_json::Value> {
+        let response = self.orders.cancel(order_id).await?;
+        self.remove(&order_id.to_lowercase())?;
+        Ok(response)
+    }
+
+    /// Matches the account's open orders against the local state by order
+    /// id. Orders only the exchange knows are adopted or cancelled per the
+    /// [`OrphanPolicy`]; orders only the local state knows are dropped.
+    pub async fn reconcile(&mut self) -> Result<ReconcileReport> {
+        let raw = self.orders.get_orders(None).await?;
+        let open = RestingOrder::from_open_orders(&raw);
+        let expirations = open_order_expirations(&raw);
+        let mut report = ReconcileReport::default();
+        let mut orphans = Vec::new();
+
+        let open_ids: HashSet<String> = open.iter().map(|o| o.id.to_lowercase()).collect();
+        for order in open {
+            if self.local.contains_key(&order.id.to_lowercase()) {
+                report.matched.push(order.id);
+            } else {
+                orphans.push(order);
+            }
+        }
+        let mut gone: Vec<String> = self
+            .local
+            .keys()
+            .filter(|id| !open_ids.contains(*id))
+            .cloned()
+            .collect();
+        gone.sort();
+        for order_id in &gone {
+            self.remove(order_id)?;
+        }
+        report.dropped = gone;
+
+        match self.orphan_policy {
+            OrphanPolicy::Adopt => {
+                for order in orphans {
+                    report.adopted.push(order.id.clone());
+                    let expiration = expirations.get(&order.id.to_lowercase()).copied();
+                    self.insert(ManagedOrder {
+                        order_id: order.id.to_lowercase(),
+                        salt: None,
+                        token_id: order.token_id,
+                        side: order.side,
+                        price: order.price,
+                        size: order.size,
+                        order_type: None,
+                        expiration,
+                    })?;
+                }
+            }
+            OrphanPolicy::Cancel if !orphans.is_empty() => {
+                let ids: Vec<String> = orphans.into_iter().map(|o| o.id).collect();
+                self.orders.cancel_orders(&ids).await?;
+                report.cancelled = ids;
+            }
+            OrphanPolicy::Cancel => {}
+        }
+        Ok(report)
+    }
+
+    /// Fetches every tracked order and reports the shares matched since the
+    /// last poll. Orders that are no longer live stop being tracked.
+    pub async fn poll_fills(&mut self) -> Result<Vec<FillEvent>> {
+        let mut tracked: Vec<(String, String)> = self
+            .local
+            .values()
+            .map(|order| (order.order_id.clone(), order.token_id.clone()))
+            .collect();
+        tracked.sort();
+        let mut events = Vec::new();
+        for (order_id, token_id) in tracked {
+            let status = self.orders.get_order(&order_id).await?;
+            let fee_rate_bps = self
+                .orders
+                .market_data()
+                .get_fee_rate_bps(&token_id)
+                .await
+                .unwrap_or(0);
+            events.extend(self.apply_order_status(&status, fee_rate_bps)?);
+        }
+        Ok(events)
+    }
+
+    /// Updates a tracked order from `status`, e.g. from a user feed, and
+    /// returns the newly matched part, if any. The first status of an
+    /// order placed elsewhere (adopted) only sets the baseline.
+    pub fn apply_order_status(
+        &mut self,
+        status: &OrderStatus,
+        fee_rate_bps: i32,
+    ) -> Result<Option<FillEvent>> {
+        let order_id = status.id.to_lowercase();
+        let Some(order) = self.local.get(&order_id) else {
+            return Ok(None);
+        };
+        let size_matched = amount_to_f64(status.size_matched);
+        let previous = match self.matched.get(&order_id) {
+            Some(previous) => *previous,
+            None if order.salt.is_some() => 0.0,
+            None => size_matched,
+        };
+
+        let event = (size_matched - previous > SIZE_EPSILON).then(|| {
+            let size = size_matched - previous;
+            FillEvent {
+                order_id: status.id.clone(),
+                token_id: order.token_id.clone(),
+                side: order.side,
+                price: order.price,
+                size,
+                fee: fees::fee(fee_rate_bps, order.price, size),
+            }
+        });
+        self.matched.insert(order_id.clone(), size_matched);
+        if let (Some(event), Some(tx)) = (&event, &self.fills) {
+            // a dropped receiver just means nobody listens
+            let _ = tx.send(event.clone());
+        }
+        if status.status.is_terminal() {
+            self.remove(&order_id)?;
+        }
+        Ok(event)
+    }
+
+    /// When the next tracked GTD order expires, in Unix seconds.
+    pub fn next_expiration(&self) -> Option<u64> {
+        self.local
+            .values()
+            .filter_map(|order| order.expiration)
+            .min()
+    }
+
+    /// Stops tracking GTD orders whose expiration has passed on the
+    /// server's clock, since the exchange expires them without telling
+    /// anyone, and re-quotes them if [`with_expiry_requote`] is set.
+    ///
+    /// [`with_expiry_requote`]: Self::with_expiry_requote
+    pub async fn sweep_expired(&mut self) -> Result<ExpirySweep> {
+        let now = self.orders.order_builder().signer().clock().now()?;
+        let mut expired: Vec<String> = self
+            .local
+            .values()
+            .filter(|order| order.expiration.is_some_and(|at| at <= now))
+            .map(|order| order.order_id.clone())
+            .collect();
+        expired.sort();
+
+        let mut sweep = ExpirySweep::default();
+        for order_id in expired {
+            if let Some(order) = self.remove(&order_id)? {
+                sweep.expired.push(order);
+            }
+        }
+        for order in &sweep.expired {
+            let Some(args) = self.requote.as_ref().and_then(|requote| requote(order)) else {
+                continue;
+            };
+            let order_type = order.order_type.unwrap_or(OrderType::GTD);
+            let result = self.place(&args, order_type).await;
+            sweep.requoted.push((order.order_id.clone(), result));
+        }
+        Ok(sweep)
+    }
+
+    /// Runs [`sweep_expired`](Self::sweep_expired) on `manager` every
+    /// `interval` until the task is aborted or the client shuts down. Errors
+    /// are logged and retried on the next round.
+    pub fn spawn_expiry_sweeper(manager: Arc<Mutex<Self>>, interval: Duration) -> JoinHandle<()> {
+        // the client's task registry sits behind the async lock, so the task
+        // registers itself once it holds it
+        let (register, registered) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            if let Ok(handle) = registered.await {
+                let manager = manager.lock().await;
+                let tasks = manager.orders.market_data().transport().tasks();
+                tasks.track_abort_handle(handle);
+            }
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let mut manager = manager.lock().await;
+                match manager.sweep_expired().await {
+                    Ok(sweep) => {
+                        for (order_id, result) in &sweep.requoted {
+                            if let Err(e) = result {
+                                tracing::warn!(
+                                    order_id = %order_id,
+                                    error = %e,
+                                    "re-quote after expiry failed"
+                                );
+                            }
+                        }
+                    }
+                    Err(e) => tracing::warn!(error = %e, "expiry sweep failed"),
+                }
+            }
+        });
+        let _ = register.send(task.abort_handle());
+        task
+    }
+
+    fn insert(&mut self, order: ManagedOrder) -> Result<()> {
+        if let Some(store) = self.store.as_mut() {
+            store.save_order(&order)?;
+        }
+        self.local.insert(order.order_id.clone(), order);
+        Ok(())
+    }
+
+    fn remove(&mut self, order_id: &str) -> Result<Option<ManagedOrder>> {
+        if let Some(store) = self.store.as_mut() {
+            store.remove_order(order_id)?;
+        }
+        self.matched.remove(order_id);
+        Ok(self.local.remove(order_id))
+    }
+}
+
+// expiration of every open order that has one, by lowercased id
+fn open_order_expirations(open: &serde_json::Value) -> HashMap<String, u64> {
+    let orders = match open {
+        serde_json::Value::Array(orders) => orders,
+        other => match other.get("data").and_then(|d| d.as_array()) {
+            Some(orders) => orders,
+            None => return HashMap::new(),
+        },
+    };
+    orders
+        .iter()
+        .filter_map(|order| {
+            let id = order.get("id")?.as_str()?.to_lowercase();
+            let expiration = match order.get("expiration")? {
+                serde_json::Value::String(s) => s.parse().ok()?,
+                serde_json::Value::Number(n) => n.as_u64()?,
+                _ => return None,
+            };
+            (expiration > 0).then_some((id, expiration))
+        })
+        .collect()
+}
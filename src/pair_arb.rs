@@ -0,0 +1,134 @@
+use std::cmp::Ordering;
+
+use crate::types::{Market, OrderArgs, OrderBook, Side};
+
+/// A two-outcome market whose books price the pair away from $1. One YES
+/// and one NO share always pay out $1 together, so buying both below that,
+/// or selling both above it, locks in the difference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbOpportunity {
+    pub condition_id: String,
+    /// `Buy` when the asks sum below 1, `Sell` when the bids sum above 1.
+    /// Selling requires holding both tokens.
+    pub side: Side,
+    pub yes_token_id: String,
+    pub no_token_id: String,
+    /// Pairs that can trade while every pair of levels clears the edge.
+    pub size: f64,
+    /// Deepest level reached on each book, the limit price of each leg.
+    pub yes_price: f64,
+    pub no_price: f64,
+    /// Average cost (or proceeds) of one pair over `size`.
+    pub price_sum: f64,
+    /// Total profit over `size` before fees.
+    pub profit: f64,
+    /// One order per leg at its limit price.
+    pub orders: Vec<OrderArgs>,
+}
+
+/// The YES and NO tokens of a two-outcome market. Markets whose outcomes
+/// aren't labelled Yes/No are taken in listed order.
+pub fn pair_token_ids(market: &Market) -> Option<(&str, &str)> {
+    let [first, second] = market.tokens.as_slice() else {
+        return None;
+    };
+    if second.outcome.eq_ignore_ascii_case("yes") {
+        Some((&second.token_id, &first.token_id))
+    } else {
+        Some((&first.token_id, &second.token_id))
+    }
+}
+
+// (price, size) levels of the side taken by `side`, best first
+fn levels(book: &OrderBook, side: Side) -> Vec<(f64, f64)> {
+    let mut levels: Vec<(f64, f64)> = match side {
+        Side::Buy => &book.asks,
+        Side::Sell => &book.bids,
+    }
+    .iter()
+    .map(|l| (l.price_f64(), l.size_f64()))
+    .filter(|(_, size)| *size > 0.0)
+    .collect();
+    levels.sort_by(|a, b| {
+        let order = a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal);
+        match side {
+            Side::Buy => order,
+            Side::Sell => order.reverse(),
+        }
+    });
+    levels
+}
+
+fn walk(
+    yes: &OrderBook,
+    no: &OrderBook,
+    side: Side,
+    min_edge: f64,
+) -> Option<(f64, f64, f64, f64)> {
+    let (yes_levels, no_levels) = (levels(yes, side), levels(no, side));
+    let (mut i, mut j) = (0, 0);
+    let (mut yes_left, mut no_left) = (yes_levels.first()?.1, no_levels.first()?.1);
+    let (mut size, mut total, mut yes_price, mut no_price) = (0.0, 0.0, 0.0, 0.0);
+
+    while i < yes_levels.len() && j < no_levels.len() {
+        let sum = yes_levels[i].0 + no_levels[j].0;
+        let edge = match side {
+            Side::Buy => 1.0 - sum,
+            Side::Sell => sum - 1.0,
+        };
+        if edge <= min_edge {
+            break;
+        }
+        let take = yes_left.min(no_left);
+        size += take;
+        total += take * sum;
+        yes_price = yes_levels[i].0;
+        no_price = no_levels[j].0;
+        yes_left -= take;
+        no_left -= take;
+        if yes_left <= 0.0 {
+            i += 1;
+            yes_left = yes_levels.get(i).map_or(0.0, |l| l.1);
+        }
+        if no_left <= 0.0 {
+            j += 1;
+            no_left = no_levels.get(j).map_or(0.0, |l| l.1);
+        }
+    }
+    (size > 0.0).then_some((size, total / size, yes_price, no_price))
+}
+
+/// Walks both books of a market for pairs worth more than `min_edge` each.
+/// Buying is preferred when both directions qualify. `None` if no pair
+/// clears the edge.
+pub fn plan_pair_arb(
+    condition_id: &str,
+    yes_book: &OrderBook,
+    no_book: &OrderBook,
+    min_edge: f64,
+) -> Option<ArbOpportunity> {
+    let (side, (size, price_sum, yes_price, no_price)) =
+        match walk(yes_book, no_book, Side::Buy, min_edge) {
+            Some(found) => (Side::Buy, found),
+            None => (Side::Sell, walk(yes_book, no_book, Side::Sell, min_edge)?),
+        };
+    let profit = match side {
+        Side::Buy => (1.0 - price_sum) * size,
+        Side::Sell => (price_sum - 1.0) * size,
+    };
+    Some(ArbOpportunity {
+        condition_id: condition_id.to_string(),
+        side,
+        yes_token_id: yes_book.asset_id.clone(),
+        no_token_id: no_book.asset_id.clone(),
+        size,
+        yes_price,
+        no_price,
+        price_sum,
+        profit,
+        orders: vec![
+            OrderArgs::new(yes_book.asset_id.clone(), yes_price, size, side),
+            OrderArgs::new(no_book.asset_id.clone(), no_price, size, side),
+        ],
+    })
+}
@@ -0,0 +1,280 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::fees;
+use crate::state_store::StateStore;
+use crate::types::Side;
+
+/// One execution of one of our orders.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fill {
+    /// Unique per fill; a trade where several of our maker orders matched
+    /// yields one fill per order.
+    pub id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    pub size: f64,
+    /// Fee paid in collateral.
+    pub fee: f64,
+}
+
+fn number(value: &serde_json::Value, key: &str) -> Option<f64> {
+    match value.get(key)? {
+        serde_json::Value::String(s) => s.parse().ok(),
+        serde_json::Value::Number(n) => n.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+fn side(value: &serde_json::Value) -> Option<Side> {
+    match value.get("side")?.as_str()?.to_uppercase().as_str() {
+        "BUY" => Some(Side::Buy),
+        "SELL" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+fn fee(value: &serde_json::Value, price: f64, size: f64) -> f64 {
+    let rate = number(value, "fee_rate_bps").unwrap_or(0.0);
+    fees::fee(rate.round() as i32, price, size)
+}
+
+impl Fill {
+    /// Our side of each trade returned by `get_trades`, either a bare array
+    /// or a page with a `data` array. Taker trades give one fill; maker
+    /// trades give one per maker order placed by `maker_address`.
+    pub fn from_trades(value: &serde_json::Value, maker_address: &str) -> Vec<Fill> {
+        let trades = match value {
+            serde_json::Value::Array(trades) => trades,
+            other => match other.get("data").and_then(|d| d.as_array()) {
+                Some(trades) => trades,
+                None => return Vec::new(),
+            },
+        };
+
+        let mut fills = Vec::new();
+        for trade in trades {
+            let Some(trade_id) = trade.get("id").and_then(|id| id.as_str()) else {
+                continue;
+            };
+            let is_maker = trade
+                .get("trader_side")
+                .and_then(|s| s.as_str())
+                .is_some_and(|s| s.eq_ignore_ascii_case("MAKER"));
+
+            if !is_maker {
+                let fill = (|| {
+                    let price = number(trade, "price")?;
+                    let size = number(trade, "size")?;
+                    Some(Fill {
+                        id: trade_id.to_string(),
+                        token_id: trade.get("asset_id")?.as_str()?.to_string(),
+                        side: side(trade)?,
+                        price,
+                        size,
+                        fee: fee(trade, price, size),
+                    })
+                })();
+                fills.extend(fill);
+                continue;
+            }
+
+            let makers = trade.get("maker_orders").and_then(|m| m.as_array());
+            for order in makers.into_iter().flatten() {
+                let ours = order
+                    .get("maker_address")
+                    .and_then(|a| a.as_str())
+                    .is_some_and(|a| a.eq_ignore_ascii_case(maker_address));
+                if !ours {
+                    continue;
+                }
+                let fill = (|| {
+                    let price = number(order, "price")?;
+                    let size = number(order, "matched_amount")?;
+                    Some(Fill {
+                        id: format!("{}:{}", trade_id, order.get("order_id")?.as_str()?),
+                        token_id: order.get("asset_id")?.as_str()?.to_string(),
+                        side: side(order)?,
+                        price,
+                        size,
+                        fee: fee(order, price, size),
+                    })
+                })();
+                fills.extend(fill);
+            }
+        }
+        fills
+    }
+}
+
+/// Holdings in one token. `size` is negative when more was sold than bought.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Position {
+    pub size: f64,
+    /// Average entry price of the open size.
+    pub avg_cost: f64,
+    /// Closed P&L, net of fees.
+    pub realized_pnl: f64,
+    pub fees: f64,
+}
+
+impl Position {
+    fn apply(&mut self, fill: &Fill) {
+        let signed = match fill.side {
+            Side::Buy => fill.size,
+            Side::Sell => -fill.size,
+        };
+        self.fees += fill.fee;
+        self.realized_pnl -= fill.fee;
+
+        if self.size == 0.0 || self.size.signum() == signed.signum() {
+            let size = self.size + signed;
+            self.avg_cost = (self.avg_cost * self.size.abs() + fill.price * fill.size) / size.abs();
+            self.size = size;
+            return;
+        }
+
+        let closed = signed.abs().min(self.size.abs());
+        self.realized_pnl += (fill.price - self.avg_cost) * closed * self.size.signum();
+        self.size += signed;
+        if self.size.abs() < 1e-9 {
+            self.size = 0.0;
+            self.avg_cost = 0.0;
+        } else if self.size.signum() == signed.signum() {
+            // flipped through zero, the remainder opens at the fill price
+            self.avg_cost = fill.price;
+        }
+    }
+
+    pub fn unrealized_pnl(&self, mark: f64) -> f64 {
+        (mark - self.avg_cost) * self.size
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PositionSnapshot {
+    pub token_id: String,
+    pub size: f64,
+    pub avg_cost: f64,
+    /// Last midpoint, if one was set.
+    pub mark: Option<f64>,
+    pub realized_pnl: f64,
+    /// Zero until the token has a mark.
+    pub unrealized_pnl: f64,
+    pub fees: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PortfolioSnapshot {
+    /// Sorted by token id.
+    pub positions: Vec<PositionSnapshot>,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub total_pnl: f64,
+}
+
+/// Positions and P&L built from our fills, marked to midpoints.
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    positions: HashMap<String, Position>,
+    marks: HashMap<String, f64>,
+    seen: HashSet<String>,
+}
+
+impl Portfolio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `false` for a fill that was already applied, so the same
+    /// trades can be fed in repeatedly.
+    pub fn apply_fill(&mut self, fill: &Fill) -> bool {
+        if !self.seen.insert(fill.id.clone()) {
+            return false;
+        }
+        self.positions
+            .entry(fill.token_id.clone())
+            .or_default()
+            .apply(fill);
+        true
+    }
+
+    /// Rebuilds positions from the fills saved in `store`.
+    pub fn restore(store: &dyn StateStore) -> Result<Self> {
+        let mut portfolio = Self::new();
+        for fill in store.load_fills()? {
+            portfolio.apply_fill(&fill);
+        }
+        Ok(portfolio)
+    }
+
+    /// [`apply_fill`](Self::apply_fill), saving new fills to `store` first.
+    pub fn record_fill(&mut self, fill: &Fill, store: &mut dyn StateStore) -> Result<bool> {
+        if self.seen.contains(&fill.id) {
+            return Ok(false);
+        }
+        store.save_fill(fill)?;
+        Ok(self.apply_fill(fill))
+    }
+
+    /// Applies the fills in a `get_trades` response. Returns how many were
+    /// new.
+    pub fn apply_trades(&mut self, trades: &serde_json::Value, maker_address: &str) -> usize {
+        Fill::from_trades(trades, maker_address)
+            .iter()
+            .filter(|fill| self.apply_fill(fill))
+            .count()
+    }
+
+    pub fn set_mark(&mut self, token_id: impl Into<String>, mid: f64) {
+        self.marks.insert(token_id.into(), mid);
+    }
+
+    pub fn position(&self, token_id: &str) -> Option<&Position> {
+        self.positions.get(token_id)
+    }
+
+    /// Tokens with an open position.
+    pub fn open_tokens(&self) -> Vec<String> {
+        let mut tokens: Vec<String> = self
+            .positions
+            .iter()
+            .filter(|(_, p)| p.size != 0.0)
+            .map(|(token, _)| token.clone())
+            .collect();
+        tokens.sort();
+        tokens
+    }
+
+    pub fn snapshot(&self) -> PortfolioSnapshot {
+        let mut positions: Vec<PositionSnapshot> = self
+            .positions
+            .iter()
+            .map(|(token_id, p)| {
+                let mark = self.marks.get(token_id).copied();
+                PositionSnapshot {
+                    token_id: token_id.clone(),
+                    size: p.size,
+                    avg_cost: p.avg_cost,
+                    mark,
+                    realized_pnl: p.realized_pnl,
+                    unrealized_pnl: mark.map_or(0.0, |m| p.unrealized_pnl(m)),
+                    fees: p.fees,
+                }
+            })
+            .collect();
+        positions.sort_by(|a, b| a.token_id.cmp(&b.token_id));
+
+        let realized_pnl = positions.iter().map(|p| p.realized_pnl).sum();
+        let unrealized_pnl = positions.iter().map(|p| p.unrealized_pnl).sum();
+        PortfolioSnapshot {
+            positions,
+            realized_pnl,
+            unrealized_pnl,
+            total_pnl: realized_pnl + unrealized_pnl,
+        }
+    }
+}
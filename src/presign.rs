@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use crate::api::OrdersApi;
+use crate::error::{ClobError, Result};
+use crate::order_builder::SignedOrder;
+use crate::types::{OrderArgs, OrderType, PostOrderResponse, Side};
+
+// grid prices and sizes closer than this are the same point
+const EPSILON: f64 = 1e-9;
+
+// the exchange rejects GTD orders expiring within the next minute
+const GTD_MIN_LIFETIME: u64 = 60;
+
+#[derive(Debug, Clone)]
+pub struct PresignedOrder {
+    pub price: f64,
+    pub size: f64,
+    pub order: SignedOrder,
+    /// Unix seconds, on the signer's clock, after which the pool won't post
+    /// it.
+    pub expires_at: u64,
+}
+
+/// Orders for one token and side signed ahead of time over a grid of prices
+/// and sizes, so that acting on a signal costs only the request. Each signed
+/// order is posted at most once, and entries older than the TTL are dropped
+/// rather than posted; GTD orders also carry an expiration just past it, so
+/// the exchange refuses a stale one too.
+pub struct PresignedPool {
+    orders: OrdersApi,
+    token_id: String,
+    side: Side,
+    order_type: OrderType,
+    ttl: Duration,
+    entries: Vec<PresignedOrder>,
+}
+
+impl PresignedPool {
+    pub fn new(orders: OrdersApi, token_id: impl Into<String>, side: Side) -> Self {
+        Self {
+            orders,
+            token_id: token_id.into(),
+            side,
+            order_type: OrderType::GTC,
+            ttl: Duration::from_secs(300),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Defaults to GTC.
+    pub fn with_order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = order_type;
+        self
+    }
+
+    /// How long a signed order stays postable. Defaults to five minutes.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[PresignedOrder] {
+        &self.entries
+    }
+
+    /// When the next entry expires, in Unix seconds.
+    pub fn next_expiry(&self) -> Option<u64> {
+        self.entries.iter().map(|e| e.expires_at).min()
+    }
+
+    fn now(&self) -> Result<u64> {
+        self.orders.order_builder().signer().clock().now()
+    }
+
+    fn position(&self, price: f64, size: f64) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|e| (e.price - price).abs() < EPSILON && (e.size - size).abs() < EPSILON)
+    }
+
+    /// Signs one order per price and size, replacing any entry already
+    /// signed for the same point. Returns the number signed.
+    pub async fn fill(&mut self, prices: &[f64], sizes: &[f64]) -> Result<usize> {
+        let expires_at = self.now()? + self.ttl.as_secs();
+        let mut signed = 0;
+        for &price in prices {
+            for &size in sizes {
+                let mut args = OrderArgs::new(self.token_id.clone(), price, size, self.side);
+                if self.order_type == OrderType::GTD {
+                    args.expiration = expires_at + GTD_MIN_LIFETIME;
+                }
+                let order = self.orders.create_order(&args, None).await?;
+                let entry = PresignedOrder {
+                    price,
+                    size,
+                    order,
+                    expires_at,
+                };
+                match self.position(price, size) {
+                    Some(index) => self.entries[index] = entry,
+                    None => self.entries.push(entry),
+                }
+                signed += 1;
+            }
+        }
+        Ok(signed)
+    }
+
+    /// Drops entries past their expiry and returns how many went.
+    pub fn evict_expired(&mut self) -> Result<usize> {
+        let now = self.now()?;
+        let before = self.entries.len();
+        self.entries.retain(|e| e.expires_at > now);
+        Ok(before - self.entries.len())
+    }
+
+    /// Removes and returns the unexpired entry for `price` and `size`.
+    pub fn take(&mut self, price: f64, size: f64) -> Result<Option<PresignedOrder>> {
+        self.evict_expired()?;
+        Ok(self
+            .position(price, size)
+            .map(|index| self.entries.swap_remove(index)))
+    }
+
+    /// Posts the entry for `price` and `size`, which leaves the pool whether
+    /// or not the exchange accepts it.
+    pub async fn post(&mut self, price: f64, size: f64) -> Result<PostOrderResponse> {
+        let entry = self.take(price, size)?.ok_or_else(|| {
+            ClobError::InvalidParameter(format!(
+                "no pre-signed {} order at {} for {}",
+                self.side, price, size
+            ))
+        })?;
+        self.orders.post_order(&entry.order, self.order_type).await
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use futures::future::join_all;
+use futures::{stream, Stream, StreamExt};
+
+use crate::error::Result;
+use crate::market_data_service::{MarketDataService, QuoteSource};
+
+// keeps a move of exactly the threshold from failing on float error
+const EPSILON: f64 = 1e-9;
+
+/// Top of a token's book as one [`PriceWatcher`] poll saw it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceSnapshot {
+    pub token_id: String,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    /// `None` while the book is one-sided.
+    pub midpoint: Option<f64>,
+    pub source: QuoteSource,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceChange {
+    /// The snapshot last reported for the token, `None` on the first one.
+    pub previous: Option<PriceSnapshot>,
+    pub current: PriceSnapshot,
+}
+
+#[derive(Debug, Default)]
+struct Watched {
+    reported: Option<PriceSnapshot>,
+    // when the move from `reported` was first seen
+    pending_since: Option<Instant>,
+}
+
+/// Polls tokens through a [`MarketDataService`] (so from its live source
+/// when it has one, and REST otherwise) and reports a token only when its
+/// best bid, best ask or midpoint moved at least `threshold` from what was
+/// last reported, or a side appeared or emptied. With a debounce, a move has
+/// to hold for that long before it is reported, so a quote that flickers
+/// and comes back reports nothing.
+pub struct PriceWatcher {
+    service: MarketDataService,
+    tokens: Vec<String>,
+    threshold: f64,
+    debounce: Duration,
+    interval: Duration,
+    watched: HashMap<String, Watched>,
+}
+
+impl PriceWatcher {
+    pub fn new<I, T>(service: MarketDataService, token_ids: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self {
+            service,
+            tokens: token_ids.into_iter().map(Into::into).collect(),
+            threshold: 0.0,
+            debounce: Duration::ZERO,
+            interval: Duration::from_secs(1),
+            watched: HashMap::new(),
+        }
+    }
+
+    /// Smallest price move reported. Defaults to any move.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold.max(0.0);
+        self
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Pause between polls of [`Self::into_stream`]. Defaults to a second.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The snapshot last reported for `token_id`.
+    pub fn last_reported(&self, token_id: &str) -> Option<&PriceSnapshot> {
+        self.watched.get(token_id)?.reported.as_ref()
+    }
+
+    /// Fetches every token once, concurrently, and returns the changes to
+    /// report, or the error for a token that couldn't be fetched.
+    pub async fn poll(&mut self) -> Vec<Result<PriceChange>> {
+        let service = &self.service;
+        let snapshots = join_all(self.tokens.iter().map(|token_id| async move {
+            let (book, source) = service.book(token_id).await?;
+            let book = book.to_numeric()?;
+            Ok(PriceSnapshot {
+                token_id: token_id.clone(),
+                best_bid: book.best_bid().map(|level| level.price),
+                best_ask: book.best_ask().map(|level| level.price),
+                midpoint: book.midpoint(),
+                source,
+            })
+        }))
+        .await;
+
+        let now = Instant::now();
+        let mut changes = Vec::new();
+        for snapshot in snapshots {
+            match snapshot {
+                Ok(snapshot) => {
+                    if let Some(change) = self.observe(snapshot, now) {
+                        changes.push(Ok(change));
+                    }
+                }
+                Err(e) => changes.push(Err(e)),
+            }
+        }
+        changes
+    }
+
+    fn observe(&mut self, current: PriceSnapshot, now: Instant) -> Option<PriceChange> {
+        let watched = self.watched.entry(current.token_id.clone()).or_default();
+        if let Some(reported) = &watched.reported {
+            if !moved(reported, &current, self.threshold) {
+                watched.pending_since = None;
+                return None;
+            }
+            let since = *watched.pending_since.get_or_insert(now);
+            if now.duration_since(since) < self.debounce {
+                return None;
+            }
+        }
+        watched.pending_since = None;
+        let previous = watched.reported.replace(current.clone());
+        Some(PriceChange { previous, current })
+    }
+
+    /// Polls every interval, yielding changes as they are found.
+    pub fn into_stream(self) -> impl Stream<Item = Result<PriceChange>> + Send {
+        let ticker = tokio::time::interval(self.interval);
+        stream::unfold((self, ticker), |(mut watcher, mut ticker)| async move {
+            ticker.tick().await;
+            let changes = watcher.poll().await;
+            Some((stream::iter(changes), (watcher, ticker)))
+        })
+        .flatten()
+    }
+}
+
+fn moved(reported: &PriceSnapshot, current: &PriceSnapshot, threshold: f64) -> bool {
+    [
+        (reported.best_bid, current.best_bid),
+        (reported.best_ask, current.best_ask),
+        (reported.midpoint, current.midpoint),
+    ]
+    .into_iter()
+    .any(|pair| match pair {
+        (Some(before), Some(after)) => {
+            before != after && (after - before).abs() + EPSILON >= threshold
+        }
+        (before, after) => before.is_some() != after.is_some(),
+    })
+}
@@ -0,0 +1,95 @@
+//! Conversions between CLOB prices (probabilities in `(0, 1)`), betting odds
+//! and vig-free implied probabilities.
+
+use crate::types::{amount_to_f64, Market, OrderBook};
+
+fn is_probability(price: f64) -> bool {
+    price > 0.0 && price < 1.0
+}
+
+/// Decimal odds paid for a share bought at `price` (stake included).
+pub fn price_to_decimal_odds(price: f64) -> Option<f64> {
+    is_probability(price).then(|| 1.0 / price)
+}
+
+pub fn decimal_odds_to_price(odds: f64) -> Option<f64> {
+    (odds > 1.0).then(|| 1.0 / odds)
+}
+
+/// American odds: negative for favourites (`price > 0.5`), positive otherwise.
+pub fn price_to_american_odds(price: f64) -> Option<f64> {
+    if !is_probability(price) {
+        return None;
+    }
+    if price > 0.5 {
+        Some(-100.0 * price / (1.0 - price))
+    } else {
+        Some(100.0 * (1.0 - price) / price)
+    }
+}
+
+pub fn american_odds_to_price(odds: f64) -> Option<f64> {
+    if odds >= 100.0 {
+        Some(100.0 / (odds + 100.0))
+    } else if odds <= -100.0 {
+        Some(-odds / (-odds + 100.0))
+    } else {
+        None
+    }
+}
+
+/// Amount by which the outcome prices of a market sum past 1. Positive means
+/// the book carries vig, negative means the outcomes are under-priced.
+pub fn overround(prices: &[f64]) -> f64 {
+    prices.iter().sum::<f64>() - 1.0
+}
+
+/// Normalises outcome prices so they sum to 1 (proportional vig removal).
+pub fn remove_vig(prices: &[f64]) -> Option<Vec<f64>> {
+    let total: f64 = prices.iter().sum();
+    if total <= 0.0 || prices.iter().any(|p| *p < 0.0) {
+        return None;
+    }
+    Some(prices.iter().map(|p| p / total).collect())
+}
+
+/// Vig-free `(outcome, probability)` from the last prices on the market's
+/// tokens. Works for two-outcome markets as well as neg-risk outcomes
+/// flattened into one list.
+pub fn market_implied_probabilities(market: &Market) -> Option<Vec<(String, f64)>> {
+    let prices: Vec<f64> = market
+        .tokens
+        .iter()
+        .map(|t| amount_to_f64(t.price))
+        .collect();
+    let probabilities = remove_vig(&prices)?;
+    Some(
+        market
+            .tokens
+            .iter()
+            .zip(probabilities)
+            .map(|(t, p)| (t.outcome.clone(), p))
+            .collect(),
+    )
+}
+
+// levels are not guaranteed best-first, so take the minimum
+fn best_asks(books: &[OrderBook]) -> Option<Vec<f64>> {
+    books
+        .iter()
+        .map(|b| b.asks.iter().map(|l| l.price_f64()).reduce(f64::min))
+        .collect()
+}
+
+/// Probability implied by the price you would pay to buy each outcome now.
+/// Pass the YES and NO books of a binary market, or the YES book of every
+/// outcome in a neg-risk event. `None` if any book has no asks.
+pub fn book_implied_probabilities(books: &[OrderBook]) -> Option<Vec<f64>> {
+    remove_vig(&best_asks(books)?)
+}
+
+/// Overround of buying every outcome at the best ask. Negative values mean
+/// the full set costs less than the $1 it pays out.
+pub fn book_overround(books: &[OrderBook]) -> Option<f64> {
+    Some(overround(&best_asks(books)?))
+}
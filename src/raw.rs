@@ -0,0 +1,56 @@
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A decoded response together with the JSON it came from, for fields the
+/// typed structs don't model yet. Returned by the `_raw` variants of the
+/// endpoints. Serializes as the original JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raw<T> {
+    pub data: T,
+    pub json: serde_json::Value,
+}
+
+impl<T> Raw<T> {
+    pub fn new(data: T, json: serde_json::Value) -> Self {
+        Self { data, json }
+    }
+
+    /// The value at a JSON pointer such as `"/market/rewards"`.
+    pub fn pointer(&self, pointer: &str) -> Option<&serde_json::Value> {
+        self.json.pointer(pointer)
+    }
+
+    /// Decodes a field the typed struct leaves out. `None` if it is missing.
+    pub fn field<U: DeserializeOwned>(&self, name: &str) -> Option<serde_json::Result<U>> {
+        self.json
+            .get(name)
+            .map(|value| serde_json::from_value(value.clone()))
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Raw<U> {
+        Raw {
+            data: f(self.data),
+            json: self.json,
+        }
+    }
+
+    pub fn into_parts(self) -> (T, serde_json::Value) {
+        (self.data, self.json)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Raw<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = serde_json::Value::deserialize(deserializer)?;
+        // keep the failing field's path in the error, as the typed endpoints do
+        let data = serde_path_to_error::deserialize(&json)
+            .map_err(|e| D::Error::custom(format!("{}: {}", e.path(), e.inner())))?;
+        Ok(Self { data, json })
+    }
+}
+
+impl<T> Serialize for Raw<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.json.serialize(serializer)
+    }
+}
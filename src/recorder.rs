@@ -0,0 +1,393 @@
+//! Records books and trade prints to disk in the [`backtest`](crate::backtest)
+//! capture format.
+//!
+//! There is no push feed in this crate yet, so [`Recorder`] polls the REST
+//! endpoints and only writes books that changed since the previous poll.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tokio::sync::mpsc;
+
+use crate::api::MarketDataApi;
+use crate::backtest::{read_capture, write_capture_event, CaptureEvent};
+use crate::error::{ClobError, Result};
+use crate::trade_prints::{parse_side, timestamp_ms};
+use crate::types::{amount_to_f64, BookParams, MarketTradeEvent, OrderBook};
+
+enum Sink {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl Sink {
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Sink::Plain(w) => w,
+            Sink::Gzip(w) => w,
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        match self {
+            Sink::Plain(mut w) => w.flush()?,
+            Sink::Gzip(w) => w.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+
+struct OpenFile {
+    sink: Sink,
+    start_ts: u64,
+    bytes: u64,
+}
+
+/// Writes capture events to `<dir>/capture-<first ts>.jsonl[.gz]`, starting a
+/// new file once the current one passes the size or age limit. Age is measured
+/// in event time, so rotation is the same when a capture is re-written.
+pub struct CaptureWriter {
+    dir: PathBuf,
+    compress: bool,
+    rotate_bytes: Option<u64>,
+    rotate_interval: Option<Duration>,
+    current: Option<OpenFile>,
+    files: Vec<PathBuf>,
+}
+
+impl CaptureWriter {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            compress: false,
+            rotate_bytes: None,
+            rotate_interval: None,
+            current: None,
+            files: Vec::new(),
+        }
+    }
+
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Rotates after this many uncompressed bytes.
+    pub fn with_rotate_bytes(mut self, bytes: u64) -> Self {
+        self.rotate_bytes = Some(bytes);
+        self
+    }
+
+    pub fn with_rotate_interval(mut self, interval: Duration) -> Self {
+        self.rotate_interval = Some(interval);
+        self
+    }
+
+    /// Files written so far, oldest first.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    pub fn write(&mut self, event: &CaptureEvent) -> Result<()> {
+        let ts = event.ts();
+        let expired = self.current.as_ref().is_some_and(|file| {
+            self.rotate_bytes.is_some_and(|max| file.bytes >= max)
+                || self
+                    .rotate_interval
+                    .is_some_and(|max| ts.saturating_sub(file.start_ts) >= max.as_millis() as u64)
+        });
+        if expired {
+            self.close()?;
+        }
+
+        let file = match &mut self.current {
+            Some(file) => file,
+            None => self.open(ts)?,
+        };
+        let mut line = Vec::new();
+        write_capture_event(&mut line, event)?;
+        file.sink.writer().write_all(&line)?;
+        file.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    /// Flushes and closes the current file. The next write starts a new one.
+    pub fn close(&mut self) -> Result<()> {
+        match self.current.take() {
+            Some(file) => file.sink.finish(),
+            None => Ok(()),
+        }
+    }
+
+    /// Closes the current file and returns every file written.
+    pub fn finish(mut self) -> Result<Vec<PathBuf>> {
+        self.close()?;
+        Ok(std::mem::take(&mut self.files))
+    }
+
+    fn open(&mut self, start_ts: u64) -> Result<&mut OpenFile> {
+        std::fs::create_dir_all(&self.dir)?;
+        let ext = if self.compress { "jsonl.gz" } else { "jsonl" };
+        // a second file in the same millisecond gets a suffix
+        let mut path = self.dir.join(format!("capture-{}.{}", start_ts, ext));
+        let mut n = 1;
+        while path.exists() {
+            path = self.dir.join(format!("capture-{}-{}.{}", start_ts, n, ext));
+            n += 1;
+        }
+
+        let writer = BufWriter::new(File::create(&path)?);
+        let sink = if self.compress {
+            Sink::Gzip(GzEncoder::new(writer, Compression::default()))
+        } else {
+            Sink::Plain(writer)
+        };
+        self.files.push(path);
+        Ok(self.current.insert(OpenFile {
+            sink,
+            start_ts,
+            bytes: 0,
+        }))
+    }
+}
+
+impl Drop for CaptureWriter {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+/// Reads a capture file written by [`CaptureWriter`], decompressing `.gz`
+/// files.
+pub fn read_capture_file(
+    path: impl AsRef<Path>,
+) -> Result<Box<dyn Iterator<Item = Result<CaptureEvent>>>> {
+    let path = path.as_ref();
+    let file = File::open(path)?;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        Ok(Box::new(read_capture(BufReader::new(GzDecoder::new(file)))))
+    } else {
+        Ok(Box::new(read_capture(BufReader::new(file))))
+    }
+}
+
+/// What to do when the writer falls behind and the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backpressure {
+    /// Delay the next poll until the writer catches up.
+    Block,
+    /// Drop new events and count them in [`RecorderStats::dropped`].
+    DropNewest,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecorderStats {
+    pub polls: u64,
+    pub books: u64,
+    pub trades: u64,
+    pub dropped: u64,
+    /// Polls that failed; the recorder keeps going after them.
+    pub errors: u64,
+    pub files: Vec<PathBuf>,
+}
+
+pub struct Recorder {
+    market_data: MarketDataApi,
+    token_ids: Vec<String>,
+    writer: CaptureWriter,
+    poll_interval: Duration,
+    trades: bool,
+    queue_size: usize,
+    backpressure: Backpressure,
+}
+
+impl Recorder {
+    pub fn new(market_data: MarketDataApi, token_ids: Vec<String>, writer: CaptureWriter) -> Self {
+        Self {
+            market_data,
+            token_ids,
+            writer,
+            poll_interval: Duration::from_secs(1),
+            trades: true,
+            queue_size: 1024,
+            backpressure: Backpressure::Block,
+        }
+    }
+
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Also record trade prints from the markets the tokens belong to.
+    pub fn with_trades(mut self, trades: bool) -> Self {
+        self.trades = trades;
+        self
+    }
+
+    /// Events buffered between the poller and the file writer.
+    pub fn with_queue_size(mut self, size: usize) -> Self {
+        self.queue_size = size.max(1);
+        self
+    }
+
+    pub fn with_backpressure(mut self, backpressure: Backpressure) -> Self {
+        self.backpressure = backpressure;
+        self
+    }
+
+    /// Polls until `shutdown` resolves, then drains the queue and closes the
+    /// current file. Only write errors end the run early.
+    pub async fn run<F>(self, shutdown: F) -> Result<RecorderStats>
+    where
+        F: Future<Output = ()>,
+    {
+        let Recorder {
+            market_data,
+            token_ids,
+            mut writer,
+            poll_interval,
+            trades,
+            queue_size,
+            backpressure,
+        } = self;
+
+        let (tx, mut rx) = mpsc::channel::<CaptureEvent>(queue_size);
+        let write_task = tokio::task::spawn_blocking(move || -> Result<CaptureWriter> {
+            while let Some(event) = rx.blocking_recv() {
+                writer.write(&event)?;
+            }
+            Ok(writer)
+        });
+
+        let mut stats = RecorderStats::default();
+        let mut poller = Poller {
+            market_data,
+            params: token_ids
+                .iter()
+                .map(|token_id| BookParams {
+                    token_id: token_id.clone(),
+                    side: None,
+                })
+                .collect(),
+            tokens: token_ids.into_iter().collect(),
+            trades,
+            books: HashMap::new(),
+            seen_trades: HashMap::new(),
+        };
+
+        let mut interval = tokio::time::interval(poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        tokio::pin!(shutdown);
+
+        'poll: loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {}
+            }
+
+            stats.polls += 1;
+            let events = match poller.poll().await {
+                Ok(events) => events,
+                Err(_) => {
+                    stats.errors += 1;
+                    continue;
+                }
+            };
+
+            for event in events {
+                let is_book = matches!(event, CaptureEvent::Book { .. });
+                let sent = match backpressure {
+                    Backpressure::Block => match tx.send(event).await {
+                        Ok(()) => true,
+                        // the writer stopped; its error is reported below
+                        Err(_) => break 'poll,
+                    },
+                    Backpressure::DropNewest => match tx.try_send(event) {
+                        Ok(()) => true,
+                        Err(mpsc::error::TrySendError::Full(_)) => false,
+                        Err(mpsc::error::TrySendError::Closed(_)) => break 'poll,
+                    },
+                };
+                match (sent, is_book) {
+                    (false, _) => stats.dropped += 1,
+                    (true, true) => stats.books += 1,
+                    (true, false) => stats.trades += 1,
+                }
+            }
+        }
+
+        drop(tx);
+        let writer = write_task
+            .await
+            .map_err(|e| ClobError::Io(std::io::Error::other(e)))??;
+        stats.files = writer.finish()?;
+        Ok(stats)
+    }
+}
+
+struct Poller {
+    market_data: MarketDataApi,
+    params: Vec<BookParams>,
+    tokens: HashSet<String>,
+    trades: bool,
+    books: HashMap<String, OrderBook>,
+    // trade ids from the previous response, per market
+    seen_trades: HashMap<String, HashSet<String>>,
+}
+
+impl Poller {
+    async fn poll(&mut self) -> Result<Vec<CaptureEvent>> {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let mut events = Vec::new();
+
+        for book in self.market_data.get_order_books(&self.params).await? {
+            let changed = self
+                .books
+                .get(&book.asset_id)
+                .is_none_or(|prev| prev.bids != book.bids || prev.asks != book.asks);
+            if changed {
+                let ts = book.timestamp.parse().unwrap_or(now);
+                self.books.insert(book.asset_id.clone(), book.clone());
+                events.push(CaptureEvent::Book { ts, book });
+            }
+        }
+
+        if self.trades {
+            let markets: HashSet<String> = self.books.values().map(|b| b.market.clone()).collect();
+            for market in markets {
+                let prints = self.market_data.get_market_trades_events(&market).await?;
+                let ids: HashSet<String> = prints.iter().map(|t| t.id.clone()).collect();
+                // the first response is history from before the recording began
+                if let Some(seen) = self.seen_trades.insert(market, ids) {
+                    let mut new: Vec<_> = prints
+                        .into_iter()
+                        .filter(|t| !seen.contains(&t.id) && self.tokens.contains(&t.token_id))
+                        .filter_map(|t| trade_event(t, now))
+                        .collect();
+                    new.sort_by_key(|e| e.ts());
+                    events.extend(new);
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+fn trade_event(trade: MarketTradeEvent, now: u64) -> Option<CaptureEvent> {
+    Some(CaptureEvent::Trade {
+        ts: timestamp_ms(&trade.timestamp).unwrap_or(now),
+        token_id: trade.token_id,
+        side: parse_side(&trade.side)?,
+        price: amount_to_f64(trade.price),
+        size: amount_to_f64(trade.size),
+    })
+}
@@ -0,0 +1,104 @@
+use crate::api::OrdersApi;
+use crate::error::Result;
+use crate::kill_switch::canceled_ids;
+use crate::types::{OrderArgs, OrderType, PostOrderResponse};
+
+/// The order in which [`OrdersApi::replace_order_with`] cancels and posts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplaceStrategy {
+    /// Posts only once the exchange confirms the cancel, so the two orders
+    /// are never live together. The token is briefly unquoted.
+    #[default]
+    CancelThenPost,
+    /// Posts first and cancels once the new order is accepted, so the token
+    /// stays quoted. Both orders are live, and can both fill, in between.
+    PostThenCancel,
+}
+
+/// Both halves of a replace. A step that wasn't attempted because the other
+/// one failed is `None`, e.g. no post after an unconfirmed cancel.
+#[derive(Debug)]
+pub struct ReplaceResult {
+    /// The order being replaced.
+    pub order_id: String,
+    pub strategy: ReplaceStrategy,
+    /// Raw response of the cancel request.
+    pub cancel: Option<Result<serde_json::Value>>,
+    pub post: Option<Result<PostOrderResponse>>,
+}
+
+impl ReplaceResult {
+    /// The new order was accepted and the old one cancelled.
+    pub fn is_replaced(&self) -> bool {
+        let canceled =
+            matches!(&self.cancel, Some(Ok(response)) if is_canceled(response, &self.order_id));
+        let posted = matches!(&self.post, Some(Ok(response)) if response.success);
+        canceled && posted
+    }
+
+    /// Id of the new order, if it was accepted.
+    pub fn new_order_id(&self) -> Option<&str> {
+        match &self.post {
+            Some(Ok(response)) if response.success => Some(&response.order_id),
+            _ => None,
+        }
+    }
+}
+
+fn is_canceled(response: &serde_json::Value, order_id: &str) -> bool {
+    canceled_ids(response).any(|id| id == order_id)
+}
+
+impl OrdersApi {
+    /// Reprices `order_id` by replacing it with an order for `new_args`,
+    /// cancelling first. See [`Self::replace_order_with`].
+    pub async fn replace_order(
+        &self,
+        order_id: &str,
+        new_args: &OrderArgs,
+        order_type: OrderType,
+    ) -> Result<ReplaceResult> {
+        self.replace_order_with(order_id, new_args, order_type, ReplaceStrategy::default())
+            .await
+    }
+
+    /// Cancels `order_id` and posts an order for `new_args` in the order
+    /// `strategy` gives, skipping the second step when the first one fails.
+    /// The new order is signed before anything is sent, so an `Err` means
+    /// neither request went out.
+    pub async fn replace_order_with(
+        &self,
+        order_id: &str,
+        new_args: &OrderArgs,
+        order_type: OrderType,
+        strategy: ReplaceStrategy,
+    ) -> Result<ReplaceResult> {
+        let order = self.create_order(new_args, None).await?;
+        let (cancel, post) = match strategy {
+            ReplaceStrategy::CancelThenPost => {
+                let cancel = self.cancel(order_id).await;
+                let post = match &cancel {
+                    Ok(response) if is_canceled(response, order_id) => {
+                        Some(self.post_order(&order, order_type).await)
+                    }
+                    _ => None,
+                };
+                (Some(cancel), post)
+            }
+            ReplaceStrategy::PostThenCancel => {
+                let post = self.post_order(&order, order_type).await;
+                let cancel = match &post {
+                    Ok(response) if response.success => Some(self.cancel(order_id).await),
+                    _ => None,
+                };
+                (cancel, Some(post))
+            }
+        };
+        Ok(ReplaceResult {
+            order_id: order_id.to_string(),
+            strategy,
+            cancel,
+            post,
+        })
+    }
+}
@@ -0,0 +1,77 @@
+use crate::portfolio::PositionSnapshot;
+use crate::types::Market;
+
+/// What one outcome token redeems for once its condition has resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Payout {
+    pub token_id: String,
+    pub outcome: String,
+    /// USDC per share, from 0 to 1.
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    /// Still trading.
+    Open,
+    /// Closed to trading but no outcome reported yet.
+    Pending,
+    /// One payout per outcome token, in the market's token order.
+    Resolved(Vec<Payout>),
+}
+
+impl Resolution {
+    /// Reads the `winner` flags of the market's tokens. The API only sets
+    /// them in some responses, so a closed market without a flagged winner
+    /// is [`Pending`](Self::Pending) here even if it has resolved on-chain;
+    /// `OnchainClient::resolution` reads the contract instead.
+    pub fn from_market(market: &Market) -> Self {
+        if market.tokens.iter().any(|token| token.winner) {
+            let payouts = market
+                .tokens
+                .iter()
+                .map(|token| Payout {
+                    token_id: token.token_id.clone(),
+                    outcome: token.outcome.clone(),
+                    value: if token.winner { 1.0 } else { 0.0 },
+                })
+                .collect();
+            Resolution::Resolved(payouts)
+        } else if market.closed {
+            Resolution::Pending
+        } else {
+            Resolution::Open
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, Resolution::Resolved(_))
+    }
+
+    /// The outcome that pays out in full, if one does. `None` for split
+    /// (e.g. 50-50) resolutions.
+    pub fn winner(&self) -> Option<&Payout> {
+        match self {
+            Resolution::Resolved(payouts) => payouts.iter().find(|p| p.value >= 1.0),
+            _ => None,
+        }
+    }
+
+    /// USDC per share of `token_id`, once resolved.
+    pub fn payout(&self, token_id: &str) -> Option<f64> {
+        match self {
+            Resolution::Resolved(payouts) => payouts
+                .iter()
+                .find(|p| p.token_id == token_id)
+                .map(|p| p.value),
+            _ => None,
+        }
+    }
+
+    /// USDC that redeeming `position` would return: zero until the condition
+    /// resolves, or for tokens of other markets.
+    pub fn redeemable_amount(&self, position: &PositionSnapshot) -> f64 {
+        self.payout(&position.token_id)
+            .map_or(0.0, |value| position.size.max(0.0) * value)
+    }
+}
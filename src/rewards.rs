@@ -0,0 +1,96 @@
+use crate::ladder::QuoteLadder;
+use crate::types::{amount_to_f64, MarketRewards, OrderArgs, TickSize};
+
+// slack for prices that are on the boundary up to representation error
+const EPSILON: f64 = 1e-9;
+
+/// Liquidity reward terms of a market, for sizing and pricing quotes that
+/// earn rewards while taking as little risk as possible.
+///
+/// An order scores when it is at least `min_size` and within `max_spread`
+/// of the midpoint, weighted by `((max_spread - distance) / max_spread)^2`:
+/// tighter quotes earn more but get picked off more. [`Self::ladder`] takes
+/// the cautious end of that trade-off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardQuoter {
+    /// In price units, e.g. 0.03 for 3 cents.
+    pub max_spread: f64,
+    /// Smallest order that scores, in shares.
+    pub min_size: f64,
+    /// Total rewards paid per day, over all reward assets.
+    pub daily_rate: f64,
+}
+
+impl RewardQuoter {
+    /// `None` unless the rewards are active, see [`MarketRewards::is_active`].
+    pub fn from_rewards(rewards: &MarketRewards) -> Option<Self> {
+        if !rewards.is_active() {
+            return None;
+        }
+        let daily_rate = match &rewards.rates {
+            Some(serde_json::Value::Array(rates)) => rates
+                .iter()
+                .filter_map(|rate| match rate.get("rewards_daily_rate")? {
+                    serde_json::Value::String(s) => s.parse().ok(),
+                    serde_json::Value::Number(n) => n.as_f64(),
+                    _ => None,
+                })
+                .sum(),
+            _ => 0.0,
+        };
+        Some(Self {
+            max_spread: rewards.max_spread_price(),
+            min_size: amount_to_f64(rewards.min_size),
+            daily_rate,
+        })
+    }
+
+    /// Score weight per share of an order `distance` from the midpoint, from
+    /// 1 at the midpoint down to 0 at `max_spread` and beyond.
+    pub fn score(&self, distance: f64) -> f64 {
+        if self.max_spread <= 0.0 || distance > self.max_spread + EPSILON {
+            return 0.0;
+        }
+        let weight = ((self.max_spread - distance.abs()) / self.max_spread).max(0.0);
+        weight * weight
+    }
+
+    /// Whether `order` earns rewards with the book at `midpoint`.
+    pub fn qualifies(&self, order: &OrderArgs, midpoint: f64) -> bool {
+        order.size + EPSILON >= self.min_size
+            && (order.price - midpoint).abs() <= self.max_spread + EPSILON
+    }
+
+    /// Quotes `min_size` on both sides at the widest ticks that still score,
+    /// plus up to `levels - 1` more levels a tick further in each. Both
+    /// sides are quoted since markets near 0 or 1 only reward two-sided
+    /// liquidity. `None` if not even one tick fits inside `max_spread`.
+    pub fn ladder(
+        &self,
+        token_id: impl Into<String>,
+        midpoint: f64,
+        tick_size: TickSize,
+        levels: usize,
+    ) -> Option<QuoteLadder> {
+        let tick = tick_size.as_f64();
+        let ticks_per_unit = (1.0 / tick).round();
+        // widest bid and ask on the tick that stay within max_spread
+        let bid = ((midpoint - self.max_spread) * ticks_per_unit - EPSILON).ceil() / ticks_per_unit;
+        let ask =
+            ((midpoint + self.max_spread) * ticks_per_unit + EPSILON).floor() / ticks_per_unit;
+        let widest = (midpoint - bid).min(ask - midpoint);
+        // every level must stay at least one tick off the midpoint side
+        let room = ((widest + EPSILON) * ticks_per_unit).floor() as usize;
+        if room == 0 || widest <= 0.0 {
+            return None;
+        }
+        let levels = levels.clamp(1, room);
+        let half_spread = widest - (levels - 1) as f64 * tick;
+        let size = (self.min_size * 100.0 - EPSILON).ceil() / 100.0;
+        Some(
+            QuoteLadder::new(token_id, midpoint, half_spread, tick_size)
+                .with_levels(levels)
+                .with_size(size.max(0.01)),
+        )
+    }
+}
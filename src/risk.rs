@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use crate::config::COLLATERAL_DECIMALS;
+use crate::error::{ClobError, Result};
+use crate::order_builder::SignedOrder;
+use crate::types::Side;
+
+/// Limits checked locally before an order is posted. Every limit is off
+/// until set. Violations fail with [`ClobError::RiskLimit`] without sending
+/// anything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskConfig {
+    /// Largest collateral value of a single order.
+    pub max_order_notional: Option<f64>,
+    /// Largest number of shares held in one token once a buy fills. Checked
+    /// against the current conditional token balance.
+    pub max_position: Option<f64>,
+    /// Most open orders allowed before posting another.
+    pub max_open_orders: Option<usize>,
+    /// Furthest an order's price may sit from the midpoint, in price units.
+    pub max_price_deviation: Option<f64>,
+}
+
+impl RiskConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_order_notional(mut self, notional: f64) -> Self {
+        self.max_order_notional = Some(notional);
+        self
+    }
+
+    pub fn with_max_position(mut self, size: f64) -> Self {
+        self.max_position = Some(size);
+        self
+    }
+
+    pub fn with_max_open_orders(mut self, count: usize) -> Self {
+        self.max_open_orders = Some(count);
+        self
+    }
+
+    pub fn with_max_price_deviation(mut self, deviation: f64) -> Self {
+        self.max_price_deviation = Some(deviation);
+        self
+    }
+
+    pub fn check_notional(&self, order: &OrderTerms) -> Result<()> {
+        match self.max_order_notional {
+            Some(max) if order.notional > max => Err(ClobError::RiskLimit {
+                message: format!(
+                    "order notional {} exceeds the maximum of {}",
+                    order.notional, max
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Sells only shrink a position, so they always pass.
+    pub fn check_position(&self, order: &OrderTerms, held: f64) -> Result<()> {
+        let after = held + order.size;
+        match self.max_position {
+            Some(max) if order.side == Side::Buy && after > max => Err(ClobError::RiskLimit {
+                message: format!(
+                    "position in {} would reach {}, above the maximum of {}",
+                    order.token_id, after, max
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_open_orders(&self, open: usize) -> Result<()> {
+        match self.max_open_orders {
+            Some(max) if open >= max => Err(ClobError::RiskLimit {
+                message: format!("{} open orders, the maximum is {}", open, max),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_price(&self, order: &OrderTerms, midpoint: f64) -> Result<()> {
+        match self.max_price_deviation {
+            Some(max) if (order.price - midpoint).abs() > max + 1e-9 => Err(ClobError::RiskLimit {
+                message: format!(
+                    "price {} is more than {} from the midpoint {}",
+                    order.price, max, midpoint
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Catches resting orders priced through the other side of the book, like a
+/// bid above the best ask, which would trade at once instead of quoting.
+/// Violations fail with [`ClobError::RiskLimit`], or only log a `tracing`
+/// warning when `warn_only` is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadGuard {
+    /// How far past the best opposite price an order may go, in price units.
+    /// Zero still lets an order match at exactly the best price.
+    pub tolerance: f64,
+    pub warn_only: bool,
+    /// How old a cached book may be before a fresh one is fetched.
+    pub max_book_age: Duration,
+}
+
+impl Default for SpreadGuard {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.0,
+            warn_only: false,
+            max_book_age: Duration::from_secs(1),
+        }
+    }
+}
+
+impl SpreadGuard {
+    pub fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_warn_only(mut self, warn_only: bool) -> Self {
+        self.warn_only = warn_only;
+        self
+    }
+
+    pub fn with_max_book_age(mut self, max_age: Duration) -> Self {
+        self.max_book_age = max_age;
+        self
+    }
+
+    /// An empty opposite side has nothing to cross.
+    pub fn check(
+        &self,
+        order: &OrderTerms,
+        best_bid: Option<f64>,
+        best_ask: Option<f64>,
+    ) -> Result<()> {
+        let (best, crossed_by) = match order.side {
+            Side::Buy => match best_ask {
+                Some(ask) => (ask, order.price - ask),
+                None => return Ok(()),
+            },
+            Side::Sell => match best_bid {
+                Some(bid) => (bid, bid - order.price),
+                None => return Ok(()),
+            },
+        };
+        if crossed_by <= self.tolerance + 1e-9 {
+            return Ok(());
+        }
+        let opposite = match order.side {
+            Side::Buy => "ask",
+            Side::Sell => "bid",
+        };
+        Err(ClobError::RiskLimit {
+            message: format!(
+                "{} at {} crosses the best {} of {} by more than {}",
+                order.side, order.price, opposite, best, self.tolerance
+            ),
+        })
+    }
+}
+
+/// Price, size and value of a signed order, recovered from its amounts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderTerms {
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    /// Shares.
+    pub size: f64,
+    /// Collateral.
+    pub notional: f64,
+}
+
+impl OrderTerms {
+    /// Terms of an order signed for 6-decimal collateral (USDC).
+    pub fn from_order(order: &SignedOrder) -> Result<Self> {
+        Self::from_order_with_decimals(order, COLLATERAL_DECIMALS)
+    }
+
+    /// Terms of an order whose collateral has `decimals` decimals, see
+    /// [`ContractConfig::collateral_decimals`](crate::ContractConfig).
+    pub fn from_order_with_decimals(order: &SignedOrder, decimals: u32) -> Result<Self> {
+        let unit = 10f64.powi(decimals as i32);
+        let amount = |value: &str| -> Result<f64> {
+            value.parse::<u128>().map(|v| v as f64 / unit).map_err(|_| {
+                ClobError::InvalidParameter(format!("invalid order amount: {}", value))
+            })
+        };
+        let maker = amount(&order.maker_amount)?;
+        let taker = amount(&order.taker_amount)?;
+        // buys pay collateral for shares, sells the other way round
+        let (side, notional, size) = if order.side == 0 {
+            (Side::Buy, maker, taker)
+        } else {
+            (Side::Sell, taker, maker)
+        };
+        Ok(Self {
+            token_id: order.token_id.clone(),
+            side,
+            price: if size > 0.0 { notional / size } else { 0.0 },
+            size,
+            notional,
+        })
+    }
+}
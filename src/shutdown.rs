@@ -0,0 +1,108 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::task::{AbortHandle, JoinHandle};
+
+use crate::kill_switch::{KillSwitch, KillSwitchReport};
+
+// how often `drain` checks whether aborted tasks have wound down
+const DRAIN_POLL: Duration = Duration::from_millis(5);
+
+/// Background tasks started through one client, shared by its clones and
+/// services, so [`ClobClient::shutdown`](crate::ClobClient::shutdown) can
+/// stop them all.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TaskRegistry {
+    tasks: Arc<Mutex<Vec<AbortHandle>>>,
+}
+
+impl TaskRegistry {
+    pub(crate) fn track<T>(&self, handle: &JoinHandle<T>) {
+        self.track_abort_handle(handle.abort_handle());
+    }
+
+    pub(crate) fn track_abort_handle(&self, handle: AbortHandle) {
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(handle);
+    }
+
+    /// Aborts every task still running and waits up to `timeout` for them
+    /// to finish. Returns how many were stopped and whether all finished.
+    pub(crate) async fn stop_all(&self, timeout: Duration) -> (usize, bool) {
+        let tasks: Vec<AbortHandle> = std::mem::take(&mut *self.tasks.lock().unwrap())
+            .into_iter()
+            .filter(|task| !task.is_finished())
+            .collect();
+        tasks.iter().for_each(AbortHandle::abort);
+
+        let deadline = Instant::now() + timeout;
+        while !tasks.iter().all(AbortHandle::is_finished) {
+            if Instant::now() >= deadline {
+                return (tasks.len(), false);
+            }
+            tokio::time::sleep(DRAIN_POLL).await;
+        }
+        (tasks.len(), true)
+    }
+}
+
+/// What [`ClobClient::shutdown`](crate::ClobClient::shutdown) does besides
+/// stopping background tasks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShutdownPolicy {
+    /// Run the kill switch with these settings once the tasks are stopped.
+    /// `None` leaves open orders resting.
+    pub cancel_orders: Option<KillSwitch>,
+    /// Longest wait for aborted tasks to finish.
+    pub timeout: Duration,
+}
+
+impl ShutdownPolicy {
+    pub fn keep_orders() -> Self {
+        Self {
+            cancel_orders: None,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    pub fn cancel_orders() -> Self {
+        Self {
+            cancel_orders: Some(KillSwitch::default()),
+            ..Self::keep_orders()
+        }
+    }
+
+    pub fn with_kill_switch(mut self, kill_switch: KillSwitch) -> Self {
+        self.cancel_orders = Some(kill_switch);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Default for ShutdownPolicy {
+    fn default() -> Self {
+        Self::keep_orders()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownReport {
+    /// Background tasks that were still running.
+    pub tasks_stopped: usize,
+    /// `false` if some task hadn't finished by the timeout.
+    pub drained: bool,
+    /// The kill switch run, if the policy asked for one.
+    pub cancelled: Option<KillSwitchReport>,
+}
+
+impl ShutdownReport {
+    /// Every task stopped and, if asked, no orders left resting.
+    pub fn is_clean(&self) -> bool {
+        self.drained && self.cancelled.as_ref().is_none_or(|r| r.is_flat())
+    }
+}
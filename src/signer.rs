@@ -1,12 +1,17 @@
 use alloy_primitives::{Address, B256};
+#[cfg(feature = "onchain")]
 use alloy_signer::Signer as AlloySigner;
+use alloy_signer::SignerSync;
 use alloy_signer_local::PrivateKeySigner;
 
 use crate::error::{ClobError, Result};
+use crate::headers::Clock;
 
+#[derive(Clone)]
 pub struct Signer {
     inner: PrivateKeySigner,
     chain_id: u64,
+    clock: Clock,
 }
 
 impl Signer {
@@ -16,7 +21,21 @@ impl Signer {
             message: format!("invalid private key: {}", e),
         })?;
 
-        Ok(Self { inner, chain_id })
+        Ok(Self {
+            inner,
+            chain_id,
+            clock: Clock::default(),
+        })
+    }
+
+    /// Clock used for auth header timestamps. Clones of the signer share it.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
     }
 
     pub fn address(&self) -> Address {
@@ -31,15 +50,37 @@ impl Signer {
         self.chain_id
     }
 
+    pub fn set_chain_id(&mut self, chain_id: u64) {
+        self.chain_id = chain_id;
+    }
+
     pub async fn sign_hash(&self, hash: B256) -> Result<String> {
+        let sig = self.sign_hash_bytes(hash)?;
+        Ok(format!("0x{}", hex::encode(sig)))
+    }
+
+    /// 65-byte `r || s || v` signature over `hash`, signed synchronously
+    /// without allocating.
+    pub fn sign_hash_bytes(&self, hash: B256) -> Result<[u8; 65]> {
         let sig = self
             .inner
-            .sign_hash(&hash)
-            .await
+            .sign_hash_sync(&hash)
             .map_err(|e| ClobError::Signing {
                 message: format!("failed to sign: {}", e),
             })?;
+        Ok(sig.as_bytes())
+    }
 
-        Ok(format!("0x{}", hex::encode(sig.as_bytes())))
+    #[cfg(feature = "onchain")]
+    pub(crate) async fn sign_hash_raw(
+        &self,
+        hash: B256,
+    ) -> Result<alloy_primitives::PrimitiveSignature> {
+        self.inner
+            .sign_hash(&hash)
+            .await
+            .map_err(|e| ClobError::Signing {
+                message: format!("failed to sign: {}", e),
+            })
     }
 }
@@ -1,7 +1,7 @@
-use alloy_primitives::{keccak256, Address, B256, U256};
-use alloy_sol_types::sol;
+use alloy_primitives::{Address, PrimitiveSignature, U256};
+use alloy_sol_types::{eip712_domain, sol, Eip712Domain, SolStruct};
 
-use crate::error::Result;
+use crate::error::{ClobError, Result};
 use crate::signer::Signer;
 
 const CLOB_DOMAIN_NAME: &str = "ClobAuthDomain";
@@ -9,71 +9,59 @@ const CLOB_VERSION: &str = "1";
 const MSG_TO_SIGN: &str = "This message attests that I control the given wallet";
 
 sol! {
-    struct EIP712Domain {
-        string name;
-        string version;
-        uint256 chainId;
-    }
-
+    /// Message signed for L1 authentication (API key creation and derivation).
+    #[derive(Debug, PartialEq, Eq)]
     struct ClobAuth {
-        address address_;
+        address address;
         string timestamp;
         uint256 nonce;
         string message;
     }
 }
 
-fn domain_separator(chain_id: u64) -> B256 {
-    let domain_type_hash = keccak256("EIP712Domain(string name,string version,uint256 chainId)");
-
-    let name_hash = keccak256(CLOB_DOMAIN_NAME);
-    let version_hash = keccak256(CLOB_VERSION);
-    let chain_id_bytes = U256::from(chain_id);
-
-    let encoded = [
-        domain_type_hash.as_slice(),
-        name_hash.as_slice(),
-        version_hash.as_slice(),
-        &chain_id_bytes.to_be_bytes::<32>(),
-    ]
-    .concat();
-
-    keccak256(&encoded)
+/// EIP-712 domain of [`ClobAuth`] messages.
+pub fn clob_auth_domain(chain_id: u64) -> Eip712Domain {
+    eip712_domain! {
+        name: CLOB_DOMAIN_NAME,
+        version: CLOB_VERSION,
+        chain_id: chain_id,
+    }
 }
 
-fn struct_hash(address: Address, timestamp: u64, nonce: u64) -> B256 {
-    let type_hash =
-        keccak256("ClobAuth(address address,string timestamp,uint256 nonce,string message)");
-
-    let timestamp_hash = keccak256(timestamp.to_string());
-    let message_hash = keccak256(MSG_TO_SIGN);
-    let nonce_bytes = U256::from(nonce);
-
-    let mut address_padded = [0u8; 32];
-    address_padded[12..].copy_from_slice(address.as_slice());
-
-    let encoded = [
-        type_hash.as_slice(),
-        &address_padded,
-        timestamp_hash.as_slice(),
-        &nonce_bytes.to_be_bytes::<32>(),
-        message_hash.as_slice(),
-    ]
-    .concat();
-
-    keccak256(&encoded)
+/// The typed data behind [`sign_clob_auth_message`], for wallets that only
+/// sign EIP-712 payloads.
+pub fn clob_auth(address: Address, timestamp: u64, nonce: u64) -> ClobAuth {
+    ClobAuth {
+        address,
+        timestamp: timestamp.to_string(),
+        nonce: U256::from(nonce),
+        message: MSG_TO_SIGN.to_string(),
+    }
 }
 
 pub async fn sign_clob_auth_message(signer: &Signer, timestamp: u64, nonce: u64) -> Result<String> {
-    let domain_sep = domain_separator(signer.chain_id());
-    let struct_h = struct_hash(signer.address(), timestamp, nonce);
-
-    // EIP-712: "\x19\x01" + domain_separator + struct_hash
-    let mut message = Vec::with_capacity(66);
-    message.extend_from_slice(&[0x19, 0x01]);
-    message.extend_from_slice(domain_sep.as_slice());
-    message.extend_from_slice(struct_h.as_slice());
-
-    let hash = keccak256(&message);
+    let auth = clob_auth(signer.address(), timestamp, nonce);
+    let hash = auth.eip712_signing_hash(&clob_auth_domain(signer.chain_id()));
     signer.sign_hash(hash).await
 }
+
+/// [`sign_clob_auth_message`] with any alloy signer, through its
+/// `sign_typed_data`.
+pub async fn sign_clob_auth_message_with<S>(
+    signer: &S,
+    chain_id: u64,
+    timestamp: u64,
+    nonce: u64,
+) -> Result<String>
+where
+    S: alloy_signer::Signer<PrimitiveSignature> + Send + Sync,
+{
+    let auth = clob_auth(signer.address(), timestamp, nonce);
+    let signature = signer
+        .sign_typed_data(&auth, &clob_auth_domain(chain_id))
+        .await
+        .map_err(|e| ClobError::Signing {
+            message: format!("failed to sign: {}", e),
+        })?;
+    Ok(format!("0x{}", hex::encode(signature.as_bytes())))
+}
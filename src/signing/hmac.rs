@@ -1,27 +1,36 @@
 use base64::{engine::general_purpose::URL_SAFE, Engine};
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::error::{ClobError, Result};
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// `POLY_SIGNATURE` for an L2 request: URL-safe base64 HMAC-SHA256 of
+/// timestamp, method, path and body, keyed by the base64 API secret.
 pub fn build_hmac_signature(
     secret: &str,
     timestamp: u64,
     method: &str,
     request_path: &str,
     body: Option<&str>,
-) -> String {
-    let decoded_secret = URL_SAFE.decode(secret).expect("invalid base64 secret");
+) -> Result<String> {
+    let decoded_secret =
+        Zeroizing::new(URL_SAFE.decode(secret).map_err(|e| ClobError::Signing {
+            message: format!("API secret is not valid base64: {}", e),
+        })?);
 
     let mut message = format!("{}{}{}", timestamp, method, request_path);
     if let Some(b) = body {
         message.push_str(b);
     }
 
-    let mut mac =
-        HmacSha256::new_from_slice(&decoded_secret).expect("HMAC can take key of any size");
+    let mut mac = HmacSha256::new_from_slice(&decoded_secret).map_err(|e| ClobError::Signing {
+        message: e.to_string(),
+    })?;
     mac.update(message.as_bytes());
 
     let result = mac.finalize();
-    URL_SAFE.encode(result.into_bytes())
+    Ok(URL_SAFE.encode(result.into_bytes()))
 }
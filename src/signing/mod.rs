@@ -1,5 +1,5 @@
 pub mod eip712;
 pub mod hmac;
 
-pub use eip712::sign_clob_auth_message;
+pub use eip712::{sign_clob_auth_message, sign_clob_auth_message_with};
 pub use hmac::build_hmac_signature;
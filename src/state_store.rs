@@ -0,0 +1,290 @@
+//! Persistence for the orders an [`OrderManager`](crate::OrderManager)
+//! tracks and the fills a [`Portfolio`](crate::Portfolio) is built from.
+//!
+//! Positions aren't stored: they are rebuilt by replaying the fills with
+//! [`Portfolio::restore`](crate::Portfolio::restore).
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ClobError, Result};
+use crate::portfolio::Fill;
+use crate::types::{OrderType, Side};
+
+/// An order placed or adopted by an [`OrderManager`](crate::OrderManager).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManagedOrder {
+    /// Order hash, which the exchange uses as the order id.
+    pub order_id: String,
+    /// `None` for adopted orders, which weren't signed here.
+    pub salt: Option<String>,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    /// Size as placed, or the unfilled size for adopted orders.
+    pub size: f64,
+    pub order_type: Option<OrderType>,
+    /// Unix seconds a GTD order expires at, `None` if it doesn't.
+    #[serde(default)]
+    pub expiration: Option<u64>,
+}
+
+/// Storage backend for orders and fills. Writes are made as they happen, so
+/// a store should be durable once a call returns.
+pub trait StateStore: Send {
+    fn load_orders(&self) -> Result<Vec<ManagedOrder>>;
+
+    /// Inserts or replaces the order with the same id.
+    fn save_order(&mut self, order: &ManagedOrder) -> Result<()>;
+
+    fn remove_order(&mut self, order_id: &str) -> Result<()>;
+
+    /// Fills in the order they were saved.
+    fn load_fills(&self) -> Result<Vec<Fill>>;
+
+    /// Saving a fill id that is already stored is a no-op.
+    fn save_fill(&mut self, fill: &Fill) -> Result<()>;
+}
+
+/// Keeps everything in memory, for tests and dry runs.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    orders: BTreeMap<String, ManagedOrder>,
+    fills: Vec<Fill>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn load_orders(&self) -> Result<Vec<ManagedOrder>> {
+        Ok(self.orders.values().cloned().collect())
+    }
+
+    fn save_order(&mut self, order: &ManagedOrder) -> Result<()> {
+        self.orders.insert(order.order_id.clone(), order.clone());
+        Ok(())
+    }
+
+    fn remove_order(&mut self, order_id: &str) -> Result<()> {
+        self.orders.remove(order_id);
+        Ok(())
+    }
+
+    fn load_fills(&self) -> Result<Vec<Fill>> {
+        Ok(self.fills.clone())
+    }
+
+    fn save_fill(&mut self, fill: &Fill) -> Result<()> {
+        if !self.fills.iter().any(|f| f.id == fill.id) {
+            self.fills.push(fill.clone());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct JsonState {
+    #[serde(default)]
+    orders: Vec<ManagedOrder>,
+    #[serde(default)]
+    fills: Vec<Fill>,
+}
+
+/// A single JSON file, rewritten on every change. Fine for a bot's handful
+/// of resting orders; use [`SqliteStore`] for long fill histories.
+#[derive(Debug)]
+pub struct JsonFileStore {
+    path: PathBuf,
+    memory: MemoryStore,
+}
+
+impl JsonFileStore {
+    /// Opens `path`, starting empty if it doesn't exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let state: JsonState = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| ClobError::Json {
+                message: format!("invalid state file {}: {}", path.display(), e),
+            })?
+        } else {
+            JsonState::default()
+        };
+        let mut memory = MemoryStore::new();
+        for order in &state.orders {
+            memory.save_order(order)?;
+        }
+        memory.fills = state.fills;
+        Ok(Self { path, memory })
+    }
+
+    // written to a temporary file first and synced, file then directory, so
+    // a crash or power loss leaves either the old state or the new one
+    fn flush(&self) -> Result<()> {
+        let state = JsonState {
+            orders: self.memory.load_orders()?,
+            fills: self.memory.load_fills()?,
+        };
+        let json = serde_json::to_string_pretty(&state).map_err(|e| ClobError::Json {
+            message: e.to_string(),
+        })?;
+        let tmp = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+        sync_dir(&self.path)
+    }
+}
+
+// makes the rename itself durable
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+impl StateStore for JsonFileStore {
+    fn load_orders(&self) -> Result<Vec<ManagedOrder>> {
+        self.memory.load_orders()
+    }
+
+    fn save_order(&mut self, order: &ManagedOrder) -> Result<()> {
+        self.memory.save_order(order)?;
+        self.flush()
+    }
+
+    fn remove_order(&mut self, order_id: &str) -> Result<()> {
+        self.memory.remove_order(order_id)?;
+        self.flush()
+    }
+
+    fn load_fills(&self) -> Result<Vec<Fill>> {
+        self.memory.load_fills()
+    }
+
+    fn save_fill(&mut self, fill: &Fill) -> Result<()> {
+        self.memory.save_fill(fill)?;
+        self.flush()
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use std::path::Path;
+
+    use rusqlite::{params, Connection};
+
+    use super::{ManagedOrder, StateStore};
+    use crate::error::{ClobError, Result};
+    use crate::portfolio::Fill;
+
+    fn db_error(e: rusqlite::Error) -> ClobError {
+        ClobError::Io(std::io::Error::other(e))
+    }
+
+    fn json_error(e: serde_json::Error) -> ClobError {
+        ClobError::Json {
+            message: e.to_string(),
+        }
+    }
+
+    /// A SQLite database with one row per order and per fill.
+    pub struct SqliteStore {
+        conn: Connection,
+    }
+
+    impl SqliteStore {
+        /// Opens or creates the database at `path`.
+        pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+            Self::init(Connection::open(path).map_err(db_error)?)
+        }
+
+        pub fn open_in_memory() -> Result<Self> {
+            Self::init(Connection::open_in_memory().map_err(db_error)?)
+        }
+
+        fn init(conn: Connection) -> Result<Self> {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS orders (
+                     order_id TEXT PRIMARY KEY,
+                     data TEXT NOT NULL
+                 );
+                 CREATE TABLE IF NOT EXISTS fills (
+                     seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                     id TEXT NOT NULL UNIQUE,
+                     data TEXT NOT NULL
+                 );",
+            )
+            .map_err(db_error)?;
+            Ok(Self { conn })
+        }
+
+        fn load<T: serde::de::DeserializeOwned>(&self, sql: &str) -> Result<Vec<T>> {
+            let mut stmt = self.conn.prepare(sql).map_err(db_error)?;
+            let rows = stmt
+                .query_map([], |row| row.get::<_, String>(0))
+                .map_err(db_error)?;
+            rows.map(|data| serde_json::from_str(&data.map_err(db_error)?).map_err(json_error))
+                .collect()
+        }
+    }
+
+    impl StateStore for SqliteStore {
+        fn load_orders(&self) -> Result<Vec<ManagedOrder>> {
+            self.load("SELECT data FROM orders ORDER BY order_id")
+        }
+
+        fn save_order(&mut self, order: &ManagedOrder) -> Result<()> {
+            let data = serde_json::to_string(order).map_err(json_error)?;
+            self.conn
+                .execute(
+                    "INSERT OR REPLACE INTO orders (order_id, data) VALUES (?1, ?2)",
+                    params![order.order_id, data],
+                )
+                .map_err(db_error)?;
+            Ok(())
+        }
+
+        fn remove_order(&mut self, order_id: &str) -> Result<()> {
+            self.conn
+                .execute("DELETE FROM orders WHERE order_id = ?1", params![order_id])
+                .map_err(db_error)?;
+            Ok(())
+        }
+
+        fn load_fills(&self) -> Result<Vec<Fill>> {
+            self.load("SELECT data FROM fills ORDER BY seq")
+        }
+
+        fn save_fill(&mut self, fill: &Fill) -> Result<()> {
+            let data = serde_json::to_string(fill).map_err(json_error)?;
+            self.conn
+                .execute(
+                    "INSERT OR IGNORE INTO fills (id, data) VALUES (?1, ?2)",
+                    params![fill.id, data],
+                )
+                .map_err(db_error)?;
+            Ok(())
+        }
+    }
+}
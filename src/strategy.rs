@@ -0,0 +1,232 @@
+//! An optional skeleton for live bots.
+//!
+//! A [`Strategy`] reacts to book updates, fills and a timer by queueing
+//! orders and cancels on its [`Context`]; a [`StrategyRunner`] feeds it
+//! events and executes the queue through an [`OrdersApi`], so the risk,
+//! balance and accepting-orders checks configured there apply to every
+//! order. The crate has no websocket client, so events come from any
+//! [`Stream`] of [`StrategyEvent`]s the caller builds.
+
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use tokio::time::{Instant, Interval};
+
+use crate::api::OrdersApi;
+use crate::error::{ClobError, Result};
+use crate::portfolio::{Fill, Portfolio};
+use crate::types::{OrderArgs, OrderBook, OrderType, PostOrderResponse};
+
+#[derive(Debug, Clone)]
+pub enum StrategyEvent {
+    Book(OrderBook),
+    Fill(Fill),
+}
+
+/// Something the strategy asked the runner to do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Place {
+        args: OrderArgs,
+        order_type: OrderType,
+    },
+    Cancel(String),
+    CancelAll,
+}
+
+/// What the exchange answered to an [`Action`].
+#[derive(Debug, Clone)]
+pub enum ActionResult {
+    Placed(PostOrderResponse),
+    Cancelled(serde_json::Value),
+}
+
+/// Handed to every callback: read the portfolio, queue actions, stop.
+#[derive(Debug, Default)]
+pub struct Context {
+    portfolio: Portfolio,
+    actions: Vec<Action>,
+    stopped: bool,
+}
+
+impl Context {
+    /// Positions built from the fills seen so far, marked to book midpoints.
+    pub fn portfolio(&self) -> &Portfolio {
+        &self.portfolio
+    }
+
+    pub fn place(&mut self, args: OrderArgs, order_type: OrderType) {
+        self.actions.push(Action::Place { args, order_type });
+    }
+
+    pub fn cancel(&mut self, order_id: impl Into<String>) {
+        self.actions.push(Action::Cancel(order_id.into()));
+    }
+
+    pub fn cancel_all(&mut self) {
+        self.actions.push(Action::CancelAll);
+    }
+
+    /// Ends [`StrategyRunner::run`] once the queued actions are executed.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+}
+
+/// Callbacks of a live strategy. All default to doing nothing.
+pub trait Strategy: Send {
+    fn on_book_update(&mut self, _book: &OrderBook, _ctx: &mut Context) {}
+
+    /// Called once per fill id.
+    fn on_fill(&mut self, _fill: &Fill, _ctx: &mut Context) {}
+
+    fn on_timer(&mut self, _ctx: &mut Context) {}
+
+    /// Outcome of each executed action. A failed action doesn't stop the
+    /// runner.
+    fn on_action_result(
+        &mut self,
+        _action: &Action,
+        _result: &Result<ActionResult>,
+        _ctx: &mut Context,
+    ) {
+    }
+}
+
+/// Counts from one [`StrategyRunner::run`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunSummary {
+    pub events: u64,
+    pub timer_ticks: u64,
+    pub actions: u64,
+    pub failed_actions: u64,
+}
+
+pub struct StrategyRunner<S> {
+    strategy: S,
+    orders: OrdersApi,
+    timer: Option<Duration>,
+    ctx: Context,
+}
+
+impl<S: Strategy> StrategyRunner<S> {
+    pub fn new(strategy: S, orders: OrdersApi) -> Self {
+        Self {
+            strategy,
+            orders,
+            timer: None,
+            ctx: Context::default(),
+        }
+    }
+
+    /// Calls [`Strategy::on_timer`] every `interval`, starting one interval
+    /// after the run begins.
+    pub fn with_timer(mut self, interval: Duration) -> Self {
+        self.timer = Some(interval);
+        self
+    }
+
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    pub fn portfolio(&self) -> &Portfolio {
+        &self.ctx.portfolio
+    }
+
+    pub fn into_strategy(self) -> S {
+        self.strategy
+    }
+
+    /// Dispatches events until `events` ends or the strategy stops.
+    pub async fn run<E>(&mut self, events: E) -> Result<RunSummary>
+    where
+        E: Stream<Item = StrategyEvent>,
+    {
+        let mut summary = RunSummary::default();
+        let mut timer = self
+            .timer
+            .map(|period| tokio::time::interval_at(Instant::now() + period, period));
+        futures::pin_mut!(events);
+        self.ctx.stopped = false;
+
+        while !self.ctx.stopped {
+            tokio::select! {
+                event = events.next() => {
+                    let Some(event) = event else { break };
+                    summary.events += 1;
+                    self.dispatch(event);
+                }
+                _ = tick(&mut timer) => {
+                    summary.timer_ticks += 1;
+                    self.strategy.on_timer(&mut self.ctx);
+                }
+            }
+            self.execute(&mut summary).await;
+        }
+        Ok(summary)
+    }
+
+    fn dispatch(&mut self, event: StrategyEvent) {
+        match event {
+            StrategyEvent::Book(book) => {
+                if let Some(mid) = book.midpoint() {
+                    self.ctx.portfolio.set_mark(book.asset_id.clone(), mid);
+                }
+                self.strategy.on_book_update(&book, &mut self.ctx);
+            }
+            StrategyEvent::Fill(fill) => {
+                if self.ctx.portfolio.apply_fill(&fill) {
+                    self.strategy.on_fill(&fill, &mut self.ctx);
+                }
+            }
+        }
+    }
+
+    // results may queue follow-up actions, which run in the same pass
+    async fn execute(&mut self, summary: &mut RunSummary) {
+        while !self.ctx.actions.is_empty() {
+            for action in std::mem::take(&mut self.ctx.actions) {
+                let result = self.perform(&action).await;
+                summary.actions += 1;
+                if result.is_err() {
+                    summary.failed_actions += 1;
+                }
+                self.strategy
+                    .on_action_result(&action, &result, &mut self.ctx);
+            }
+        }
+    }
+
+    async fn perform(&self, action: &Action) -> Result<ActionResult> {
+        match action {
+            Action::Place { args, order_type } => {
+                let response = self
+                    .orders
+                    .create_and_post_order(args, None, *order_type)
+                    .await?;
+                if !response.success {
+                    return Err(ClobError::Api {
+                        message: response.error_msg,
+                    });
+                }
+                Ok(ActionResult::Placed(response))
+            }
+            Action::Cancel(order_id) => self
+                .orders
+                .cancel(order_id)
+                .await
+                .map(ActionResult::Cancelled),
+            Action::CancelAll => self.orders.cancel_all().await.map(ActionResult::Cancelled),
+        }
+    }
+}
+
+async fn tick(timer: &mut Option<Interval>) {
+    match timer {
+        Some(timer) => {
+            timer.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
@@ -0,0 +1,270 @@
+//! Decoding that notices schema drift: fields the response has but the
+//! struct doesn't model, and modeled fields the response left out. Runs the
+//! normal `Deserialize` impls over a parsed `serde_json::Value`, recording
+//! what each struct expected (serde hands over the field names) against the
+//! keys present.
+//!
+//! Structs with `#[serde(flatten)]` and enum contents aren't checked, and an
+//! `Option` or defaulted field the server omits counts as missing.
+
+use std::cell::RefCell;
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value};
+
+/// What [`Transport::with_strict_decoding`](crate::Transport::with_strict_decoding)
+/// does about drift in a response that still decodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrictDecoding {
+    /// A `tracing` warning per response.
+    Log,
+    /// Fail the request with [`ClobError::SchemaDrift`](crate::ClobError::SchemaDrift).
+    Fail,
+}
+
+/// Paths into a response body, like `data[0].rewards.max_spread`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDrift {
+    pub unknown: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+impl SchemaDrift {
+    pub fn is_empty(&self) -> bool {
+        self.unknown.is_empty() && self.missing.is_empty()
+    }
+}
+
+// serde lists each alias as a field of its own, so these group the names of
+// one field, per struct: the field is there if any of them is
+const ALIASES: &[(&str, &[&[&str]])] = &[(
+    "ApiCreds",
+    &[
+        &["apiKey", "api_key"],
+        &["secret", "api_secret"],
+        &["passphrase", "api_passphrase"],
+    ],
+)];
+
+fn field_names(name: &str, field: &str) -> Option<&'static [&'static str]> {
+    ALIASES
+        .iter()
+        .filter(|(struct_name, _)| *struct_name == name)
+        .flat_map(|(_, groups)| groups.iter())
+        .find(|names| names.contains(&field))
+        .copied()
+}
+
+type DecodeResult<T> = Result<T, serde_path_to_error::Error<serde_json::Error>>;
+
+pub(crate) fn decode<T: DeserializeOwned>(value: &Value) -> (DecodeResult<T>, SchemaDrift) {
+    let drift = RefCell::new(SchemaDrift::default());
+    let result = serde_path_to_error::deserialize(Strict {
+        value,
+        path: String::new(),
+        drift: &drift,
+    });
+    let mut drift = drift.into_inner();
+    drift.unknown.sort();
+    drift.missing.sort();
+    (result, drift)
+}
+
+struct Strict<'de, 'r> {
+    value: &'de Value,
+    path: String,
+    drift: &'r RefCell<SchemaDrift>,
+}
+
+impl<'de, 'r> Strict<'de, 'r> {
+    fn field(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        }
+    }
+
+    fn check_fields(&self, map: &Map<String, Value>, name: &str, fields: &[&str]) {
+        let mut drift = self.drift.borrow_mut();
+        for key in map.keys() {
+            if !fields.contains(&key.as_str()) {
+                drift.unknown.push(self.field(key));
+            }
+        }
+        for field in fields {
+            let (present, reported) = match field_names(name, field) {
+                // a field missing under every name is reported once
+                Some(names) => (
+                    names.iter().any(|n| map.contains_key(*n)),
+                    names[0] == *field,
+                ),
+                None => (map.contains_key(*field), true),
+            };
+            if !present && reported {
+                drift.missing.push(self.field(field));
+            }
+        }
+    }
+
+    fn visit_object<V: Visitor<'de>>(
+        self,
+        map: &'de Map<String, Value>,
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error> {
+        visitor.visit_map(StrictMap {
+            entries: map.iter(),
+            pending: None,
+            parent: self,
+        })
+    }
+
+    fn visit_array<V: Visitor<'de>>(
+        self,
+        items: &'de [Value],
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error> {
+        visitor.visit_seq(StrictSeq {
+            items: items.iter().enumerate(),
+            parent: self,
+        })
+    }
+}
+
+impl<'de, 'r> Deserializer<'de> for Strict<'de, 'r> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(map) => self.visit_object(map, visitor),
+            Value::Array(items) => self.visit_array(items, visitor),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Array(items) => self.visit_array(items, visitor),
+            value => value.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(map) => self.visit_object(map, visitor),
+            value => value.deserialize_map(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(map) => {
+                self.check_fields(map, name, fields);
+                self.visit_object(map, visitor)
+            }
+            value => value.deserialize_struct(name, fields, visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct identifier ignored_any
+    }
+}
+
+struct StrictMap<'de, 'r, I> {
+    entries: I,
+    pending: Option<(&'de String, &'de Value)>,
+    parent: Strict<'de, 'r>,
+}
+
+impl<'de, 'r, I> MapAccess<'de> for StrictMap<'de, 'r, I>
+where
+    I: Iterator<Item = (&'de String, &'de Value)>,
+{
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.pending = Some((key, value));
+        seed.deserialize(BorrowedStrDeserializer::new(key.as_str()))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .pending
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(Strict {
+            value,
+            path: self.parent.field(key),
+            drift: self.parent.drift,
+        })
+    }
+}
+
+struct StrictSeq<'de, 'r, I> {
+    items: I,
+    parent: Strict<'de, 'r>,
+}
+
+impl<'de, 'r, I> SeqAccess<'de> for StrictSeq<'de, 'r, I>
+where
+    I: Iterator<Item = (usize, &'de Value)>,
+{
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some((index, value)) = self.items.next() else {
+            return Ok(None);
+        };
+        seed.deserialize(Strict {
+            value,
+            path: format!("{}[{}]", self.parent.path, index),
+            drift: self.parent.drift,
+        })
+        .map(Some)
+    }
+}
@@ -0,0 +1,139 @@
+use crate::error::{ClobError, Result};
+use crate::order_builder::SignedOrder;
+use crate::types::{OrderBook, PostOrderResponse, Side};
+
+/// How much of the book a sweep is allowed to consume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SweepLimit {
+    /// Take at most this many price levels.
+    Levels(usize),
+    /// Take at most this many shares.
+    Size(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepPlan {
+    pub side: Side,
+    /// Worst price reached; used as the limit price of the FAK order.
+    pub price: f64,
+    pub size: f64,
+    pub levels: usize,
+    /// Expected volume weighted average price if every planned level fills.
+    pub expected_avg_price: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SweepResult {
+    pub plan: SweepPlan,
+    pub order: SignedOrder,
+    pub response: PostOrderResponse,
+    /// Realized average price derived from the matched amounts, if anything filled.
+    pub avg_price: Option<f64>,
+}
+
+impl SweepResult {
+    #[cfg(feature = "http")]
+    pub(crate) fn new(plan: SweepPlan, order: SignedOrder, response: PostOrderResponse) -> Self {
+        let making: f64 = response.making_amount.parse().unwrap_or(0.0);
+        let taking: f64 = response.taking_amount.parse().unwrap_or(0.0);
+
+        // BUY makes collateral and takes shares, SELL the opposite. Both amounts
+        // share the same unit so the ratio is the price either way.
+        let (usdc, shares) = match plan.side {
+            Side::Buy => (making, taking),
+            Side::Sell => (taking, making),
+        };
+        let avg_price = if usdc > 0.0 && shares > 0.0 {
+            Some(usdc / shares)
+        } else {
+            None
+        };
+
+        Self {
+            plan,
+            order,
+            response,
+            avg_price,
+        }
+    }
+}
+
+pub(crate) fn check_limit(limit: SweepLimit) -> Result<()> {
+    let positive = match limit {
+        SweepLimit::Levels(max) => max > 0,
+        SweepLimit::Size(max) => max > 0.0,
+    };
+    if positive {
+        Ok(())
+    } else {
+        Err(ClobError::InvalidParameter(
+            "sweep limit must be positive".to_string(),
+        ))
+    }
+}
+
+/// Walks the opposite side of `book` from the best price outward, stopping at
+/// `limit` or the first level beyond `limit_price`. Returns `None` when no level
+/// is marketable within the limit price, and an error if `limit` is not positive.
+pub fn plan_sweep(
+    book: &OrderBook,
+    side: Side,
+    limit: SweepLimit,
+    limit_price: f64,
+) -> Result<Option<SweepPlan>> {
+    check_limit(limit)?;
+
+    // the API does not guarantee level ordering so sort best-first here
+    let mut levels: Vec<(f64, f64)> = match side {
+        Side::Buy => &book.asks,
+        Side::Sell => &book.bids,
+    }
+    .iter()
+    .map(|l| (l.price_f64(), l.size_f64()))
+    .filter(|(_, size)| *size > 0.0)
+    .collect();
+
+    match side {
+        Side::Buy => levels.sort_by(|a, b| a.0.total_cmp(&b.0)),
+        Side::Sell => levels.sort_by(|a, b| b.0.total_cmp(&a.0)),
+    }
+
+    let mut price = 0.0;
+    let mut size = 0.0;
+    let mut notional = 0.0;
+    let mut taken = 0;
+
+    for (level_price, level_size) in levels {
+        let within_limit = match side {
+            Side::Buy => level_price <= limit_price,
+            Side::Sell => level_price >= limit_price,
+        };
+        if !within_limit {
+            break;
+        }
+
+        let take = match limit {
+            SweepLimit::Levels(max) if taken >= max => break,
+            SweepLimit::Levels(_) => level_size,
+            SweepLimit::Size(max) if size >= max => break,
+            SweepLimit::Size(max) => level_size.min(max - size),
+        };
+
+        price = level_price;
+        size += take;
+        notional += take * level_price;
+        taken += 1;
+    }
+
+    if taken == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(SweepPlan {
+        side,
+        price,
+        size,
+        levels: taken,
+        expected_avg_price: notional / size,
+    }))
+}
@@ -0,0 +1,239 @@
+//! Public trade prints from the market channel's `last_trade_price` messages.
+//!
+//! There is no websocket client in this crate, so [`TradePrints::subscribe`]
+//! takes the channel's text frames from whatever connection the caller runs
+//! and turns them into a typed stream, optionally preceded by a REST backfill.
+
+#[cfg(feature = "http")]
+use std::collections::HashSet;
+
+#[cfg(feature = "http")]
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "http")]
+use crate::api::MarketDataApi;
+use crate::error::{ClobError, Result};
+use crate::types::{amount_to_f64, MarketTradeEvent, Side};
+
+const LAST_TRADE_PRICE_EVENT: &str = "last_trade_price";
+
+/// One public trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradePrint {
+    pub token_id: String,
+    pub price: f64,
+    pub size: f64,
+    /// Taker side.
+    pub side: Side,
+    /// Unix milliseconds.
+    pub timestamp: u64,
+}
+
+#[derive(Deserialize)]
+struct LastTradePriceMessage {
+    #[serde(default)]
+    asset_id: String,
+    #[serde(default)]
+    price: String,
+    #[serde(default)]
+    size: String,
+    #[serde(default)]
+    side: String,
+    #[serde(default)]
+    timestamp: String,
+}
+
+impl TradePrint {
+    /// Prints in one market channel frame, which holds a single message or
+    /// an array of them. Other event types and `PONG` heartbeats yield none.
+    pub fn parse_market_message(frame: &str) -> Result<Vec<TradePrint>> {
+        let frame = frame.trim();
+        if frame.is_empty() || frame == "PONG" {
+            return Ok(Vec::new());
+        }
+        let value: serde_json::Value =
+            serde_json::from_str(frame).map_err(|e| ClobError::Json {
+                message: format!("invalid market channel frame: {}", e),
+            })?;
+        let messages = match value {
+            serde_json::Value::Array(messages) => messages,
+            message => vec![message],
+        };
+
+        let mut prints = Vec::new();
+        for message in messages {
+            let is_trade =
+                message.get("event_type").and_then(|t| t.as_str()) == Some(LAST_TRADE_PRICE_EVENT);
+            if !is_trade {
+                continue;
+            }
+            let message: LastTradePriceMessage =
+                serde_json::from_value(message).map_err(|e| ClobError::Json {
+                    message: format!("invalid {} message: {}", LAST_TRADE_PRICE_EVENT, e),
+                })?;
+            prints.push(message.try_into()?);
+        }
+        Ok(prints)
+    }
+
+    /// `None` for events that are not a buy or sell with a valid timestamp.
+    pub fn from_trade_event(event: &MarketTradeEvent) -> Option<TradePrint> {
+        Some(TradePrint {
+            token_id: event.token_id.clone(),
+            price: amount_to_f64(event.price),
+            size: amount_to_f64(event.size),
+            side: parse_side(&event.side)?,
+            timestamp: timestamp_ms(&event.timestamp)?,
+        })
+    }
+}
+
+impl TryFrom<LastTradePriceMessage> for TradePrint {
+    type Error = ClobError;
+
+    fn try_from(message: LastTradePriceMessage) -> Result<Self> {
+        let invalid = |field: &str, value: &str| ClobError::Json {
+            message: format!("invalid {} {:?} in trade print", field, value),
+        };
+        Ok(TradePrint {
+            price: message
+                .price
+                .parse()
+                .map_err(|_| invalid("price", &message.price))?,
+            size: message
+                .size
+                .parse()
+                .map_err(|_| invalid("size", &message.size))?,
+            side: parse_side(&message.side).ok_or_else(|| invalid("side", &message.side))?,
+            timestamp: timestamp_ms(&message.timestamp)
+                .ok_or_else(|| invalid("timestamp", &message.timestamp))?,
+            token_id: message.asset_id,
+        })
+    }
+}
+
+pub(crate) fn parse_side(side: &str) -> Option<Side> {
+    match side.to_uppercase().as_str() {
+        "BUY" => Some(Side::Buy),
+        "SELL" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+/// Unix milliseconds from a timestamp in seconds or milliseconds. The REST
+/// trade events use seconds, the market channel milliseconds.
+pub(crate) fn timestamp_ms(timestamp: &str) -> Option<u64> {
+    match timestamp.parse::<u64>().ok()? {
+        ts if ts < 1_000_000_000_000 => Some(ts * 1000),
+        ts => Some(ts),
+    }
+}
+
+/// Builds a per-token stream of [`TradePrint`]s from market channel frames.
+#[cfg(feature = "http")]
+#[derive(Clone)]
+pub struct TradePrints {
+    market_data: MarketDataApi,
+    token_ids: HashSet<String>,
+    backfill: Option<String>,
+}
+
+#[cfg(feature = "http")]
+impl TradePrints {
+    pub fn new<I, T>(market_data: MarketDataApi, token_ids: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self {
+            market_data,
+            token_ids: token_ids.into_iter().map(Into::into).collect(),
+            backfill: None,
+        }
+    }
+
+    /// On subscribe, first yield the recent prints of `condition_id` from
+    /// `get_market_trades_events`, oldest first.
+    pub fn with_backfill(mut self, condition_id: impl Into<String>) -> Self {
+        self.backfill = Some(condition_id.into());
+        self
+    }
+
+    /// Prints for the tracked tokens, in arrival order. Subscribe to the
+    /// channel before calling this so no print falls between the backfill
+    /// and the first frame; live prints identical to a backfilled one are
+    /// dropped. A failed backfill or unparsable frame yields an error item
+    /// and the stream carries on.
+    pub fn subscribe<S>(self, frames: S) -> impl Stream<Item = Result<TradePrint>> + Send
+    where
+        S: Stream<Item = String> + Send + 'static,
+    {
+        let Self {
+            market_data,
+            token_ids,
+            backfill,
+        } = self;
+
+        stream::once(async move {
+            let backfill = match backfill {
+                Some(condition_id) => market_data.get_market_trades_events(&condition_id).await,
+                None => Ok(Vec::new()),
+            };
+            let (backfilled, seen) = match backfill {
+                Ok(events) => {
+                    let mut prints: Vec<TradePrint> = events
+                        .iter()
+                        .filter(|e| token_ids.contains(&e.token_id))
+                        .filter_map(TradePrint::from_trade_event)
+                        .collect();
+                    prints.sort_by_key(|p| p.timestamp);
+                    let seen: HashSet<PrintKey> = prints.iter().map(PrintKey::of).collect();
+                    (prints.into_iter().map(Ok).collect(), seen)
+                }
+                Err(e) => (vec![Err(e)], HashSet::new()),
+            };
+
+            let live = frames.flat_map(move |frame| {
+                let items: Vec<Result<TradePrint>> = match TradePrint::parse_market_message(&frame)
+                {
+                    Ok(prints) => prints
+                        .into_iter()
+                        .filter(|p| token_ids.contains(&p.token_id))
+                        .filter(|p| !seen.contains(&PrintKey::of(p)))
+                        .map(Ok)
+                        .collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                stream::iter(items)
+            });
+            stream::iter(backfilled).chain(live)
+        })
+        .flatten()
+    }
+}
+
+// REST timestamps only have second resolution, so backfilled and live
+// copies of a print are matched to the second.
+#[cfg(feature = "http")]
+#[derive(PartialEq, Eq, Hash)]
+struct PrintKey {
+    token_id: String,
+    side: Side,
+    price: u64,
+    size: u64,
+    second: u64,
+}
+
+#[cfg(feature = "http")]
+impl PrintKey {
+    fn of(print: &TradePrint) -> Self {
+        Self {
+            token_id: print.token_id.clone(),
+            side: print.side,
+            price: print.price.to_bits(),
+            size: print.size.to_bits(),
+            second: print.timestamp / 1000,
+        }
+    }
+}
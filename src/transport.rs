@@ -0,0 +1,687 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::future::join_all;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{
+    Certificate, Client, Identity, Method, Proxy, Request, RequestBuilder, Response, StatusCode,
+    Url,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::circuit_breaker::{Admission, CircuitBreaker};
+use crate::endpoints;
+use crate::error::{ClobError, Result};
+use crate::fixtures::{Cassette, Interaction};
+use crate::headers::{
+    Clock, L1Headers, L2Headers, POLY_ADDRESS, POLY_API_KEY, POLY_NONCE, POLY_PASSPHRASE,
+    POLY_SIGNATURE, POLY_TIMESTAMP,
+};
+use crate::latency::LatencyTracker;
+use crate::shutdown::TaskRegistry;
+use crate::strict::{self, StrictDecoding};
+
+/// Shared HTTP layer used by every API service. Cloning is cheap and clones
+/// share the same connection pool.
+#[derive(Debug, Clone)]
+pub struct Transport {
+    // primary first, then fallbacks
+    hosts: Arc<Vec<String>>,
+    // index of the host requests go to, shared by clones
+    active: Arc<AtomicUsize>,
+    http: Client,
+    options: HttpOptions,
+    clock: Option<Clock>,
+    cassette: Option<Cassette>,
+    breaker: Option<CircuitBreaker>,
+    latency: Option<LatencyTracker>,
+    strict: Option<StrictDecoding>,
+    tasks: TaskRegistry,
+}
+
+// kept so every builder call can rebuild the client with all of them
+#[derive(Debug, Clone, Default)]
+struct HttpOptions {
+    proxy: Option<Proxy>,
+    root_certificates: Vec<Certificate>,
+    identity: Option<Identity>,
+    user_agent: Option<HeaderValue>,
+    headers: HeaderMap,
+}
+
+const DEFAULT_USER_AGENT: &str = "clob-rs";
+
+// keeps idle pooled connections from being dropped by NATs and proxies
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+
+impl HttpOptions {
+    fn build(&self) -> Result<Client> {
+        let user_agent = self
+            .user_agent
+            .clone()
+            .unwrap_or(HeaderValue::from_static(DEFAULT_USER_AGENT));
+        // market scans are large JSON; advertise gzip/br and decode transparently
+        let mut builder = Client::builder()
+            .gzip(true)
+            .brotli(true)
+            .tcp_keepalive(TCP_KEEPALIVE)
+            .user_agent(user_agent)
+            .default_headers(self.headers.clone());
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        for cert in &self.root_certificates {
+            builder = builder.add_root_certificate(cert.clone());
+        }
+        if let Some(identity) = &self.identity {
+            builder = builder.identity(identity.clone());
+        }
+        Ok(builder.build()?)
+    }
+}
+
+impl Transport {
+    pub fn new(host: impl Into<String>) -> Self {
+        let options = HttpOptions::default();
+        Self {
+            hosts: Arc::new(vec![normalize_host(host.into())]),
+            active: Arc::new(AtomicUsize::new(0)),
+            http: options.build().expect("failed to build HTTP client"),
+            options,
+            clock: None,
+            cassette: None,
+            breaker: None,
+            latency: None,
+            strict: None,
+            tasks: TaskRegistry::default(),
+        }
+    }
+
+    /// Routes every request through `proxy_url`: `http://`, `https://`,
+    /// `socks5://` or `socks5h://` (DNS resolved by the proxy). Credentials
+    /// can go in the url as `user:pass@`.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self> {
+        let proxy = Proxy::all(proxy_url)
+            .map_err(|e| ClobError::InvalidParameter(format!("invalid proxy url: {}", e)))?;
+        self.options.proxy = Some(proxy);
+        self.rebuild()
+    }
+
+    /// Trusts the PEM certificate(s) in `pem` on top of the system roots,
+    /// e.g. for a TLS-intercepting egress proxy.
+    pub fn with_root_certificate_pem(mut self, pem: &[u8]) -> Result<Self> {
+        let certs = Certificate::from_pem_bundle(pem)
+            .ok()
+            .filter(|certs| !certs.is_empty())
+            .ok_or_else(|| ClobError::InvalidParameter("invalid root certificate".to_string()))?;
+        self.options.root_certificates.extend(certs);
+        self.rebuild()
+    }
+
+    /// Presents a client certificate from a PEM certificate chain and its
+    /// PKCS#8 PEM private key.
+    pub fn with_client_identity_pem(mut self, cert_pem: &[u8], key_pem: &[u8]) -> Result<Self> {
+        let identity = Identity::from_pkcs8_pem(cert_pem, key_pem).map_err(|e| {
+            ClobError::InvalidParameter(format!("invalid client certificate: {}", e))
+        })?;
+        self.options.identity = Some(identity);
+        self.rebuild()
+    }
+
+    /// Presents a client certificate from a PKCS#12 archive.
+    pub fn with_client_identity_pkcs12(mut self, der: &[u8], password: &str) -> Result<Self> {
+        let identity = Identity::from_pkcs12_der(der, password).map_err(|e| {
+            ClobError::InvalidParameter(format!("invalid client certificate: {}", e))
+        })?;
+        self.options.identity = Some(identity);
+        self.rebuild()
+    }
+
+    /// Replaces the default `clob-rs` User-Agent.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Result<Self> {
+        let value = HeaderValue::from_str(user_agent)
+            .map_err(|e| ClobError::InvalidParameter(format!("invalid user agent: {}", e)))?;
+        self.options.user_agent = Some(value);
+        self.rebuild()
+    }
+
+    /// Sends `name: value` on every request, e.g. an operator tag for traffic
+    /// attribution. Setting a name again replaces its value.
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<Self> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| ClobError::InvalidParameter(format!("invalid header name: {}", e)))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| ClobError::InvalidParameter(format!("invalid header value: {}", e)))?;
+        self.options.headers.insert(name, value);
+        self.rebuild()
+    }
+
+    fn rebuild(mut self) -> Result<Self> {
+        self.http = self.options.build()?;
+        Ok(self)
+    }
+
+    /// Re-syncs `clock` with the server whenever an authenticated request is
+    /// rejected over its timestamp. The rejected request is not retried.
+    pub fn with_clock_sync(mut self, clock: Clock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Records responses to `cassette` or replays them from it, see
+    /// [`fixtures`](crate::fixtures).
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Guards every request but cancels (`DELETE`) with `breaker`, see
+    /// [`CircuitBreaker`].
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// Times every request into `tracker`, see [`LatencyTracker`].
+    pub fn with_latency_tracker(mut self, tracker: LatencyTracker) -> Self {
+        self.latency = Some(tracker);
+        self
+    }
+
+    pub fn latency_tracker(&self) -> Option<&LatencyTracker> {
+        self.latency.as_ref()
+    }
+
+    pub(crate) fn tasks(&self) -> &TaskRegistry {
+        &self.tasks
+    }
+
+    /// Checks every response for fields the crate doesn't model and modeled
+    /// fields that are missing, see [`StrictDecoding`]. Meant for canaries
+    /// that catch upstream API changes; it costs an extra pass per response.
+    pub fn with_strict_decoding(mut self, mode: StrictDecoding) -> Self {
+        self.strict = Some(mode);
+        self
+    }
+
+    /// Backup hosts, tried in order when the current one is unreachable or
+    /// answers GETs with server errors. Selection is sticky: requests stay on
+    /// the host that last worked until it fails too. Only GETs are resent
+    /// after a server error or timeout, since a POST or DELETE may already
+    /// have taken effect; those fail, and the next request goes elsewhere.
+    pub fn with_fallback_hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut all = vec![self.hosts[0].clone()];
+        all.extend(hosts.into_iter().map(|host| normalize_host(host.into())));
+        self.hosts = Arc::new(all);
+        self.active = Arc::new(AtomicUsize::new(0));
+        self
+    }
+
+    /// The host requests currently go to.
+    pub fn host(&self) -> &str {
+        &self.hosts[self.active.load(Ordering::Relaxed) % self.hosts.len()]
+    }
+
+    /// The primary host followed by the fallbacks.
+    pub fn hosts(&self) -> &[String] {
+        &self.hosts
+    }
+
+    /// Opens `connections` pooled connections to the host, TLS handshake
+    /// included, by fetching `/time` on all of them at once. Returns the
+    /// slowest round trip.
+    pub async fn warm_up(&self, connections: usize) -> Result<Duration> {
+        let requests = (0..connections.max(1)).map(|_| async {
+            let started = Instant::now();
+            self.get::<serde_json::Value>(endpoints::TIME)
+                .await
+                .map(|_| started.elapsed())
+        });
+        let mut slowest = Duration::ZERO;
+        for elapsed in join_all(requests).await {
+            slowest = slowest.max(elapsed?);
+        }
+        Ok(slowest)
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = format!("{}{}", self.host(), path);
+        self.http
+            .request(method, url)
+            .header("Accept", "application/json")
+    }
+
+    fn request_with_l1_headers(
+        &self,
+        method: Method,
+        path: &str,
+        headers: &L1Headers,
+    ) -> RequestBuilder {
+        self.request(method, path)
+            .header(POLY_ADDRESS, &headers.address)
+            .header(POLY_SIGNATURE, &headers.signature)
+            .header(POLY_TIMESTAMP, &headers.timestamp)
+            .header(POLY_NONCE, &headers.nonce)
+    }
+
+    fn request_with_l2_headers(
+        &self,
+        method: Method,
+        path: &str,
+        headers: &L2Headers,
+    ) -> RequestBuilder {
+        self.request(method, path)
+            .header(POLY_ADDRESS, &headers.address)
+            .header(POLY_SIGNATURE, &headers.signature)
+            .header(POLY_TIMESTAMP, &headers.timestamp)
+            .header(POLY_API_KEY, &headers.api_key)
+            .header(POLY_PASSPHRASE, &headers.passphrase)
+    }
+
+    pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send(self.request(Method::GET, path)).await
+    }
+
+    /// GET with `query` url-encoded onto `path`.
+    pub(crate) async fn get_with_query<T: DeserializeOwned, Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<T> {
+        self.send(self.request(Method::GET, path).query(query))
+            .await
+    }
+
+    pub(crate) async fn post<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        let request = self
+            .request(Method::POST, path)
+            .header("Content-Type", "application/json")
+            .json(body);
+        self.send(request).await
+    }
+
+    pub(crate) async fn get_with_l1_headers<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        headers: &L1Headers,
+    ) -> Result<T> {
+        self.send_authenticated(self.request_with_l1_headers(Method::GET, path, headers))
+            .await
+    }
+
+    pub(crate) async fn post_with_l1_headers<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        headers: &L1Headers,
+    ) -> Result<T> {
+        self.send_authenticated(self.request_with_l1_headers(Method::POST, path, headers))
+            .await
+    }
+
+    pub(crate) async fn get_with_l2_headers<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        headers: &L2Headers,
+    ) -> Result<T> {
+        self.send_authenticated(self.request_with_l2_headers(Method::GET, path, headers))
+            .await
+    }
+
+    pub(crate) async fn get_with_l2_headers_and_query<
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    >(
+        &self,
+        path: &str,
+        headers: &L2Headers,
+        query: &Q,
+    ) -> Result<T> {
+        let request = self
+            .request_with_l2_headers(Method::GET, path, headers)
+            .query(query);
+        self.send_authenticated(request).await
+    }
+
+    pub(crate) async fn post_with_l2_headers<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        headers: &L2Headers,
+        body: &B,
+    ) -> Result<T> {
+        let request = self
+            .request_with_l2_headers(Method::POST, path, headers)
+            .header("Content-Type", "application/json")
+            .json(body);
+        self.send_authenticated(request).await
+    }
+
+    pub(crate) async fn delete_with_l2_headers<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        headers: &L2Headers,
+    ) -> Result<T> {
+        self.send_authenticated(self.request_with_l2_headers(Method::DELETE, path, headers))
+            .await
+    }
+
+    pub(crate) async fn delete_with_l2_headers_and_body<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        headers: &L2Headers,
+        body: &B,
+    ) -> Result<T> {
+        let request = self
+            .request_with_l2_headers(Method::DELETE, path, headers)
+            .header("Content-Type", "application/json")
+            .json(body);
+        self.send_authenticated(request).await
+    }
+
+    async fn send_authenticated<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let result = self.send(request).await;
+        if let (Err(e), Some(clock)) = (&result, &self.clock) {
+            if is_timestamp_rejection(e) {
+                let _ = clock.sync(self).await;
+            }
+        }
+        result
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let request = request.build()?;
+        let method = request.method().clone();
+        let path = match request.url().query() {
+            Some(query) => format!("{}?{}", request.url().path(), query),
+            None => request.url().path().to_string(),
+        };
+        let (status, headers, body) = match &self.cassette {
+            Some(cassette) => {
+                self.send_with_cassette(cassette, request, &method, &path)
+                    .await?
+            }
+            None => self.execute(request).await?,
+        };
+
+        if !status.is_success() {
+            return Err(error_for_status(
+                status,
+                &headers,
+                &String::from_utf8_lossy(&body),
+            ));
+        }
+
+        let endpoint = || format!("{} {}", method, path);
+        let decode_error = |field: String, e: serde_json::Error| {
+            let text = String::from_utf8_lossy(&body);
+            if is_cloudflare_challenge(&HeaderMap::new(), &text) {
+                return ClobError::Blocked {
+                    message: "Cloudflare challenge page returned instead of JSON".to_string(),
+                };
+            }
+            ClobError::Decode {
+                endpoint: endpoint(),
+                status: status.as_u16(),
+                path: field,
+                message: e.to_string(),
+                snippet: snippet(&text).to_string(),
+            }
+        };
+
+        let Some(mode) = self.strict else {
+            let mut de = serde_json::Deserializer::from_slice(&body);
+            let value = serde_path_to_error::deserialize(&mut de)
+                .map_err(|e| (e.path().to_string(), e.into_inner()))
+                .and_then(|value| de.end().map(|_| value).map_err(|e| (".".to_string(), e)));
+            return value.map_err(|(field, e)| decode_error(field, e));
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&body).map_err(|e| decode_error(".".to_string(), e))?;
+        let (value, drift) = strict::decode(&json);
+        let value = value.map_err(|e| decode_error(e.path().to_string(), e.into_inner()))?;
+        if !drift.is_empty() {
+            match mode {
+                StrictDecoding::Log => tracing::warn!(
+                    endpoint = %endpoint(),
+                    unknown = ?drift.unknown,
+                    missing = ?drift.missing,
+                    "response doesn't match the modeled schema"
+                ),
+                StrictDecoding::Fail => {
+                    return Err(ClobError::SchemaDrift {
+                        endpoint: endpoint(),
+                        unknown: drift.unknown,
+                        missing: drift.missing,
+                    })
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    async fn send_with_cassette(
+        &self,
+        cassette: &Cassette,
+        request: Request,
+        method: &Method,
+        path: &str,
+    ) -> Result<(StatusCode, HeaderMap, Vec<u8>)> {
+        if !cassette.is_recording() {
+            let recorded = cassette.play(method.as_str(), path)?;
+            let status = StatusCode::from_u16(recorded.status).map_err(|_| {
+                ClobError::InvalidParameter(format!("invalid recorded status {}", recorded.status))
+            })?;
+            return Ok((status, HeaderMap::new(), recorded.body.into_bytes()));
+        }
+
+        let (status, headers, body) = self.execute(request).await?;
+        cassette.save(Interaction {
+            method: method.to_string(),
+            path: path.to_string(),
+            status: status.as_u16(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })?;
+        Ok((status, headers, body))
+    }
+
+    async fn execute(&self, request: Request) -> Result<(StatusCode, HeaderMap, Vec<u8>)> {
+        let Some(tracker) = &self.latency else {
+            return self.execute_guarded(request).await;
+        };
+        let method = request.method().clone();
+        let path = request.url().path().to_string();
+        let started = Instant::now();
+        let result = self.execute_guarded(request).await;
+        if !matches!(result, Err(ClobError::CircuitOpen { .. })) {
+            tracker.record(method.as_str(), &path, started.elapsed());
+        }
+        result
+    }
+
+    async fn execute_guarded(&self, request: Request) -> Result<(StatusCode, HeaderMap, Vec<u8>)> {
+        // cancels skip the breaker: an outage is exactly when they must go out
+        let breaker = self
+            .breaker
+            .as_ref()
+            .filter(|_| request.method() != Method::DELETE);
+        let Some(breaker) = breaker else {
+            return read_response(self.dispatch(request).await?).await;
+        };
+        if let Admission::Probe = breaker.admit()? {
+            // the same health check as `get_ok`
+            let probe = self.request(Method::GET, "/").build()?;
+            let started = Instant::now();
+            let response = self.dispatch(probe).await;
+            let healthy = matches!(&response, Ok(r) if r.status().is_success());
+            breaker.record(healthy, started.elapsed());
+            breaker.admit()?;
+        }
+        let started = Instant::now();
+        let response = self.dispatch(request).await;
+        let healthy = matches!(&response, Ok(r) if !r.status().is_server_error());
+        breaker.record(healthy, started.elapsed());
+        read_response(response?).await
+    }
+
+    // sends `request`, failing over to the next host where that is safe
+    async fn dispatch(&self, mut request: Request) -> reqwest::Result<Response> {
+        let Some(mut index) = self.host_index(request.url()) else {
+            return self.http.execute(request).await;
+        };
+        let idempotent = request.method() == Method::GET;
+        for _ in 1..self.hosts.len() {
+            let retry = request.try_clone();
+            let result = self.http.execute(request).await;
+            let (unhealthy, resend) = match &result {
+                Ok(response) => {
+                    let failed = response.status().is_server_error();
+                    (failed, failed && idempotent)
+                }
+                Err(e) if e.is_connect() => (true, true),
+                Err(e) if e.is_timeout() => (true, idempotent),
+                Err(_) => (false, false),
+            };
+            if !unhealthy {
+                return result;
+            }
+            let next = (index + 1) % self.hosts.len();
+            // leave it alone if a concurrent request already moved on
+            let _ = self
+                .active
+                .compare_exchange(index, next, Ordering::Relaxed, Ordering::Relaxed);
+            match retry.filter(|_| resend) {
+                Some(mut retry) => {
+                    *retry.url_mut() = rebase(retry.url(), &self.hosts[index], &self.hosts[next]);
+                    request = retry;
+                    index = next;
+                }
+                None => return result,
+            }
+        }
+        self.http.execute(request).await
+    }
+
+    fn host_index(&self, url: &Url) -> Option<usize> {
+        if self.hosts.len() < 2 {
+            return None;
+        }
+        self.hosts
+            .iter()
+            .position(|host| url.as_str().starts_with(host_prefix(host).as_str()))
+    }
+}
+
+fn normalize_host(host: String) -> String {
+    match host.strip_suffix('/') {
+        Some(host) => host.to_string(),
+        None => host,
+    }
+}
+
+/// `base` (an endpoint ending in `/`) with `segment` appended as one
+/// percent-encoded path segment, so an id can't add segments or a query.
+pub(crate) fn join_segment(base: &str, segment: &str) -> String {
+    let mut url = Url::parse("http://localhost").expect("valid url");
+    url.set_path(base);
+    url.path_segments_mut()
+        .expect("http urls have a path")
+        .pop_if_empty()
+        .push(segment);
+    url.path().to_string()
+}
+
+// `host` as it appears at the start of a parsed url
+fn host_prefix(host: &str) -> String {
+    Url::parse(host)
+        .map(|url| url.as_str().trim_end_matches('/').to_string())
+        .unwrap_or_else(|_| host.to_string())
+}
+
+fn rebase(url: &Url, from: &str, to: &str) -> Url {
+    let rest = &url.as_str()[host_prefix(from).len()..];
+    Url::parse(&format!("{}{}", to, rest)).unwrap_or_else(|_| url.clone())
+}
+
+// the raw body; `text()` would copy it into a `String` only for the decoder
+// to read it back
+async fn read_response(response: Response) -> Result<(StatusCode, HeaderMap, Vec<u8>)> {
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = if status.is_success() {
+        response.bytes().await?
+    } else {
+        response.bytes().await.unwrap_or_default()
+    };
+    Ok((status, headers, body.into()))
+}
+
+const GEOBLOCK_MARKERS: [&str; 2] = ["restricted in your region", "geoblock"];
+const BODY_SNIPPET_LEN: usize = 200;
+
+fn is_cloudflare_challenge(headers: &HeaderMap, body: &str) -> bool {
+    if headers.contains_key("cf-mitigated") {
+        return true;
+    }
+
+    let is_html = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.contains("text/html"))
+        .unwrap_or(false)
+        || body.trim_start().starts_with('<');
+    let body = body.to_ascii_lowercase();
+
+    is_html && (body.contains("cloudflare") || body.contains("attention required"))
+}
+
+// the server answers a stale POLY_TIMESTAMP with a 401, sometimes naming it
+fn is_timestamp_rejection(error: &ClobError) -> bool {
+    match error {
+        ClobError::Api { message } | ClobError::Forbidden { message } => {
+            message.starts_with("HTTP 401") || message.to_ascii_lowercase().contains("timestamp")
+        }
+        _ => false,
+    }
+}
+
+fn snippet(body: &str) -> &str {
+    match body.char_indices().nth(BODY_SNIPPET_LEN) {
+        Some((idx, _)) => &body[..idx],
+        None => body,
+    }
+}
+
+fn error_for_status(status: StatusCode, headers: &HeaderMap, body: &str) -> ClobError {
+    if is_cloudflare_challenge(headers, body) {
+        return ClobError::Blocked {
+            message: format!("Cloudflare challenge (HTTP {})", status),
+        };
+    }
+
+    if status == StatusCode::FORBIDDEN {
+        let lower = body.to_ascii_lowercase();
+        if GEOBLOCK_MARKERS.iter().any(|m| lower.contains(m)) {
+            return ClobError::Blocked {
+                message: format!("HTTP {}: {}", status, snippet(body)),
+            };
+        }
+        return ClobError::Forbidden {
+            message: format!("HTTP {}: {}", status, snippet(body)),
+        };
+    }
+
+    // TODO: some better error handling would be nice instead of plain string
+    ClobError::Api {
+        message: format!("HTTP {}: {}", status, body),
+    }
+}
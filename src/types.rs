@@ -1,11 +1,35 @@
+use alloy_primitives::Address;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
 
-fn deserialize_string_to_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+use crate::secret::SecretString;
+use crate::units::{Price, Size};
+
+/// Prices, sizes and other money-like fields of the response types: `f64`,
+/// or `rust_decimal::Decimal` with the `decimal` feature.
+#[cfg(not(feature = "decimal"))]
+pub type Amount = f64;
+#[cfg(feature = "decimal")]
+pub type Amount = rust_decimal::Decimal;
+
+/// An [`Amount`] as `f64`, for the float math done internally.
+#[cfg(not(feature = "decimal"))]
+pub fn amount_to_f64(value: Amount) -> f64 {
+    value
+}
+
+/// An [`Amount`] as `f64`, for the float math done internally.
+#[cfg(feature = "decimal")]
+pub fn amount_to_f64(value: Amount) -> f64 {
+    rust_decimal::prelude::ToPrimitive::to_f64(&value).unwrap_or(0.0)
+}
+
+fn deserialize_string_to_amount<'de, D>(deserializer: D) -> Result<Amount, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: String = Deserialize::deserialize(deserializer)?;
-    s.parse::<f64>().map_err(serde::de::Error::custom)
+    s.parse::<Amount>().map_err(serde::de::Error::custom)
 }
 
 #[allow(dead_code)]
@@ -31,14 +55,51 @@ where
 
 pub const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
 
+/// L2 API credentials. The secret and passphrase are redacted from `Debug`
+/// and wiped from memory on drop.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiCreds {
-    #[serde(rename = "apiKey")]
+    #[serde(rename = "apiKey", alias = "api_key")]
     pub api_key: String,
-    #[serde(rename = "secret")]
-    pub api_secret: String,
-    #[serde(rename = "passphrase")]
-    pub api_passphrase: String,
+    #[serde(rename = "secret", alias = "api_secret")]
+    pub api_secret: SecretString,
+    #[serde(rename = "passphrase", alias = "api_passphrase")]
+    pub api_passphrase: SecretString,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeysResponse {
+    #[serde(
+        rename = "apiKeys",
+        default,
+        deserialize_with = "deserialize_null_to_empty_vec"
+    )]
+    pub api_keys: Vec<String>,
+}
+
+/// Result of deleting an API key. The endpoint answers with a bare `"OK"`
+/// string; anything else is kept in `message`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeleteApiKeyResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+impl<'de> Deserialize<'de> for DeleteApiKeyResponse {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let message = match &value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        Ok(Self {
+            success: message.eq_ignore_ascii_case("ok"),
+            message,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -47,6 +108,7 @@ pub enum OrderType {
     GTC,
     FOK,
     GTD,
+    FAK,
 }
 
 impl std::fmt::Display for OrderType {
@@ -55,11 +117,12 @@ impl std::fmt::Display for OrderType {
             OrderType::GTC => write!(f, "GTC"),
             OrderType::FOK => write!(f, "FOK"),
             OrderType::GTD => write!(f, "GTD"),
+            OrderType::FAK => write!(f, "FAK"),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct OrderArgs {
     pub token_id: String,
     pub price: f64,
@@ -84,11 +147,145 @@ impl OrderArgs {
             taker: ZERO_ADDRESS.to_string(),
         }
     }
+
+    /// Restricts the order to one counterparty.
+    pub fn with_taker(mut self, taker: Address) -> Self {
+        self.taker = taker.to_checksum(None);
+        self
+    }
+
+    /// Like [`new`](Self::new), from a price and size already checked
+    /// against the market's tick and the exchange's size precision.
+    pub fn from_units(token_id: impl Into<String>, price: Price, size: Size, side: Side) -> Self {
+        Self::new(token_id, price.value(), size.value(), side)
+    }
+
+    /// The price on the grid of `tick_size`, failing if it's off the tick.
+    pub fn checked_price(&self, tick_size: TickSize) -> crate::Result<Price> {
+        Price::new(self.price, tick_size)
+    }
+
+    pub fn builder(token_id: impl Into<String>) -> OrderArgsBuilder {
+        OrderArgsBuilder::new(token_id)
+    }
+
+    pub fn round_up_to_min_size(mut self, min_size: f64) -> Self {
+        self.size = round_up_to_min_size(self.size, min_size);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderArgsBuilder {
+    token_id: String,
+    price: Option<f64>,
+    size: Option<f64>,
+    side: Option<Side>,
+    fee_rate_bps: i32,
+    nonce: u64,
+    expiration: u64,
+    taker: String,
+}
+
+impl OrderArgsBuilder {
+    pub fn new(token_id: impl Into<String>) -> Self {
+        Self {
+            token_id: token_id.into(),
+            price: None,
+            size: None,
+            side: None,
+            fee_rate_bps: 0,
+            nonce: 0,
+            expiration: 0,
+            taker: ZERO_ADDRESS.to_string(),
+        }
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn side(mut self, side: Side) -> Self {
+        self.side = Some(side);
+        self
+    }
+
+    pub fn fee_rate_bps(mut self, fee_rate_bps: i32) -> Self {
+        self.fee_rate_bps = fee_rate_bps;
+        self
+    }
+
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn expiration(mut self, expiration: u64) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
+    pub fn taker(mut self, taker: impl Into<String>) -> Self {
+        self.taker = taker.into();
+        self
+    }
+
+    pub fn taker_address(mut self, taker: Address) -> Self {
+        self.taker = taker.to_checksum(None);
+        self
+    }
+
+    pub fn build(self) -> crate::Result<OrderArgs> {
+        let invalid = |msg: &str| crate::ClobError::InvalidParameter(msg.to_string());
+
+        if self.token_id.is_empty() {
+            return Err(invalid("token_id is required"));
+        }
+        let price = self.price.ok_or_else(|| invalid("price is required"))?;
+        let size = self.size.ok_or_else(|| invalid("size is required"))?;
+        let side = self.side.ok_or_else(|| invalid("side is required"))?;
+
+        if !(price > 0.0 && price < 1.0) {
+            return Err(invalid("price must be between 0 and 1 exclusive"));
+        }
+        if !(size > 0.0 && size.is_finite()) {
+            return Err(invalid("size must be positive"));
+        }
+        if self.fee_rate_bps < 0 {
+            return Err(invalid("fee_rate_bps must not be negative"));
+        }
+        if self.taker.parse::<Address>().is_err() {
+            return Err(invalid("invalid taker address"));
+        }
+
+        Ok(OrderArgs {
+            token_id: self.token_id,
+            price,
+            size,
+            side,
+            fee_rate_bps: self.fee_rate_bps,
+            nonce: self.nonce,
+            expiration: self.expiration,
+            taker: self.taker,
+        })
+    }
+}
+
+pub fn round_up_to_min_size(size: f64, min_size: f64) -> f64 {
+    size.max(min_size)
 }
 
 #[derive(Debug, Clone)]
 pub struct MarketOrderArgs {
     pub token_id: String,
+    /// USDC to spend on a buy, shares to sell on a sell. See
+    /// [`buy_usdc`](Self::buy_usdc) and [`sell_shares`](Self::sell_shares).
     pub amount: f64,
     pub side: Side,
     pub price: f64,
@@ -111,9 +308,59 @@ impl MarketOrderArgs {
             order_type: OrderType::FOK,
         }
     }
+
+    /// Restricts the order to one counterparty.
+    pub fn with_taker(mut self, taker: Address) -> Self {
+        self.taker = taker.to_checksum(None);
+        self
+    }
+
+    /// A buy that spends `usdc` dollars.
+    pub fn buy_usdc(token_id: impl Into<String>, usdc: f64) -> crate::Result<Self> {
+        check_market_amount(usdc, "USDC amount")?;
+        Ok(Self::new(token_id, usdc, Side::Buy))
+    }
+
+    /// A sell of `shares` outcome tokens.
+    pub fn sell_shares(token_id: impl Into<String>, shares: f64) -> crate::Result<Self> {
+        check_market_amount(shares, "share amount")?;
+        Ok(Self::new(token_id, shares, Side::Sell))
+    }
+
+    /// Worst price to accept. Without one, `OrdersApi::create_market_order`
+    /// uses the price that fills the order on the current book.
+    pub fn with_price(mut self, price: f64) -> Self {
+        self.price = price;
+        self
+    }
+
+    pub fn with_order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = order_type;
+        self
+    }
+
+    /// What this order would get from `book` right now. `amount` is read as
+    /// USDC for buys and shares for sells.
+    pub fn quote(&self, book: &NumericOrderBook) -> Option<MarketQuote> {
+        match self.side {
+            Side::Buy => book.quote_buy_usdc(self.amount),
+            Side::Sell => book.quote_sell_shares(self.amount),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+fn check_market_amount(amount: f64, what: &str) -> crate::Result<()> {
+    if amount > 0.0 && amount.is_finite() {
+        Ok(())
+    } else {
+        Err(crate::ClobError::InvalidParameter(format!(
+            "{} must be positive, got {}",
+            what, amount
+        )))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct TradeParams {
     pub id: Option<String>,
     pub maker_address: Option<String>,
@@ -123,7 +370,7 @@ pub struct TradeParams {
     pub after: Option<u64>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct OpenOrderParams {
     pub id: Option<String>,
     pub market: Option<String>,
@@ -145,7 +392,7 @@ impl std::fmt::Display for AssetType {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct BalanceAllowanceParams {
     pub asset_type: Option<AssetType>,
     pub token_id: Option<String>,
@@ -166,6 +413,21 @@ impl BalanceAllowanceResponse {
     pub fn balance_usdc(&self) -> f64 {
         self.balance_raw() as f64 / 1_000_000.0
     }
+
+    /// Allowance granted to `spender`, in raw units. Unlimited approvals
+    /// (`2^256 - 1`) saturate to `u128::MAX`.
+    pub fn allowance_raw(&self, spender: &str) -> Option<u128> {
+        let (_, value) = self
+            .allowances
+            .iter()
+            .find(|(address, _)| address.eq_ignore_ascii_case(spender))?;
+        let digits = !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+        Some(match value.parse() {
+            Ok(raw) => raw,
+            Err(_) if digits => u128::MAX,
+            Err(_) => 0,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -187,12 +449,16 @@ pub struct DropNotificationParams {
 pub struct CreateOrderOptions {
     pub tick_size: TickSize,
     pub neg_risk: bool,
+    /// Fixed salt, e.g. to re-sign the exact same order on retry. Falls back
+    /// to the order builder's salt provider.
+    pub salt: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct PartialCreateOrderOptions {
     pub tick_size: Option<TickSize>,
     pub neg_risk: Option<bool>,
+    pub salt: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -202,13 +468,16 @@ pub struct RoundConfig {
     pub amount: u32,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ContractConfig {
     pub exchange: &'static str,
     pub collateral: &'static str,
     pub conditional_tokens: &'static str,
+    /// Decimals of the collateral token, which outcome tokens share.
+    pub collateral_decimals: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrderSummary {
     pub price: String,
     pub size: String,
@@ -222,9 +491,18 @@ impl OrderSummary {
     pub fn size_f64(&self) -> f64 {
         self.size.parse().unwrap_or(0.0)
     }
+
+    /// Exact with the `decimal` feature. `None` if the price is malformed.
+    pub fn price_amount(&self) -> Option<Amount> {
+        self.price.parse().ok()
+    }
+
+    pub fn size_amount(&self) -> Option<Amount> {
+        self.size.parse().ok()
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OrderBook {
     pub market: String,
     pub asset_id: String,
@@ -264,24 +542,265 @@ impl OrderBook {
             _ => None,
         }
     }
+
+    pub fn min_order_size_f64(&self) -> Option<f64> {
+        self.min_order_size.as_deref().and_then(|s| s.parse().ok())
+    }
+
+    /// `(price, size)` levels of one side, best price first regardless of the
+    /// order the API returned them in.
+    fn sorted_levels(&self, bids: bool) -> Vec<(f64, f64)> {
+        let side = if bids { &self.bids } else { &self.asks };
+        let mut levels: Vec<(f64, f64)> =
+            side.iter().map(|l| (l.price_f64(), l.size_f64())).collect();
+        if bids {
+            levels.sort_by(|a, b| b.0.total_cmp(&a.0));
+        } else {
+            levels.sort_by(|a, b| a.0.total_cmp(&b.0));
+        }
+        levels
+    }
+
+    fn liquidity_within(&self, bids: bool, bps: f64) -> Option<f64> {
+        let best_bid = self.sorted_levels(true).first()?.0;
+        let best_ask = self.sorted_levels(false).first()?.0;
+        let mid = (best_bid + best_ask) / 2.0;
+        let band = mid * bps / 10_000.0;
+        Some(
+            self.sorted_levels(bids)
+                .into_iter()
+                .take_while(|(price, _)| (price - mid).abs() <= band + f64::EPSILON)
+                .map(|(_, size)| size)
+                .sum(),
+        )
+    }
+
+    /// Shares bid within `bps` basis points of the midpoint.
+    pub fn bid_liquidity_within(&self, bps: f64) -> Option<f64> {
+        self.liquidity_within(true, bps)
+    }
+
+    /// Shares offered within `bps` basis points of the midpoint.
+    pub fn ask_liquidity_within(&self, bps: f64) -> Option<f64> {
+        self.liquidity_within(false, bps)
+    }
+
+    /// Average fill price for a market order of `size` shares (a buy walks
+    /// the asks, a sell the bids). `None` if the book is too thin.
+    pub fn vwap_for_size(&self, side: Side, size: f64) -> Option<f64> {
+        if size <= 0.0 {
+            return None;
+        }
+
+        let mut remaining = size;
+        let mut notional = 0.0;
+        for (price, level_size) in self.sorted_levels(side == Side::Sell) {
+            let fill = remaining.min(level_size);
+            notional += fill * price;
+            remaining -= fill;
+            if remaining <= 0.0 {
+                return Some(notional / size);
+            }
+        }
+        None
+    }
+
+    /// `(bids - asks) / (bids + asks)` over total resting size, in `[-1, 1]`.
+    /// Positive values mean more size is bid than offered.
+    pub fn imbalance(&self) -> Option<f64> {
+        let bids: f64 = self.bids.iter().map(|l| l.size_f64()).sum();
+        let asks: f64 = self.asks.iter().map(|l| l.size_f64()).sum();
+        let total = bids + asks;
+        (total > 0.0).then(|| (bids - asks) / total)
+    }
+
+    /// Running totals of size per side, best price first.
+    pub fn cumulative_depth(&self) -> CumulativeDepth {
+        let accumulate = |levels: Vec<(f64, f64)>| {
+            let mut total = 0.0;
+            levels
+                .into_iter()
+                .map(|(price, size)| {
+                    total += size;
+                    (price, total)
+                })
+                .collect()
+        };
+
+        CumulativeDepth {
+            bids: accumulate(self.sorted_levels(true)),
+            asks: accumulate(self.sorted_levels(false)),
+        }
+    }
+}
+
+/// Parsed price level of a [`NumericOrderBook`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Level {
+    pub price: f64,
+    pub size: f64,
+}
+
+/// [`OrderBook`] with parsed levels. Bids are sorted highest price first and
+/// asks lowest price first, so index 0 is always the top of the book.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericOrderBook {
+    pub market: String,
+    pub asset_id: String,
+    pub timestamp: String,
+    pub hash: String,
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+    pub min_order_size: Option<f64>,
+    pub tick_size: Option<f64>,
+    pub neg_risk: Option<bool>,
+}
+
+impl NumericOrderBook {
+    pub fn best_bid(&self) -> Option<Level> {
+        self.bids.first().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<Level> {
+        self.asks.first().copied()
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    pub fn midpoint(&self) -> Option<f64> {
+        Some((self.best_ask()?.price + self.best_bid()?.price) / 2.0)
+    }
+
+    /// Shares that spending `usdc` on the asks would buy right now, or `None`
+    /// if the asks can't absorb all of it.
+    pub fn quote_buy_usdc(&self, usdc: f64) -> Option<MarketQuote> {
+        let mut quote = MarketQuote::default();
+        for level in self.asks.iter().filter(|l| l.price > 0.0) {
+            let spend = (level.size * level.price).min(usdc - quote.usdc);
+            quote.add(level.price, spend / level.price);
+            if quote.usdc >= usdc - 1e-9 {
+                return Some(quote.finish());
+            }
+        }
+        None
+    }
+
+    /// USDC that selling `shares` into the bids would return right now, or
+    /// `None` if the bids can't absorb all of them.
+    pub fn quote_sell_shares(&self, shares: f64) -> Option<MarketQuote> {
+        let mut quote = MarketQuote::default();
+        for level in &self.bids {
+            quote.add(level.price, level.size.min(shares - quote.shares));
+            if quote.shares >= shares - 1e-9 {
+                return Some(quote.finish());
+            }
+        }
+        None
+    }
+}
+
+/// Expected result of a market order against a book snapshot.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MarketQuote {
+    pub shares: f64,
+    pub usdc: f64,
+    pub avg_price: f64,
+    /// Price of the last level touched; a limit here fills the whole order
+    /// if the book doesn't move.
+    pub worst_price: f64,
+}
+
+impl MarketQuote {
+    fn add(&mut self, price: f64, shares: f64) {
+        self.shares += shares;
+        self.usdc += shares * price;
+        self.worst_price = price;
+    }
+
+    fn finish(mut self) -> Self {
+        self.avg_price = self.usdc / self.shares;
+        self
+    }
+}
+
+fn parse_number(value: &str, what: &str) -> crate::Result<f64> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| crate::ClobError::Json {
+            message: format!("invalid {} {:?} in order book", what, value),
+        })
+}
+
+fn parse_levels(levels: &[OrderSummary], side: &str) -> crate::Result<Vec<Level>> {
+    levels
+        .iter()
+        .map(|l| {
+            Ok(Level {
+                price: parse_number(&l.price, &format!("{} price", side))?,
+                size: parse_number(&l.size, &format!("{} size", side))?,
+            })
+        })
+        .collect()
+}
+
+impl OrderBook {
+    /// Parses every level once, failing on the first malformed value instead
+    /// of silently reading it as 0 like [`OrderSummary::price_f64`].
+    pub fn to_numeric(&self) -> crate::Result<NumericOrderBook> {
+        let mut bids = parse_levels(&self.bids, "bid")?;
+        let mut asks = parse_levels(&self.asks, "ask")?;
+        bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+
+        Ok(NumericOrderBook {
+            market: self.market.clone(),
+            asset_id: self.asset_id.clone(),
+            timestamp: self.timestamp.clone(),
+            hash: self.hash.clone(),
+            bids,
+            asks,
+            min_order_size: self
+                .min_order_size
+                .as_deref()
+                .map(|v| parse_number(v, "min_order_size"))
+                .transpose()?,
+            tick_size: self
+                .tick_size
+                .as_deref()
+                .map(|v| parse_number(v, "tick_size"))
+                .transpose()?,
+            neg_risk: self.neg_risk,
+        })
+    }
+}
+
+/// `(price, cumulative size)` per level, best price first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CumulativeDepth {
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MidpointResponse {
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub mid: f64,
+    #[serde(deserialize_with = "deserialize_string_to_amount")]
+    pub mid: Amount,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceResponse {
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub price: f64,
+    #[serde(deserialize_with = "deserialize_string_to_amount")]
+    pub price: Amount,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpreadResponse {
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub spread: f64,
+    #[serde(deserialize_with = "deserialize_string_to_amount")]
+    pub spread: Amount,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -317,8 +836,8 @@ pub struct FeeRateResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LastTradePriceResponse {
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub price: f64,
+    #[serde(deserialize_with = "deserialize_string_to_amount")]
+    pub price: Amount,
 }
 
 // Server time is returned as a raw integer timestamp
@@ -347,38 +866,67 @@ impl BookParams {
     }
 }
 
+/// A batch chunk that failed, with the tokens it covered.
+#[derive(Debug)]
+pub struct BatchError {
+    pub token_ids: Vec<String>,
+    pub error: crate::ClobError,
+}
+
+/// Merged results of a chunked batch request. Chunks that failed are listed
+/// in `errors` instead of failing the whole call.
+#[derive(Debug)]
+pub struct BatchResponse<T> {
+    pub data: Vec<T>,
+    pub errors: Vec<BatchError>,
+}
+
+impl<T> BatchResponse<T> {
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// All results, or the first chunk error.
+    pub fn into_result(self) -> crate::Result<Vec<T>> {
+        match self.errors.into_iter().next() {
+            Some(failed) => Err(failed.error),
+            None => Ok(self.data),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BatchMidpointResponse {
     pub token_id: String,
-    pub mid: Option<f64>,
+    pub mid: Option<Amount>,
 }
 
 #[derive(Debug, Clone)]
 pub struct BatchPriceResponse {
     pub token_id: String,
-    pub buy: Option<f64>,
-    pub sell: Option<f64>,
+    pub buy: Option<Amount>,
+    pub sell: Option<Amount>,
 }
 
 #[derive(Debug, Clone)]
 pub struct BatchSpreadResponse {
     pub token_id: String,
-    pub spread: Option<f64>,
+    pub spread: Option<Amount>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LastTradesPriceEntry {
     pub token_id: String,
     pub side: String,
-    #[serde(deserialize_with = "deserialize_string_to_f64")]
-    pub price: f64,
+    #[serde(deserialize_with = "deserialize_string_to_amount")]
+    pub price: Amount,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Token {
     pub token_id: String,
     pub outcome: String,
-    pub price: f64,
+    pub price: Amount,
     #[serde(default)]
     pub winner: bool,
 }
@@ -388,9 +936,33 @@ pub struct MarketRewards {
     #[serde(default)]
     pub rates: Option<serde_json::Value>,
     #[serde(default)]
-    pub min_size: f64,
+    pub min_size: Amount,
+    /// Furthest from the midpoint an order still earns rewards, in cents.
     #[serde(default)]
-    pub max_spread: f64,
+    pub max_spread: Amount,
+}
+
+impl MarketRewards {
+    /// Rates are set and orders can qualify, i.e. `max_spread` is positive.
+    pub fn is_active(&self) -> bool {
+        let has_rates = match &self.rates {
+            Some(serde_json::Value::Array(rates)) => !rates.is_empty(),
+            Some(serde_json::Value::Object(rates)) => !rates.is_empty(),
+            _ => false,
+        };
+        has_rates && self.max_spread_price() > 0.0
+    }
+
+    /// `max_spread` in price units.
+    pub fn max_spread_price(&self) -> f64 {
+        amount_to_f64(self.max_spread) / 100.0
+    }
+
+    /// Whether an order at `price` sits close enough to `midpoint` to earn
+    /// rewards.
+    pub fn is_within_max_spread(&self, price: f64, midpoint: f64) -> bool {
+        (price - midpoint).abs() <= self.max_spread_price() + 1e-9
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -409,9 +981,9 @@ pub struct Market {
     #[serde(default)]
     pub accepting_order_timestamp: Option<String>,
     #[serde(default)]
-    pub minimum_order_size: f64,
+    pub minimum_order_size: Amount,
     #[serde(default)]
-    pub minimum_tick_size: f64,
+    pub minimum_tick_size: Amount,
     #[serde(default)]
     pub seconds_delay: i32,
     #[serde(default)]
@@ -450,6 +1022,159 @@ pub struct Market {
     pub tags: Vec<String>,
 }
 
+impl Market {
+    pub fn is_open(&self) -> bool {
+        self.active && !self.closed && !self.archived
+    }
+
+    /// Open, accepting orders and paying liquidity rewards.
+    pub fn has_active_rewards(&self) -> bool {
+        self.is_open()
+            && self.accepting_orders
+            && self.rewards.as_ref().is_some_and(MarketRewards::is_active)
+    }
+
+    pub fn end_date(&self) -> Option<DateTime<Utc>> {
+        parse_market_time(self.end_date_iso.as_deref())
+    }
+
+    /// Kick-off time of a sports market. Only set for game markets.
+    pub fn game_start(&self) -> Option<DateTime<Utc>> {
+        parse_market_time(self.game_start_time.as_deref())
+    }
+
+    /// True once a sports market's game is under way. Live game markets
+    /// delay order matching by [`Market::seconds_delay`].
+    pub fn is_in_play(&self, now: DateTime<Utc>) -> bool {
+        self.game_start().is_some_and(|start| start <= now) && !self.closed
+    }
+
+    /// When the market starts taking orders, if it announced a start time.
+    pub fn accepting_orders_from(&self) -> Option<DateTime<Utc>> {
+        parse_market_time(self.accepting_order_timestamp.as_deref())
+    }
+
+    /// True if an order posted at `now` would be accepted: the market is
+    /// open, not paused, and past its `accepting_order_timestamp`.
+    pub fn is_accepting_orders(&self, now: DateTime<Utc>) -> bool {
+        self.is_open()
+            && self.accepting_orders
+            && self.accepting_orders_from().is_none_or(|from| from <= now)
+    }
+}
+
+// game_start_time comes as "2024-01-01 20:00:00+00" rather than RFC 3339
+fn parse_market_time(value: Option<&str>) -> Option<DateTime<Utc>> {
+    let value = value?;
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%#z"))
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Client-side market filter for [`crate::MarketDataApi::find_markets`]. Unset
+/// fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct MarketFilter {
+    pub slug_contains: Option<String>,
+    pub tag: Option<String>,
+    pub active: Option<bool>,
+    pub closed: Option<bool>,
+    pub end_date_after: Option<DateTime<Utc>>,
+    pub end_date_before: Option<DateTime<Utc>>,
+    pub neg_risk: Option<bool>,
+    pub neg_risk_market_id: Option<String>,
+}
+
+impl MarketFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Case-insensitive substring of `market_slug`.
+    pub fn slug_contains(mut self, slug: impl Into<String>) -> Self {
+        self.slug_contains = Some(slug.into());
+        self
+    }
+
+    /// Case-insensitive exact tag match.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = Some(active);
+        self
+    }
+
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = Some(closed);
+        self
+    }
+
+    /// Markets without a parseable end date never match a date bound.
+    pub fn end_date_between(
+        mut self,
+        after: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.end_date_after = after;
+        self.end_date_before = before;
+        self
+    }
+
+    pub fn neg_risk(mut self, neg_risk: bool) -> Self {
+        self.neg_risk = Some(neg_risk);
+        self
+    }
+
+    /// Outcome markets of one neg-risk event.
+    pub fn neg_risk_market_id(mut self, id: impl Into<String>) -> Self {
+        self.neg_risk_market_id = Some(id.into());
+        self
+    }
+
+    pub fn matches(&self, market: &Market) -> bool {
+        if let Some(id) = &self.neg_risk_market_id {
+            if market.neg_risk_market_id.as_deref() != Some(id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(slug) = &self.slug_contains {
+            let matched = market
+                .market_slug
+                .as_deref()
+                .is_some_and(|s| s.to_lowercase().contains(&slug.to_lowercase()));
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if !market.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                return false;
+            }
+        }
+        if self.active.is_some_and(|a| a != market.active)
+            || self.closed.is_some_and(|c| c != market.closed)
+            || self.neg_risk.is_some_and(|n| n != market.neg_risk)
+        {
+            return false;
+        }
+        if self.end_date_after.is_some() || self.end_date_before.is_some() {
+            let Some(end) = market.end_date() else {
+                return false;
+            };
+            if self.end_date_after.is_some_and(|after| end < after)
+                || self.end_date_before.is_some_and(|before| end > before)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimplifiedMarket {
     pub condition_id: String,
@@ -464,6 +1189,13 @@ pub struct MarketsResponse {
     pub next_cursor: String,
 }
 
+/// A page of `/data/orders`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenOrdersResponse {
+    pub data: Vec<OrderStatus>,
+    pub next_cursor: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimplifiedMarketsResponse {
     pub data: Vec<SimplifiedMarket>,
@@ -478,13 +1210,13 @@ pub struct MarketTradeEvent {
     pub timestamp: String,
     pub token_id: String,
     pub side: String,
-    pub price: f64,
-    pub size: f64,
+    pub price: Amount,
+    pub size: Amount,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PostOrderResponse {
-    pub success:bool,
+    pub success: bool,
     #[serde(rename = "errorMsg", default)]
     pub error_msg: String,
     #[serde(rename = "orderID", default)]
@@ -499,8 +1231,105 @@ pub struct PostOrderResponse {
     pub order_hashes: Vec<String>,
 }
 
+/// Where an order is in its lifecycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderState {
+    Live,
+    /// Marketable order held back by the matching delay.
+    Delayed,
+    Matched,
+    Canceled,
+    /// Marketable order whose delayed placement failed.
+    Unmatched,
+    Other(String),
+}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl OrderState {
+    /// No further fills or cancels can happen.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderState::Matched | OrderState::Canceled | OrderState::Unmatched
+        )
+    }
+}
+
+impl From<&str> for OrderState {
+    fn from(value: &str) -> Self {
+        let upper = value.to_ascii_uppercase();
+        match upper.strip_prefix("ORDER_STATUS_").unwrap_or(&upper) {
+            "LIVE" => OrderState::Live,
+            "DELAYED" => OrderState::Delayed,
+            "MATCHED" => OrderState::Matched,
+            "CANCELED" | "CANCELLED" | "CANCELED_MARKET_RESOLVED" => OrderState::Canceled,
+            "UNMATCHED" => OrderState::Unmatched,
+            _ => OrderState::Other(value.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Ok(OrderState::from(value.as_str()))
+    }
+}
+
+// order amounts come back as strings or bare numbers
+fn deserialize_lenient_amount<'de, D>(deserializer: D) -> Result<Amount, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => s.parse().map_err(serde::de::Error::custom),
+        serde_json::Value::Number(n) => n.to_string().parse().map_err(serde::de::Error::custom),
+        serde_json::Value::Null => Ok(Amount::default()),
+        other => Err(serde::de::Error::custom(format!(
+            "expected a number, got {}",
+            other
+        ))),
+    }
+}
+
+/// An order as returned by `get_order`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderStatus {
+    pub id: String,
+    pub status: OrderState,
+    #[serde(default)]
+    pub market: String,
+    #[serde(default)]
+    pub asset_id: String,
+    pub side: Side,
+    #[serde(deserialize_with = "deserialize_lenient_amount")]
+    pub price: Amount,
+    #[serde(deserialize_with = "deserialize_lenient_amount")]
+    pub original_size: Amount,
+    #[serde(default, deserialize_with = "deserialize_lenient_amount")]
+    pub size_matched: Amount,
+    #[serde(default)]
+    pub outcome: String,
+    #[serde(default)]
+    pub order_type: String,
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub maker_address: String,
+    /// Ids of the trades that filled it.
+    #[serde(default, deserialize_with = "deserialize_null_to_empty_vec")]
+    pub associate_trades: Vec<String>,
+}
+
+impl OrderStatus {
+    pub fn remaining_size(&self) -> Amount {
+        self.original_size - self.size_matched
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Side {
     Buy,
@@ -516,6 +1345,43 @@ impl std::fmt::Display for Side {
     }
 }
 
+/// Lookback window for [`crate::MarketDataApi::get_price_history`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceHistoryInterval {
+    OneHour,
+    SixHours,
+    OneDay,
+    OneWeek,
+    OneMonth,
+    Max,
+}
+
+impl PriceHistoryInterval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceHistoryInterval::OneHour => "1h",
+            PriceHistoryInterval::SixHours => "6h",
+            PriceHistoryInterval::OneDay => "1d",
+            PriceHistoryInterval::OneWeek => "1w",
+            PriceHistoryInterval::OneMonth => "1m",
+            PriceHistoryInterval::Max => "max",
+        }
+    }
+}
+
+/// One sample of a price history: unix timestamp (seconds) and price.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PricePoint {
+    pub t: i64,
+    pub p: Amount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceHistoryResponse {
+    #[serde(default)]
+    pub history: Vec<PricePoint>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum TickSize {
     Size0_1,
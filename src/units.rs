@@ -0,0 +1,231 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Mul};
+
+use crate::error::{ClobError, Result};
+use crate::types::TickSize;
+
+// prices are kept as whole units of the finest tick
+const UNITS_PER_PRICE: i64 = 10_000;
+const SIZE_DECIMALS: f64 = 100.0;
+// slack for f64 inputs that are on the tick up to representation error
+const EPSILON: f64 = 1e-9;
+
+fn units_per_tick(tick_size: TickSize) -> i64 {
+    match tick_size {
+        TickSize::Size0_1 => 1_000,
+        TickSize::Size0_01 => 100,
+        TickSize::Size0_001 => 10,
+        TickSize::Size0_0001 => 1,
+    }
+}
+
+/// A price on a market's tick grid, strictly between 0 and 1. Prices
+/// compare by value, whatever their tick size.
+#[derive(Debug, Clone, Copy)]
+pub struct Price {
+    units: i64,
+    tick_size: TickSize,
+}
+
+impl Price {
+    /// `value` must already be on the tick.
+    pub fn new(value: f64, tick_size: TickSize) -> Result<Self> {
+        let price = Self::round_to_tick(value, tick_size)?;
+        if (price.value() - value).abs() > EPSILON {
+            return Err(ClobError::InvalidParameter(format!(
+                "price {} is not a multiple of tick size {}",
+                value,
+                tick_size.as_str()
+            )));
+        }
+        Ok(price)
+    }
+
+    /// The nearest tick to `value`, which must be inside the valid range.
+    pub fn round_to_tick(value: f64, tick_size: TickSize) -> Result<Self> {
+        if !value.is_finite() {
+            return Err(ClobError::InvalidParameter(format!(
+                "invalid price {}",
+                value
+            )));
+        }
+        let ticks = (value * (UNITS_PER_PRICE / units_per_tick(tick_size)) as f64).round();
+        Self::from_ticks(ticks as i64, tick_size)
+    }
+
+    /// The nearest tick to `value`, moved inside `[tick, 1 - tick]` if it
+    /// falls outside. NaN becomes the lowest tick.
+    pub fn clamped(value: f64, tick_size: TickSize) -> Self {
+        let max_ticks = UNITS_PER_PRICE / units_per_tick(tick_size) - 1;
+        let ticks = (value * (max_ticks + 1) as f64).round() as i64;
+        Self {
+            units: ticks.clamp(1, max_ticks) * units_per_tick(tick_size),
+            tick_size,
+        }
+    }
+
+    pub fn from_ticks(ticks: i64, tick_size: TickSize) -> Result<Self> {
+        let max_ticks = UNITS_PER_PRICE / units_per_tick(tick_size) - 1;
+        if !(1..=max_ticks).contains(&ticks) {
+            return Err(ClobError::InvalidParameter(format!(
+                "{} ticks of {} is not a price between 0 and 1",
+                ticks,
+                tick_size.as_str()
+            )));
+        }
+        Ok(Self {
+            units: ticks * units_per_tick(tick_size),
+            tick_size,
+        })
+    }
+
+    pub fn value(self) -> f64 {
+        self.units as f64 / UNITS_PER_PRICE as f64
+    }
+
+    pub fn tick_size(self) -> TickSize {
+        self.tick_size
+    }
+
+    /// Whole ticks from zero.
+    pub fn ticks(self) -> i64 {
+        self.units / units_per_tick(self.tick_size)
+    }
+
+    /// `ticks` further up (or down if negative), or `None` past the range.
+    pub fn offset_ticks(self, ticks: i64) -> Option<Self> {
+        Self::from_ticks(self.ticks() + ticks, self.tick_size).ok()
+    }
+
+    /// `1 - price`, the same bet on the other outcome.
+    pub fn complement(self) -> Self {
+        Self {
+            units: UNITS_PER_PRICE - self.units,
+            tick_size: self.tick_size,
+        }
+    }
+
+    /// This price on the grid of another tick size, rounded to the nearest
+    /// tick.
+    pub fn with_tick_size(self, tick_size: TickSize) -> Result<Self> {
+        Self::round_to_tick(self.value(), tick_size)
+    }
+}
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.units == other.units
+    }
+}
+
+impl Eq for Price {}
+
+impl Hash for Price {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.units.hash(state);
+    }
+}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.units.cmp(&other.units)
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = self.tick_size.as_str().len() - 2;
+        write!(f, "{:.*}", decimals, self.value())
+    }
+}
+
+impl From<Price> for f64 {
+    fn from(price: Price) -> f64 {
+        price.value()
+    }
+}
+
+/// A positive number of shares, in the exchange's two-decimal precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Size {
+    cents: u64,
+}
+
+impl Size {
+    /// `value` must be positive with at most two decimals.
+    pub fn new(value: f64) -> Result<Self> {
+        let size = Self::round_down(value)?;
+        if (size.value() - value).abs() > EPSILON {
+            return Err(ClobError::InvalidParameter(format!(
+                "size {} has more than 2 decimals",
+                value
+            )));
+        }
+        Ok(size)
+    }
+
+    /// `value` truncated to two decimals, which must leave something.
+    pub fn round_down(value: f64) -> Result<Self> {
+        let cents = (value * SIZE_DECIMALS + EPSILON).floor();
+        if !(cents >= 1.0 && cents < u64::MAX as f64) {
+            return Err(ClobError::InvalidParameter(format!(
+                "invalid size {}",
+                value
+            )));
+        }
+        Ok(Self {
+            cents: cents as u64,
+        })
+    }
+
+    pub fn value(self) -> f64 {
+        self.cents as f64 / SIZE_DECIMALS
+    }
+
+    /// `None` if `other` is as big or bigger.
+    pub fn checked_sub(self, other: Size) -> Option<Size> {
+        self.cents
+            .checked_sub(other.cents)
+            .filter(|&cents| cents > 0)
+            .map(|cents| Size { cents })
+    }
+}
+
+impl Add for Size {
+    type Output = Size;
+
+    fn add(self, other: Size) -> Size {
+        Size {
+            cents: self.cents.saturating_add(other.cents),
+        }
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.value())
+    }
+}
+
+impl From<Size> for f64 {
+    fn from(size: Size) -> f64 {
+        size.value()
+    }
+}
+
+/// Notional in USDC.
+impl Mul<Size> for Price {
+    type Output = f64;
+
+    fn mul(self, size: Size) -> f64 {
+        (self.units as f64 * size.cents as f64) / (UNITS_PER_PRICE as f64 * SIZE_DECIMALS)
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use clob_rs::{AssetType, MissingApproval};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::client;
+
+const EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+const NEG_RISK_EXCHANGE: &str = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
+
+#[tokio::test]
+async fn test_ensure_allowances_checks_both_exchanges() {
+    let mock_server = MockServer::start().await;
+    // collateral is approved to both, the token only to the standard exchange
+    Mock::given(method("GET"))
+        .and(path("/balance-allowance"))
+        .and(query_param("asset_type", "COLLATERAL"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "balance": "10000000",
+            "allowances": {
+                EXCHANGE.to_lowercase(): "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                NEG_RISK_EXCHANGE: "5000000"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/balance-allowance"))
+        .and(query_param("asset_type", "CONDITIONAL"))
+        .and(query_param("token_id", "123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "balance": "0",
+            "allowances": {EXCHANGE: "1", NEG_RISK_EXCHANGE: "0"}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = client(mock_server.uri());
+    let report = client
+        .orders()
+        .unwrap()
+        .ensure_allowances("123")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report.missing,
+        vec![MissingApproval {
+            asset_type: AssetType::CONDITIONAL,
+            spender: NEG_RISK_EXCHANGE.to_string(),
+            neg_risk: true,
+        }]
+    );
+    assert!(!report.is_complete());
+    assert!(report.is_complete_for(false));
+    assert!(!report.is_complete_for(true));
+}
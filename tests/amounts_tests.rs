@@ -0,0 +1,217 @@
+//! Order amounts checked against a port of py-clob-client's float math
+//! (`order_builder/helpers.py`), whose `decimal_places` goes through
+//! `Decimal(str(x))`.
+
+use clob_rs::{
+    CreateOrderOptions, MarketOrderArgs, OrderArgs, OrderBuilder, Side, SignedOrder, Signer,
+    TickSize,
+};
+use proptest::prelude::*;
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// `abs(Decimal(str(x)).as_tuple().exponent)`. Python's `str` is the shortest
+/// round-trip repr, in scientific notation below 1e-4 and from 1e16 up, and
+/// always has a fractional digit otherwise (`5.0`).
+fn py_decimal_places(x: f64) -> u32 {
+    let sci = format!("{:e}", x);
+    let (digits, exp) = sci.split_once('e').unwrap();
+    let exp: i32 = exp.parse().unwrap();
+    let fraction = digits.split_once('.').map_or(0, |(_, f)| f.len() as i32);
+    let exponent = if (-4..16).contains(&exp) {
+        (exp - fraction).min(-1)
+    } else {
+        exp - fraction
+    };
+    exponent.unsigned_abs()
+}
+
+fn py_round_down(x: f64, digits: u32) -> f64 {
+    (x * 10f64.powi(digits as i32)).floor() / 10f64.powi(digits as i32)
+}
+
+fn py_round_normal(x: f64, digits: u32) -> f64 {
+    (x * 10f64.powi(digits as i32)).round() / 10f64.powi(digits as i32)
+}
+
+fn py_round_up(x: f64, digits: u32) -> f64 {
+    (x * 10f64.powi(digits as i32)).ceil() / 10f64.powi(digits as i32)
+}
+
+fn py_to_token_decimals(x: f64) -> u128 {
+    let mut f = 1_000_000.0 * x;
+    if py_decimal_places(f) > 0 {
+        f = py_round_normal(f, 0);
+    }
+    f as u128
+}
+
+fn py_fit_amount(mut amount: f64, digits: u32) -> f64 {
+    if py_decimal_places(amount) > digits {
+        amount = py_round_up(amount, digits + 4);
+        if py_decimal_places(amount) > digits {
+            amount = py_round_down(amount, digits);
+        }
+    }
+    amount
+}
+
+// ROUNDING_CONFIG of the Python client: (price, size, amount) decimals
+fn round_config(tick_size: TickSize) -> (u32, u32, u32) {
+    match tick_size {
+        TickSize::Size0_1 => (1, 2, 3),
+        TickSize::Size0_01 => (2, 2, 4),
+        TickSize::Size0_001 => (3, 2, 5),
+        TickSize::Size0_0001 => (4, 2, 6),
+    }
+}
+
+fn py_order_amounts(side: Side, size: f64, price: f64, tick_size: TickSize) -> (u128, u128) {
+    let (price_digits, size_digits, amount_digits) = round_config(tick_size);
+    let raw_price = py_round_normal(price, price_digits);
+    let shares = py_round_down(size, size_digits);
+    let collateral = py_fit_amount(shares * raw_price, amount_digits);
+    match side {
+        Side::Buy => (
+            py_to_token_decimals(collateral),
+            py_to_token_decimals(shares),
+        ),
+        Side::Sell => (
+            py_to_token_decimals(shares),
+            py_to_token_decimals(collateral),
+        ),
+    }
+}
+
+fn py_market_order_amounts(
+    side: Side,
+    amount: f64,
+    price: f64,
+    tick_size: TickSize,
+) -> (u128, u128) {
+    let (price_digits, size_digits, amount_digits) = round_config(tick_size);
+    let raw_price = py_round_normal(price, price_digits);
+    let maker = py_round_down(amount, size_digits);
+    let taker = match side {
+        Side::Buy => py_fit_amount(maker / raw_price, amount_digits),
+        Side::Sell => py_fit_amount(maker * raw_price, amount_digits),
+    };
+    (py_to_token_decimals(maker), py_to_token_decimals(taker))
+}
+
+fn amounts(order: &SignedOrder) -> (u128, u128) {
+    (
+        order.maker_amount.parse().unwrap(),
+        order.taker_amount.parse().unwrap(),
+    )
+}
+
+fn builder() -> OrderBuilder {
+    OrderBuilder::new(Signer::new(TEST_PRIVATE_KEY, 137).unwrap())
+}
+
+fn options(tick_size: TickSize) -> CreateOrderOptions {
+    CreateOrderOptions {
+        tick_size,
+        neg_risk: false,
+        salt: Some(1),
+    }
+}
+
+fn tick_size() -> impl Strategy<Value = TickSize> {
+    prop_oneof![
+        Just(TickSize::Size0_1),
+        Just(TickSize::Size0_01),
+        Just(TickSize::Size0_001),
+        Just(TickSize::Size0_0001),
+    ]
+}
+
+fn side() -> impl Strategy<Value = Side> {
+    prop_oneof![Just(Side::Buy), Just(Side::Sell)]
+}
+
+// a price on the tick, strictly inside (0, 1)
+fn tick_and_price() -> impl Strategy<Value = (TickSize, f64)> {
+    tick_size().prop_flat_map(|tick_size| {
+        let ticks = (1.0 / tick_size.as_f64()).round() as u32;
+        (1..ticks).prop_map(move |n| (tick_size, n as f64 / ticks as f64))
+    })
+}
+
+// sizes up to ten million shares, with up to 4 decimals so rounding down
+// to the size precision is exercised too
+fn size() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        (1u64..100_000_000).prop_map(|n| n as f64 / 100.0),
+        (1u64..1_000_000_000).prop_map(|n| n as f64 / 10_000.0),
+        (1u64..10_000).prop_map(|n| n as f64),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(500))]
+
+    #[test]
+    fn test_limit_order_amounts_match_python_client(
+        (tick_size, price) in tick_and_price(),
+        size in size(),
+        side in side(),
+    ) {
+        let mut order = SignedOrder::default();
+        builder()
+            .create_order_into(
+                &OrderArgs::new("123", price, size, side),
+                &options(tick_size),
+                &mut order,
+            )
+            .unwrap();
+        prop_assert_eq!(amounts(&order), py_order_amounts(side, size, price, tick_size));
+    }
+
+    #[test]
+    fn test_market_order_amounts_match_python_client(
+        (tick_size, price) in tick_and_price(),
+        amount in size(),
+        side in side(),
+    ) {
+        let args = MarketOrderArgs {
+            price,
+            ..MarketOrderArgs::new("123", amount, side)
+        };
+        let order = futures::executor::block_on(
+            builder().create_market_order(&args, &options(tick_size)),
+        )
+        .unwrap();
+        prop_assert_eq!(amounts(&order), py_market_order_amounts(side, amount, price, tick_size));
+    }
+}
+
+#[test]
+fn test_amounts_in_scientific_notation_range() {
+    // raw amounts under 1e-4 print as `1e-06` in Python
+    for (side, size, price, tick_size) in [
+        (Side::Buy, 0.01, 0.0001, TickSize::Size0_0001),
+        (Side::Sell, 0.01, 0.0003, TickSize::Size0_0001),
+        (Side::Buy, 0.03, 0.0007, TickSize::Size0_0001),
+        (Side::Buy, 99_999_999.99, 0.9999, TickSize::Size0_0001),
+        (Side::Sell, 12_345_678.91, 0.123, TickSize::Size0_001),
+    ] {
+        let mut order = SignedOrder::default();
+        builder()
+            .create_order_into(
+                &OrderArgs::new("123", price, size, side),
+                &options(tick_size),
+                &mut order,
+            )
+            .unwrap();
+        assert_eq!(
+            amounts(&order),
+            py_order_amounts(side, size, price, tick_size),
+            "{:?} {} @ {}",
+            side,
+            size,
+            price
+        );
+    }
+}
@@ -0,0 +1,1046 @@
+#![cfg(feature = "http")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use clob_rs::{
+    amount_to_f64, ApiCreds, AuthApi, BookParams, ClobClient, ClobError, DryRunLog,
+    IncrementingNonce, Market, MarketDataApi, MarketFilter, OrderBuilder, OrderType, OrdersApi,
+    PriceHistoryInterval, Signer, TickSize, Transport,
+};
+use futures::TryStreamExt;
+use wiremock::matchers::{body_json, header, header_exists, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const POLYGON_CHAIN_ID: u64 = 137;
+
+fn create_test_signer() -> Signer {
+    Signer::new(TEST_PRIVATE_KEY, POLYGON_CHAIN_ID).unwrap()
+}
+
+async fn mount_fee_rate(mock_server: &MockServer, base_fee: i32) {
+    Mock::given(method("GET"))
+        .and(path("/fee-rate"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"base_fee": base_fee})),
+        )
+        .mount(mock_server)
+        .await;
+}
+
+fn create_test_creds() -> ApiCreds {
+    ApiCreds {
+        api_key: "test-api-key".to_string(),
+        api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+        api_passphrase: "test-passphrase".into(),
+    }
+}
+
+#[tokio::test]
+async fn test_market_data_api_standalone() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/tick-size"))
+        .and(query_param("token_id", "123"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"minimum_tick_size": 0.01})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let market_data = MarketDataApi::new(Transport::new(format!("{}/", mock_server.uri())));
+
+    assert_eq!(
+        market_data.get_tick_size("123").await.unwrap(),
+        TickSize::Size0_01
+    );
+    // second lookup is served from the cache shared by clones
+    let cloned = market_data.clone();
+    assert_eq!(
+        cloned.get_tick_size("123").await.unwrap(),
+        TickSize::Size0_01
+    );
+}
+
+#[tokio::test]
+async fn test_query_strings_are_encoded() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .and(query_param("token_id", "a&b=c d"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": "0.5"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/trades"))
+        .and(query_param("market", "0x1&after=0"))
+        .and(query_param("after", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    assert_eq!(market_data.get_midpoint("a&b=c d").await.unwrap(), 0.5);
+
+    let orders = OrdersApi::new(market_data, OrderBuilder::new(create_test_signer()))
+        .with_creds(create_test_creds());
+    let params = clob_rs::TradeParams {
+        market: Some("0x1&after=0".to_string()),
+        after: Some(10),
+        ..Default::default()
+    };
+    orders.get_trades(Some(&params)).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_orders_api_standalone() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/order"))
+        .and(header_exists("POLY_API_KEY"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "orderID": "0xabc",
+            "status": "LIVE"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    mount_fee_rate(&mock_server, 0).await;
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let orders = OrdersApi::new(market_data, OrderBuilder::new(create_test_signer()))
+        .with_creds(create_test_creds());
+
+    let order_args = clob_rs::OrderArgs::new("123456", 0.5, 100.0, clob_rs::Side::Buy);
+    let options = clob_rs::PartialCreateOrderOptions {
+        tick_size: Some(TickSize::Size0_01),
+        neg_risk: Some(false),
+        salt: None,
+    };
+    let signed_order = orders
+        .create_order(&order_args, Some(options))
+        .await
+        .unwrap();
+    let response = orders
+        .post_order(&signed_order, OrderType::GTC)
+        .await
+        .unwrap();
+
+    assert!(response.success);
+    assert_eq!(response.order_id, "0xabc");
+}
+
+#[tokio::test]
+async fn test_market_order_price_comes_from_book() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "market": "0xmarket",
+            "asset_id": "123456",
+            "timestamp": "0",
+            "hash": "",
+            "bids": [],
+            "asks": [{"price": "0.6", "size": "100"}, {"price": "0.5", "size": "20"}]
+        })))
+        .mount(&mock_server)
+        .await;
+    mount_fee_rate(&mock_server, 0).await;
+
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let orders = OrdersApi::new(market_data, OrderBuilder::new(create_test_signer()));
+    let options = clob_rs::PartialCreateOrderOptions {
+        tick_size: Some(TickSize::Size0_01),
+        neg_risk: Some(false),
+        salt: None,
+    };
+
+    let args = clob_rs::MarketOrderArgs::buy_usdc("123456", 25.0).unwrap();
+    let quote = orders
+        .market_data()
+        .quote_market_order(&args)
+        .await
+        .unwrap();
+    assert_eq!(quote.worst_price, 0.6);
+
+    let order = orders
+        .create_market_order(&args, Some(options.clone()))
+        .await
+        .unwrap();
+    assert_eq!(order.maker_amount, "25000000");
+    let shares: f64 = order.taker_amount.parse::<f64>().unwrap() / 1e6;
+    assert!((shares - 25.0 / 0.6).abs() < 1e-3, "{}", shares);
+
+    let too_big = clob_rs::MarketOrderArgs::buy_usdc("123456", 1000.0).unwrap();
+    let err = orders
+        .create_market_order(&too_big, Some(options))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("too thin"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_post_order_signed_elsewhere() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/order"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "orderID": "0xabc",
+            "status": "LIVE"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // signing side: no transport, just the builder
+    let builder = OrderBuilder::new(create_test_signer());
+    let order_args = clob_rs::OrderArgs::new("123456", 0.5, 100.0, clob_rs::Side::Buy);
+    let options = clob_rs::CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+    let signed = builder.create_order(&order_args, &options).await.unwrap();
+    let wire = serde_json::to_string(&signed).unwrap();
+
+    // submitting side
+    let received: clob_rs::SignedOrder = serde_json::from_str(&wire).unwrap();
+    assert_eq!(received.signature, signed.signature);
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let orders = OrdersApi::new(market_data, OrderBuilder::new(create_test_signer()))
+        .with_creds(create_test_creds());
+    let response = orders.post_order(&received, OrderType::GTC).await.unwrap();
+    assert_eq!(response.order_id, "0xabc");
+}
+
+#[tokio::test]
+async fn test_post_order_with_deadline() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/order"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"success": true, "orderID": "0xabc"}))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    mount_fee_rate(&mock_server, 0).await;
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let orders = OrdersApi::new(market_data, OrderBuilder::new(create_test_signer()))
+        .with_creds(create_test_creds());
+
+    let order_args = clob_rs::OrderArgs::new("123456", 0.5, 100.0, clob_rs::Side::Buy);
+    let options = clob_rs::PartialCreateOrderOptions {
+        tick_size: Some(TickSize::Size0_01),
+        neg_risk: Some(false),
+        salt: None,
+    };
+    let signed_order = orders
+        .create_order(&order_args, Some(options))
+        .await
+        .unwrap();
+
+    let err = orders
+        .post_order_with_deadline(&signed_order, OrderType::GTC, Duration::from_millis(50))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ClobError::Timeout { deadline } if deadline == Duration::from_millis(50))
+    );
+
+    let response = orders
+        .post_order_with_deadline(&signed_order, OrderType::GTC, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(response.order_id, "0xabc");
+}
+
+#[tokio::test]
+async fn test_auth_api_requires_creds_for_l2() {
+    let auth = AuthApi::new(
+        Transport::new("http://localhost:8080"),
+        create_test_signer(),
+    );
+
+    let result = auth.get_api_keys().await;
+    assert!(result.unwrap_err().to_string().contains("credentials"));
+}
+
+#[tokio::test]
+async fn test_client_services_require_signer() {
+    let client = ClobClient::new("http://localhost:8080");
+
+    assert!(client.auth().is_err());
+    assert!(client.orders().is_err());
+}
+
+#[tokio::test]
+async fn test_client_creds_before_signer() {
+    let client = ClobClient::new("http://localhost:8080")
+        .with_creds(create_test_creds())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap();
+
+    assert!(client.orders().unwrap().creds().is_some());
+    assert!(client.auth().unwrap().creds().is_some());
+}
+
+#[tokio::test]
+async fn test_create_order_below_min_size_is_rejected() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "market": "0xmarket",
+            "asset_id": "123456",
+            "timestamp": "0",
+            "hash": "",
+            "bids": [],
+            "asks": [],
+            "min_order_size": "5",
+            "tick_size": "0.01",
+            "neg_risk": false
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    mount_fee_rate(&mock_server, 0).await;
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let orders = OrdersApi::new(market_data.clone(), OrderBuilder::new(create_test_signer()));
+
+    assert_eq!(market_data.get_min_order_size("123456").await.unwrap(), 5.0);
+
+    let options = clob_rs::PartialCreateOrderOptions {
+        tick_size: Some(TickSize::Size0_01),
+        neg_risk: Some(false),
+        salt: None,
+    };
+    let order_args = clob_rs::OrderArgs::new("123456", 0.5, 2.0, clob_rs::Side::Buy);
+    let result = orders
+        .create_order(&order_args, Some(options.clone()))
+        .await;
+    assert!(matches!(
+        result,
+        Err(clob_rs::ClobError::BelowMinimumSize { size, min_size }) if size == 2.0 && min_size == 5.0
+    ));
+
+    let order_args = order_args.round_up_to_min_size(5.0);
+    let signed_order = orders
+        .create_order(&order_args, Some(options))
+        .await
+        .unwrap();
+    assert_eq!(signed_order.taker_amount, "5000000");
+}
+
+#[test]
+fn test_round_up_to_min_size() {
+    assert_eq!(clob_rs::round_up_to_min_size(1.5, 5.0), 5.0);
+    assert_eq!(clob_rs::round_up_to_min_size(12.0, 5.0), 12.0);
+}
+
+fn markets_page() -> serde_json::Value {
+    serde_json::json!({
+        "data": [
+            {
+                "condition_id": "0xopen",
+                "question_id": "0xq1",
+                "tokens": [{"token_id": "1", "outcome": "Yes", "price": 0.5}],
+                "active": true,
+                "closed": false
+            },
+            {
+                "condition_id": "0xclosed",
+                "question_id": "0xq2",
+                "tokens": [{"token_id": "2", "outcome": "Yes", "price": 1.0, "winner": true}],
+                "active": true,
+                "closed": true,
+                "archived": true
+            }
+        ],
+        "next_cursor": "LTE="
+    })
+}
+
+#[tokio::test]
+async fn test_closed_markets_not_indexed_by_default() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(markets_page()))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let markets = client.markets().get_markets().await.unwrap();
+
+    assert_eq!(markets.len(), 2);
+    assert_eq!(
+        client.markets().market_for_token("1").unwrap().condition_id,
+        "0xopen"
+    );
+    assert!(client.markets().market_for_token("2").is_none());
+}
+
+#[tokio::test]
+async fn test_closed_markets_indexed_when_enabled() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(markets_page()))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri()).with_closed_markets(true);
+    client.markets().get_markets().await.unwrap();
+
+    let market = client.markets().market_for_token("2").unwrap();
+    assert_eq!(market.condition_id, "0xclosed");
+    assert!(market.tokens[0].winner);
+}
+
+#[tokio::test]
+async fn test_create_order_applies_market_fee_rate() {
+    let mock_server = MockServer::start().await;
+    mount_fee_rate(&mock_server, 1000).await;
+
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let orders = OrdersApi::new(market_data, OrderBuilder::new(create_test_signer()));
+    let options = clob_rs::PartialCreateOrderOptions {
+        tick_size: Some(TickSize::Size0_01),
+        neg_risk: Some(false),
+        salt: None,
+    };
+
+    let order_args = clob_rs::OrderArgs::new("123456", 0.5, 10.0, clob_rs::Side::Buy);
+    let signed_order = orders
+        .create_order(&order_args, Some(options.clone()))
+        .await
+        .unwrap();
+    assert_eq!(signed_order.fee_rate_bps, "1000");
+
+    // an explicit fee is left alone
+    let mut order_args = order_args;
+    order_args.fee_rate_bps = 10;
+    let signed_order = orders
+        .create_order(&order_args, Some(options.clone()))
+        .await
+        .unwrap();
+    assert_eq!(signed_order.fee_rate_bps, "10");
+
+    let orders = orders.with_auto_fee_rate(false);
+    order_args.fee_rate_bps = 0;
+    let signed_order = orders
+        .create_order(&order_args, Some(options))
+        .await
+        .unwrap();
+    assert_eq!(signed_order.fee_rate_bps, "0");
+}
+
+#[tokio::test]
+async fn test_auth_api_uses_nonce_provider() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/auth/api-key"))
+        .and(header("POLY_NONCE", "42"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // create fails, derive must reuse the same nonce
+    Mock::given(method("GET"))
+        .and(path("/auth/derive-api-key"))
+        .and(header("POLY_NONCE", "42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "apiKey": "derived-key",
+            "secret": "c2VjcmV0",
+            "passphrase": "pass"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let auth = AuthApi::new(Transport::new(mock_server.uri()), create_test_signer())
+        .with_nonce_provider(Arc::new(IncrementingNonce::new(42)));
+
+    let creds = auth.create_or_derive_api_key(None).await.unwrap();
+    assert_eq!(creds.api_key, "derived-key");
+}
+
+#[tokio::test]
+async fn test_find_markets_filters_across_pages() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/markets"))
+        .and(query_param("next_cursor", "MA=="))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "condition_id": "0xa",
+                    "question_id": "0xq1",
+                    "tokens": [],
+                    "active": true,
+                    "closed": false,
+                    "market_slug": "will-btc-hit-100k",
+                    "end_date_iso": "2025-01-01T00:00:00Z",
+                    "tags": ["Crypto"]
+                },
+                {
+                    "condition_id": "0xb",
+                    "question_id": "0xq2",
+                    "tokens": [],
+                    "active": true,
+                    "closed": true,
+                    "market_slug": "will-btc-hit-50k",
+                    "tags": ["Crypto"]
+                }
+            ],
+            "next_cursor": "MQ=="
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/markets"))
+        .and(query_param("next_cursor", "MQ=="))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {
+                    "condition_id": "0xc",
+                    "question_id": "0xq3",
+                    "tokens": [],
+                    "active": true,
+                    "closed": false,
+                    "market_slug": "will-BTC-hit-200k",
+                    "end_date_iso": "2026-01-01T00:00:00Z",
+                    "neg_risk": true,
+                    "tags": ["crypto"]
+                }
+            ],
+            "next_cursor": "LTE="
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let find = |filter| {
+        client
+            .markets()
+            .find_markets(filter)
+            .map_ok(|m| m.condition_id)
+            .try_collect::<Vec<_>>()
+    };
+
+    let filter = MarketFilter::new().slug_contains("btc").closed(false);
+    assert_eq!(find(filter).await.unwrap(), vec!["0xa", "0xc"]);
+
+    let filter = MarketFilter::new().tag("CRYPTO").neg_risk(true);
+    assert_eq!(find(filter).await.unwrap(), vec!["0xc"]);
+
+    let after = "2025-06-01T00:00:00Z".parse().unwrap();
+    let filter = MarketFilter::new().end_date_between(Some(after), None);
+    assert_eq!(find(filter).await.unwrap(), vec!["0xc"]);
+}
+
+#[tokio::test]
+async fn test_simplified_markets_follow_cursor() {
+    let mock_server = MockServer::start().await;
+
+    for (endpoint, prefix) in [
+        ("/simplified-markets", "0xs"),
+        ("/sampling-simplified-markets", "0xr"),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .and(query_param("next_cursor", "MA=="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"condition_id": format!("{}1", prefix), "tokens": []},
+                    {"condition_id": format!("{}2", prefix), "tokens": []}
+                ],
+                "next_cursor": "MQ=="
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .and(query_param("next_cursor", "MQ=="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"condition_id": format!("{}3", prefix), "tokens": [], "neg_risk": true}],
+                "next_cursor": "LTE="
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let client = ClobClient::new(mock_server.uri());
+    let markets = client.markets().get_simplified_markets().await.unwrap();
+    let ids: Vec<_> = markets.iter().map(|m| m.condition_id.as_str()).collect();
+    assert_eq!(ids, vec!["0xs1", "0xs2", "0xs3"]);
+    assert!(markets[2].neg_risk);
+
+    let sampling: Vec<_> = client
+        .markets()
+        .stream_sampling_simplified_markets()
+        .map_ok(|m| m.condition_id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(sampling, vec!["0xr1", "0xr2", "0xr3"]);
+}
+
+async fn mount_simplified_page(
+    mock_server: &MockServer,
+    cursor: &str,
+    ids: &[&str],
+    next_cursor: &str,
+) {
+    let data: Vec<_> = ids
+        .iter()
+        .map(|id| serde_json::json!({"condition_id": id, "tokens": []}))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path("/simplified-markets"))
+        .and(query_param("next_cursor", cursor))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": data,
+            "next_cursor": next_cursor
+        })))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_simplified_markets_concurrent_prefetches_pages() {
+    let mock_server = MockServer::start().await;
+    // offsets 0, 2, 4; the prefetched offset 6 is past the end and fails
+    mount_simplified_page(&mock_server, "MA==", &["0x1", "0x2"], "Mg==").await;
+    mount_simplified_page(&mock_server, "Mg==", &["0x3", "0x4"], "NA==").await;
+    mount_simplified_page(&mock_server, "NA==", &["0x5"], "LTE=").await;
+    Mock::given(method("GET"))
+        .and(path("/simplified-markets"))
+        .and(query_param("next_cursor", "Ng=="))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let markets = client
+        .markets()
+        .get_simplified_markets_concurrent(3)
+        .await
+        .unwrap();
+    let ids: Vec<_> = markets.iter().map(|m| m.condition_id.as_str()).collect();
+    assert_eq!(ids, vec!["0x1", "0x2", "0x3", "0x4", "0x5"]);
+
+    // cursors that aren't offsets are followed one page at a time
+    let mock_server = MockServer::start().await;
+    mount_simplified_page(&mock_server, "MA==", &["0x1"], "opaque").await;
+    mount_simplified_page(&mock_server, "opaque", &["0x2"], "LTE=").await;
+    let client = ClobClient::new(mock_server.uri());
+    let markets = client
+        .markets()
+        .get_simplified_markets_concurrent(3)
+        .await
+        .unwrap();
+    assert_eq!(markets.len(), 2);
+}
+
+#[tokio::test]
+async fn test_reward_markets_from_all_sampling_pages() {
+    let mock_server = MockServer::start().await;
+    let market = |id: &str, rates: serde_json::Value, max_spread: f64, accepting: bool| {
+        serde_json::json!({
+            "condition_id": id, "question_id": "0xq", "tokens": [],
+            "active": true, "closed": false, "accepting_orders": accepting,
+            "rewards": {"rates": rates, "min_size": 50, "max_spread": max_spread}
+        })
+    };
+    let rates = serde_json::json!([{"asset_address": "0xusdc", "rewards_daily_rate": 5}]);
+    Mock::given(method("GET"))
+        .and(path("/sampling-markets"))
+        .and(query_param("next_cursor", "MA=="))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                market("0xa", rates.clone(), 3.5, true),
+                market("0xb", serde_json::Value::Null, 3.5, true)
+            ],
+            "next_cursor": "MQ=="
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/sampling-markets"))
+        .and(query_param("next_cursor", "MQ=="))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                market("0xc", rates.clone(), 0.0, true),
+                market("0xd", rates.clone(), 2.0, false),
+                market("0xe", rates, 2.0, true)
+            ],
+            "next_cursor": "LTE="
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let sampling = client.markets().get_sampling_markets().await.unwrap();
+    assert_eq!(sampling.len(), 5);
+
+    let rewarded = client.markets().get_reward_markets().await.unwrap();
+    let ids: Vec<_> = rewarded.iter().map(|m| m.condition_id.as_str()).collect();
+    assert_eq!(ids, vec!["0xa", "0xe"]);
+
+    let rewards = rewarded[0].rewards.as_ref().unwrap();
+    assert!((rewards.max_spread_price() - 0.035).abs() < 1e-12);
+    assert!(rewards.is_within_max_spread(0.47, 0.5));
+    assert!(!rewards.is_within_max_spread(0.46, 0.5));
+}
+
+#[tokio::test]
+async fn test_get_token_ids_by_slug_and_condition_id() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [{
+                "condition_id": "0xabc",
+                "question_id": "0xq1",
+                "tokens": [
+                    {"token_id": "11", "outcome": "Yes", "price": 0.4},
+                    {"token_id": "12", "outcome": "No", "price": 0.6}
+                ],
+                "active": true,
+                "closed": false,
+                "market_slug": "will-x-happen"
+            }],
+            "next_cursor": "LTE="
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let expected = vec![
+        ("Yes".to_string(), "11".to_string()),
+        ("No".to_string(), "12".to_string()),
+    ];
+
+    assert_eq!(
+        client
+            .markets()
+            .get_token_ids("will-x-happen")
+            .await
+            .unwrap(),
+        expected
+    );
+    // served from the index; only the unknown slug below scans again
+    assert_eq!(
+        client
+            .markets()
+            .get_token_ids("will-x-happen")
+            .await
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        client.markets().get_token_ids("0xabc").await.unwrap(),
+        expected
+    );
+
+    assert!(client
+        .markets()
+        .get_token_ids("no-such-market")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_get_price_history() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/prices-history"))
+        .and(query_param("market", "123"))
+        .and(query_param("interval", "1d"))
+        .and(query_param("fidelity", "60"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "history": [
+                {"t": 1700000000, "p": 0.42},
+                {"t": 1700003600, "p": 0.45}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/prices-history"))
+        .and(query_param("startTs", "1700000000"))
+        .and(query_param("endTs", "1700003600"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"history": []})))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let history = client
+        .prices()
+        .get_price_history("123", PriceHistoryInterval::OneDay, Some(60))
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].t, 1700003600);
+    assert_eq!(amount_to_f64(history[1].p), 0.45);
+
+    let history = client
+        .prices()
+        .get_price_history_range("123", 1700000000, 1700003600, None)
+        .await
+        .unwrap();
+    assert!(history.is_empty());
+}
+
+#[test]
+fn test_market_game_metadata() {
+    let market: Market = serde_json::from_value(serde_json::json!({
+        "condition_id": "0xgame",
+        "question_id": "0xq",
+        "tokens": [],
+        "active": true,
+        "closed": false,
+        "seconds_delay": 3,
+        "game_start_time": "2024-11-10 18:00:00+00"
+    }))
+    .unwrap();
+
+    let start = market.game_start().unwrap();
+    assert_eq!(start.to_rfc3339(), "2024-11-10T18:00:00+00:00");
+    assert!(!market.is_in_play(start - chrono::Duration::minutes(1)));
+    assert!(market.is_in_play(start));
+}
+
+fn book_json(token_id: &str) -> serde_json::Value {
+    serde_json::json!({
+        "market": "0xmarket",
+        "asset_id": token_id,
+        "timestamp": "0",
+        "hash": "",
+        "bids": [],
+        "asks": []
+    })
+}
+
+#[tokio::test]
+async fn test_get_order_books_chunks_requests() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/books"))
+        .and(body_json(
+            serde_json::json!([{"token_id": "1"}, {"token_id": "2"}]),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!([book_json("1"), book_json("2")])),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/books"))
+        .and(body_json(
+            serde_json::json!([{"token_id": "3"}, {"token_id": "4"}]),
+        ))
+        .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/books"))
+        .and(body_json(serde_json::json!([{"token_id": "5"}])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([book_json("5")])))
+        .mount(&mock_server)
+        .await;
+
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri())).with_batch_size(2);
+    let params: Vec<_> = ["1", "2", "3", "4", "5"]
+        .into_iter()
+        .map(BookParams::new)
+        .collect();
+
+    let response = market_data.get_order_books_partial(&params).await;
+    let tokens: Vec<_> = response.data.iter().map(|b| b.asset_id.as_str()).collect();
+    assert_eq!(tokens, vec!["1", "2", "5"]);
+    assert_eq!(response.errors.len(), 1);
+    assert_eq!(response.errors[0].token_ids, vec!["3", "4"]);
+
+    assert!(market_data.get_order_books(&params).await.is_err());
+}
+
+#[tokio::test]
+async fn test_batch_results_in_request_order_and_keyed() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/midpoints"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "a": "0.1",
+            "b": "0.2",
+            "c": "0.3"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/prices"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "a": {"BUY": "0.1", "SELL": "0.15"},
+            "c": {"BUY": "0.3"}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let params: Vec<_> = ["c", "a", "b"].into_iter().map(BookParams::new).collect();
+
+    let mids = client.prices().get_midpoints(&params).await.unwrap();
+    let order: Vec<_> = mids.iter().map(|m| m.token_id.as_str()).collect();
+    assert_eq!(order, vec!["c", "a", "b"]);
+
+    let mids = client
+        .prices()
+        .get_midpoints_by_token(&params)
+        .await
+        .unwrap();
+    assert_eq!(mids["b"].mid.map(amount_to_f64), Some(0.2));
+
+    let prices = client.prices().get_prices_by_token(&params).await.unwrap();
+    assert_eq!(prices["a"].sell.map(amount_to_f64), Some(0.15));
+    assert_eq!(prices["c"].sell, None);
+    assert!(!prices.contains_key("b"));
+}
+
+#[tokio::test]
+async fn test_typed_api_key_responses() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/auth/api-keys"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"apiKeys": ["key-1", "key-2"]})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/auth/api-key"))
+        .and(header("POLY_API_KEY", "other-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json("OK"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let auth = AuthApi::new(Transport::new(mock_server.uri()), create_test_signer())
+        .with_creds(create_test_creds());
+
+    let keys = auth.get_api_keys().await.unwrap();
+    assert_eq!(keys.api_keys, vec!["key-1", "key-2"]);
+
+    let mut other = create_test_creds();
+    other.api_key = "other-key".to_string();
+    let response = auth.revoke_api_key(&other).await.unwrap();
+    assert!(response.success);
+}
+
+#[tokio::test]
+async fn test_dry_run_records_instead_of_sending() {
+    let mock_server = MockServer::start().await;
+    mount_fee_rate(&mock_server, 0).await;
+
+    Mock::given(method("POST"))
+        .and(path("/order"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let log = DryRunLog::new();
+    let client = ClobClient::new(mock_server.uri())
+        .with_dry_run(log.clone())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap();
+    let orders = client.orders().unwrap();
+
+    let order_args = clob_rs::OrderArgs::new("123456", 0.5, 100.0, clob_rs::Side::Buy);
+    let options = clob_rs::PartialCreateOrderOptions {
+        tick_size: Some(TickSize::Size0_01),
+        neg_risk: Some(false),
+        salt: None,
+    };
+    let signed_order = orders
+        .create_order(&order_args, Some(options))
+        .await
+        .unwrap();
+
+    let response = orders
+        .post_order(&signed_order, OrderType::GTC)
+        .await
+        .unwrap();
+    assert!(response.success);
+    assert_eq!(response.order_id, "dry-run-1");
+
+    let canceled = orders.cancel("dry-run-1").await.unwrap();
+    assert_eq!(canceled["canceled"][0], "dry-run-1");
+
+    let requests = log.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/order");
+    assert_eq!(requests[0].body.as_ref().unwrap()["orderType"], "GTC");
+    assert_eq!(requests[1].method, "DELETE");
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn test_decimal_amounts_are_exact() {
+    use clob_rs::{Amount, OrderSummary, Token};
+
+    let amount = |s: &str| s.parse::<Amount>().unwrap();
+    let token: Token = serde_json::from_value(serde_json::json!({
+        "token_id": "1",
+        "outcome": "Yes",
+        "price": 0.1
+    }))
+    .unwrap();
+    let level = OrderSummary {
+        price: "0.2".to_string(),
+        size: "100".to_string(),
+    };
+    assert_eq!(token.price + level.price_amount().unwrap(), amount("0.3"));
+    assert_eq!(level.size_amount(), Some(amount("100")));
+}
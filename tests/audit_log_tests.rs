@@ -0,0 +1,93 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use clob_rs::{
+    ApiCreds, AuditLog, MarketDataApi, OrderArgs, OrderBuilder, OrderType, OrdersApi,
+    PartialCreateOrderOptions, Side, Signer, TickSize, Transport,
+};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::temp_dir;
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+#[tokio::test]
+async fn test_audit_log_records_order_flow() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/order"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"success": true, "orderID": "0xabc"})),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/order"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("down"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/fee-rate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"base_fee": 0})))
+        .mount(&mock_server)
+        .await;
+
+    let file = temp_dir("audit").join("audit.jsonl");
+    let _ = std::fs::remove_file(&file);
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let signer = Signer::new(TEST_PRIVATE_KEY, 137).unwrap();
+    let orders = OrdersApi::new(market_data, OrderBuilder::new(signer))
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        })
+        .with_audit_log(AuditLog::open(&file).unwrap());
+
+    let options = PartialCreateOrderOptions {
+        tick_size: Some(TickSize::Size0_01),
+        neg_risk: Some(false),
+        salt: None,
+    };
+    let order = orders
+        .create_order(&OrderArgs::new("123", 0.5, 10.0, Side::Buy), Some(options))
+        .await
+        .unwrap();
+    orders.post_order(&order, OrderType::GTC).await.unwrap();
+    assert!(orders.cancel("0xabc").await.is_err());
+
+    // reopening continues the chain
+    AuditLog::open(&file)
+        .unwrap()
+        .record("note", &serde_json::json!({"text": "restart"}))
+        .unwrap();
+
+    let entries = AuditLog::verify(&file).unwrap();
+    let events: Vec<&str> = entries.iter().map(|e| e.event.as_str()).collect();
+    assert_eq!(
+        events,
+        vec![
+            "sign_order",
+            "post_order",
+            "response",
+            "cancel",
+            "response",
+            "note"
+        ]
+    );
+    assert_eq!(entries[0].data["signature"], "[redacted]");
+    assert_eq!(entries[1].data["order"]["signature"], "[redacted]");
+    assert_eq!(entries[1].data["orderType"], "GTC");
+    assert_eq!(entries[2].data["request_seq"], 2);
+    assert_eq!(entries[2].data["response"]["orderID"], "0xabc");
+    assert_eq!(entries[3].data["orderID"], "0xabc");
+    assert!(entries[4].data["error"].as_str().unwrap().contains("down"));
+
+    let contents = std::fs::read_to_string(&file).unwrap();
+    std::fs::write(&file, contents.replacen("\"GTC\"", "\"FOK\"", 1)).unwrap();
+    let err = AuditLog::verify(&file).unwrap_err();
+    assert!(err.to_string().contains("line 2"), "{}", err);
+}
@@ -0,0 +1,138 @@
+use std::io::Cursor;
+
+use clob_rs::backtest::{read_capture, write_capture_event, Backtest, CaptureEvent, PaperEngine};
+use clob_rs::{OrderBook, Side};
+
+fn book(ts: u64, bids: &[(&str, &str)], asks: &[(&str, &str)]) -> CaptureEvent {
+    let levels = |levels: &[(&str, &str)]| -> Vec<serde_json::Value> {
+        levels
+            .iter()
+            .map(|(price, size)| serde_json::json!({"price": price, "size": size}))
+            .collect()
+    };
+    let book: OrderBook = serde_json::from_value(serde_json::json!({
+        "market": "0xmarket",
+        "asset_id": "1",
+        "timestamp": ts.to_string(),
+        "hash": "",
+        "bids": levels(bids),
+        "asks": levels(asks)
+    }))
+    .unwrap();
+    CaptureEvent::Book { ts, book }
+}
+
+fn trade(ts: u64, side: Side, price: f64, size: f64) -> CaptureEvent {
+    CaptureEvent::Trade {
+        ts,
+        token_id: "1".to_string(),
+        side,
+        price,
+        size,
+    }
+}
+
+#[test]
+fn test_capture_round_trip() {
+    let events = vec![
+        book(1, &[("0.40", "100")], &[("0.45", "50")]),
+        trade(2, Side::Buy, 0.45, 10.0),
+    ];
+
+    let mut buf = Vec::new();
+    for event in &events {
+        write_capture_event(&mut buf, event).unwrap();
+    }
+    let line = String::from_utf8(buf.clone()).unwrap();
+    assert!(line.lines().nth(1).unwrap().contains("\"type\":\"trade\""));
+
+    let read: Vec<_> = read_capture(Cursor::new(buf))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(read, events);
+
+    let bad: Vec<_> = read_capture(Cursor::new("{\"type\":\"nope\"}\n")).collect();
+    assert!(bad[0].is_err());
+}
+
+#[test]
+fn test_paper_engine_fills() {
+    let mut engine = PaperEngine::new();
+    engine
+        .apply(&book(
+            1,
+            &[("0.40", "100")],
+            &[("0.45", "50"), ("0.50", "50")],
+        ))
+        .unwrap();
+
+    // crosses both ask levels, rests the remainder
+    engine.place_limit("1", Side::Buy, 0.50, 120.0).unwrap();
+    assert_eq!(engine.fills().len(), 2);
+    assert!(engine.fills().iter().all(|f| f.taker));
+    assert_eq!(engine.position("1"), 100.0);
+    assert!((engine.cash() + 47.5).abs() < 1e-9);
+    assert_eq!(engine.open_orders()[0].remaining, 20.0);
+
+    // the taken liquidity is gone until the next snapshot
+    let id = engine.place_limit("1", Side::Buy, 0.50, 5.0).unwrap();
+    assert_eq!(engine.open_orders().len(), 2);
+    assert!(engine.cancel(id));
+
+    engine.apply(&trade(5, Side::Sell, 0.49, 15.0)).unwrap();
+    assert_eq!(engine.now(), 5);
+    assert_eq!(engine.open_orders()[0].remaining, 5.0);
+    let last = engine.fills().last().unwrap();
+    assert!(!last.taker);
+    assert_eq!(last.price, 0.50);
+    assert_eq!(last.ts, 5);
+
+    engine
+        .apply(&book(7, &[("0.40", "100")], &[("0.48", "100")]))
+        .unwrap();
+    assert!(engine.open_orders().is_empty());
+    assert_eq!(engine.position("1"), 120.0);
+
+    assert!(engine.place_limit("1", Side::Sell, 1.5, 1.0).is_err());
+}
+
+#[test]
+fn test_backtest_run_reports_pnl() {
+    let capture = [
+        book(1_000, &[("0.40", "100")], &[("0.42", "100")]),
+        book(2_000, &[("0.50", "100")], &[("0.52", "100")]),
+        book(3_000, &[("0.30", "100")], &[("0.32", "100")]),
+    ];
+    let mut buf = Vec::new();
+    for event in &capture {
+        write_capture_event(&mut buf, event).unwrap();
+    }
+
+    let mut seen = Vec::new();
+    let mut backtest = Backtest::new(|event: &CaptureEvent, engine: &mut PaperEngine| {
+        seen.push(engine.now());
+        match event.ts() {
+            1_000 => {
+                engine.place_limit("1", Side::Buy, 0.42, 10.0).unwrap();
+            }
+            2_000 => {
+                engine.place_limit("1", Side::Sell, 0.50, 10.0).unwrap();
+            }
+            _ => {}
+        }
+    });
+    let report = backtest.run_capture(Cursor::new(buf)).unwrap();
+    drop(backtest);
+
+    assert_eq!(seen, vec![1_000, 2_000, 3_000]);
+    assert_eq!(report.events, 3);
+    assert_eq!(report.start_ts, Some(1_000));
+    assert_eq!(report.end_ts, Some(3_000));
+    assert_eq!(report.orders_placed, 2);
+    assert_eq!(report.taker_fills(), 2);
+    assert_eq!(report.maker_fills(), 0);
+    assert_eq!(report.volume, 20.0);
+    assert!((report.pnl - 0.8).abs() < 1e-9);
+    assert_eq!(report.positions["1"], 0.0);
+    assert!((report.max_drawdown - 0.1).abs() < 1e-9);
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "http")]
+
+use std::time::Duration;
+
+use clob_rs::{candles, candles_from_history, Candle, PricePoint, Side, TradePrint};
+use futures::{stream, StreamExt};
+
+fn print(timestamp: u64, price: f64, size: f64) -> TradePrint {
+    TradePrint {
+        token_id: "1".to_string(),
+        price,
+        size,
+        side: Side::Buy,
+        timestamp,
+    }
+}
+
+#[tokio::test]
+async fn test_candles_from_prints() {
+    let prints = stream::iter(vec![
+        print(60_000, 0.50, 10.0),
+        print(61_000, 0.55, 5.0),
+        print(62_000, 0.45, 1.0),
+        print(119_999, 0.48, 2.0),
+        // late print for a closed interval
+        print(59_000, 0.90, 1.0),
+        print(240_500, 0.60, 3.0),
+    ]);
+    let out: Vec<Candle> = candles(prints, Duration::from_secs(60)).collect().await;
+
+    assert_eq!(
+        out,
+        vec![
+            Candle {
+                token_id: "1".to_string(),
+                start: 60_000,
+                open: 0.50,
+                high: 0.55,
+                low: 0.45,
+                close: 0.48,
+                volume: 18.0,
+                trades: 4,
+            },
+            Candle {
+                token_id: "1".to_string(),
+                start: 240_000,
+                open: 0.60,
+                high: 0.60,
+                low: 0.60,
+                close: 0.60,
+                volume: 3.0,
+                trades: 1,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_candles_from_history() {
+    let history: Vec<PricePoint> = serde_json::from_value(serde_json::json!([
+        {"t": 3_700, "p": 0.4},
+        {"t": 3_600, "p": 0.5},
+        {"t": 7_200, "p": 0.3}
+    ]))
+    .unwrap();
+    let out = candles_from_history("1", &history, Duration::from_secs(3600));
+
+    let summary: Vec<(u64, f64, f64, u32)> = out
+        .iter()
+        .map(|c| (c.start, c.open, c.close, c.trades))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(3_600_000, 0.5, 0.4, 2), (7_200_000, 0.3, 0.3, 1)]
+    );
+}
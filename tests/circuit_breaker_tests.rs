@@ -0,0 +1,148 @@
+#![cfg(feature = "http")]
+
+use std::time::Duration;
+
+use clob_rs::{ApiCreds, CircuitBreaker, CircuitState, ClobClient, ClobError, KillSwitch};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+async fn requests_to(server: &MockServer, route: &str) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == route)
+        .count()
+}
+
+#[tokio::test]
+async fn test_breaker_opens_fails_fast_and_closes_after_probe() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(502).set_body_string("bad gateway"))
+        .up_to_n_times(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": "0.5"})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json("OK"))
+        .mount(&mock_server)
+        .await;
+
+    let breaker = CircuitBreaker::new(2, Duration::from_millis(100));
+    let client = ClobClient::new(mock_server.uri()).with_circuit_breaker(breaker.clone());
+
+    for _ in 0..2 {
+        let err = client.prices().get_midpoint("123").await.unwrap_err();
+        assert!(matches!(err, ClobError::Api { .. }), "{}", err);
+    }
+    assert_eq!(breaker.state(), CircuitState::Open);
+    let err = client.prices().get_midpoint("123").await.unwrap_err();
+    assert!(matches!(err, ClobError::CircuitOpen { .. }), "{}", err);
+    assert_eq!(requests_to(&mock_server, "/midpoint").await, 2);
+
+    // the first probe fails, so the breaker stays open
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let err = client.prices().get_midpoint("123").await.unwrap_err();
+    assert!(
+        matches!(err, ClobError::CircuitOpen { retry_in } if retry_in > Duration::ZERO),
+        "{}",
+        err
+    );
+    assert_eq!(requests_to(&mock_server, "/").await, 1);
+    assert_eq!(requests_to(&mock_server, "/midpoint").await, 2);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(client.prices().get_midpoint("123").await.unwrap(), 0.5);
+    assert_eq!(breaker.state(), CircuitState::Closed);
+    assert_eq!(requests_to(&mock_server, "/").await, 2);
+}
+
+#[tokio::test]
+async fn test_slow_calls_count_as_failures() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"mid": "0.5"}))
+                .set_delay(Duration::from_millis(50)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let breaker =
+        CircuitBreaker::new(1, Duration::from_secs(60)).with_slow_call(Duration::from_millis(10));
+    let client = ClobClient::new(mock_server.uri()).with_circuit_breaker(breaker.clone());
+
+    // the slow answer still comes back, but trips the breaker
+    assert_eq!(client.prices().get_midpoint("123").await.unwrap(), 0.5);
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert!(matches!(
+        client.prices().get_midpoint("123").await,
+        Err(ClobError::CircuitOpen { .. })
+    ));
+
+    breaker.reset();
+    assert_eq!(client.prices().get_midpoint("123").await.unwrap(), 0.5);
+}
+
+#[tokio::test]
+async fn test_open_breaker_lets_kill_switch_cancel() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(502))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/cancel-all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "canceled": ["0x1"],
+            "not_canceled": {}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+    let client = ClobClient::new(mock_server.uri())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        })
+        .with_circuit_breaker(breaker.clone());
+    assert!(client.prices().get_midpoint("123").await.is_err());
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    let report = client
+        .orders()
+        .unwrap()
+        .kill_switch_with(KillSwitch::new().with_attempts(1))
+        .await;
+
+    assert_eq!(report.canceled, vec!["0x1"]);
+    // listing open orders is still held back
+    assert!(!report.verified);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert_eq!(requests_to(&mock_server, "/data/orders").await, 0);
+}
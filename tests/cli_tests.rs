@@ -0,0 +1,58 @@
+use std::process::Command;
+
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn clob(host: &str, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_clob"))
+        .env_clear()
+        .arg("--host")
+        .arg(host)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_cli_book() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .and(query_param("token_id", "123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "market": "0xmarket",
+            "asset_id": "123",
+            "timestamp": "0",
+            "hash": "",
+            "bids": [{"price": "0.40", "size": "100"}, {"price": "0.45", "size": "10"}],
+            "asks": [{"price": "0.50", "size": "25"}]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let output = clob(&mock_server.uri(), &["book", "123", "--depth", "1"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("0.5000        25.00  ask"));
+    assert!(stdout.contains("0.4500        10.00  bid"));
+    assert!(!stdout.contains("0.4000"));
+    assert!(stdout.contains("spread 0.0500"));
+}
+
+#[tokio::test]
+async fn test_cli_requires_credentials() {
+    let mock_server = MockServer::start().await;
+
+    let output = clob(&mock_server.uri(), &["cancel-all"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("error: "));
+
+    let output = clob(&mock_server.uri(), &["--api-key", "k", "cancel-all"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("must be set together"));
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use clob_rs::{ApiCreds, ClobClient, ClobConfig, SecretString};
+
+use common::temp_dir;
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const EXPECTED_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+#[test]
+fn test_config_from_file() {
+    let dir = temp_dir("config");
+    let path = dir.join("clob.toml");
+    std::fs::write(
+        &path,
+        format!(
+            r#"
+host = "http://localhost:8080"
+chain_id = 80002
+private_key = "{}"
+signature_type = 1
+
+[creds]
+api_key = "key"
+api_secret = "c2VjcmV0"
+api_passphrase = "pass"
+"#,
+            TEST_PRIVATE_KEY
+        ),
+    )
+    .unwrap();
+
+    let config = ClobConfig::from_file(&path).unwrap();
+    assert_eq!(config.host, "http://localhost:8080");
+    assert_eq!(config.chain_id, 80002);
+    assert_eq!(config.signature_type, Some(1));
+    assert_eq!(config.creds.as_ref().unwrap().api_key, "key");
+    assert!(config.funder.is_none());
+
+    let debug = format!("{:?}", config);
+    assert!(!debug.contains(&TEST_PRIVATE_KEY[2..]));
+    assert!(debug.contains("<redacted>"));
+
+    // defaults, and a creds file next to the config
+    ApiCreds {
+        api_key: "file-key".to_string(),
+        api_secret: "c2VjcmV0".into(),
+        api_passphrase: "pass".into(),
+    }
+    .save(dir.join("creds.json"), None)
+    .unwrap();
+    std::fs::write(&path, "creds_file = \"creds.json\"\n").unwrap();
+    let config = ClobConfig::from_file(&path).unwrap();
+    assert_eq!(config.host, clob_rs::config::HOST);
+    assert_eq!(config.chain_id, 137);
+    assert_eq!(config.creds_file, Some(dir.join("creds.json")));
+    assert_eq!(config.resolve_creds().unwrap().unwrap().api_key, "file-key");
+
+    std::fs::write(&path, "hots = \"typo\"\n").unwrap();
+    assert!(ClobConfig::from_file(&path).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_config_from_env() {
+    std::env::set_var("CLOB_HOST", "http://localhost:9000");
+    std::env::set_var(
+        "CLOB_FALLBACK_HOSTS",
+        "http://localhost:9001, http://localhost:9002",
+    );
+    std::env::set_var("CLOB_PRIVATE_KEY", TEST_PRIVATE_KEY);
+    std::env::set_var("CLOB_SIGNATURE_TYPE", "2");
+    std::env::set_var("CLOB_API_KEY", "key");
+
+    // a partial set of creds is rejected
+    assert!(ClobConfig::from_env().is_err());
+
+    std::env::set_var("CLOB_API_SECRET", "c2VjcmV0");
+    std::env::set_var("CLOB_API_PASSPHRASE", "pass");
+    let config = ClobConfig::from_env().unwrap();
+    assert_eq!(config.host, "http://localhost:9000");
+    assert_eq!(
+        config.fallback_hosts,
+        ["http://localhost:9001", "http://localhost:9002"]
+    );
+    assert_eq!(config.chain_id, 137);
+    assert_eq!(
+        config.private_key.as_ref().map(SecretString::expose),
+        Some(TEST_PRIVATE_KEY)
+    );
+    assert_eq!(config.signature_type, Some(2));
+    assert_eq!(config.creds.unwrap().api_passphrase.expose(), "pass");
+
+    std::env::set_var("CLOB_SIGNATURE_TYPE", "proxy");
+    assert!(ClobConfig::from_env().is_err());
+}
+
+#[test]
+fn test_client_from_config() {
+    let config = ClobConfig {
+        private_key: Some(TEST_PRIVATE_KEY.into()),
+        funder: Some("0x0000000000000000000000000000000000000001".to_string()),
+        creds: Some(ApiCreds {
+            api_key: "key".to_string(),
+            api_secret: "c2VjcmV0".into(),
+            api_passphrase: "pass".into(),
+        }),
+        ..ClobConfig::default()
+    };
+    let client = ClobClient::from_config(&config).unwrap();
+    assert_eq!(client.address().unwrap(), EXPECTED_ADDRESS);
+    assert!(client.orders().is_ok());
+
+    let client = ClobClient::from_config(&ClobConfig::default()).unwrap();
+    assert!(client.address().is_none());
+
+    let config = ClobConfig {
+        private_key: Some(TEST_PRIVATE_KEY.into()),
+        funder: Some("not an address".to_string()),
+        ..ClobConfig::default()
+    };
+    assert!(ClobClient::from_config(&config).is_err());
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::client;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+async fn mount_server_time(server: &MockServer, ahead: u64) {
+    Mock::given(method("GET"))
+        .and(path("/time"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(now() + ahead))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_sync_time_offsets_auth_timestamps() {
+    let mock_server = MockServer::start().await;
+    mount_server_time(&mock_server, 120).await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&mock_server)
+        .await;
+
+    let client = client(mock_server.uri());
+    let offset = client.sync_time().await.unwrap();
+    assert!((119..=121).contains(&offset), "offset {}", offset);
+
+    client.orders().unwrap().get_orders(None).await.unwrap();
+    let requests = mock_server.received_requests().await.unwrap();
+    let sent = requests.last().unwrap().headers[&"poly_timestamp".into()]
+        .last()
+        .as_str()
+        .parse::<u64>()
+        .unwrap();
+    assert!(sent.abs_diff(now() + 120) <= 2);
+}
+
+#[tokio::test]
+async fn test_timestamp_rejection_resyncs_clock() {
+    let mock_server = MockServer::start().await;
+    mount_server_time(&mock_server, 60).await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("invalid timestamp"))
+        .mount(&mock_server)
+        .await;
+
+    // without time sync a rejection leaves the clock alone
+    let client = client(mock_server.uri());
+    assert!(client.orders().unwrap().get_orders(None).await.is_err());
+    assert_eq!(client.clock().offset(), 0);
+
+    let client = client.with_time_sync();
+    assert!(client.orders().unwrap().get_orders(None).await.is_err());
+    assert!((59..=61).contains(&client.clock().offset()));
+}
+
+#[tokio::test]
+async fn test_drift_monitor_tracks_skew_of_signing_clock() {
+    let mock_server = MockServer::start().await;
+    mount_server_time(&mock_server, 30).await;
+
+    let client = client(mock_server.uri());
+    let monitor = client.spawn_drift_monitor(Duration::from_millis(20), Duration::from_secs(5));
+    let stats = loop {
+        match monitor.stats() {
+            Some(stats) if stats.samples >= 3 => break stats,
+            _ => tokio::time::sleep(Duration::from_millis(10)).await,
+        }
+    };
+    assert!((stats.skew_secs - 30.0).abs() <= 1.5, "{:?}", stats);
+    assert!(stats.jitter_secs < 1.0, "{:?}", stats);
+    assert!(stats.exceeds(Duration::from_secs(5)));
+
+    // once the clock is synced the remaining skew is within tolerance
+    client.sync_time().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let stats = monitor.stats().unwrap();
+    assert!(!stats.exceeds(Duration::from_secs(5)), "{:?}", stats);
+}
@@ -0,0 +1,59 @@
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+#[cfg(feature = "http")]
+use clob_rs::{ApiCreds, ClobClient};
+use clob_rs::{OrderBook, OrderSummary};
+
+pub const TEST_PRIVATE_KEY: &str =
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// An empty directory for `name`, unique to this test run.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("clob-rs-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A client with a signer and L2 creds, talking to `uri`.
+#[cfg(feature = "http")]
+pub fn client(uri: String) -> ClobClient {
+    ClobClient::new(uri)
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        })
+}
+
+pub fn level(price: &str, size: &str) -> OrderSummary {
+    OrderSummary {
+        price: price.to_string(),
+        size: size.to_string(),
+    }
+}
+
+/// A book for `asset_id` in "0xmarket" with the given levels and market flags.
+pub fn book(
+    asset_id: &str,
+    bids: Vec<OrderSummary>,
+    asks: Vec<OrderSummary>,
+    tick_size: Option<&str>,
+    neg_risk: Option<bool>,
+) -> OrderBook {
+    OrderBook {
+        market: "0xmarket".to_string(),
+        asset_id: asset_id.to_string(),
+        timestamp: "0".to_string(),
+        hash: "".to_string(),
+        bids,
+        asks,
+        min_order_size: None,
+        tick_size: tick_size.map(str::to_string),
+        neg_risk,
+    }
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "http")]
+
+use std::path::PathBuf;
+
+use clob_rs::{ApiCreds, ClobClient, SecretString};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("clob-rs-{}-{}.json", std::process::id(), name))
+}
+
+fn create_test_creds() -> ApiCreds {
+    ApiCreds {
+        api_key: "test-api-key".to_string(),
+        api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+        api_passphrase: "test-passphrase".into(),
+    }
+}
+
+#[test]
+fn test_creds_plain_round_trip() {
+    let path = temp_path("plain");
+    create_test_creds().save(&path, None).unwrap();
+
+    let loaded = ApiCreds::load(&path, None).unwrap();
+    // a passphrase means the file must be encrypted, so no silent downgrade
+    assert!(ApiCreds::load(&path, Some("hunter2")).is_err());
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.api_key, "test-api-key");
+    assert_eq!(loaded.api_secret.expose(), "dGVzdC1hcGktc2VjcmV0");
+    assert_eq!(loaded.api_passphrase.expose(), "test-passphrase");
+}
+
+#[test]
+fn test_secrets_redacted_from_debug() {
+    let debug = format!("{:?}", create_test_creds());
+    assert!(debug.contains("test-api-key"));
+    assert!(!debug.contains("dGVzdC1hcGktc2VjcmV0"));
+    assert!(!debug.contains("test-passphrase"));
+
+    // no Drop impl, so struct update syntax still moves the secrets over
+    let renamed = ApiCreds {
+        api_key: "other-key".to_string(),
+        ..create_test_creds()
+    };
+    assert_eq!(renamed.api_secret.expose(), "dGVzdC1hcGktc2VjcmV0");
+
+    let key = SecretString::from(TEST_PRIVATE_KEY);
+    assert_eq!(format!("{:?}", key), "<redacted>");
+    assert_eq!(key.expose(), TEST_PRIVATE_KEY);
+    assert_eq!(
+        serde_json::to_string(&key).unwrap(),
+        format!("\"{}\"", TEST_PRIVATE_KEY)
+    );
+}
+
+#[test]
+fn test_creds_encrypted_round_trip() {
+    let path = temp_path("encrypted");
+    create_test_creds().save(&path, Some("hunter2")).unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("test-api-key"));
+    assert!(!contents.contains("dGVzdC1hcGktc2VjcmV0"));
+
+    assert!(ApiCreds::load(&path, None).is_err());
+    assert!(ApiCreds::load(&path, Some("wrong")).is_err());
+
+    let loaded = ApiCreds::load(&path, Some("hunter2")).unwrap();
+    assert_eq!(loaded.api_key, "test-api-key");
+    assert_eq!(loaded.api_secret.expose(), "dGVzdC1hcGktc2VjcmV0");
+
+    // an unbounded round count would stall the load
+    let mut file: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(file["cipher"], "chacha20poly1305");
+    file["iterations"] = u32::MAX.into();
+    std::fs::write(&path, file.to_string()).unwrap();
+    let err = ApiCreds::load(&path, Some("hunter2")).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(err.to_string().contains("iterations"), "{}", err);
+}
+
+#[test]
+fn test_client_with_creds_file() {
+    let path = temp_path("client");
+    create_test_creds().save(&path, Some("hunter2")).unwrap();
+
+    let client = ClobClient::polygon()
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds_file(&path, Some("hunter2"))
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        client.orders().unwrap().creds().unwrap().api_key,
+        "test-api-key"
+    );
+    assert!(ClobClient::polygon()
+        .with_creds_file(temp_path("missing"), None)
+        .is_err());
+}
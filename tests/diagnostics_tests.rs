@@ -0,0 +1,129 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::sync::Arc;
+
+use clob_rs::diagnostics::{compare_books, BookCrossCheck};
+use clob_rs::{MarketDataApi, MarketDataService, QuoteSource, Transport};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{book, level};
+
+#[test]
+fn test_compare_identical_books() {
+    let a = book(
+        "1",
+        vec![level("0.4", "10")],
+        vec![level("0.6", "5")],
+        None,
+        None,
+    );
+    let divergence = compare_books(&a, &a.clone());
+
+    assert!(divergence.is_consistent());
+    assert_eq!(divergence.best_bid_diff, Some(0.0));
+}
+
+#[test]
+fn test_compare_divergent_books() {
+    let local = book(
+        "1",
+        vec![level("0.4", "10"), level("0.39", "3")],
+        vec![level("0.6", "5")],
+        None,
+        None,
+    );
+    let rest = book(
+        "1",
+        vec![level("0.41", "1"), level("0.4", "12")],
+        vec![level("0.6", "5")],
+        None,
+        None,
+    );
+    let divergence = compare_books(&local, &rest);
+
+    assert!(!divergence.is_consistent());
+    assert_eq!(divergence.size_mismatches, 1);
+    assert_eq!(divergence.missing_levels, 2);
+    assert!((divergence.best_bid_diff.unwrap() + 0.01).abs() < 1e-9);
+    assert_eq!(divergence.best_ask_diff, Some(0.0));
+}
+
+#[tokio::test]
+async fn test_cross_check_records_stats() {
+    let mock_server = MockServer::start().await;
+    let rest_books = vec![
+        book(
+            "1",
+            vec![level("0.4", "10")],
+            vec![level("0.6", "5")],
+            None,
+            None,
+        ),
+        book(
+            "2",
+            vec![level("0.2", "10")],
+            vec![level("0.3", "5")],
+            None,
+            None,
+        ),
+    ];
+
+    Mock::given(method("POST"))
+        .and(path("/books"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&rest_books))
+        .mount(&mock_server)
+        .await;
+
+    let local = Arc::new(rest_books[0].clone());
+    let source = move |token_id: &str| (token_id == "1").then(|| (*local).clone());
+
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let mut check = BookCrossCheck::new(market_data, source, vec!["1".into(), "2".into()]);
+    let divergences = check.check_once().await.unwrap();
+
+    assert_eq!(divergences.len(), 2);
+    let stats = check.stats();
+    assert_eq!(stats.samples, 2);
+    assert_eq!(stats.divergent, 1);
+    assert_eq!(stats.missing_local, 1);
+    assert!(stats.last["1"].is_consistent());
+    assert_eq!(stats.divergence_rate(), 0.5);
+}
+
+#[tokio::test]
+async fn test_market_data_service_prefers_live_state() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .and(query_param("token_id", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": "0.25"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let live = book(
+        "1",
+        vec![level("0.38", "3"), level("0.4", "10")],
+        vec![level("0.62", "1"), level("0.6", "5")],
+        None,
+        None,
+    );
+    let source = move |token_id: &str| (token_id == "1").then(|| live.clone());
+    let service = MarketDataService::new(MarketDataApi::new(Transport::new(mock_server.uri())))
+        .with_live_source(source);
+
+    let bid = service.best_bid("1").await.unwrap().unwrap();
+    assert_eq!((bid.price, bid.source), (0.4, QuoteSource::Live));
+    let ask = service.best_ask("1").await.unwrap().unwrap();
+    assert_eq!(ask.price, 0.6);
+    let mid = service.midpoint("1").await.unwrap();
+    assert!((mid.price - 0.5).abs() < 1e-9);
+    assert_eq!(mid.source, QuoteSource::Live);
+
+    let mid = service.midpoint("2").await.unwrap();
+    assert_eq!((mid.price, mid.source), (0.25, QuoteSource::Rest));
+}
@@ -0,0 +1,94 @@
+use clob_rs::export::{write_csv, PositionRecord};
+use clob_rs::{Fill, Portfolio, Side, TradePrint};
+
+fn fill(id: &str, side: Side, price: f64) -> Fill {
+    Fill {
+        id: id.to_string(),
+        token_id: "123".to_string(),
+        side,
+        price,
+        size: 10.0,
+        fee: 0.1,
+    }
+}
+
+#[test]
+fn test_fills_csv() {
+    let mut out = Vec::new();
+    write_csv(
+        &mut out,
+        &[fill("a", Side::Buy, 0.4), fill("b,\"c\"", Side::Sell, 0.5)],
+    )
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "id,token_id,side,price,size,fee\n\
+         a,123,BUY,0.4,10,0.1\n\
+         \"b,\"\"c\"\"\",123,SELL,0.5,10,0.1\n"
+    );
+}
+
+#[test]
+fn test_trade_prints_and_positions_csv() {
+    let print = TradePrint {
+        token_id: "123".to_string(),
+        price: 0.45,
+        size: 5.0,
+        side: Side::Buy,
+        timestamp: 1_700_000_000_000,
+    };
+    let mut out = Vec::new();
+    write_csv(&mut out, &[print]).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "timestamp_ms,token_id,side,price,size\n1700000000000,123,BUY,0.45,5\n"
+    );
+
+    let mut portfolio = Portfolio::new();
+    portfolio.apply_fill(&fill("a", Side::Buy, 0.4));
+    let mut history = PositionRecord::from_snapshot(1_000, &portfolio.snapshot());
+    portfolio.set_mark("123", 0.5);
+    history.extend(PositionRecord::from_snapshot(2_000, &portfolio.snapshot()));
+
+    let mut out = Vec::new();
+    write_csv(&mut out, &history).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "timestamp_ms,token_id,size,avg_cost,mark,realized_pnl,unrealized_pnl,fees"
+    );
+    assert_eq!(lines[1], "1000,123,10,0.4,,-0.1,0,0.1");
+    assert!(lines[2].starts_with("2000,123,10,0.4,0.5,-0.1,"));
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_positions_parquet() {
+    use clob_rs::export::write_parquet;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+
+    let mut portfolio = Portfolio::new();
+    portfolio.apply_fill(&fill("a", Side::Buy, 0.4));
+    let mut history = PositionRecord::from_snapshot(1_000, &portfolio.snapshot());
+    portfolio.set_mark("123", 0.5);
+    history.extend(PositionRecord::from_snapshot(2_000, &portfolio.snapshot()));
+
+    let path =
+        std::env::temp_dir().join(format!("clob-rs-{}-positions.parquet", std::process::id()));
+    write_parquet(std::fs::File::create(&path).unwrap(), &history).unwrap();
+
+    let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let rows: Vec<_> = reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| row.unwrap())
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get_long(0).unwrap(), 1_000);
+    assert_eq!(rows[0].get_string(1).unwrap(), "123");
+    assert!(rows[0].get_double(4).is_err());
+    assert_eq!(rows[1].get_double(4).unwrap(), 0.5);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "http")]
+
+use clob_rs::{ClobClient, ClobConfig, ClobError};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_midpoint(server: &MockServer, mid: &str) {
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": mid})))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_fails_over_to_backup_and_sticks_to_it() {
+    let primary = MockServer::start().await;
+    let backup = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&primary)
+        .await;
+    mount_midpoint(&backup, "0.4").await;
+
+    let config = ClobConfig {
+        host: primary.uri(),
+        fallback_hosts: vec![format!("{}/", backup.uri())],
+        ..ClobConfig::default()
+    };
+    let client = ClobClient::from_config(&config).unwrap();
+    assert_eq!(client.host(), primary.uri());
+
+    assert_eq!(client.prices().get_midpoint("123").await.unwrap(), 0.4);
+    assert_eq!(client.host(), backup.uri());
+    assert_eq!(client.prices().get_midpoint("123").await.unwrap(), 0.4);
+    assert_eq!(primary.received_requests().await.unwrap().len(), 1);
+    assert_eq!(backup.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_unreachable_host_is_skipped_and_wraps_around() {
+    let backup = MockServer::start().await;
+    mount_midpoint(&backup, "0.6").await;
+
+    // nothing listens on the primary
+    let client = ClobClient::new("http://127.0.0.1:9").with_fallback_hosts([backup.uri()]);
+    assert_eq!(client.prices().get_midpoint("123").await.unwrap(), 0.6);
+    assert_eq!(client.host(), backup.uri());
+
+    // once the backup fails too, requests go back to the primary
+    backup.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&backup)
+        .await;
+    let err = client.prices().get_midpoint("123").await.unwrap_err();
+    assert!(matches!(err, ClobError::Http(_)), "{}", err);
+    assert_eq!(client.host(), "http://127.0.0.1:9");
+}
@@ -0,0 +1,48 @@
+use clob_rs::fees::{fee, fee_in_shares, maker_fee, taker_fee};
+use clob_rs::{CreateOrderOptions, Market, OrderArgs, OrderBuilder, Side, Signer, TickSize};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn test_fee_formula() {
+    // 2% of min(p, 1 - p) per share
+    assert!(close(fee(200, 0.5, 100.0), 1.0));
+    assert!(close(fee(200, 0.9, 100.0), 0.2));
+    assert!(close(fee(200, 0.1, 100.0), 0.2));
+    assert_eq!(fee(0, 0.5, 100.0), 0.0);
+    assert!(close(fee_in_shares(200, 0.1, 100.0), 2.0));
+    assert_eq!(fee_in_shares(200, 0.0, 100.0), 0.0);
+
+    let market: Market = serde_json::from_value(serde_json::json!({
+        "condition_id": "0xa",
+        "question_id": "0xq",
+        "tokens": [],
+        "active": true,
+        "closed": false,
+        "maker_base_fee": 0,
+        "taker_base_fee": 100
+    }))
+    .unwrap();
+    assert!(close(taker_fee(&market, 0.4, 50.0), 0.2));
+    assert_eq!(maker_fee(&market, 0.4, 50.0), 0.0);
+}
+
+#[tokio::test]
+async fn test_order_fees() {
+    let mut args = OrderArgs::new("123", 0.25, 40.0, Side::Sell);
+    args.fee_rate_bps = 100;
+    assert!(close(args.fee(), 0.1));
+
+    let builder = OrderBuilder::new(Signer::new(TEST_PRIVATE_KEY, 137).unwrap());
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+    let order = builder.create_order(&args, &options).await.unwrap();
+    assert!(close(order.fee().unwrap(), 0.1));
+}
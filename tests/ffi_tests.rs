@@ -0,0 +1,107 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use clob_rs::ffi::{clob_l2_headers, clob_last_error, clob_sign_order, clob_string_free};
+use clob_rs::signing::build_hmac_signature;
+use clob_rs::{CreateOrderOptions, OrderArgs, OrderBuilder, Side, Signer, TickSize};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const POLYGON_CHAIN_ID: u64 = 137;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+fn take(s: *mut std::ffi::c_char) -> String {
+    assert!(!s.is_null());
+    let out = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+    unsafe { clob_string_free(s) };
+    out
+}
+
+fn last_error() -> String {
+    unsafe { CStr::from_ptr(clob_last_error()) }
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn test_ffi_sign_order_matches_builder() {
+    let request = c(
+        r#"{"token_id": "123456", "price": 0.56, "size": 21.04, "side": "BUY",
+                        "tick_size": "0.01", "neg_risk": false, "salt": 479249096354}"#,
+    );
+    let key = c(TEST_PRIVATE_KEY);
+    let signed = take(unsafe { clob_sign_order(key.as_ptr(), POLYGON_CHAIN_ID, request.as_ptr()) });
+
+    let builder = OrderBuilder::new(Signer::new(TEST_PRIVATE_KEY, POLYGON_CHAIN_ID).unwrap());
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: Some(479249096354),
+    };
+    let mut expected = Default::default();
+    builder
+        .create_order_into(
+            &OrderArgs::new("123456", 0.56, 21.04, Side::Buy),
+            &options,
+            &mut expected,
+        )
+        .unwrap();
+
+    assert_eq!(signed, serde_json::to_string(&expected).unwrap());
+}
+
+#[test]
+fn test_ffi_sign_order_errors() {
+    let key = c(TEST_PRIVATE_KEY);
+    let request = c(
+        r#"{"token_id": "1", "price": 0.5, "size": 10, "side": "BUY",
+                        "tick_size": "0.02", "neg_risk": false}"#,
+    );
+    let signed = unsafe { clob_sign_order(key.as_ptr(), POLYGON_CHAIN_ID, request.as_ptr()) };
+    assert!(signed.is_null());
+    assert!(last_error().contains("invalid tick size 0.02"));
+
+    let signed = unsafe { clob_sign_order(key.as_ptr(), POLYGON_CHAIN_ID, ptr::null()) };
+    assert!(signed.is_null());
+    assert!(last_error().contains("request_json is NULL"));
+}
+
+#[test]
+fn test_ffi_l2_headers() {
+    let secret = "dGVzdC1hcGktc2VjcmV0";
+    let args = [
+        c("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+        c("key"),
+        c(secret),
+        c("pass"),
+        c("POST"),
+        c("/order"),
+        c("{}"),
+    ];
+    let headers = take(unsafe {
+        clob_l2_headers(
+            args[0].as_ptr(),
+            args[1].as_ptr(),
+            args[2].as_ptr(),
+            args[3].as_ptr(),
+            1_700_000_000,
+            args[4].as_ptr(),
+            args[5].as_ptr(),
+            args[6].as_ptr(),
+        )
+    });
+    let headers: serde_json::Value = serde_json::from_str(&headers).unwrap();
+
+    assert_eq!(headers["POLY_API_KEY"], "key");
+    assert_eq!(headers["POLY_PASSPHRASE"], "pass");
+    assert_eq!(headers["POLY_TIMESTAMP"], "1700000000");
+    assert_eq!(
+        headers["POLY_SIGNATURE"],
+        build_hmac_signature(secret, 1_700_000_000, "POST", "/order", Some("{}")).unwrap()
+    );
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use clob_rs::fixtures::Cassette;
+use clob_rs::{ClobClient, ClobError};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::temp_dir;
+
+#[tokio::test]
+async fn test_cassette_records_and_replays() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .and(query_param("token_id", "123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": "0.5"})))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .and(query_param("token_id", "123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": "0.6"})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/tick-size"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("market not found"))
+        .mount(&mock_server)
+        .await;
+
+    let file = temp_dir("fixtures").join("nested").join("cassette.json");
+    let _ = std::fs::remove_file(&file);
+
+    let recorder = ClobClient::new(mock_server.uri()).with_cassette(Cassette::record(&file));
+    assert_eq!(recorder.prices().get_midpoint("123").await.unwrap(), 0.5);
+    assert_eq!(recorder.prices().get_midpoint("123").await.unwrap(), 0.6);
+    assert!(recorder.markets().get_tick_size("456").await.is_err());
+
+    let cassette = Cassette::replay(&file).unwrap();
+    assert!(!cassette.is_recording());
+    let interactions = cassette.interactions();
+    assert_eq!(interactions.len(), 3);
+    assert_eq!(interactions[0].method, "GET");
+    assert_eq!(interactions[0].path, "/midpoint?token_id=123");
+    assert_eq!(interactions[2].status, 404);
+
+    // nothing listens here: every answer comes from the file
+    let replayer = ClobClient::new("http://127.0.0.1:9").with_cassette(cassette);
+    assert_eq!(replayer.prices().get_midpoint("123").await.unwrap(), 0.5);
+    assert_eq!(replayer.prices().get_midpoint("123").await.unwrap(), 0.6);
+    assert_eq!(replayer.prices().get_midpoint("123").await.unwrap(), 0.6);
+    let err = replayer.markets().get_tick_size("456").await.unwrap_err();
+    assert!(err.to_string().contains("market not found"), "{}", err);
+
+    let err = replayer.prices().get_midpoint("789").await.unwrap_err();
+    assert!(
+        matches!(&err, ClobError::InvalidParameter(m) if m.contains("GET /midpoint?token_id=789")),
+        "{}",
+        err
+    );
+}
@@ -8,9 +8,8 @@ const POLYGON_CHAIN_ID: u64 = 137;
 fn get_test_creds() -> ApiCreds {
     ApiCreds {
         api_key: "000000000-0000-0000-0000-000000000000".to_string(),
-        api_passphrase: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
-            .to_string(),
-        api_secret: "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+        api_passphrase: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".into(),
+        api_secret: "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".into(),
     }
 }
 
@@ -45,7 +44,7 @@ fn test_create_level_2_headers_no_body() {
     let signer = Signer::new(TEST_PRIVATE_KEY, POLYGON_CHAIN_ID).unwrap();
     let creds = get_test_creds();
 
-    let headers = create_level_2_headers(&signer, &creds, "GET", "/order", None);
+    let headers = create_level_2_headers(&signer, &creds, "GET", "/order", None).unwrap();
 
     assert!(!headers.address.is_empty());
     assert_eq!(headers.address, EXPECTED_ADDRESS);
@@ -53,7 +52,7 @@ fn test_create_level_2_headers_no_body() {
     assert!(!headers.timestamp.is_empty());
     assert!(headers.timestamp.parse::<u64>().is_ok());
     assert_eq!(headers.api_key, creds.api_key);
-    assert_eq!(headers.passphrase, creds.api_passphrase);
+    assert_eq!(headers.passphrase, creds.api_passphrase.expose());
 }
 
 #[test]
@@ -67,7 +66,8 @@ fn test_create_level_2_headers_with_body() {
         "POST",
         "/order",
         Some(r#"{"hash": "0x123"}"#),
-    );
+    )
+    .unwrap();
 
     assert!(!headers.address.is_empty());
     assert_eq!(headers.address, EXPECTED_ADDRESS);
@@ -75,5 +75,5 @@ fn test_create_level_2_headers_with_body() {
     assert!(!headers.timestamp.is_empty());
     assert!(headers.timestamp.parse::<u64>().is_ok());
     assert_eq!(headers.api_key, creds.api_key);
-    assert_eq!(headers.passphrase, creds.api_passphrase);
+    assert_eq!(headers.passphrase, creds.api_passphrase.expose());
 }
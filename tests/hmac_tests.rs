@@ -1,4 +1,5 @@
 use clob_rs::signing::hmac::build_hmac_signature;
+use clob_rs::ClobError;
 
 #[test]
 fn test_build_hmac_signature() {
@@ -8,7 +9,8 @@ fn test_build_hmac_signature() {
         "test-sign",
         "/orders",
         Some(r#"{"hash": "0x123"}"#),
-    );
+    )
+    .unwrap();
     assert!(!signature.is_empty());
     assert_eq!(signature, "ZwAdJKvoYRlEKDkNMwd5BuwNNtg93kNaR_oU2HrfVvc=");
 }
@@ -21,6 +23,13 @@ fn test_build_hmac_signature_no_body() {
         "GET",
         "/orders",
         None,
-    );
+    )
+    .unwrap();
     assert!(!signature.is_empty());
 }
+
+#[test]
+fn test_build_hmac_signature_rejects_invalid_secret() {
+    let err = build_hmac_signature("not base64!", 1000000, "GET", "/orders", None).unwrap_err();
+    assert!(matches!(err, ClobError::Signing { .. }), "{}", err);
+}
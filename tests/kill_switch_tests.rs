@@ -0,0 +1,167 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::time::Duration;
+
+use clob_rs::{kill_switch_all, DeadManSwitch, DeadManTrigger, KillSwitch};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::client;
+
+fn fast() -> KillSwitch {
+    KillSwitch::new()
+        .with_attempts(3)
+        .with_retry_delay(Duration::from_millis(1))
+}
+
+#[tokio::test]
+async fn test_kill_switch_retries_survivors_until_flat() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/cancel-all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "canceled": ["0x1"],
+            "not_canceled": {"0x2": "busy"}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // the first listing still shows the survivor, the second is empty
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "0x2", "asset_id": "123", "side": "BUY", "price": "0.4", "original_size": "10"}
+        ])))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/orders"))
+        .and(body_json(serde_json::json!(["0x2"])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "canceled": ["0x2"],
+            "not_canceled": {}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = client(mock_server.uri());
+    let report = client.orders().unwrap().kill_switch_with(fast()).await;
+
+    assert!(report.is_flat());
+    assert_eq!(report.attempts, 2);
+    assert_eq!(report.canceled, vec!["0x1", "0x2"]);
+    assert!(report.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_kill_switch_reports_failures() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/cancel-all"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+
+    let accounts = vec![client(mock_server.uri()).orders().unwrap().clone()];
+    let reports = kill_switch_all(&accounts, fast()).await;
+
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert_eq!(report.attempts, 3);
+    assert!(!report.verified);
+    assert!(!report.is_flat());
+    assert_eq!(report.errors.len(), 6);
+}
+
+async fn mount_flat_cancel(mock_server: &MockServer) {
+    Mock::given(method("DELETE"))
+        .and(path("/cancel-all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "canceled": ["0x1"],
+            "not_canceled": {}
+        })))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_dead_man_switch_trips_once_per_missed_heartbeat() {
+    let mock_server = MockServer::start().await;
+    mount_flat_cancel(&mock_server).await;
+
+    let client = client(mock_server.uri());
+    let switch = client.orders().unwrap().spawn_dead_man_switch(
+        DeadManSwitch::new(Duration::from_millis(150))
+            .with_check_interval(Duration::from_millis(10))
+            .with_kill_switch(fast()),
+    );
+
+    // heartbeats keep it quiet
+    for _ in 0..5 {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        switch.heartbeat();
+    }
+    assert!(switch.trips().is_empty());
+
+    // stays tripped until the next heartbeat
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    let trips = switch.trips();
+    assert_eq!(trips.len(), 1);
+    assert_eq!(trips[0].trigger, DeadManTrigger::MissedHeartbeat);
+    assert!(trips[0].report.is_flat());
+    assert_eq!(trips[0].report.canceled, vec!["0x1"]);
+
+    switch.heartbeat();
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert_eq!(switch.trips().len(), 2);
+}
+
+#[tokio::test]
+async fn test_dead_man_switch_trips_on_long_disconnect() {
+    let mock_server = MockServer::start().await;
+    mount_flat_cancel(&mock_server).await;
+
+    let client = client(mock_server.uri());
+    let switch = client.orders().unwrap().spawn_dead_man_switch(
+        DeadManSwitch::new(Duration::from_secs(60))
+            .with_disconnect_timeout(Duration::from_millis(100))
+            .with_check_interval(Duration::from_millis(10))
+            .with_kill_switch(fast()),
+    );
+
+    // a short drop is tolerated
+    switch.set_connected(false);
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    switch.set_connected(true);
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(switch.trips().is_empty());
+
+    switch.set_connected(false);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let trips = switch.trips();
+    assert_eq!(trips.len(), 1);
+    assert_eq!(trips[0].trigger, DeadManTrigger::Disconnected);
+}
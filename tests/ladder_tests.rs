@@ -0,0 +1,187 @@
+#![cfg(feature = "http")]
+
+use clob_rs::{
+    diff_ladder, ApiCreds, ClobClient, DryRunLog, OrderArgs, OrderType, QuoteLadder, RestingOrder,
+    Side, TickSize,
+};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn prices(orders: &[OrderArgs], side: Side) -> Vec<(f64, f64)> {
+    orders
+        .iter()
+        .filter(|o| o.side == side)
+        .map(|o| (o.price, o.size))
+        .collect()
+}
+
+fn resting(id: &str, side: Side, price: f64, size: f64) -> RestingOrder {
+    RestingOrder {
+        id: id.to_string(),
+        token_id: "123".to_string(),
+        side,
+        price,
+        size,
+    }
+}
+
+#[test]
+fn test_quote_ladder_orders() {
+    let ladder = QuoteLadder::new("123", 0.5, 0.015, TickSize::Size0_01)
+        .with_levels(3)
+        .with_sizes(vec![10.0, 20.0]);
+    let orders = ladder.orders();
+
+    assert_eq!(
+        prices(&orders, Side::Buy),
+        vec![(0.48, 10.0), (0.47, 20.0), (0.46, 20.0)]
+    );
+    assert_eq!(
+        prices(&orders, Side::Sell),
+        vec![(0.52, 10.0), (0.53, 20.0), (0.54, 20.0)]
+    );
+    assert!(orders.iter().all(|o| o.token_id == "123"));
+
+    // on-tick prices stay put and levels past the price range are dropped
+    let ladder = QuoteLadder::new("123", 0.03, 0.01, TickSize::Size0_01)
+        .with_levels(4)
+        .with_level_spacing(0.01)
+        .with_size(5.0);
+    let orders = ladder.orders();
+    assert_eq!(prices(&orders, Side::Buy), vec![(0.02, 5.0), (0.01, 5.0)]);
+    assert_eq!(prices(&orders, Side::Sell).len(), 4);
+    assert_eq!(prices(&orders, Side::Sell)[0], (0.04, 5.0));
+
+    // coarse ticks collapse fine spacing onto one level
+    let ladder = QuoteLadder::new("123", 0.5, 0.05, TickSize::Size0_1)
+        .with_levels(3)
+        .with_level_spacing(0.01)
+        .with_size(1.0);
+    let orders = ladder.orders();
+    assert_eq!(prices(&orders, Side::Buy), vec![(0.4, 1.0)]);
+    assert_eq!(prices(&orders, Side::Sell), vec![(0.6, 1.0)]);
+}
+
+#[test]
+fn test_diff_ladder() {
+    let target = QuoteLadder::new("123", 0.5, 0.01, TickSize::Size0_01)
+        .with_levels(2)
+        .with_size(10.0)
+        .orders();
+    let resting = vec![
+        resting("keep-bid", Side::Buy, 0.49, 10.0),
+        resting("partly-filled", Side::Buy, 0.48, 4.0),
+        resting("keep-ask", Side::Sell, 0.51, 10.0),
+        resting("stale", Side::Sell, 0.60, 10.0),
+    ];
+
+    let diff = diff_ladder(&target, &resting);
+    assert_eq!(diff.keep, vec!["keep-bid", "keep-ask"]);
+    assert_eq!(diff.cancel.len(), 2);
+    assert!(diff.cancel.contains(&"partly-filled".to_string()));
+    assert!(diff.cancel.contains(&"stale".to_string()));
+    assert_eq!(
+        diff.post
+            .iter()
+            .map(|o| (o.side, o.price))
+            .collect::<Vec<_>>(),
+        vec![(Side::Buy, 0.48), (Side::Sell, 0.52)]
+    );
+
+    assert!(diff_ladder(&target[..1], &resting[..1]).is_empty());
+}
+
+#[test]
+fn test_resting_orders_from_open_orders() {
+    let page = serde_json::json!({
+        "data": [
+            {"id": "a", "asset_id": "123", "side": "BUY", "price": "0.49",
+             "original_size": "10", "size_matched": "2.5"},
+            {"id": "b", "asset_id": "123", "side": "SELL", "price": "0.51",
+             "original_size": "10"},
+            {"id": "c", "asset_id": "123", "side": "HOLD", "price": "0.51",
+             "original_size": "10"}
+        ],
+        "next_cursor": "LTE="
+    });
+
+    let orders = RestingOrder::from_open_orders(&page);
+    assert_eq!(
+        orders,
+        vec![
+            resting("a", Side::Buy, 0.49, 7.5),
+            resting("b", Side::Sell, 0.51, 10.0)
+        ]
+    );
+    assert_eq!(
+        RestingOrder::from_open_orders(&page["data"]).len(),
+        orders.len()
+    );
+    assert!(RestingOrder::from_open_orders(&serde_json::json!({})).is_empty());
+}
+
+#[tokio::test]
+async fn test_replace_ladder_cancels_once_and_posts_missing_levels() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .and(query_param("asset_id", "123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "keep", "asset_id": "123", "side": "BUY", "price": "0.49",
+             "original_size": "10", "size_matched": "0"},
+            {"id": "stale-1", "asset_id": "123", "side": "SELL", "price": "0.60",
+             "original_size": "10", "size_matched": "0"},
+            {"id": "stale-2", "asset_id": "123", "side": "SELL", "price": "0.61",
+             "original_size": "10", "size_matched": "0"}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/neg-risk"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"neg_risk": false})),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/fee-rate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"base_fee": 0})))
+        .mount(&mock_server)
+        .await;
+
+    let log = DryRunLog::new();
+    let client = ClobClient::new(mock_server.uri())
+        .with_dry_run(log.clone())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        });
+
+    let ladder = QuoteLadder::new("123", 0.5, 0.01, TickSize::Size0_01).with_size(10.0);
+    let update = client
+        .orders()
+        .unwrap()
+        .replace_ladder(&ladder, OrderType::GTC)
+        .await
+        .unwrap();
+
+    assert_eq!(update.diff.keep, vec!["keep"]);
+    assert_eq!(update.posted.len(), 1);
+    assert_eq!(
+        update.canceled.unwrap()["canceled"],
+        serde_json::json!(["stale-1", "stale-2"])
+    );
+
+    let requests = log.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "DELETE");
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(requests[1].body.as_ref().unwrap()["order"]["side"], "SELL");
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "http")]
+
+use std::time::Duration;
+
+use clob_rs::{ClobClient, LatencyTracker};
+use wiremock::matchers::{method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_latency_stats_per_endpoint() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": "0.5"})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex("^/markets/.+"))
+        .respond_with(
+            ResponseTemplate::new(500)
+                .set_body_string("oops")
+                .set_delay(Duration::from_millis(50)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    client.prices().get_midpoint("123").await.unwrap();
+    assert!(client.latency_stats().is_empty());
+
+    let tracker = LatencyTracker::new();
+    let client = client.with_latency_tracker(tracker.clone());
+    for _ in 0..20 {
+        client.prices().get_midpoint("123").await.unwrap();
+    }
+    assert!(client.markets().get_market("0xabc").await.is_err());
+    assert!(client.markets().get_market("0xdef").await.is_err());
+
+    let stats = client.latency_stats();
+    assert_eq!(
+        stats.keys().collect::<Vec<_>>(),
+        vec!["GET /markets/{id}", "GET /midpoint"]
+    );
+
+    let midpoint = stats["GET /midpoint"];
+    assert_eq!(midpoint.count, 20);
+    assert!(midpoint.min <= midpoint.p50);
+    assert!(midpoint.p50 <= midpoint.p95 && midpoint.p95 <= midpoint.p99);
+    assert!(midpoint.p99 <= midpoint.max);
+
+    // failed requests are timed too
+    let market = tracker.endpoint("GET /markets/{id}").unwrap();
+    assert_eq!(market.count, 2);
+    assert!(market.p50 >= Duration::from_millis(50));
+    assert!(market.p50 <= market.max);
+
+    tracker.reset();
+    assert!(client.latency_stats().is_empty());
+}
@@ -0,0 +1,189 @@
+#![cfg(feature = "http")]
+
+use clob_rs::{ClobClient, MarketSource, TickSize};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn market(
+    condition_id: &str,
+    slug: &str,
+    tokens: [&str; 2],
+    tick: f64,
+    neg_risk: bool,
+) -> serde_json::Value {
+    serde_json::json!({
+        "condition_id": condition_id,
+        "question_id": "0xq",
+        "tokens": [
+            {"token_id": tokens[0], "outcome": "Yes", "price": 0.5},
+            {"token_id": tokens[1], "outcome": "No", "price": 0.5}
+        ],
+        "active": true,
+        "closed": false,
+        "market_slug": slug,
+        "minimum_tick_size": tick,
+        "neg_risk": neg_risk
+    })
+}
+
+#[tokio::test]
+async fn test_market_cache_serves_metadata_lookups() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                market("0xa", "will-it-rain", ["1", "2"], 0.01, false),
+                market("0xb", "who-wins", ["3", "4"], 0.001, true)
+            ],
+            "next_cursor": "LTE="
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    for endpoint in ["/tick-size", "/neg-risk"] {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let client = ClobClient::new(mock_server.uri());
+    let markets = client.markets();
+    assert!(markets.market_cache().is_empty());
+    assert_eq!(
+        markets
+            .refresh_market_cache(MarketSource::Markets)
+            .await
+            .unwrap(),
+        2
+    );
+
+    let cache = markets.market_cache();
+    assert!(cache.loaded_at().is_some());
+    assert_eq!(
+        cache.market("0xb").unwrap().market_slug.as_deref(),
+        Some("who-wins")
+    );
+    assert_eq!(cache.market_for_token("2").unwrap().condition_id, "0xa");
+    assert_eq!(
+        cache.market_by_slug("who-wins").unwrap().condition_id,
+        "0xb"
+    );
+    assert!(cache.market("0xc").is_none());
+
+    assert_eq!(
+        markets.get_tick_size("1").await.unwrap(),
+        TickSize::Size0_01
+    );
+    assert_eq!(
+        markets.get_tick_size("4").await.unwrap(),
+        TickSize::Size0_001
+    );
+    assert!(!markets.get_neg_risk("2").await.unwrap());
+    assert!(markets.get_neg_risk("3").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_market_cache_from_simplified_markets() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/simplified-markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [{
+                "condition_id": "0xa",
+                "tokens": [
+                    {"token_id": "1", "outcome": "Yes", "price": 0.5},
+                    {"token_id": "2", "outcome": "No", "price": 0.5}
+                ],
+                "neg_risk": true
+            }],
+            "next_cursor": "LTE="
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/tick-size"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"minimum_tick_size": 0.1})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let markets = client.markets();
+    markets
+        .refresh_market_cache(MarketSource::SimplifiedMarkets)
+        .await
+        .unwrap();
+
+    assert!(markets.get_neg_risk("2").await.unwrap());
+    assert!(markets.market_cache().market("0xa").is_none());
+    // simplified markets carry no tick size, so that still goes to the API
+    assert_eq!(markets.get_tick_size("1").await.unwrap(), TickSize::Size0_1);
+}
+
+#[tokio::test]
+async fn test_lookup_token_reads_indexed_markets() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [market("0xa", "will-it-rain", ["1", "2"], 0.001, true)],
+            "next_cursor": "LTE="
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/simplified-markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                {"condition_id": "0xa", "tokens": [{"token_id": "1", "outcome": "Yes", "price": 0.5}], "neg_risk": true},
+                {"condition_id": "0xc", "tokens": [{"token_id": "5", "outcome": "Yes", "price": 0.5}]}
+            ],
+            "next_cursor": "LTE="
+        })))
+        .mount(&mock_server)
+        .await;
+    for endpoint in ["/tick-size", "/neg-risk"] {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let client = ClobClient::new(mock_server.uri());
+    assert!(client.lookup_token("2").is_none());
+    client.markets().get_markets_page(None).await.unwrap();
+
+    let info = client.lookup_token("2").unwrap();
+    assert_eq!(info.condition_id, "0xa");
+    assert_eq!(info.outcome, "No");
+    assert_eq!(info.tick_size, Some(TickSize::Size0_001));
+    assert!(info.neg_risk);
+    let markets = client.markets();
+    assert_eq!(
+        markets.get_tick_size("2").await.unwrap(),
+        TickSize::Size0_001
+    );
+    assert!(markets.get_neg_risk("1").await.unwrap());
+
+    // a simplified listing keeps the tick size a full one found
+    client
+        .markets()
+        .get_simplified_markets_page(None)
+        .await
+        .unwrap();
+    assert_eq!(
+        client.lookup_token("1").unwrap().tick_size,
+        Some(TickSize::Size0_001)
+    );
+    // and still answers neg risk for its other tokens
+    assert!(client.lookup_token("5").is_none());
+    assert!(!markets.get_neg_risk("5").await.unwrap());
+}
@@ -0,0 +1,235 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use clob_rs::{
+    group_neg_risk_events, normalize_probabilities, plan_complete_set_arb, ClobClient,
+    CompleteSetQuote, Market, Side,
+};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{book, level};
+
+fn market(condition_id: &str, neg_risk_market_id: &str, yes: &str, no: &str) -> serde_json::Value {
+    serde_json::json!({
+        "condition_id": condition_id,
+        "question_id": "0xq",
+        "tokens": [
+            {"token_id": no, "outcome": "No", "price": 0.5},
+            {"token_id": yes, "outcome": "Yes", "price": 0.5}
+        ],
+        "active": true,
+        "closed": false,
+        "neg_risk": true,
+        "neg_risk_market_id": neg_risk_market_id
+    })
+}
+
+#[test]
+fn test_complete_set_quote_uses_best_levels() {
+    let books = vec![
+        book(
+            "1",
+            vec![level("0.28", "5"), level("0.30", "40")],
+            vec![level("0.33", "20"), level("0.32", "50")],
+            Some("0.01"),
+            Some(true),
+        ),
+        book(
+            "2",
+            vec![level("0.60", "10")],
+            vec![level("0.62", "30")],
+            Some("0.01"),
+            Some(true),
+        ),
+    ];
+
+    let quote = CompleteSetQuote::from_books(&books);
+    assert!((quote.ask_sum.unwrap() - 0.94).abs() < 1e-9);
+    assert!((quote.bid_sum.unwrap() - 0.90).abs() < 1e-9);
+    assert_eq!(quote.ask_size, 30.0);
+    assert_eq!(quote.bid_size, 10.0);
+
+    let one_sided = vec![book(
+        "1",
+        vec![],
+        vec![level("0.40", "1")],
+        Some("0.01"),
+        Some(true),
+    )];
+    assert_eq!(CompleteSetQuote::from_books(&one_sided).bid_sum, None);
+}
+
+#[test]
+fn test_plan_complete_set_arb_directions() {
+    let cheap = vec![
+        book(
+            "1",
+            vec![],
+            vec![level("0.30", "25")],
+            Some("0.01"),
+            Some(true),
+        ),
+        book(
+            "2",
+            vec![],
+            vec![level("0.60", "10")],
+            Some("0.01"),
+            Some(true),
+        ),
+    ];
+    let arb = plan_complete_set_arb(&cheap, 0.05).unwrap();
+    assert_eq!(arb.side, Side::Buy);
+    assert!((arb.edge - 0.10).abs() < 1e-9);
+    assert_eq!(arb.size, 10.0);
+    assert_eq!(arb.orders.len(), 2);
+    assert_eq!(arb.orders[0].token_id, "1");
+    assert_eq!(arb.orders[0].price, 0.30);
+    assert!(arb
+        .orders
+        .iter()
+        .all(|o| o.size == 10.0 && o.side == Side::Buy));
+
+    // edge below the threshold
+    assert!(plan_complete_set_arb(&cheap, 0.15).is_none());
+
+    let rich = vec![
+        book(
+            "1",
+            vec![level("0.45", "8")],
+            vec![],
+            Some("0.01"),
+            Some(true),
+        ),
+        book(
+            "2",
+            vec![level("0.60", "20")],
+            vec![],
+            Some("0.01"),
+            Some(true),
+        ),
+    ];
+    let arb = plan_complete_set_arb(&rich, 0.0).unwrap();
+    assert_eq!(arb.side, Side::Sell);
+    assert!((arb.price_sum - 1.05).abs() < 1e-9);
+    assert_eq!(arb.size, 8.0);
+    assert_eq!(arb.orders[1].price, 0.60);
+}
+
+#[tokio::test]
+async fn test_plan_neg_risk_arb_fetches_sibling_books() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [
+                market("0xa", "0xevent", "11", "12"),
+                market("0xb", "0xother", "21", "22"),
+                market("0xc", "0xevent", "31", "32"),
+            ],
+            "next_cursor": "LTE="
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/books"))
+        .and(body_json(
+            serde_json::json!([{"token_id": "11"}, {"token_id": "31"}]),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+            book(
+                "11",
+                vec![],
+                vec![level("0.40", "15")],
+                Some("0.01"),
+                Some(true),
+            ),
+            book(
+                "31",
+                vec![],
+                vec![level("0.55", "40")],
+                Some("0.01"),
+                Some(true),
+            ),
+        ]))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let siblings = client
+        .markets()
+        .get_neg_risk_siblings("0xevent")
+        .await
+        .unwrap();
+    assert_eq!(siblings.len(), 2);
+
+    let arb = client
+        .prices()
+        .plan_neg_risk_arb("0xevent", 0.01)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(arb.side, Side::Buy);
+    assert_eq!(arb.size, 15.0);
+    let tokens: Vec<_> = arb.orders.iter().map(|o| o.token_id.as_str()).collect();
+    assert_eq!(tokens, vec!["11", "31"]);
+
+    assert!(client
+        .prices()
+        .plan_neg_risk_arb("0xother", 0.01)
+        .await
+        .is_err());
+}
+
+fn priced(condition_id: &str, neg_risk_market_id: Option<&str>, yes_price: f64) -> Market {
+    serde_json::from_value(serde_json::json!({
+        "condition_id": condition_id,
+        "question_id": "0xq",
+        "tokens": [
+            {"token_id": format!("{}-no", condition_id), "outcome": "No", "price": 1.0 - yes_price},
+            {"token_id": format!("{}-yes", condition_id), "outcome": "Yes", "price": yes_price}
+        ],
+        "active": true,
+        "closed": false,
+        "neg_risk": neg_risk_market_id.is_some(),
+        "neg_risk_market_id": neg_risk_market_id
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_group_neg_risk_events_and_normalize() {
+    let events = group_neg_risk_events(vec![
+        priced("0xa", Some("0xevent2"), 0.50),
+        priced("0xb", Some("0xevent1"), 0.90),
+        priced("0xc", Some("0xevent2"), 0.30),
+        priced("0xd", None, 0.70),
+        priced("0xe", Some("0xevent2"), 0.30),
+    ]);
+    let ids: Vec<_> = events
+        .iter()
+        .map(|e| e.neg_risk_market_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["0xevent1", "0xevent2"]);
+
+    let event = &events[1];
+    assert_eq!(event.markets.len(), 3);
+    assert_eq!(event.probabilities, vec![0.50, 0.30, 0.30]);
+    assert!((event.total() - 1.10).abs() < 1e-9);
+    assert_eq!(event.probability("0xc"), Some(0.30));
+    assert_eq!(event.probability("0xd"), None);
+
+    let normalized = event.normalized().unwrap();
+    assert!((normalized.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!((normalized[0] - 0.50 / 1.10).abs() < 1e-9);
+
+    let scaled = normalize_probabilities(&[0.2, -0.1, f64::NAN, 0.6]).unwrap();
+    for (p, expected) in scaled.iter().zip([0.25, 0.0, 0.0, 0.75]) {
+        assert!((p - expected).abs() < 1e-9);
+    }
+    assert_eq!(normalize_probabilities(&[0.0, 0.0]), None);
+}
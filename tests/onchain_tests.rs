@@ -0,0 +1,227 @@
+#![cfg(feature = "onchain")]
+
+use clob_rs::onchain::{convert_positions_calldata, neg_risk_index_set, OnchainClient};
+use clob_rs::{AllowanceReport, AssetType, MissingApproval, Signer};
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const MARKET_ID: &str = "0xe3b1bc389210504ebcb9cffe4b0ed06ccac50561e0f24abb6379984cec030f00";
+
+fn question_id(index: u8) -> String {
+    format!("{}{:02x}", &MARKET_ID[..64], index)
+}
+
+async fn mock_rpc(server: &MockServer, rpc_method: &str, result: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(body_partial_json(serde_json::json!({"method": rpc_method})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": result
+        })))
+        .mount(server)
+        .await;
+}
+
+#[test]
+fn test_index_set_and_calldata() {
+    let index_set = neg_risk_index_set(&[question_id(0), question_id(2)]).unwrap();
+    assert_eq!(index_set, alloy_primitives::U256::from(0b101u8));
+    assert!(neg_risk_index_set(&["0x1234"]).is_err());
+
+    let data = convert_positions_calldata(
+        MARKET_ID.parse().unwrap(),
+        index_set,
+        alloy_primitives::U256::from(5_000_000u64),
+    );
+    assert_eq!(data.len(), 4 + 3 * 32);
+    assert_eq!(&data[4..36], &hex::decode(&MARKET_ID[2..]).unwrap()[..]);
+    assert_eq!(data[67], 0b101);
+}
+
+#[tokio::test]
+async fn test_convert_positions_sends_signed_transaction() {
+    let server = MockServer::start().await;
+    mock_rpc(&server, "eth_getTransactionCount", "0x3".into()).await;
+    mock_rpc(&server, "eth_estimateGas", "0x186a0".into()).await;
+    mock_rpc(&server, "eth_gasPrice", "0x6fc23ac00".into()).await;
+    mock_rpc(&server, "eth_maxPriorityFeePerGas", "0x6fc23ac00".into()).await;
+
+    let tx_hash = format!("0x{}", "ab".repeat(32));
+    Mock::given(method("POST"))
+        .and(body_partial_json(
+            serde_json::json!({"method": "eth_sendRawTransaction"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": tx_hash
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let signer = Signer::new(TEST_PRIVATE_KEY, 137).unwrap();
+    let client = OnchainClient::new(server.uri(), signer).unwrap();
+    let hash = client
+        .convert_positions(MARKET_ID.parse().unwrap(), "3".parse().unwrap(), 5.0)
+        .await
+        .unwrap();
+    assert_eq!(hash.to_string(), tx_hash);
+
+    let requests = server.received_requests().await.unwrap();
+    let sent: serde_json::Value = requests.last().unwrap().body_json().unwrap();
+    let raw = sent["params"][0].as_str().unwrap();
+    // EIP-1559 typed transaction
+    assert!(raw.starts_with("0x02"));
+}
+
+#[tokio::test]
+async fn test_approve_missing_sends_one_approval_each() {
+    let server = MockServer::start().await;
+    mock_rpc(&server, "eth_getTransactionCount", "0x3".into()).await;
+    mock_rpc(&server, "eth_estimateGas", "0x186a0".into()).await;
+    mock_rpc(&server, "eth_gasPrice", "0x6fc23ac00".into()).await;
+    mock_rpc(&server, "eth_maxPriorityFeePerGas", "0x6fc23ac00".into()).await;
+    mock_rpc(
+        &server,
+        "eth_sendRawTransaction",
+        format!("0x{}", "cd".repeat(32)).into(),
+    )
+    .await;
+
+    let exchange = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
+    let report = AllowanceReport {
+        missing: [AssetType::COLLATERAL, AssetType::CONDITIONAL]
+            .into_iter()
+            .map(|asset_type| MissingApproval {
+                asset_type,
+                spender: exchange.to_string(),
+                neg_risk: true,
+            })
+            .collect(),
+    };
+    let signer = Signer::new(TEST_PRIVATE_KEY, 137).unwrap();
+    let client = OnchainClient::new(server.uri(), signer).unwrap();
+    assert_eq!(client.approve_missing(&report).await.unwrap().len(), 2);
+
+    // USDC approve, then setApprovalForAll on the conditional tokens
+    let targets: Vec<(String, String)> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter_map(|r| r.body_json::<serde_json::Value>().ok())
+        .filter(|body| body["method"] == "eth_estimateGas")
+        .map(|body| {
+            let tx = &body["params"][0];
+            (
+                tx["to"].as_str().unwrap().to_lowercase(),
+                tx["data"].as_str().unwrap()[..10].to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        targets,
+        vec![
+            (
+                "0x2791bca1f2de4661ed88a30c99a7a9449aa84174".to_string(),
+                "0x095ea7b3".to_string()
+            ),
+            (
+                "0x4d97dcd97ec945f40cf65f87097ace5ea0476045".to_string(),
+                "0xa22cb465".to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_rpc_error_is_reported() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": {"code": -32000, "message": "insufficient funds"}
+        })))
+        .mount(&server)
+        .await;
+
+    let signer = Signer::new(TEST_PRIVATE_KEY, 137).unwrap();
+    let client = OnchainClient::new(server.uri(), signer).unwrap();
+    let err = client.approve_neg_risk_adapter().await.unwrap_err();
+    assert!(err.to_string().contains("insufficient funds"));
+
+    let unsupported = Signer::new(TEST_PRIVATE_KEY, 1).unwrap();
+    assert!(OnchainClient::new(server.uri(), unsupported).is_err());
+}
+
+fn uint_word(value: u64) -> String {
+    format!("{:064x}", value)
+}
+
+async fn mock_eth_call(server: &MockServer, data: String, result: u64) {
+    Mock::given(method("POST"))
+        .and(body_partial_json(serde_json::json!({
+            "method": "eth_call",
+            "params": [{"data": data}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": format!("0x{}", uint_word(result))
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_resolution_reads_payouts() {
+    let server = MockServer::start().await;
+    let selector = |signature: &str| hex::encode(&alloy_primitives::keccak256(signature)[..4]);
+    let condition_id = &MARKET_ID[2..];
+    mock_eth_call(
+        &server,
+        format!(
+            "0x{}{}",
+            selector("payoutDenominator(bytes32)"),
+            condition_id
+        ),
+        2,
+    )
+    .await;
+    for (index, numerator) in [(0, 0), (1, 2)] {
+        mock_eth_call(
+            &server,
+            format!(
+                "0x{}{}{}",
+                selector("payoutNumerators(bytes32,uint256)"),
+                condition_id,
+                uint_word(index)
+            ),
+            numerator,
+        )
+        .await;
+    }
+
+    let market: clob_rs::Market = serde_json::from_value(serde_json::json!({
+        "condition_id": MARKET_ID,
+        "question_id": question_id(0),
+        "tokens": [
+            {"token_id": "1", "outcome": "Yes", "price": 0},
+            {"token_id": "2", "outcome": "No", "price": 1}
+        ],
+        "active": true,
+        "closed": true
+    }))
+    .unwrap();
+
+    let signer = Signer::new(TEST_PRIVATE_KEY, 137).unwrap();
+    let client = OnchainClient::new(server.uri(), signer).unwrap();
+    let resolution = client.resolution(&market).await.unwrap();
+    assert_eq!(resolution.payout("1"), Some(0.0));
+    assert_eq!(resolution.payout("2"), Some(1.0));
+    assert_eq!(resolution.winner().unwrap().outcome, "No");
+}
@@ -0,0 +1,134 @@
+use clob_rs::{Level, MarketOrderArgs, NumericOrderBook, OrderArgs, OrderType, Side};
+
+const TAKER: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+#[test]
+fn test_order_args_builder() {
+    let order_args = OrderArgs::builder("123")
+        .price(0.45)
+        .size(100.0)
+        .side(Side::Buy)
+        .fee_rate_bps(100)
+        .expiration(1_700_000_000)
+        .nonce(7)
+        .taker(TAKER)
+        .build()
+        .unwrap();
+
+    assert_eq!(order_args.token_id, "123");
+    assert_eq!(order_args.price, 0.45);
+    assert_eq!(order_args.size, 100.0);
+    assert_eq!(order_args.side, Side::Buy);
+    assert_eq!(order_args.fee_rate_bps, 100);
+    assert_eq!(order_args.expiration, 1_700_000_000);
+    assert_eq!(order_args.nonce, 7);
+    assert_eq!(order_args.taker, TAKER);
+}
+
+#[test]
+fn test_order_args_builder_defaults_match_new() {
+    let built = OrderArgs::builder("123")
+        .price(0.5)
+        .size(10.0)
+        .side(Side::Sell)
+        .build()
+        .unwrap();
+    let new = OrderArgs::new("123", 0.5, 10.0, Side::Sell);
+
+    assert_eq!(built.fee_rate_bps, new.fee_rate_bps);
+    assert_eq!(built.nonce, new.nonce);
+    assert_eq!(built.expiration, new.expiration);
+    assert_eq!(built.taker, new.taker);
+}
+
+#[test]
+fn test_order_args_builder_requires_fields() {
+    let err = OrderArgs::builder("123")
+        .size(10.0)
+        .side(Side::Buy)
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("price"));
+
+    let err = OrderArgs::builder("123")
+        .price(0.5)
+        .size(10.0)
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("side"));
+}
+
+#[test]
+fn test_order_args_builder_validates_values() {
+    let base = OrderArgs::builder("123").size(10.0).side(Side::Buy);
+
+    assert!(base.clone().price(0.0).build().is_err());
+    assert!(base.clone().price(1.0).build().is_err());
+    assert!(base.clone().price(0.5).size(-1.0).build().is_err());
+    assert!(base.clone().price(0.5).fee_rate_bps(-1).build().is_err());
+    assert!(base.clone().price(0.5).taker("nope").build().is_err());
+    assert!(OrderArgs::builder("")
+        .price(0.5)
+        .size(10.0)
+        .side(Side::Buy)
+        .build()
+        .is_err());
+}
+
+fn book() -> NumericOrderBook {
+    let level = |price, size| Level { price, size };
+    NumericOrderBook {
+        market: "0x1".to_string(),
+        asset_id: "123".to_string(),
+        timestamp: "0".to_string(),
+        hash: String::new(),
+        bids: vec![level(0.48, 100.0), level(0.45, 50.0)],
+        asks: vec![level(0.5, 20.0), level(0.6, 100.0)],
+        min_order_size: None,
+        tick_size: Some(0.01),
+        neg_risk: None,
+    }
+}
+
+#[test]
+fn test_market_order_constructors() {
+    let buy = MarketOrderArgs::buy_usdc("123", 25.0).unwrap();
+    assert_eq!((buy.side, buy.amount), (Side::Buy, 25.0));
+    let sell = MarketOrderArgs::sell_shares("123", 120.0)
+        .unwrap()
+        .with_price(0.4)
+        .with_order_type(OrderType::FAK);
+    assert_eq!(
+        (sell.side, sell.amount, sell.price),
+        (Side::Sell, 120.0, 0.4)
+    );
+    assert_eq!(sell.order_type, OrderType::FAK);
+
+    assert!(MarketOrderArgs::buy_usdc("123", 0.0).is_err());
+    assert!(MarketOrderArgs::sell_shares("123", f64::NAN).is_err());
+}
+
+#[test]
+fn test_market_order_quotes() {
+    let book = book();
+
+    // 10 USDC at 0.5 buys 20 shares, 15 more at 0.6 buys 25
+    let quote = MarketOrderArgs::buy_usdc("123", 25.0)
+        .unwrap()
+        .quote(&book)
+        .unwrap();
+    assert!((quote.shares - 45.0).abs() < 1e-9);
+    assert!((quote.usdc - 25.0).abs() < 1e-9);
+    assert_eq!(quote.worst_price, 0.6);
+    assert!((quote.avg_price - 25.0 / 45.0).abs() < 1e-9);
+
+    let quote = MarketOrderArgs::sell_shares("123", 120.0)
+        .unwrap()
+        .quote(&book)
+        .unwrap();
+    assert!((quote.usdc - (48.0 + 9.0)).abs() < 1e-9);
+    assert_eq!(quote.worst_price, 0.45);
+
+    assert!(book.quote_buy_usdc(100.0).is_none());
+    assert!(book.quote_sell_shares(151.0).is_none());
+}
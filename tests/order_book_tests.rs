@@ -0,0 +1,128 @@
+use clob_rs::{Level, OrderBook, OrderSummary, Side};
+
+#[test]
+fn test_order_book_depth_analytics() {
+    let level = |price: &str, size: &str| OrderSummary {
+        price: price.to_string(),
+        size: size.to_string(),
+    };
+    // levels deliberately not best-first
+    let book = OrderBook {
+        market: "test".to_string(),
+        asset_id: "123".to_string(),
+        timestamp: "1234567890".to_string(),
+        hash: "abc".to_string(),
+        bids: vec![level("0.40", "200"), level("0.49", "100")],
+        asks: vec![level("0.60", "50"), level("0.51", "150")],
+        min_order_size: None,
+        tick_size: Some("0.01".to_string()),
+        neg_risk: Some(false),
+    };
+
+    // mid is 0.50, 200bps band is +-0.01
+    assert_eq!(book.bid_liquidity_within(200.0), Some(100.0));
+    assert_eq!(book.ask_liquidity_within(200.0), Some(150.0));
+    assert_eq!(book.bid_liquidity_within(5000.0), Some(300.0));
+
+    let vwap = book.vwap_for_size(Side::Buy, 200.0).unwrap();
+    assert!((vwap - (0.51 * 150.0 + 0.60 * 50.0) / 200.0).abs() < 1e-12);
+    let vwap = book.vwap_for_size(Side::Sell, 50.0).unwrap();
+    assert!((vwap - 0.49).abs() < 1e-12);
+    assert!(book.vwap_for_size(Side::Buy, 201.0).is_none());
+
+    assert!((book.imbalance().unwrap() - 0.2).abs() < 1e-12);
+
+    let depth = book.cumulative_depth();
+    assert_eq!(depth.bids, vec![(0.49, 100.0), (0.40, 300.0)]);
+    assert_eq!(depth.asks, vec![(0.51, 150.0), (0.60, 200.0)]);
+}
+
+#[test]
+fn test_order_book_to_numeric() {
+    let level = |price: &str, size: &str| OrderSummary {
+        price: price.to_string(),
+        size: size.to_string(),
+    };
+    let mut book = OrderBook {
+        market: "test".to_string(),
+        asset_id: "123".to_string(),
+        timestamp: "1234567890".to_string(),
+        hash: "abc".to_string(),
+        bids: vec![level("0.40", "200"), level("0.49", "100")],
+        asks: vec![level("0.60", "50"), level("0.51", "150")],
+        min_order_size: Some("5".to_string()),
+        tick_size: Some("0.01".to_string()),
+        neg_risk: Some(false),
+    };
+
+    let numeric = book.to_numeric().unwrap();
+    assert_eq!(
+        numeric.best_bid(),
+        Some(Level {
+            price: 0.49,
+            size: 100.0
+        })
+    );
+    assert_eq!(numeric.asks[0].price, 0.51);
+    assert_eq!(numeric.asks[1].price, 0.60);
+    assert_eq!(numeric.min_order_size, Some(5.0));
+    assert!((numeric.midpoint().unwrap() - 0.50).abs() < 1e-12);
+
+    book.asks.push(level("0.7", "lots"));
+    let err = book.to_numeric().unwrap_err();
+    assert!(err.to_string().contains("ask size"));
+}
+
+#[test]
+fn test_order_book_hash() {
+    let mut book = OrderBook {
+        market: "0xaabbcc".to_string(),
+        asset_id: "100".to_string(),
+        timestamp: "123456789".to_string(),
+        hash: "".to_string(),
+        bids: vec![OrderSummary {
+            price: "0.3".to_string(),
+            size: "100".to_string(),
+        }],
+        asks: vec![OrderSummary {
+            price: "0.6".to_string(),
+            size: "100".to_string(),
+        }],
+        min_order_size: Some("5".to_string()),
+        tick_size: Some("0.01".to_string()),
+        neg_risk: Some(false),
+    };
+    assert!(!book.verify_hash());
+
+    // sha1 of the compact JSON summary with an empty hash field
+    let hash = book.compute_hash();
+    assert_eq!(hash, "03a7f0f29cac22d635d006771d1b3d297143ecbd");
+    book.hash = hash;
+    assert!(book.verify_hash());
+
+    book.bids[0].size = "99".to_string();
+    assert!(!book.verify_hash());
+}
+
+// py-clob-client's generate_orderbook_summary_hash applied to this book
+#[test]
+fn test_order_book_hash_matches_reference_client() {
+    let level = |price: &str, size: &str| OrderSummary {
+        price: price.to_string(),
+        size: size.to_string(),
+    };
+    let book = OrderBook {
+        market: "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1".to_string(),
+        asset_id: "52114319501245915516055106046884209969926127482827954674443846427813813222426"
+            .to_string(),
+        timestamp: "1757908892351".to_string(),
+        hash: "3f473239aa6d17eb24250e41edfee3480bb8fbac".to_string(),
+        bids: vec![level("0.48", "1250.5"), level("0.47", "3000")],
+        asks: vec![level("0.5", "812.33"), level("0.51", "20000")],
+        min_order_size: Some("5".to_string()),
+        tick_size: Some("0.01".to_string()),
+        neg_risk: Some(false),
+    };
+    assert_eq!(book.compute_hash(), book.hash);
+    assert!(book.verify_hash());
+}
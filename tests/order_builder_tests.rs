@@ -1,6 +1,11 @@
+#![cfg(feature = "http")]
+
+use std::sync::Arc;
+
+use alloy_primitives::PrimitiveSignature;
 use clob_rs::{
-    ApiCreds, ClobClient, CreateOrderOptions, OrderArgs, OrderBuilder, Side, Signer, TickSize,
-    POLY_PROXY,
+    ApiCreds, ClobClient, CreateOrderOptions, FixedSalt, IncrementingNonce, NonceProvider,
+    OrderArgs, OrderBuilder, Side, SignedOrder, Signer, TickSize, TimestampNonce, POLY_PROXY,
 };
 
 const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
@@ -8,6 +13,8 @@ const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
 const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
 const EXPECTED_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
 const POLYGON_CHAIN_ID: u64 = 137;
+// salt 1234, token 123, buy 10 @ 0.5
+const GOLDEN_SIGNATURE: &str = "0x2b645e15c3c3db55492bc0ad52d4cc5a12a4aacd8df384baa080ed4142e4a1021689b376bd7c2e70367c27ff0e66564b0a2a2198d89ba9b9123174f0abe35b201b";
 
 fn create_test_signer() -> Signer {
     Signer::new(TEST_PRIVATE_KEY, POLYGON_CHAIN_ID).unwrap()
@@ -23,8 +30,8 @@ async fn test_create_client() {
         .with_signature_type(POLY_PROXY)
         .with_creds(ApiCreds {
             api_key: "api_key".to_string(),
-            api_secret: "api_secret".to_string(),
-            api_passphrase: "api_passphrase".to_string(),
+            api_secret: "api_secret".into(),
+            api_passphrase: "api_passphrase".into(),
         });
     assert_eq!(client.address(), Some(EXPECTED_ADDRESS.to_string()));
 }
@@ -48,6 +55,7 @@ async fn test_create_order_decimal_accuracy_buy() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -74,6 +82,7 @@ async fn test_create_order_decimal_accuracy_sell() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -100,6 +109,7 @@ async fn test_create_order_decimal_accuracy_buy_82() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -126,6 +136,7 @@ async fn test_create_order_decimal_accuracy_sell_82() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -152,6 +163,7 @@ async fn test_create_order_decimal_accuracy_buy_78() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -178,6 +190,7 @@ async fn test_create_order_decimal_accuracy_sell_78() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -204,6 +217,7 @@ async fn test_create_order_sell_39() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -230,6 +244,7 @@ async fn test_create_order_sell_43() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -256,6 +271,7 @@ async fn test_create_order_buy_58() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -286,6 +302,7 @@ async fn test_create_order_buy_0_1() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_1,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -328,6 +345,7 @@ async fn test_create_order_buy_0_01() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -359,6 +377,7 @@ async fn test_create_order_buy_0_001() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_001,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -390,6 +409,7 @@ async fn test_create_order_buy_0_0001() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_0001,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -421,6 +441,7 @@ async fn test_create_order_sell_0_1() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_1,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -453,6 +474,7 @@ async fn test_create_order_sell_0_01() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -484,6 +506,7 @@ async fn test_create_order_sell_0_001() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_001,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -515,6 +538,7 @@ async fn test_create_order_sell_0_0001() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_0001,
         neg_risk: false,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -546,6 +570,7 @@ async fn test_create_order_decimal_accuracy_neg_risk_buy() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: true,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -571,6 +596,7 @@ async fn test_create_order_decimal_accuracy_neg_risk_sell() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_01,
         neg_risk: true,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -596,6 +622,7 @@ async fn test_create_order_buy_0_1_neg_risk() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_1,
         neg_risk: true,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -627,6 +654,7 @@ async fn test_create_order_sell_0_1_neg_risk() {
     let options = CreateOrderOptions {
         tick_size: TickSize::Size0_1,
         neg_risk: true,
+        salt: None,
     };
 
     let signed_order = builder.create_order(&order_args, &options).await.unwrap();
@@ -772,3 +800,284 @@ fn test_order_book_empty() {
     assert!(book.spread().is_none());
     assert!(book.midpoint().is_none());
 }
+
+#[tokio::test]
+async fn test_signed_order_round_trip() {
+    let builder = OrderBuilder::new(create_test_signer());
+    let order_args = OrderArgs::new("123", 0.24, 15.0, Side::Sell);
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+    let signed_order = builder.create_order(&order_args, &options).await.unwrap();
+
+    let json = serde_json::to_string(&signed_order).unwrap();
+    assert!(json.contains("\"side\":\"SELL\""));
+    let decoded: SignedOrder = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, signed_order);
+
+    // numeric side and string salt are accepted too
+    let mut value = serde_json::to_value(&signed_order).unwrap();
+    value["side"] = serde_json::json!(1);
+    value["salt"] = serde_json::json!(signed_order.salt);
+    let decoded: SignedOrder = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded, signed_order);
+}
+
+#[tokio::test]
+async fn test_signed_order_hash_recovers_signer() {
+    let builder = OrderBuilder::new(create_test_signer());
+    let order_args = OrderArgs::new("123", 0.5, 10.0, Side::Buy);
+
+    for neg_risk in [false, true] {
+        let options = CreateOrderOptions {
+            tick_size: TickSize::Size0_01,
+            neg_risk,
+            salt: None,
+        };
+        let signed_order = builder.create_order(&order_args, &options).await.unwrap();
+
+        let hash = signed_order.hash(POLYGON_CHAIN_ID, neg_risk).unwrap();
+        let signature: PrimitiveSignature = signed_order.signature.parse().unwrap();
+        let recovered = signature.recover_address_from_prehash(&hash).unwrap();
+        assert_eq!(recovered.to_string(), EXPECTED_ADDRESS);
+
+        assert_ne!(
+            signed_order.hash(POLYGON_CHAIN_ID, !neg_risk).unwrap(),
+            hash
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_registered_contract_config_signs_for_custom_chain() {
+    use clob_rs::config::{get_contract_config, register_contract_config};
+    use clob_rs::ContractConfig;
+
+    const FORK_CHAIN_ID: u64 = 31337;
+    let fork = ContractConfig {
+        exchange: "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+        collateral: "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512",
+        conditional_tokens: "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+        collateral_decimals: 6,
+    };
+    assert!(get_contract_config(FORK_CHAIN_ID, false).is_none());
+    register_contract_config(FORK_CHAIN_ID, false, fork).unwrap();
+    assert_eq!(get_contract_config(FORK_CHAIN_ID, false), Some(&fork));
+
+    let builder = OrderBuilder::new(Signer::new(TEST_PRIVATE_KEY, FORK_CHAIN_ID).unwrap());
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+    let order_args = OrderArgs::new("123", 0.5, 10.0, Side::Buy);
+    let signed_order = builder.create_order(&order_args, &options).await.unwrap();
+    let hash = signed_order.hash(FORK_CHAIN_ID, false).unwrap();
+    let signature: PrimitiveSignature = signed_order.signature.parse().unwrap();
+    let recovered = signature.recover_address_from_prehash(&hash).unwrap();
+    assert_eq!(recovered.to_string(), EXPECTED_ADDRESS);
+
+    // replacing the exchange changes the domain the order is signed for
+    let redeployed = ContractConfig {
+        exchange: "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9",
+        ..fork
+    };
+    register_contract_config(FORK_CHAIN_ID, false, redeployed).unwrap();
+    assert_ne!(signed_order.hash(FORK_CHAIN_ID, false).unwrap(), hash);
+
+    let invalid = ContractConfig {
+        exchange: "not-an-address",
+        ..fork
+    };
+    assert!(register_contract_config(FORK_CHAIN_ID, true, invalid).is_err());
+    assert!(get_contract_config(FORK_CHAIN_ID, true).is_none());
+}
+
+#[tokio::test]
+async fn test_collateral_decimals_scale_order_amounts() {
+    use clob_rs::config::{collateral_decimals, register_contract_config};
+    use clob_rs::{ContractConfig, OrderTerms};
+
+    const TESTNET_CHAIN_ID: u64 = 31338;
+    let config = ContractConfig {
+        exchange: "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+        collateral: "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512",
+        conditional_tokens: "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+        collateral_decimals: 18,
+    };
+    register_contract_config(TESTNET_CHAIN_ID, false, config).unwrap();
+    assert_eq!(collateral_decimals(TESTNET_CHAIN_ID), 18);
+    assert_eq!(collateral_decimals(POLYGON_CHAIN_ID), 6);
+
+    let builder = OrderBuilder::new(Signer::new(TEST_PRIVATE_KEY, TESTNET_CHAIN_ID).unwrap());
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+    // 1234.56 shares overflow u64 at 18 decimals
+    let order_args = OrderArgs::new("123", 0.57, 1234.56, Side::Buy);
+    let signed_order = builder.create_order(&order_args, &options).await.unwrap();
+    assert_eq!(signed_order.taker_amount, "1234560000000000000000");
+    assert_eq!(signed_order.maker_amount, "703699200000000000000");
+
+    let terms = OrderTerms::from_order_with_decimals(&signed_order, 18).unwrap();
+    assert!((terms.size - 1234.56).abs() < 1e-9);
+    assert!((terms.price - 0.57).abs() < 1e-9);
+
+    let too_many = ContractConfig {
+        collateral_decimals: 24,
+        ..config
+    };
+    assert!(register_contract_config(TESTNET_CHAIN_ID, true, too_many).is_err());
+}
+
+#[tokio::test]
+async fn test_order_nonce_provider() {
+    let builder = OrderBuilder::new(create_test_signer())
+        .with_nonce_provider(Arc::new(IncrementingNonce::new(5)));
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+
+    let mut order_args = OrderArgs::new("123", 0.5, 10.0, Side::Buy);
+    let first = builder.create_order(&order_args, &options).await.unwrap();
+    let second = builder.create_order(&order_args, &options).await.unwrap();
+    assert_eq!(first.nonce, "5");
+    assert_eq!(second.nonce, "6");
+
+    // an explicit nonce wins over the provider
+    order_args.nonce = 99;
+    let explicit = builder.create_order(&order_args, &options).await.unwrap();
+    assert_eq!(explicit.nonce, "99");
+
+    let builder = OrderBuilder::new(create_test_signer()).with_nonce_provider(Arc::new(|| 7));
+    order_args.nonce = 0;
+    let custom = builder.create_order(&order_args, &options).await.unwrap();
+    assert_eq!(custom.nonce, "7");
+}
+
+#[tokio::test]
+async fn test_salt_injection_makes_signing_reproducible() {
+    let builder =
+        OrderBuilder::new(create_test_signer()).with_salt_provider(Arc::new(FixedSalt(1234)));
+    let mut options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+    let order_args = OrderArgs::new("123", 0.5, 10.0, Side::Buy);
+
+    let first = builder.create_order(&order_args, &options).await.unwrap();
+    let second = builder.create_order(&order_args, &options).await.unwrap();
+    assert_eq!(first.salt, "1234");
+    assert_eq!(first, second);
+    assert_eq!(first.signature, GOLDEN_SIGNATURE);
+
+    // a salt in the options wins over the provider
+    options.salt = Some(42);
+    let explicit = builder.create_order(&order_args, &options).await.unwrap();
+    assert_eq!(explicit.salt, "42");
+    assert_ne!(explicit.signature, first.signature);
+
+    options.salt = None;
+    let client = ClobClient::polygon()
+        .with_salt_provider(|| 1234)
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap();
+    let from_client = client
+        .orders()
+        .unwrap()
+        .order_builder()
+        .create_order(&order_args, &options)
+        .await
+        .unwrap();
+    assert_eq!(from_client, first);
+}
+
+#[test]
+fn test_timestamp_nonce_strictly_increasing() {
+    let provider = TimestampNonce::new();
+    let nonces: Vec<u64> = (0..100).map(|_| provider.next_nonce()).collect();
+    assert!(nonces.windows(2).all(|w| w[1] > w[0]));
+}
+
+#[tokio::test]
+async fn test_create_order_into_reuses_buffers() {
+    let builder =
+        OrderBuilder::new(create_test_signer()).with_salt_provider(Arc::new(FixedSalt(1234)));
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+
+    let mut order = SignedOrder::default();
+    let sell = OrderArgs::new("456", 0.7, 25.0, Side::Sell);
+    builder
+        .create_order_into(&sell, &options, &mut order)
+        .unwrap();
+    assert_eq!(order.side, 1);
+
+    let buy = OrderArgs::new("123", 0.5, 10.0, Side::Buy);
+    builder
+        .create_order_into(&buy, &options, &mut order)
+        .unwrap();
+    assert_eq!(order.signature, GOLDEN_SIGNATURE);
+    assert_eq!(order, builder.create_order(&buy, &options).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_create_orders_batch() {
+    let builder =
+        OrderBuilder::new(create_test_signer()).with_salt_provider(Arc::new(FixedSalt(1234)));
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: None,
+    };
+    let ladder: Vec<OrderArgs> = (1..=60)
+        .map(|i| OrderArgs::new("123", i as f64 / 100.0, 10.0, Side::Buy))
+        .collect();
+
+    let signed = builder.create_orders(&ladder, &options).unwrap();
+    assert_eq!(signed.len(), ladder.len());
+    for (args, order) in ladder.iter().zip(&signed) {
+        assert_eq!(order, &builder.create_order(args, &options).await.unwrap());
+    }
+    // the 50th rung is the golden 10 @ 0.5 order
+    assert_eq!(signed[49].signature, GOLDEN_SIGNATURE);
+
+    assert!(builder.create_orders(&[], &options).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_invalid_taker_is_rejected() {
+    let builder = OrderBuilder::new(create_test_signer());
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: false,
+        salt: Some(1234),
+    };
+
+    let mut args = OrderArgs::new("123", 0.5, 10.0, Side::Buy);
+    args.taker = "0xnot-an-address".to_string();
+    let err = builder.create_order(&args, &options).await.unwrap_err();
+    assert!(err.to_string().contains("invalid taker address"), "{}", err);
+
+    let taker: alloy_primitives::Address = EXPECTED_ADDRESS.parse().unwrap();
+    let args = OrderArgs::new("123", 0.5, 10.0, Side::Buy).with_taker(taker);
+    let signed_order = builder.create_order(&args, &options).await.unwrap();
+    assert_eq!(signed_order.taker, EXPECTED_ADDRESS);
+    assert!(OrderArgs::builder("123")
+        .price(0.5)
+        .size(10.0)
+        .side(Side::Buy)
+        .taker_address(taker)
+        .build()
+        .is_ok());
+}
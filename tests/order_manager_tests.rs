@@ -0,0 +1,318 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use clob_rs::{
+    ApiCreds, ClobClient, DryRunLog, FillEvent, OrderArgs, OrderManager, OrderType, OrphanPolicy,
+    ShutdownPolicy, Side,
+};
+use tokio::sync::Mutex;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::temp_dir;
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn dry_run_client(uri: String, log: &DryRunLog) -> ClobClient {
+    ClobClient::new(uri)
+        .with_dry_run(log.clone())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        })
+}
+
+async fn mount_order_lookups(mock_server: &MockServer) {
+    for (endpoint, body) in [
+        ("/tick-size", serde_json::json!({"minimum_tick_size": 0.01})),
+        ("/neg-risk", serde_json::json!({"neg_risk": false})),
+        ("/fee-rate", serde_json::json!({"base_fee": 0})),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(mock_server)
+            .await;
+    }
+}
+
+fn open_order(id: &str) -> serde_json::Value {
+    serde_json::json!({"id": id, "asset_id": "123", "side": "BUY", "price": "0.4",
+                       "original_size": "10", "size_matched": "2"})
+}
+
+#[tokio::test]
+async fn test_place_persists_orders_across_restarts() {
+    let mock_server = MockServer::start().await;
+    mount_order_lookups(&mock_server).await;
+    let state = temp_dir("order-manager-place").join("orders.json");
+    let _ = std::fs::remove_file(&state);
+
+    let log = DryRunLog::new();
+    let client = dry_run_client(mock_server.uri(), &log);
+    let mut manager = OrderManager::new(client.orders().unwrap().clone())
+        .with_state_file(&state)
+        .unwrap();
+    let response = manager
+        .place(&OrderArgs::new("123", 0.4, 10.0, Side::Buy), OrderType::GTC)
+        .await
+        .unwrap();
+    assert!(response.success);
+
+    let restarted = OrderManager::new(client.orders().unwrap().clone())
+        .with_state_file(&state)
+        .unwrap();
+    let order = restarted.get(&response.order_id).unwrap();
+    assert_eq!((order.price, order.size), (0.4, 10.0));
+    assert_eq!(order.order_type, Some(OrderType::GTC));
+    assert!(order.salt.is_some());
+}
+
+#[tokio::test]
+async fn test_reconcile_matches_drops_and_handles_orphans() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            open_order("0xAAA"),
+            open_order("0xccc")
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let dir = temp_dir("order-manager-reconcile");
+    let saved = |id: &str| {
+        serde_json::json!({"order_id": id, "salt": "1", "token_id": "123", "side": "BUY",
+                           "price": 0.4, "size": 10.0, "order_type": "GTC"})
+    };
+    for policy in ["cancel", "adopt"] {
+        let state = dir.join(format!("{}.json", policy));
+        std::fs::write(
+            &state,
+            serde_json::json!({"orders": [saved("0xaaa"), saved("0xbbb")]}).to_string(),
+        )
+        .unwrap();
+
+        let log = DryRunLog::new();
+        let client = dry_run_client(mock_server.uri(), &log);
+        let orphan_policy = match policy {
+            "cancel" => OrphanPolicy::Cancel,
+            _ => OrphanPolicy::Adopt,
+        };
+        let mut manager = OrderManager::new(client.orders().unwrap().clone())
+            .with_state_file(&state)
+            .unwrap()
+            .with_orphan_policy(orphan_policy);
+        let report = manager.reconcile().await.unwrap();
+
+        assert_eq!(report.matched, vec!["0xAAA"]);
+        assert_eq!(report.dropped, vec!["0xbbb"]);
+        assert!(manager.get("0xaaa").is_some());
+        assert!(manager.get("0xbbb").is_none());
+
+        let requests = log.requests();
+        match orphan_policy {
+            OrphanPolicy::Cancel => {
+                assert_eq!(report.cancelled, vec!["0xccc"]);
+                assert_eq!(requests.len(), 1);
+                assert_eq!(requests[0].body, Some(serde_json::json!(["0xccc"])));
+                assert_eq!(manager.orders().count(), 1);
+            }
+            OrphanPolicy::Adopt => {
+                assert_eq!(report.adopted, vec!["0xccc"]);
+                assert!(requests.is_empty());
+                let adopted = manager.get("0xccc").unwrap();
+                assert_eq!((adopted.size, adopted.salt.clone()), (8.0, None));
+            }
+        }
+
+        let reloaded = OrderManager::new(client.orders().unwrap().clone())
+            .with_state_file(&state)
+            .unwrap();
+        assert_eq!(reloaded.orders().count(), manager.orders().count());
+    }
+}
+
+fn order_status(id: &str, status: &str, size_matched: &str) -> serde_json::Value {
+    serde_json::json!({"id": id, "status": status, "asset_id": "123", "side": "BUY",
+                       "price": "0.4", "original_size": "10", "size_matched": size_matched})
+}
+
+#[tokio::test]
+async fn test_poll_fills_reports_size_matched_deltas() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/fee-rate"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"base_fee": 100})),
+        )
+        .mount(&mock_server)
+        .await;
+    for (id, first, second) in [
+        (
+            "0xaaa",
+            order_status("0xaaa", "LIVE", "2"),
+            order_status("0xaaa", "MATCHED", "10"),
+        ),
+        (
+            "0xccc",
+            order_status("0xccc", "LIVE", "3"),
+            order_status("0xccc", "LIVE", "4"),
+        ),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(format!("/data/order/{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/data/order/{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(second))
+            .mount(&mock_server)
+            .await;
+    }
+
+    // 0xaaa was placed here, 0xccc adopted from the exchange
+    let state = temp_dir("order-manager-fills").join("orders.json");
+    std::fs::write(
+        &state,
+        serde_json::json!({"orders": [
+            {"order_id": "0xaaa", "salt": "1", "token_id": "123", "side": "BUY",
+             "price": 0.4, "size": 10.0, "order_type": "GTC"},
+            {"order_id": "0xccc", "salt": null, "token_id": "123", "side": "BUY",
+             "price": 0.4, "size": 7.0, "order_type": null}
+        ]})
+        .to_string(),
+    )
+    .unwrap();
+
+    let client = dry_run_client(mock_server.uri(), &DryRunLog::new());
+    let mut manager = OrderManager::new(client.orders().unwrap().clone())
+        .with_state_file(&state)
+        .unwrap();
+    let mut events = manager.fill_events();
+
+    let fill = |order_id: &str, size: f64| FillEvent {
+        order_id: order_id.to_string(),
+        token_id: "123".to_string(),
+        side: Side::Buy,
+        price: 0.4,
+        size,
+        fee: 0.01 * 0.4 * size,
+    };
+    let first = manager.poll_fills().await.unwrap();
+    assert_eq!(first, vec![fill("0xaaa", 2.0)]);
+
+    let second = manager.poll_fills().await.unwrap();
+    assert_eq!(second, vec![fill("0xaaa", 8.0), fill("0xccc", 1.0)]);
+    // fully matched orders stop being tracked
+    assert!(manager.get("0xaaa").is_none());
+    assert!(manager.get("0xccc").is_some());
+
+    for expected in first.into_iter().chain(second) {
+        assert_eq!(events.try_recv().unwrap(), expected);
+    }
+    assert!(events.try_recv().is_err());
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[tokio::test]
+async fn test_sweep_expired_drops_and_requotes_gtd_orders() {
+    let mock_server = MockServer::start().await;
+    mount_order_lookups(&mock_server).await;
+
+    let log = DryRunLog::new();
+    let client = dry_run_client(mock_server.uri(), &log);
+    let mut manager =
+        OrderManager::new(client.orders().unwrap().clone()).with_expiry_requote(|expired| {
+            let args = OrderArgs::new(&expired.token_id, expired.price, expired.size, expired.side);
+            Some(OrderArgs {
+                expiration: unix_now() + 3600,
+                ..args
+            })
+        });
+
+    let mut gtd = OrderArgs::new("123", 0.4, 10.0, Side::Buy);
+    gtd.expiration = unix_now() - 5;
+    let expiring = manager.place(&gtd, OrderType::GTD).await.unwrap();
+    let resting = manager
+        .place(&OrderArgs::new("123", 0.3, 10.0, Side::Buy), OrderType::GTC)
+        .await
+        .unwrap();
+    assert_eq!(manager.next_expiration(), Some(gtd.expiration));
+
+    let sweep = manager.sweep_expired().await.unwrap();
+    assert_eq!(sweep.expired.len(), 1);
+    assert_eq!(sweep.expired[0].order_id, expiring.order_id.to_lowercase());
+    assert!(manager.get(&expiring.order_id).is_none());
+    assert!(manager.get(&resting.order_id).is_some());
+
+    assert_eq!(sweep.requoted.len(), 1);
+    let requote = sweep.requoted[0].1.as_ref().unwrap();
+    let replacement = manager.get(&requote.order_id).unwrap();
+    assert_eq!(replacement.order_type, Some(OrderType::GTD));
+    assert!(replacement.expiration.unwrap() > unix_now());
+
+    // nothing else is due
+    assert!(manager.sweep_expired().await.unwrap().expired.is_empty());
+}
+
+#[tokio::test]
+async fn test_expiry_sweeper_runs_in_background() {
+    let mock_server = MockServer::start().await;
+    mount_order_lookups(&mock_server).await;
+
+    let log = DryRunLog::new();
+    let client = dry_run_client(mock_server.uri(), &log);
+    let manager = Arc::new(Mutex::new(OrderManager::new(
+        client.orders().unwrap().clone(),
+    )));
+    let mut gtd = OrderArgs::new("123", 0.4, 10.0, Side::Buy);
+    gtd.expiration = unix_now() - 1;
+    manager
+        .lock()
+        .await
+        .place(&gtd, OrderType::GTD)
+        .await
+        .unwrap();
+
+    let sweeper = OrderManager::spawn_expiry_sweeper(manager.clone(), Duration::from_millis(10));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    sweeper.abort();
+    assert_eq!(manager.lock().await.orders().count(), 0);
+}
+
+#[tokio::test]
+async fn test_expiry_sweeper_stops_on_client_shutdown() {
+    let mock_server = MockServer::start().await;
+    let log = DryRunLog::new();
+    let client = dry_run_client(mock_server.uri(), &log);
+    let manager = Arc::new(Mutex::new(OrderManager::new(
+        client.orders().unwrap().clone(),
+    )));
+
+    let sweeper = OrderManager::spawn_expiry_sweeper(manager, Duration::from_secs(60));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let report = client
+        .shutdown(ShutdownPolicy::keep_orders())
+        .await
+        .unwrap();
+
+    assert_eq!(report.tasks_stopped, 1);
+    assert!(report.drained);
+    assert!(sweeper.is_finished());
+}
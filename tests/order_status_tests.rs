@@ -0,0 +1,181 @@
+#![cfg(feature = "http")]
+
+use std::time::Duration;
+
+use clob_rs::{ApiCreds, ClobClient, ClobError, OpenOrderParams, OrderState, Side};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn order_client(uri: String) -> ClobClient {
+    ClobClient::new(uri)
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        })
+}
+
+fn order(status: &str, size_matched: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "id": "0xabc",
+        "status": status,
+        "market": "0xm",
+        "asset_id": "123",
+        "side": "BUY",
+        "price": "0.55",
+        "original_size": 100,
+        "size_matched": size_matched,
+        "outcome": "Yes",
+        "order_type": "GTC",
+        "associate_trades": null
+    })
+}
+
+#[tokio::test]
+async fn test_wait_for_order_terminal() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data/order/0xabc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(order("LIVE", "40".into())))
+        .up_to_n_times(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/order/0xabc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(order("MATCHED", "100".into())))
+        .mount(&mock_server)
+        .await;
+
+    let client = order_client(mock_server.uri());
+    let orders = client.orders().unwrap();
+    let live = orders.get_order("0xabc").await.unwrap();
+    assert_eq!(live.status, OrderState::Live);
+    assert!(!live.status.is_terminal());
+    assert_eq!(live.side, Side::Buy);
+    assert_eq!(clob_rs::amount_to_f64(live.remaining_size()), 60.0);
+    assert!(live.associate_trades.is_empty());
+
+    let done = orders
+        .wait_for_order_terminal("0xabc", Duration::from_millis(10), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(done.status, OrderState::Matched);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_wait_for_order_terminal_times_out() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data/order/0xabc"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(order("ORDER_STATUS_LIVE", 0.into())),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = order_client(mock_server.uri());
+    let orders = client.orders().unwrap();
+    let err = orders
+        .wait_for_order_terminal(
+            "0xabc",
+            Duration::from_millis(10),
+            Duration::from_millis(80),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClobError::Timeout { .. }));
+
+    assert_eq!(
+        OrderState::from("CANCELED_MARKET_RESOLVED"),
+        OrderState::Canceled
+    );
+    assert!(OrderState::from("unmatched").is_terminal());
+    assert_eq!(
+        OrderState::from("SOMETHING_NEW"),
+        OrderState::Other("SOMETHING_NEW".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_open_orders_follow_next_cursor() {
+    let mock_server = MockServer::start().await;
+    let with_id = |id: &str| {
+        let mut order = order("LIVE", serde_json::json!(0));
+        order["id"] = serde_json::json!(id);
+        order
+    };
+
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .and(query_param("next_cursor", "MA=="))
+        .and(query_param("market", "0xm"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [with_id("0x1"), with_id("0x2")],
+            "next_cursor": "NTAw",
+            "limit": 2,
+            "count": 2
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .and(query_param("next_cursor", "NTAw"))
+        .and(query_param("market", "0xm"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [with_id("0x3")],
+            "next_cursor": "LTE=",
+            "limit": 2,
+            "count": 1
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = order_client(mock_server.uri());
+    let orders = client.orders().unwrap();
+    let params = OpenOrderParams {
+        market: Some("0xm".to_string()),
+        ..Default::default()
+    };
+
+    let first = orders.get_orders_page(Some(&params), None).await.unwrap();
+    assert_eq!(first.data.len(), 2);
+    assert_eq!(first.next_cursor, "NTAw");
+
+    let all = orders.get_all_orders(Some(&params)).await.unwrap();
+    let ids: Vec<_> = all.iter().map(|o| o.id.as_str()).collect();
+    assert_eq!(ids, ["0x1", "0x2", "0x3"]);
+    assert_eq!(all[0].status, OrderState::Live);
+
+    let raw = orders.get_orders(Some(&params)).await.unwrap();
+    assert_eq!(raw["data"].as_array().unwrap().len(), 3);
+    assert_eq!(raw["next_cursor"], "LTE=");
+}
+
+#[tokio::test]
+async fn test_ids_are_one_path_segment() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data/order/0xabc%2F..%3Fx=1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(order("LIVE", 0.into())))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/markets/0x%20m%23"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let client = order_client(mock_server.uri());
+    let order = client.orders().unwrap().get_order("0xabc/..?x=1").await;
+    assert_eq!(order.unwrap().status, OrderState::Live);
+    assert!(client.markets().get_market("0x m#").await.is_err());
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[1].url.path(), "/markets/0x%20m%23");
+    assert!(requests.iter().all(|r| r.url.query().is_none()));
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use clob_rs::{pair_token_ids, plan_pair_arb, ClobClient, Market, Side};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{book, level};
+
+fn market(condition_id: &str, yes: &str, no: &str, accepting_orders: bool) -> Market {
+    serde_json::from_value(serde_json::json!({
+        "condition_id": condition_id,
+        "question_id": "0xq",
+        "tokens": [
+            {"token_id": no, "outcome": "No", "price": 0.5},
+            {"token_id": yes, "outcome": "Yes", "price": 0.5}
+        ],
+        "active": true,
+        "closed": false,
+        "accepting_orders": accepting_orders
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_pair_token_ids_finds_yes_outcome() {
+    let m = market("0xa", "1", "2", true);
+    assert_eq!(pair_token_ids(&m), Some(("1", "2")));
+}
+
+#[test]
+fn test_plan_pair_arb_walks_depth_while_edge_holds() {
+    // 0.42 + 0.55 clears 0.02; 0.42 + 0.58 doesn't
+    let yes = book(
+        "1",
+        vec![],
+        vec![level("0.42", "30"), level("0.40", "10")],
+        Some("0.01"),
+        Some(false),
+    );
+    let no = book(
+        "2",
+        vec![],
+        vec![level("0.55", "15"), level("0.58", "50")],
+        Some("0.01"),
+        Some(false),
+    );
+
+    let arb = plan_pair_arb("0xa", &yes, &no, 0.02).unwrap();
+    assert_eq!(arb.side, Side::Buy);
+    // 10 at 0.40 + 0.55, then 5 at 0.42 + 0.55
+    assert!((arb.size - 15.0).abs() < 1e-9);
+    assert_eq!(arb.yes_price, 0.42);
+    assert_eq!(arb.no_price, 0.55);
+    assert!((arb.profit - (10.0 * 0.05 + 5.0 * 0.03)).abs() < 1e-9);
+    assert!((arb.price_sum - (1.0 - arb.profit / arb.size)).abs() < 1e-9);
+    assert_eq!(arb.orders.len(), 2);
+    assert_eq!(arb.orders[0].token_id, "1");
+    assert_eq!(arb.orders[0].price, 0.42);
+    assert_eq!(arb.orders[1].price, 0.55);
+    assert!(arb.orders.iter().all(|o| o.size == arb.size));
+
+    assert!(plan_pair_arb("0xa", &yes, &no, 0.05).is_none());
+
+    let yes = book(
+        "1",
+        vec![level("0.50", "8")],
+        vec![level("0.60", "5")],
+        Some("0.01"),
+        Some(false),
+    );
+    let no = book(
+        "2",
+        vec![level("0.53", "20")],
+        vec![level("0.60", "5")],
+        Some("0.01"),
+        Some(false),
+    );
+    let arb = plan_pair_arb("0xa", &yes, &no, 0.0).unwrap();
+    assert_eq!(arb.side, Side::Sell);
+    assert_eq!(arb.size, 8.0);
+    assert!((arb.price_sum - 1.03).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_scan_pair_arbs_batches_books() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/books"))
+        .and(body_json(serde_json::json!([
+            {"token_id": "11"}, {"token_id": "12"},
+            {"token_id": "21"}, {"token_id": "22"}
+        ])))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+            book(
+                "11",
+                vec![],
+                vec![level("0.45", "20")],
+                Some("0.01"),
+                Some(false),
+            ),
+            book(
+                "12",
+                vec![],
+                vec![level("0.50", "10")],
+                Some("0.01"),
+                Some(false),
+            ),
+            book(
+                "21",
+                vec![],
+                vec![level("0.50", "20")],
+                Some("0.01"),
+                Some(false),
+            ),
+            book(
+                "22",
+                vec![],
+                vec![level("0.52", "10")],
+                Some("0.01"),
+                Some(false),
+            ),
+        ]))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let markets = vec![
+        market("0xa", "11", "12", true),
+        market("0xb", "21", "22", true),
+        market("0xc", "31", "32", false),
+    ];
+    let client = ClobClient::new(mock_server.uri());
+    let found = client.prices().scan_pair_arbs(&markets, 0.01).await;
+
+    assert!(found.errors.is_empty());
+    assert_eq!(found.data.len(), 1);
+    let arb = &found.data[0];
+    assert_eq!(arb.condition_id, "0xa");
+    assert_eq!(arb.yes_token_id, "11");
+    assert_eq!(arb.no_token_id, "12");
+    assert_eq!(arb.size, 10.0);
+}
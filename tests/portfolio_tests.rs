@@ -0,0 +1,161 @@
+#![cfg(feature = "http")]
+
+use clob_rs::{ApiCreds, ClobClient, Fill, Portfolio, Side};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const TEST_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn fill(id: &str, side: Side, price: f64, size: f64) -> Fill {
+    Fill {
+        id: id.to_string(),
+        token_id: "123".to_string(),
+        side,
+        price,
+        size,
+        fee: 0.0,
+    }
+}
+
+fn trades() -> serde_json::Value {
+    serde_json::json!([
+        {
+            "id": "t1",
+            "asset_id": "123",
+            "side": "BUY",
+            "price": "0.40",
+            "size": "100",
+            "fee_rate_bps": "0",
+            "trader_side": "TAKER",
+            "maker_orders": []
+        },
+        {
+            "id": "t2",
+            "asset_id": "123",
+            "side": "BUY",
+            "price": "0.50",
+            "size": "60",
+            "trader_side": "MAKER",
+            "maker_orders": [
+                {
+                    "order_id": "o1",
+                    "maker_address": TEST_ADDRESS.to_lowercase(),
+                    "asset_id": "123",
+                    "side": "SELL",
+                    "price": "0.50",
+                    "matched_amount": "40",
+                    "fee_rate_bps": "100"
+                },
+                {
+                    "order_id": "o2",
+                    "maker_address": "0x0000000000000000000000000000000000000001",
+                    "asset_id": "123",
+                    "side": "SELL",
+                    "price": "0.50",
+                    "matched_amount": "20"
+                }
+            ]
+        }
+    ])
+}
+
+#[test]
+fn test_fills_from_taker_and_maker_trades() {
+    let fills = Fill::from_trades(&trades(), TEST_ADDRESS);
+    assert_eq!(fills.len(), 2);
+
+    assert_eq!(fills[0].id, "t1");
+    assert_eq!(fills[0].side, Side::Buy);
+    assert_eq!(fills[0].size, 100.0);
+
+    assert_eq!(fills[1].id, "t2:o1");
+    assert_eq!(fills[1].side, Side::Sell);
+    assert_eq!(fills[1].size, 40.0);
+    // 1% of min(0.5, 0.5) per share
+    assert!(approx(fills[1].fee, 0.2));
+
+    let page = serde_json::json!({"data": trades(), "next_cursor": "LTE="});
+    assert_eq!(Fill::from_trades(&page, TEST_ADDRESS).len(), 2);
+}
+
+#[test]
+fn test_position_accounting() {
+    let mut portfolio = Portfolio::new();
+    assert!(portfolio.apply_fill(&fill("a", Side::Buy, 0.40, 100.0)));
+    assert!(portfolio.apply_fill(&fill("b", Side::Buy, 0.60, 100.0)));
+    assert!(!portfolio.apply_fill(&fill("b", Side::Buy, 0.60, 100.0)));
+
+    let position = portfolio.position("123").unwrap();
+    assert!(approx(position.size, 200.0));
+    assert!(approx(position.avg_cost, 0.50));
+
+    portfolio.apply_fill(&fill("c", Side::Sell, 0.70, 150.0));
+    let position = portfolio.position("123").unwrap();
+    assert!(approx(position.size, 50.0));
+    assert!(approx(position.avg_cost, 0.50));
+    assert!(approx(position.realized_pnl, 30.0));
+
+    // through zero: closes 50, opens 50 short at 0.80
+    portfolio.apply_fill(&fill("d", Side::Sell, 0.80, 100.0));
+    let position = portfolio.position("123").unwrap();
+    assert!(approx(position.size, -50.0));
+    assert!(approx(position.avg_cost, 0.80));
+    assert!(approx(position.realized_pnl, 45.0));
+
+    portfolio.set_mark("123", 0.70);
+    let snapshot = portfolio.snapshot();
+    assert_eq!(snapshot.positions.len(), 1);
+    assert_eq!(snapshot.positions[0].mark, Some(0.70));
+    assert!(approx(snapshot.unrealized_pnl, 5.0));
+    assert!(approx(snapshot.total_pnl, 50.0));
+}
+
+#[tokio::test]
+async fn test_update_portfolio_applies_trades_and_marks() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/data/trades"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(trades()))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/midpoints"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"123": "0.55"})))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        });
+    let orders = client.orders().unwrap();
+
+    let mut portfolio = Portfolio::new();
+    assert_eq!(
+        orders.update_portfolio(&mut portfolio, None).await.unwrap(),
+        2
+    );
+    assert_eq!(
+        orders.update_portfolio(&mut portfolio, None).await.unwrap(),
+        0
+    );
+
+    let snapshot = portfolio.snapshot();
+    let position = &snapshot.positions[0];
+    assert!(approx(position.size, 60.0));
+    assert!(approx(position.avg_cost, 0.40));
+    assert!(approx(position.realized_pnl, 40.0 * 0.10 - 0.2));
+    assert_eq!(position.mark, Some(0.55));
+    assert!(approx(position.unrealized_pnl, 60.0 * 0.15));
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "http")]
+
+use std::time::Duration;
+
+use clob_rs::{ApiCreds, ClobClient, ClobError, DryRunLog, OrderType, PresignedPool, Side};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+async fn dry_run_client(mock_server: &MockServer, log: &DryRunLog) -> ClobClient {
+    for (endpoint, body) in [
+        ("/tick-size", serde_json::json!({"minimum_tick_size": 0.01})),
+        ("/neg-risk", serde_json::json!({"neg_risk": false})),
+        ("/fee-rate", serde_json::json!({"base_fee": 0})),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(mock_server)
+            .await;
+    }
+    ClobClient::new(mock_server.uri())
+        .with_dry_run(log.clone())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        })
+}
+
+#[tokio::test]
+async fn test_presigned_pool_posts_each_order_once() {
+    let mock_server = MockServer::start().await;
+    let log = DryRunLog::new();
+    let client = dry_run_client(&mock_server, &log).await;
+
+    let mut pool = PresignedPool::new(client.orders().unwrap().clone(), "123", Side::Buy);
+    let signed = pool.fill(&[0.40, 0.41], &[10.0, 25.0]).await.unwrap();
+    assert_eq!(signed, 4);
+    assert_eq!(pool.len(), 4);
+
+    // refilling a point replaces its entry
+    pool.fill(&[0.41], &[10.0]).await.unwrap();
+    assert_eq!(pool.len(), 4);
+
+    let response = pool.post(0.41, 10.0).await.unwrap();
+    assert!(response.success);
+    assert_eq!(pool.len(), 3);
+    assert!(pool
+        .entries()
+        .iter()
+        .all(|e| e.order.expiration == "0" && e.order.token_id == "123"));
+
+    assert!(matches!(
+        pool.post(0.41, 10.0).await,
+        Err(ClobError::InvalidParameter(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_presigned_pool_expires_entries() {
+    let mock_server = MockServer::start().await;
+    let log = DryRunLog::new();
+    let client = dry_run_client(&mock_server, &log).await;
+    let orders = client.orders().unwrap().clone();
+
+    let mut pool = PresignedPool::new(orders.clone(), "123", Side::Sell)
+        .with_order_type(OrderType::GTD)
+        .with_ttl(Duration::from_secs(120));
+    pool.fill(&[0.60], &[5.0]).await.unwrap();
+    let entry = &pool.entries()[0];
+    let expiration: u64 = entry.order.expiration.parse().unwrap();
+    assert_eq!(expiration, entry.expires_at + 60);
+    assert_eq!(pool.next_expiry(), Some(entry.expires_at));
+    assert_eq!(pool.evict_expired().unwrap(), 0);
+
+    let mut stale = PresignedPool::new(orders, "123", Side::Sell).with_ttl(Duration::ZERO);
+    stale.fill(&[0.60, 0.61], &[5.0]).await.unwrap();
+    assert!(stale.take(0.60, 5.0).unwrap().is_none());
+    assert!(stale.is_empty());
+}
@@ -0,0 +1,146 @@
+#![cfg(feature = "http")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use clob_rs::{
+    MarketDataApi, MarketDataService, OrderBook, OrderSummary, PriceWatcher, QuoteSource, Transport,
+};
+use futures::StreamExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+type Books = Arc<Mutex<HashMap<String, OrderBook>>>;
+
+fn book(asset_id: &str, bid: Option<&str>, ask: Option<&str>) -> OrderBook {
+    let level = |price: &str| OrderSummary {
+        price: price.to_string(),
+        size: "10".to_string(),
+    };
+    OrderBook {
+        market: "0xmarket".to_string(),
+        asset_id: asset_id.to_string(),
+        timestamp: "0".to_string(),
+        hash: "".to_string(),
+        bids: bid.map(level).into_iter().collect(),
+        asks: ask.map(level).into_iter().collect(),
+        min_order_size: None,
+        tick_size: None,
+        neg_risk: None,
+    }
+}
+
+fn service(uri: String, books: &Books) -> MarketDataService {
+    let books = books.clone();
+    MarketDataService::new(MarketDataApi::new(Transport::new(uri)))
+        .with_live_source(move |token_id: &str| books.lock().unwrap().get(token_id).cloned())
+}
+
+fn set(books: &Books, token_id: &str, bid: Option<&str>, ask: Option<&str>) {
+    books
+        .lock()
+        .unwrap()
+        .insert(token_id.to_string(), book(token_id, bid, ask));
+}
+
+#[tokio::test]
+async fn test_price_watcher_reports_moves_past_threshold() {
+    let books = Books::default();
+    set(&books, "1", Some("0.40"), Some("0.60"));
+    let mut watcher =
+        PriceWatcher::new(service("http://127.0.0.1:1".into(), &books), ["1"]).with_threshold(0.02);
+
+    let first = watcher.poll().await;
+    assert_eq!(first.len(), 1);
+    let change = first[0].as_ref().unwrap();
+    assert!(change.previous.is_none());
+    assert_eq!(change.current.best_bid, Some(0.4));
+    assert_eq!(change.current.source, QuoteSource::Live);
+
+    // too small, compared with what was last reported rather than last seen
+    set(&books, "1", Some("0.41"), Some("0.60"));
+    assert!(watcher.poll().await.is_empty());
+    set(&books, "1", Some("0.42"), Some("0.60"));
+    let change = watcher.poll().await.remove(0).unwrap();
+    assert_eq!(change.previous.unwrap().best_bid, Some(0.4));
+    assert_eq!(change.current.best_bid, Some(0.42));
+    assert_eq!(watcher.last_reported("1").unwrap().best_bid, Some(0.42));
+
+    // a side emptying always counts
+    set(&books, "1", Some("0.42"), None);
+    let change = watcher.poll().await.remove(0).unwrap();
+    assert_eq!(change.current.best_ask, None);
+    assert_eq!(change.current.midpoint, None);
+    assert!(watcher.poll().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_price_watcher_debounces_flickers() {
+    let books = Books::default();
+    set(&books, "1", Some("0.40"), Some("0.60"));
+    let mut watcher = PriceWatcher::new(service("http://127.0.0.1:1".into(), &books), ["1"])
+        .with_debounce(Duration::from_millis(100));
+    assert_eq!(watcher.poll().await.len(), 1);
+
+    // moves and comes back within the debounce
+    set(&books, "1", Some("0.30"), Some("0.60"));
+    assert!(watcher.poll().await.is_empty());
+    set(&books, "1", Some("0.40"), Some("0.60"));
+    assert!(watcher.poll().await.is_empty());
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(watcher.poll().await.is_empty());
+
+    // holds past it
+    set(&books, "1", Some("0.45"), Some("0.60"));
+    assert!(watcher.poll().await.is_empty());
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    let change = watcher.poll().await.remove(0).unwrap();
+    assert_eq!(change.current.best_bid, Some(0.45));
+}
+
+#[tokio::test]
+async fn test_price_watcher_stream_falls_back_to_rest() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "market": "0xmarket",
+            "asset_id": "2",
+            "timestamp": "0",
+            "hash": "",
+            "bids": [{"price": "0.2", "size": "10"}],
+            "asks": [{"price": "0.3", "size": "10"}]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let books = Books::default();
+    set(&books, "1", Some("0.40"), Some("0.60"));
+    let watcher = PriceWatcher::new(service(mock_server.uri(), &books), ["1", "2"])
+        .with_interval(Duration::from_millis(10));
+    let mut stream = Box::pin(watcher.into_stream());
+    let mut sources = Vec::new();
+    for _ in 0..2 {
+        let change = stream.next().await.unwrap().unwrap();
+        assert!(change.previous.is_none());
+        sources.push((change.current.token_id, change.current.source));
+    }
+    sources.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        sources,
+        vec![
+            ("1".to_string(), QuoteSource::Live),
+            ("2".to_string(), QuoteSource::Rest)
+        ]
+    );
+
+    set(&books, "1", Some("0.45"), Some("0.60"));
+    let change = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(change.current.token_id, "1");
+    assert_eq!(change.previous.unwrap().best_bid, Some(0.4));
+}
@@ -0,0 +1,81 @@
+use clob_rs::pricing::*;
+use clob_rs::{Market, OrderBook, OrderSummary};
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn book_with_asks(asks: &[&str]) -> OrderBook {
+    OrderBook {
+        market: "0xmarket".to_string(),
+        asset_id: "1".to_string(),
+        timestamp: "0".to_string(),
+        hash: "".to_string(),
+        bids: vec![],
+        asks: asks
+            .iter()
+            .map(|p| OrderSummary {
+                price: p.to_string(),
+                size: "100".to_string(),
+            })
+            .collect(),
+        min_order_size: None,
+        tick_size: None,
+        neg_risk: None,
+    }
+}
+
+#[test]
+fn test_odds_conversions() {
+    assert!(approx(price_to_decimal_odds(0.25).unwrap(), 4.0));
+    assert!(approx(decimal_odds_to_price(4.0).unwrap(), 0.25));
+
+    assert!(approx(price_to_american_odds(0.25).unwrap(), 300.0));
+    assert!(approx(price_to_american_odds(0.75).unwrap(), -300.0));
+    assert!(approx(american_odds_to_price(300.0).unwrap(), 0.25));
+    assert!(approx(american_odds_to_price(-300.0).unwrap(), 0.75));
+
+    assert!(price_to_decimal_odds(0.0).is_none());
+    assert!(price_to_american_odds(1.0).is_none());
+    assert!(american_odds_to_price(50.0).is_none());
+}
+
+#[test]
+fn test_remove_vig() {
+    let fair = remove_vig(&[0.55, 0.50]).unwrap();
+    assert!(approx(fair[0] + fair[1], 1.0));
+    assert!(approx(fair[0], 0.55 / 1.05));
+    assert!(approx(overround(&[0.55, 0.50]), 0.05));
+}
+
+#[test]
+fn test_market_and_book_implied_probabilities() {
+    let market: Market = serde_json::from_value(serde_json::json!({
+        "condition_id": "0xabc",
+        "question_id": "0xq",
+        "tokens": [
+            {"token_id": "1", "outcome": "Yes", "price": 0.6},
+            {"token_id": "2", "outcome": "No", "price": 0.45}
+        ],
+        "active": true,
+        "closed": false
+    }))
+    .unwrap();
+
+    let probs = market_implied_probabilities(&market).unwrap();
+    assert_eq!(probs[0].0, "Yes");
+    assert!(approx(probs[0].1, 0.6 / 1.05));
+
+    // neg-risk event with three outcomes, asks not best-first
+    let books = [
+        book_with_asks(&["0.52", "0.50"]),
+        book_with_asks(&["0.30"]),
+        book_with_asks(&["0.25"]),
+    ];
+    assert!(approx(book_overround(&books).unwrap(), 0.05));
+    let probs = book_implied_probabilities(&books).unwrap();
+    assert!(approx(probs.iter().sum::<f64>(), 1.0));
+    assert!(approx(probs[0], 0.50 / 1.05));
+
+    assert!(book_implied_probabilities(&[book_with_asks(&[])]).is_none());
+}
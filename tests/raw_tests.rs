@@ -0,0 +1,114 @@
+#![cfg(feature = "http")]
+
+use clob_rs::{
+    ApiCreds, ClobClient, ClobError, DryRunLog, OrderArgs, OrderBook, OrderType, Raw, Side,
+};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+#[tokio::test]
+async fn test_raw_responses_keep_unmodeled_fields() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "market": "0xmarket",
+            "asset_id": "123",
+            "timestamp": "0",
+            "hash": "",
+            "bids": [{"price": "0.4", "size": "10"}],
+            "asks": [],
+            "min_order_size": "5",
+            "last_trade": {"price": "0.41", "side": "BUY"}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"mid": "0.45", "stale": true})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri());
+    let book = client.prices().get_order_book_raw("123").await.unwrap();
+    assert_eq!(book.data.asset_id, "123");
+    assert_eq!(book.data.bids.len(), 1);
+    assert_eq!(
+        book.pointer("/last_trade/price"),
+        Some(&serde_json::json!("0.41"))
+    );
+    let stale = client.prices().get_midpoint_raw("123").await.unwrap();
+    assert!(stale.field::<bool>("stale").unwrap().unwrap());
+    assert!(stale.field::<bool>("missing").is_none());
+
+    // the raw call feeds the same caches as the typed one
+    assert_eq!(
+        client
+            .market_data()
+            .get_min_order_size("123")
+            .await
+            .unwrap(),
+        5.0
+    );
+
+    // serializes as what the server sent
+    let json = serde_json::to_value(&book).unwrap();
+    assert_eq!(json["last_trade"]["side"], "BUY");
+    let (data, _) = book.into_parts();
+    assert_eq!(data.market, "0xmarket");
+}
+
+#[test]
+fn test_raw_decode_errors_name_the_field() {
+    let err = serde_json::from_value::<Raw<OrderBook>>(serde_json::json!({
+        "market": "0xmarket",
+        "asset_id": "123",
+        "timestamp": "0",
+        "hash": "",
+        "bids": [{"price": "0.4", "size": []}],
+        "asks": []
+    }))
+    .unwrap_err();
+    assert!(err.to_string().starts_with("bids[0].size: "), "{}", err);
+}
+
+#[tokio::test]
+async fn test_post_order_raw_dry_run() {
+    let log = DryRunLog::new();
+    let client = ClobClient::new("http://127.0.0.1:1")
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        })
+        .with_auto_fee_rate(false)
+        .with_dry_run(log.clone());
+    let orders = client.orders().unwrap();
+    let options = clob_rs::CreateOrderOptions {
+        tick_size: clob_rs::TickSize::Size0_01,
+        neg_risk: false,
+        salt: Some(1),
+    };
+    let order = orders
+        .order_builder()
+        .create_order(&OrderArgs::new("123", 0.5, 10.0, Side::Buy), &options)
+        .await
+        .unwrap();
+
+    let response = orders.post_order_raw(&order, OrderType::GTC).await.unwrap();
+    assert!(response.data.success);
+    assert_eq!(response.json["orderID"], response.data.order_id.as_str());
+    assert_eq!(log.requests().len(), 1);
+
+    assert!(matches!(
+        client.prices().get_order_book_raw("123").await,
+        Err(ClobError::Http(_))
+    ));
+}
@@ -0,0 +1,157 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use std::time::Duration;
+
+use clob_rs::backtest::CaptureEvent;
+use clob_rs::recorder::{read_capture_file, CaptureWriter, Recorder};
+use clob_rs::{MarketDataApi, OrderBook, Side, Transport};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::temp_dir;
+
+fn book_json(bid: &str) -> serde_json::Value {
+    serde_json::json!({
+        "market": "0xmarket",
+        "asset_id": "1",
+        "timestamp": "1700000000000",
+        "hash": "",
+        "bids": [{"price": bid, "size": "100"}],
+        "asks": [{"price": "0.60", "size": "100"}]
+    })
+}
+
+fn trade(ts: u64, price: f64) -> CaptureEvent {
+    CaptureEvent::Trade {
+        ts,
+        token_id: "1".to_string(),
+        side: Side::Buy,
+        price,
+        size: 10.0,
+    }
+}
+
+#[test]
+fn test_capture_writer_rotates_and_compresses() {
+    let dir = temp_dir("rotate");
+    let mut writer = CaptureWriter::new(&dir)
+        .with_compression(true)
+        .with_rotate_interval(Duration::from_secs(60));
+
+    let events = vec![
+        trade(1_000, 0.5),
+        trade(30_000, 0.51),
+        trade(61_000, 0.52),
+        trade(62_000, 0.53),
+    ];
+    for event in &events {
+        writer.write(event).unwrap();
+    }
+    let files = writer.finish().unwrap();
+
+    assert_eq!(files.len(), 2);
+    assert!(files[0].ends_with("capture-1000.jsonl.gz"));
+    assert!(files[1].ends_with("capture-61000.jsonl.gz"));
+
+    let read: Vec<CaptureEvent> = files
+        .iter()
+        .flat_map(|f| read_capture_file(f).unwrap())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(read, events);
+
+    let mut writer = CaptureWriter::new(&dir).with_rotate_bytes(1);
+    writer.write(&trade(1_000, 0.5)).unwrap();
+    writer.write(&trade(1_000, 0.5)).unwrap();
+    let files = writer.finish().unwrap();
+    assert!(files[0].ends_with("capture-1000.jsonl"));
+    assert!(files[1].ends_with("capture-1000-1.jsonl"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_recorder_writes_changed_books_and_new_trades() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/books"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![book_json("0.40")]))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/books"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![book_json("0.45")]))
+        .mount(&mock_server)
+        .await;
+
+    let print = |id: &str, timestamp: &str| {
+        serde_json::json!({
+            "id": id,
+            "type": "TRADE",
+            "timestamp": timestamp,
+            "token_id": "1",
+            "side": "BUY",
+            "price": 0.6,
+            "size": 5
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/live-activity/events/0xmarket"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![print("a", "1700000000")]))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/live-activity/events/0xmarket"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(vec![print("b", "1700000001"), print("a", "1700000000")]),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let dir = temp_dir("recorder");
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let recorder = Recorder::new(market_data, vec!["1".to_string()], CaptureWriter::new(&dir))
+        .with_poll_interval(Duration::from_millis(10));
+    let stats = recorder
+        .run(tokio::time::sleep(Duration::from_millis(200)))
+        .await
+        .unwrap();
+
+    assert!(stats.polls >= 3);
+    assert_eq!(stats.errors, 0);
+    assert_eq!(stats.books, 2);
+    assert_eq!(stats.trades, 1);
+    assert_eq!(stats.files.len(), 1);
+
+    let events: Vec<CaptureEvent> = read_capture_file(&stats.files[0])
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(events.len(), 3);
+    let books: Vec<&OrderBook> = events
+        .iter()
+        .filter_map(|e| match e {
+            CaptureEvent::Book { book, .. } => Some(book),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(books[0].bids[0].price, "0.40");
+    assert_eq!(books[1].bids[0].price, "0.45");
+    assert!(events.contains(&CaptureEvent::Trade {
+        ts: 1_700_000_001_000,
+        token_id: "1".to_string(),
+        side: Side::Buy,
+        price: 0.6,
+        size: 5.0,
+    }));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use clob_rs::{OrderArgs, OrderType, ReplaceStrategy, Side};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::client;
+
+async fn mount_market(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/tick-size"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"minimum_tick_size": 0.01})),
+        )
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/neg-risk"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"neg_risk": false})),
+        )
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/fee-rate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"base_fee": 0})))
+        .mount(server)
+        .await;
+}
+
+async fn mount_post(server: &MockServer, expected: u64) {
+    Mock::given(method("POST"))
+        .and(path("/order"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "orderID": "0xnew",
+            "status": "live"
+        })))
+        .expect(expected)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_replace_order_cancels_then_posts() {
+    let mock_server = MockServer::start().await;
+    mount_market(&mock_server).await;
+    mount_post(&mock_server, 1).await;
+    Mock::given(method("DELETE"))
+        .and(path("/order"))
+        .and(body_partial_json(serde_json::json!({"orderID": "0xold"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "canceled": ["0xold"],
+            "not_canceled": {}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = client(mock_server.uri());
+    let args = OrderArgs::new("123", 0.46, 10.0, Side::Buy);
+    let result = client
+        .orders()
+        .unwrap()
+        .replace_order("0xold", &args, OrderType::GTC)
+        .await
+        .unwrap();
+
+    assert_eq!(result.strategy, ReplaceStrategy::CancelThenPost);
+    assert!(result.is_replaced());
+    assert_eq!(result.new_order_id(), Some("0xnew"));
+}
+
+#[tokio::test]
+async fn test_replace_order_skips_post_when_cancel_unconfirmed() {
+    let mock_server = MockServer::start().await;
+    mount_market(&mock_server).await;
+    mount_post(&mock_server, 0).await;
+    // the old order already filled
+    Mock::given(method("DELETE"))
+        .and(path("/order"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "canceled": [],
+            "not_canceled": {"0xold": "order already matched"}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = client(mock_server.uri());
+    let args = OrderArgs::new("123", 0.46, 10.0, Side::Buy);
+    let result = client
+        .orders()
+        .unwrap()
+        .replace_order("0xold", &args, OrderType::GTC)
+        .await
+        .unwrap();
+
+    assert!(result.cancel.as_ref().unwrap().is_ok());
+    assert!(result.post.is_none());
+    assert!(!result.is_replaced());
+}
+
+#[tokio::test]
+async fn test_replace_order_post_then_cancel_keeps_old_order_on_rejection() {
+    let mock_server = MockServer::start().await;
+    mount_market(&mock_server).await;
+    Mock::given(method("POST"))
+        .and(path("/order"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("not enough balance"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/order"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let client = client(mock_server.uri());
+    let args = OrderArgs::new("123", 0.46, 10.0, Side::Buy);
+    let result = client
+        .orders()
+        .unwrap()
+        .replace_order_with(
+            "0xold",
+            &args,
+            OrderType::GTC,
+            ReplaceStrategy::PostThenCancel,
+        )
+        .await
+        .unwrap();
+
+    assert!(result.post.as_ref().unwrap().is_err());
+    assert!(result.cancel.is_none());
+    assert_eq!(result.new_order_id(), None);
+}
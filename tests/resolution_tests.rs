@@ -0,0 +1,66 @@
+use clob_rs::{Market, PositionSnapshot, Resolution};
+
+fn market(closed: bool, winner: Option<&str>) -> Market {
+    serde_json::from_value(serde_json::json!({
+        "condition_id": "0xc0",
+        "question_id": "0xq",
+        "tokens": [
+            {"token_id": "1", "outcome": "Yes", "price": 1, "winner": winner == Some("1")},
+            {"token_id": "2", "outcome": "No", "price": 0, "winner": winner == Some("2")}
+        ],
+        "active": true,
+        "closed": closed
+    }))
+    .unwrap()
+}
+
+fn position(token_id: &str, size: f64) -> PositionSnapshot {
+    PositionSnapshot {
+        token_id: token_id.to_string(),
+        size,
+        avg_cost: 0.4,
+        mark: None,
+        realized_pnl: 0.0,
+        unrealized_pnl: 0.0,
+        fees: 0.0,
+    }
+}
+
+#[test]
+fn test_resolution_from_market_flags() {
+    assert_eq!(
+        Resolution::from_market(&market(false, None)),
+        Resolution::Open
+    );
+    let pending = Resolution::from_market(&market(true, None));
+    assert_eq!(pending, Resolution::Pending);
+    assert!(!pending.is_resolved());
+    assert_eq!(pending.redeemable_amount(&position("1", 10.0)), 0.0);
+
+    let resolved = Resolution::from_market(&market(true, Some("2")));
+    assert!(resolved.is_resolved());
+    assert_eq!(resolved.winner().unwrap().outcome, "No");
+    assert_eq!(resolved.payout("1"), Some(0.0));
+    assert_eq!(resolved.redeemable_amount(&position("2", 12.5)), 12.5);
+    assert_eq!(resolved.redeemable_amount(&position("1", 12.5)), 0.0);
+    assert_eq!(resolved.redeemable_amount(&position("3", 12.5)), 0.0);
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+async fn test_get_resolution() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    let body = serde_json::to_value(market(true, Some("1"))).unwrap();
+    Mock::given(method("GET"))
+        .and(path("/markets/0xc0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&mock_server)
+        .await;
+
+    let client = clob_rs::ClobClient::new(mock_server.uri());
+    let resolution = client.markets().get_resolution("0xc0").await.unwrap();
+    assert_eq!(resolution.winner().unwrap().token_id, "1");
+}
@@ -0,0 +1,84 @@
+use clob_rs::{MarketRewards, OrderArgs, RewardQuoter, Side, TickSize};
+
+fn rewards(min_size: f64, max_spread_cents: f64) -> MarketRewards {
+    serde_json::from_value(serde_json::json!({
+        "rates": [
+            {"asset_address": "0xusdc", "rewards_daily_rate": 20},
+            {"asset_address": "0xother", "rewards_daily_rate": "5.5"}
+        ],
+        "min_size": min_size,
+        "max_spread": max_spread_cents,
+    }))
+    .unwrap()
+}
+
+fn quotes(orders: &[OrderArgs], side: Side) -> Vec<(f64, f64)> {
+    orders
+        .iter()
+        .filter(|o| o.side == side)
+        .map(|o| ((o.price * 1000.0).round() / 1000.0, o.size))
+        .collect()
+}
+
+#[test]
+fn test_reward_quoter_from_market_rewards() {
+    let quoter = RewardQuoter::from_rewards(&rewards(50.0, 3.0)).unwrap();
+    assert!((quoter.max_spread - 0.03).abs() < 1e-12);
+    assert_eq!(quoter.min_size, 50.0);
+    assert_eq!(quoter.daily_rate, 25.5);
+
+    let inactive: MarketRewards = serde_json::from_value(serde_json::json!({
+        "rates": null, "min_size": 50, "max_spread": 3
+    }))
+    .unwrap();
+    assert!(RewardQuoter::from_rewards(&inactive).is_none());
+}
+
+#[test]
+fn test_reward_score_and_qualification() {
+    let quoter = RewardQuoter::from_rewards(&rewards(50.0, 4.0)).unwrap();
+    assert_eq!(quoter.score(0.0), 1.0);
+    assert!((quoter.score(0.02) - 0.25).abs() < 1e-9);
+    assert_eq!(quoter.score(0.04), 0.0);
+    assert_eq!(quoter.score(0.05), 0.0);
+
+    assert!(quoter.qualifies(&OrderArgs::new("1", 0.46, 50.0, Side::Buy), 0.50));
+    assert!(!quoter.qualifies(&OrderArgs::new("1", 0.45, 50.0, Side::Buy), 0.50));
+    assert!(!quoter.qualifies(&OrderArgs::new("1", 0.48, 49.0, Side::Buy), 0.50));
+}
+
+#[test]
+fn test_reward_ladder_quotes_widest_qualifying_ticks() {
+    let quoter = RewardQuoter::from_rewards(&rewards(50.0, 3.0)).unwrap();
+
+    // an off-tick midpoint rounds the bid down and the ask up, both must
+    // still be within 3 cents
+    let ladder = quoter.ladder("1", 0.505, TickSize::Size0_01, 1).unwrap();
+    let orders = ladder.orders();
+    assert_eq!(quotes(&orders, Side::Buy), vec![(0.48, 50.0)]);
+    assert_eq!(quotes(&orders, Side::Sell), vec![(0.53, 50.0)]);
+    assert!(orders.iter().all(|o| quoter.qualifies(o, 0.505)));
+
+    let orders = quoter
+        .ladder("1", 0.50, TickSize::Size0_01, 2)
+        .unwrap()
+        .orders();
+    assert_eq!(quotes(&orders, Side::Buy), vec![(0.48, 50.0), (0.47, 50.0)]);
+    assert_eq!(
+        quotes(&orders, Side::Sell),
+        vec![(0.52, 50.0), (0.53, 50.0)]
+    );
+
+    // levels never reach the midpoint
+    let orders = quoter
+        .ladder("1", 0.50, TickSize::Size0_01, 10)
+        .unwrap()
+        .orders();
+    assert_eq!(orders.len(), 6);
+    assert!(orders.iter().all(|o| quoter.qualifies(o, 0.50)));
+    assert!(orders.iter().all(|o| (o.price - 0.50).abs() > 0.005));
+
+    // a spread narrower than a tick can't be quoted
+    let tight = RewardQuoter::from_rewards(&rewards(50.0, 0.5)).unwrap();
+    assert!(tight.ladder("1", 0.50, TickSize::Size0_01, 1).is_none());
+}
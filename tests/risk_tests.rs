@@ -0,0 +1,355 @@
+#![cfg(feature = "http")]
+
+use clob_rs::{
+    ApiCreds, ClobClient, ClobError, DryRunLog, OrderArgs, OrderTerms, OrderType,
+    PartialCreateOrderOptions, RiskConfig, Side, SpreadGuard, TickSize,
+};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn options() -> Option<PartialCreateOrderOptions> {
+    Some(PartialCreateOrderOptions {
+        tick_size: Some(TickSize::Size0_01),
+        neg_risk: Some(false),
+        salt: None,
+    })
+}
+
+fn risk_client(uri: String, risk: RiskConfig, log: &DryRunLog) -> ClobClient {
+    ClobClient::new(uri)
+        .with_auto_fee_rate(false)
+        .with_dry_run(log.clone())
+        .with_risk_config(risk)
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        })
+}
+
+#[tokio::test]
+async fn test_order_terms_and_notional_limit() {
+    let mock_server = MockServer::start().await;
+    let log = DryRunLog::new();
+    let client = risk_client(
+        mock_server.uri(),
+        RiskConfig::new().with_max_order_notional(20.0),
+        &log,
+    );
+    let orders = client.orders().unwrap();
+
+    let order = orders
+        .create_order(&OrderArgs::new("123", 0.5, 100.0, Side::Buy), options())
+        .await
+        .unwrap();
+    let terms = OrderTerms::from_order(&order).unwrap();
+    assert_eq!(terms.side, Side::Buy);
+    assert_eq!(terms.size, 100.0);
+    assert_eq!(terms.notional, 50.0);
+    assert!((terms.price - 0.5).abs() < 1e-12);
+
+    let err = orders.post_order(&order, OrderType::GTC).await.unwrap_err();
+    assert!(matches!(err, ClobError::RiskLimit { .. }));
+    assert!(log.is_empty());
+
+    orders
+        .create_and_post_order(
+            &OrderArgs::new("123", 0.5, 40.0, Side::Buy),
+            options(),
+            OrderType::GTC,
+        )
+        .await
+        .unwrap();
+    assert_eq!(log.len(), 1);
+}
+
+#[tokio::test]
+async fn test_price_collar_against_midpoint() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": "0.55"})))
+        .mount(&mock_server)
+        .await;
+
+    let log = DryRunLog::new();
+    let client = risk_client(
+        mock_server.uri(),
+        RiskConfig::new().with_max_price_deviation(0.05),
+        &log,
+    );
+    let orders = client.orders().unwrap();
+
+    let far = OrderArgs::new("123", 0.40, 10.0, Side::Buy);
+    let err = orders
+        .create_and_post_order(&far, options(), OrderType::GTC)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClobError::RiskLimit { .. }));
+
+    let near = OrderArgs::new("123", 0.60, 10.0, Side::Sell);
+    orders
+        .create_and_post_order(&near, options(), OrderType::GTC)
+        .await
+        .unwrap();
+    assert_eq!(log.len(), 1);
+}
+
+#[tokio::test]
+async fn test_spread_guard_rejects_crossing_limits() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "market": "0xmarket", "asset_id": "123", "timestamp": "0", "hash": "",
+            "bids": [{"price": "0.48", "size": "10"}, {"price": "0.50", "size": "10"}],
+            "asks": [{"price": "0.55", "size": "10"}, {"price": "0.53", "size": "10"}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let log = DryRunLog::new();
+    let client = risk_client(mock_server.uri(), RiskConfig::new(), &log)
+        .with_spread_guard(SpreadGuard::new(0.01));
+    let orders = client.orders().unwrap();
+
+    // the book is fetched once and reused for the following checks
+    let through_ask = OrderArgs::new("123", 0.55, 10.0, Side::Buy);
+    let err = orders
+        .create_and_post_order(&through_ask, options(), OrderType::GTC)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, ClobError::RiskLimit { message } if message.contains("best ask of 0.53"))
+    );
+    let through_bid = OrderArgs::new("123", 0.48, 10.0, Side::Sell);
+    assert!(orders
+        .create_and_post_order(&through_bid, options(), OrderType::GTC)
+        .await
+        .is_err());
+
+    // within tolerance, and market orders that mean to cross
+    let at_ask = OrderArgs::new("123", 0.54, 10.0, Side::Buy);
+    orders
+        .create_and_post_order(&at_ask, options(), OrderType::GTC)
+        .await
+        .unwrap();
+    orders
+        .create_and_post_order(&through_ask, options(), OrderType::FAK)
+        .await
+        .unwrap();
+    assert_eq!(log.len(), 2);
+
+    let mut warn_only = orders.clone();
+    warn_only.set_spread_guard(Some(SpreadGuard::new(0.0).with_warn_only(true)));
+    warn_only
+        .create_and_post_order(&through_ask, options(), OrderType::GTC)
+        .await
+        .unwrap();
+    assert_eq!(log.len(), 3);
+}
+
+#[tokio::test]
+async fn test_position_and_open_order_limits() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/balance-allowance"))
+        .and(query_param("asset_type", "CONDITIONAL"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "balance": "80000000",
+            "allowances": {}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "0x1", "asset_id": "123", "side": "BUY", "price": "0.4", "original_size": "10"},
+            {"id": "0x2", "asset_id": "123", "side": "SELL", "price": "0.6", "original_size": "10"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let log = DryRunLog::new();
+    let client = risk_client(
+        mock_server.uri(),
+        RiskConfig::new().with_max_position(100.0),
+        &log,
+    );
+    let orders = client.orders().unwrap();
+
+    let too_big = OrderArgs::new("123", 0.5, 30.0, Side::Buy);
+    let err = orders
+        .create_and_post_order(&too_big, options(), OrderType::GTC)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("would reach 110"));
+
+    let ok = OrderArgs::new("123", 0.5, 20.0, Side::Buy);
+    orders
+        .create_and_post_order(&ok, options(), OrderType::GTC)
+        .await
+        .unwrap();
+
+    let client = risk_client(
+        mock_server.uri(),
+        RiskConfig::new().with_max_open_orders(2),
+        &log,
+    );
+    let err = client
+        .orders()
+        .unwrap()
+        .create_and_post_order(&ok, options(), OrderType::GTC)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClobError::RiskLimit { .. }));
+    assert_eq!(log.len(), 1);
+}
+
+#[tokio::test]
+async fn test_accepting_orders_check() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .and(query_param("token_id", "123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "market": "0xpaused",
+            "asset_id": "123",
+            "timestamp": "0",
+            "hash": "h"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/markets/0xpaused"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "condition_id": "0xpaused",
+            "question_id": "0xq",
+            "tokens": [{"token_id": "123", "outcome": "Yes", "price": 0.5}],
+            "active": true,
+            "closed": false,
+            "accepting_orders": false
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/markets/0xlater"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "condition_id": "0xlater",
+            "question_id": "0xq",
+            "tokens": [],
+            "active": true,
+            "closed": false,
+            "accepting_orders": true,
+            "accepting_order_timestamp": "2999-01-01T00:00:00Z"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let log = DryRunLog::new();
+    let client =
+        risk_client(mock_server.uri(), RiskConfig::new(), &log).with_accepting_orders_check(true);
+    let markets = client.markets();
+    assert!(!markets.is_market_accepting_orders("123").await.unwrap());
+    assert!(!markets.is_market_accepting_orders("0xlater").await.unwrap());
+    let err = markets
+        .ensure_accepting_orders("0xlater")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("accepted from 2999-01-01"));
+
+    let err = client
+        .orders()
+        .unwrap()
+        .create_and_post_order(
+            &OrderArgs::new("123", 0.5, 10.0, Side::Buy),
+            options(),
+            OrderType::GTC,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClobError::NotAcceptingOrders { ref market, .. } if market == "0xpaused"
+    ));
+    assert!(log.is_empty());
+}
+
+#[tokio::test]
+async fn test_balance_and_allowance_preflight() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/balance-allowance"))
+        .and(query_param("asset_type", "COLLATERAL"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "balance": "10000000",
+            "allowances": {}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/balance-allowance"))
+        .and(query_param("asset_type", "CONDITIONAL"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "balance": "100000000",
+            "allowances": {"0x4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e": "5000000"}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/neg-risk"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"neg_risk": false})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let log = DryRunLog::new();
+    let client = risk_client(mock_server.uri(), RiskConfig::new(), &log).with_balance_check(true);
+    let orders = client.orders().unwrap();
+
+    let buy = OrderArgs::new("123", 0.5, 40.0, Side::Buy);
+    let err = orders
+        .create_and_post_order(&buy, options(), OrderType::GTC)
+        .await
+        .unwrap_err();
+    match err {
+        ClobError::InsufficientBalance {
+            needed, available, ..
+        } => {
+            assert_eq!(needed, 20.0);
+            assert_eq!(available, 10.0);
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let sell = OrderArgs::new("123", 0.5, 10.0, Side::Sell);
+    let err = orders
+        .create_and_post_order(&sell, options(), OrderType::GTC)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ClobError::InsufficientAllowance { available, .. } if available == 5.0
+    ));
+    assert!(log.is_empty());
+
+    let small = OrderArgs::new("123", 0.5, 4.0, Side::Sell);
+    orders
+        .create_and_post_order(&small, options(), OrderType::GTC)
+        .await
+        .unwrap();
+    assert_eq!(log.len(), 1);
+
+    // an amount that doesn't parse fails the check instead of needing nothing
+    let mut garbled = orders.create_order(&small, options()).await.unwrap();
+    garbled.maker_amount = "4e6".to_string();
+    let err = orders.check_balance(&garbled).await.unwrap_err();
+    assert!(matches!(err, ClobError::InvalidParameter(_)), "{err}");
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "http")]
+
+use std::time::Duration;
+
+use clob_rs::{ApiCreds, ClobClient, ClobError, KillSwitch, MarketSource, ShutdownPolicy};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+const HOUR: Duration = Duration::from_secs(3600);
+
+#[tokio::test]
+async fn test_shutdown_stops_tracked_tasks() {
+    let mock_server = MockServer::start().await;
+    let client = ClobClient::new(mock_server.uri());
+
+    let keep_alive = client.spawn_keep_alive(HOUR);
+    let time_sync = client.spawn_time_sync(HOUR);
+    let own = tokio::spawn(std::future::pending::<()>());
+    client.track_task(&own);
+    // services share the client's registry
+    let cache_refresh = client
+        .market_data()
+        .spawn_market_cache_refresh(MarketSource::Markets, HOUR);
+
+    let report = client
+        .shutdown(ShutdownPolicy::keep_orders())
+        .await
+        .unwrap();
+    assert_eq!(report.tasks_stopped, 4);
+    assert!(report.drained);
+    assert!(report.cancelled.is_none());
+    assert!(report.is_clean());
+    for task in [keep_alive, time_sync, own, cache_refresh] {
+        assert!(task.await.unwrap_err().is_cancelled());
+    }
+
+    // nothing left to stop
+    let report = client.shutdown(ShutdownPolicy::default()).await.unwrap();
+    assert_eq!(report.tasks_stopped, 0);
+}
+
+#[tokio::test]
+async fn test_shutdown_cancels_orders_when_asked() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/cancel-all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "canceled": ["0x1"],
+            "not_canceled": {}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&mock_server)
+        .await;
+
+    // without a signer there is nothing to cancel with, so nothing is stopped
+    let unsigned = ClobClient::new(mock_server.uri());
+    let task = unsigned.spawn_keep_alive(HOUR);
+    assert!(matches!(
+        unsigned.shutdown(ShutdownPolicy::cancel_orders()).await,
+        Err(ClobError::AuthRequired(_))
+    ));
+    assert!(!task.is_finished());
+    task.abort();
+
+    let client = ClobClient::new(mock_server.uri())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        });
+    let policy = ShutdownPolicy::keep_orders().with_kill_switch(
+        KillSwitch::new()
+            .with_attempts(2)
+            .with_retry_delay(Duration::from_millis(1)),
+    );
+    let report = client.shutdown(policy).await.unwrap();
+    let cancelled = report.cancelled.as_ref().unwrap();
+    assert_eq!(cancelled.canceled, vec!["0x1"]);
+    assert!(report.is_clean());
+}
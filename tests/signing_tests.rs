@@ -1,5 +1,8 @@
-use clob_rs::signing::eip712::sign_clob_auth_message;
-use clob_rs::Signer;
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::SolStruct;
+use clob_rs::order_builder::{exchange_domain, sign_typed_order};
+use clob_rs::signing::eip712::{sign_clob_auth_message, sign_clob_auth_message_with};
+use clob_rs::{CreateOrderOptions, FixedSalt, OrderArgs, OrderBuilder, Side, Signer, TickSize};
 
 const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
 const EXPECTED_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
@@ -62,3 +65,44 @@ async fn test_sign_clob_auth_message_different_nonces() {
 
     assert_ne!(sig1, sig2);
 }
+
+#[tokio::test]
+async fn test_clob_auth_typed_data_signing() {
+    let signer = Signer::new(TEST_PRIVATE_KEY, POLYGON_CHAIN_ID).unwrap();
+    let signature = sign_clob_auth_message(&signer, 1000000, 0).await.unwrap();
+    assert_eq!(signature, "0x7c7eacdfc2d48b9e5f640f22827f3213c85eae0bcaa513234102d2403727c6111adc304f10102d4381ab30dbac63d332b65fcbaf764565226030fc05e848fb801c");
+
+    let wallet: PrivateKeySigner = TEST_PRIVATE_KEY[2..].parse().unwrap();
+    let via_wallet = sign_clob_auth_message_with(&wallet, POLYGON_CHAIN_ID, 1000000, 0)
+        .await
+        .unwrap();
+    assert_eq!(via_wallet, signature);
+}
+
+#[tokio::test]
+async fn test_order_typed_data_matches_builder() {
+    let builder = OrderBuilder::new(Signer::new(TEST_PRIVATE_KEY, POLYGON_CHAIN_ID).unwrap())
+        .with_salt_provider(std::sync::Arc::new(FixedSalt(1234)));
+    let options = CreateOrderOptions {
+        tick_size: TickSize::Size0_01,
+        neg_risk: true,
+        salt: None,
+    };
+    let signed = builder
+        .create_order(&OrderArgs::new("123", 0.5, 10.0, Side::Buy), &options)
+        .await
+        .unwrap();
+
+    let typed = signed.typed_data().unwrap();
+    let domain = exchange_domain(POLYGON_CHAIN_ID, true).unwrap();
+    assert_eq!(
+        typed.eip712_signing_hash(&domain),
+        signed.hash(POLYGON_CHAIN_ID, true).unwrap()
+    );
+
+    let wallet: PrivateKeySigner = TEST_PRIVATE_KEY[2..].parse().unwrap();
+    let via_wallet = sign_typed_order(&wallet, &typed, POLYGON_CHAIN_ID, true)
+        .await
+        .unwrap();
+    assert_eq!(via_wallet, signed);
+}
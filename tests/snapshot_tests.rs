@@ -0,0 +1,77 @@
+use clob_rs::{OrderBook, OrderSummary};
+
+fn book() -> OrderBook {
+    let level = |price: &str, size: &str| OrderSummary {
+        price: price.to_string(),
+        size: size.to_string(),
+    };
+    OrderBook {
+        market: "0xmarket".to_string(),
+        asset_id: "123".to_string(),
+        timestamp: "1700000000000".to_string(),
+        hash: "0xhash".to_string(),
+        bids: vec![level("0.48", "100"), level("0.47", "12.5")],
+        asks: vec![level("0.5", "20")],
+        min_order_size: Some("5".to_string()),
+        tick_size: Some("0.01".to_string()),
+        neg_risk: Some(false),
+    }
+}
+
+#[test]
+fn test_order_book_json_roundtrip() {
+    let book = book();
+    let json = serde_json::to_string(&book).unwrap();
+    assert_eq!(serde_json::from_str::<OrderBook>(&json).unwrap(), book);
+}
+
+#[cfg(feature = "binary")]
+#[test]
+fn test_binary_snapshots_roundtrip() {
+    use clob_rs::snapshot::{decode_snapshot, encode_snapshot, read_snapshot, write_snapshot};
+    use clob_rs::{Fill, Side, TradePrint};
+
+    let book = book();
+    let bytes = encode_snapshot(&book).unwrap();
+    assert!(bytes.len() < serde_json::to_vec(&book).unwrap().len());
+    assert_eq!(decode_snapshot::<OrderBook>(&bytes).unwrap(), book);
+
+    // wrong kind and unknown version are rejected
+    assert!(decode_snapshot::<Fill>(&bytes).is_err());
+    let mut newer = bytes.clone();
+    newer[0] = 99;
+    let err = decode_snapshot::<OrderBook>(&newer).unwrap_err();
+    assert!(err.to_string().contains("version 99"), "{}", err);
+
+    let print = TradePrint {
+        token_id: "123".to_string(),
+        price: 0.51,
+        size: 40.0,
+        side: Side::Sell,
+        timestamp: 1_700_000_000_000,
+    };
+    let fill = Fill {
+        id: "f1".to_string(),
+        token_id: "123".to_string(),
+        side: Side::Buy,
+        price: 0.5,
+        size: 10.0,
+        fee: 0.1,
+    };
+    let mut stream = Vec::new();
+    write_snapshot(&mut stream, &print).unwrap();
+    write_snapshot(&mut stream, &print).unwrap();
+    write_snapshot(&mut stream, &fill).unwrap();
+
+    let mut reader = stream.as_slice();
+    assert_eq!(
+        read_snapshot::<TradePrint, _>(&mut reader).unwrap(),
+        Some(print.clone())
+    );
+    assert_eq!(
+        read_snapshot::<TradePrint, _>(&mut reader).unwrap(),
+        Some(print)
+    );
+    assert_eq!(read_snapshot::<Fill, _>(&mut reader).unwrap(), Some(fill));
+    assert_eq!(read_snapshot::<Fill, _>(&mut reader).unwrap(), None);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use clob_rs::{Fill, JsonFileStore, ManagedOrder, MemoryStore, Portfolio, Side, StateStore};
+
+use common::temp_dir;
+
+fn order(order_id: &str) -> ManagedOrder {
+    ManagedOrder {
+        order_id: order_id.to_string(),
+        salt: Some("42".to_string()),
+        token_id: "123".to_string(),
+        side: Side::Buy,
+        price: 0.4,
+        size: 10.0,
+        order_type: None,
+        expiration: None,
+    }
+}
+
+fn fill(id: &str, side: Side, price: f64) -> Fill {
+    Fill {
+        id: id.to_string(),
+        token_id: "123".to_string(),
+        side,
+        price,
+        size: 10.0,
+        fee: 0.0,
+    }
+}
+
+// Writes through `store`, then checks what `reopen` reads back.
+fn exercise_store<S: StateStore>(mut store: S, reopen: impl Fn() -> S) {
+    store.save_order(&order("0xa")).unwrap();
+    store.save_order(&order("0xb")).unwrap();
+    store.remove_order("0xa").unwrap();
+
+    let mut portfolio = Portfolio::new();
+    assert!(portfolio
+        .record_fill(&fill("f1", Side::Buy, 0.4), &mut store)
+        .unwrap());
+    assert!(!portfolio
+        .record_fill(&fill("f1", Side::Buy, 0.4), &mut store)
+        .unwrap());
+    portfolio
+        .record_fill(&fill("f2", Side::Sell, 0.5), &mut store)
+        .unwrap();
+    store.save_fill(&fill("f2", Side::Sell, 0.5)).unwrap();
+
+    let store = reopen();
+    assert_eq!(store.load_orders().unwrap(), vec![order("0xb")]);
+    let ids: Vec<String> = store
+        .load_fills()
+        .unwrap()
+        .into_iter()
+        .map(|f| f.id)
+        .collect();
+    assert_eq!(ids, vec!["f1", "f2"]);
+
+    let restored = Portfolio::restore(&store).unwrap();
+    assert_eq!(restored.snapshot(), portfolio.snapshot());
+    assert!((restored.snapshot().realized_pnl - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_memory_store() {
+    let mut store = MemoryStore::new();
+    store.save_order(&order("0xa")).unwrap();
+    store.save_order(&order("0xa")).unwrap();
+    assert_eq!(store.load_orders().unwrap().len(), 1);
+}
+
+#[test]
+fn test_json_file_store_persists() {
+    let path = temp_dir("state-store").join("state.json");
+    let _ = std::fs::remove_file(&path);
+
+    exercise_store(JsonFileStore::open(&path).unwrap(), || {
+        JsonFileStore::open(&path).unwrap()
+    });
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_store_persists() {
+    let path = temp_dir("state-store").join("state.db");
+    let _ = std::fs::remove_file(&path);
+
+    exercise_store(clob_rs::SqliteStore::open(&path).unwrap(), || {
+        clob_rs::SqliteStore::open(&path).unwrap()
+    });
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "http")]
+
+use std::time::Duration;
+
+use clob_rs::strategy::{Action, ActionResult, Context, Strategy, StrategyEvent, StrategyRunner};
+use clob_rs::{
+    ApiCreds, ClobClient, DryRunLog, Fill, OrderArgs, OrderBook, OrderSummary, OrderType, Result,
+    Side,
+};
+use futures::{stream, StreamExt};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn book(bid: &str, ask: &str) -> OrderBook {
+    OrderBook {
+        market: "0xmarket".to_string(),
+        asset_id: "123".to_string(),
+        timestamp: "0".to_string(),
+        hash: "".to_string(),
+        bids: vec![OrderSummary {
+            price: bid.to_string(),
+            size: "10".to_string(),
+        }],
+        asks: vec![OrderSummary {
+            price: ask.to_string(),
+            size: "10".to_string(),
+        }],
+        min_order_size: None,
+        tick_size: None,
+        neg_risk: None,
+    }
+}
+
+fn fill(id: &str) -> Fill {
+    Fill {
+        id: id.to_string(),
+        token_id: "123".to_string(),
+        side: Side::Buy,
+        price: 0.4,
+        size: 5.0,
+        fee: 0.0,
+    }
+}
+
+// Joins the bid on the first book, cancels everything after a fill and
+// stops on the first timer tick.
+#[derive(Default)]
+struct JoinBid {
+    placed: Vec<String>,
+    fills: usize,
+    ticks: usize,
+}
+
+impl Strategy for JoinBid {
+    fn on_book_update(&mut self, book: &OrderBook, ctx: &mut Context) {
+        if self.placed.is_empty() {
+            let bid = book.bids[0].price.parse().unwrap();
+            ctx.place(OrderArgs::new("123", bid, 5.0, Side::Buy), OrderType::GTC);
+        }
+    }
+
+    fn on_fill(&mut self, _fill: &Fill, ctx: &mut Context) {
+        self.fills += 1;
+        assert_eq!(ctx.portfolio().position("123").unwrap().size, 5.0);
+        ctx.cancel_all();
+    }
+
+    fn on_timer(&mut self, ctx: &mut Context) {
+        self.ticks += 1;
+        ctx.stop();
+    }
+
+    fn on_action_result(
+        &mut self,
+        _action: &Action,
+        result: &Result<ActionResult>,
+        _ctx: &mut Context,
+    ) {
+        if let Ok(ActionResult::Placed(response)) = result {
+            self.placed.push(response.order_id.clone());
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_runner_executes_strategy_actions() {
+    let mock_server = MockServer::start().await;
+    for (endpoint, body) in [
+        ("/tick-size", serde_json::json!({"minimum_tick_size": 0.01})),
+        ("/neg-risk", serde_json::json!({"neg_risk": false})),
+        ("/fee-rate", serde_json::json!({"base_fee": 0})),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&mock_server)
+            .await;
+    }
+
+    let log = DryRunLog::new();
+    let client = ClobClient::new(mock_server.uri())
+        .with_dry_run(log.clone())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        });
+
+    let events = stream::iter(vec![
+        StrategyEvent::Book(book("0.4", "0.6")),
+        StrategyEvent::Book(book("0.41", "0.6")),
+        StrategyEvent::Fill(fill("f1")),
+        StrategyEvent::Fill(fill("f1")),
+    ])
+    .chain(stream::pending());
+    let mut runner = StrategyRunner::new(JoinBid::default(), client.orders().unwrap().clone())
+        .with_timer(Duration::from_millis(50));
+    let summary = runner.run(events).await.unwrap();
+
+    assert_eq!(summary.events, 4);
+    assert_eq!(summary.timer_ticks, 1);
+    assert_eq!(summary.actions, 2);
+    assert_eq!(summary.failed_actions, 0);
+    assert_eq!(runner.portfolio().snapshot().positions[0].mark, Some(0.505));
+
+    let strategy = runner.into_strategy();
+    assert_eq!(strategy.placed, vec!["dry-run-1"]);
+    assert_eq!(strategy.fills, 1);
+
+    let requests = log.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].path, "/order");
+    assert_eq!(requests[0].body.as_ref().unwrap()["order"]["side"], "BUY");
+    assert_eq!(requests[1].method, "DELETE");
+}
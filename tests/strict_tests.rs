@@ -0,0 +1,128 @@
+#![cfg(feature = "http")]
+
+use clob_rs::{AuthApi, ClobClient, ClobError, Signer, StrictDecoding, Transport};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+async fn mount_book(mock_server: &MockServer, book: serde_json::Value) {
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(book))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_strict_decoding_reports_unknown_and_missing_fields() {
+    let mock_server = MockServer::start().await;
+    mount_book(
+        &mock_server,
+        serde_json::json!({
+            "market": "0xmarket",
+            "asset_id": "123",
+            "timestamp": "0",
+            "hash": "",
+            "bids": [{"price": "0.4", "size": "10", "orders": 3}],
+            "asks": [],
+            "min_order_size": "5",
+            "tick_size": "0.01",
+            "last_trade_price": "0.41"
+        }),
+    )
+    .await;
+
+    // lenient by default
+    let client = ClobClient::new(mock_server.uri());
+    assert_eq!(
+        client
+            .prices()
+            .get_order_book("123")
+            .await
+            .unwrap()
+            .bids
+            .len(),
+        1
+    );
+
+    let strict = client.with_strict_decoding(StrictDecoding::Fail);
+    match strict.prices().get_order_book("123").await {
+        Err(ClobError::SchemaDrift {
+            endpoint,
+            unknown,
+            missing,
+        }) => {
+            assert_eq!(endpoint, "GET /book?token_id=123");
+            assert_eq!(unknown, vec!["bids[0].orders", "last_trade_price"]);
+            assert_eq!(missing, vec!["neg_risk"]);
+        }
+        other => panic!("expected schema drift, got {:?}", other),
+    }
+
+    let logged = strict.with_strict_decoding(StrictDecoding::Log);
+    let book = logged.prices().get_order_book("123").await.unwrap();
+    assert_eq!(book.bids[0].price, "0.4");
+    assert_eq!(book.min_order_size.as_deref(), Some("5"));
+}
+
+#[tokio::test]
+async fn test_strict_decoding_accepts_exact_schema() {
+    let mock_server = MockServer::start().await;
+    mount_book(
+        &mock_server,
+        serde_json::json!({
+            "market": "0xmarket",
+            "asset_id": "123",
+            "timestamp": "0",
+            "hash": "",
+            "bids": [],
+            "asks": [{"price": "0.6", "size": "100"}],
+            "min_order_size": "5",
+            "tick_size": "0.01",
+            "neg_risk": false
+        }),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/midpoint"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"mid": "0.55"})))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri()).with_strict_decoding(StrictDecoding::Fail);
+    let book = client.prices().get_order_book("123").await.unwrap();
+    assert_eq!(book.asks[0].size, "100");
+    assert_eq!(client.prices().get_midpoint("123").await.unwrap(), 0.55);
+
+    // decode errors still name the field
+    let other = MockServer::start().await;
+    mount_book(&other, serde_json::json!({"market": 1})).await;
+    let client = ClobClient::new(other.uri()).with_strict_decoding(StrictDecoding::Fail);
+    match client.prices().get_order_book("123").await {
+        Err(ClobError::Decode { path, .. }) => assert_eq!(path, "market"),
+        other => panic!("expected decode error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_strict_decoding_accepts_either_name_of_aliased_fields() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/auth/derive-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "apiKey": "derived-key",
+            "api_secret": "c2VjcmV0",
+            "passphrase": "pass"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let signer = Signer::new(TEST_PRIVATE_KEY, 137).unwrap();
+    let transport = Transport::new(mock_server.uri()).with_strict_decoding(StrictDecoding::Fail);
+    let creds = AuthApi::new(transport, signer)
+        .derive_api_key(None)
+        .await
+        .unwrap();
+    assert_eq!(creds.api_key, "derived-key");
+}
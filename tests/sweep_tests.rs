@@ -0,0 +1,161 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use clob_rs::{plan_sweep, ApiCreds, ClobClient, ClobError, OrderBook, Side, SweepLimit};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::level;
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn test_book() -> OrderBook {
+    OrderBook {
+        market: "0xmarket".to_string(),
+        asset_id: "123".to_string(),
+        timestamp: "0".to_string(),
+        hash: "".to_string(),
+        // deliberately not best-first
+        bids: vec![
+            level("0.45", "50"),
+            level("0.48", "10"),
+            level("0.47", "20"),
+        ],
+        asks: vec![
+            level("0.55", "30"),
+            level("0.52", "10"),
+            level("0.53", "20"),
+        ],
+        min_order_size: None,
+        tick_size: Some("0.01".to_string()),
+        neg_risk: Some(false),
+    }
+}
+
+#[test]
+fn test_plan_sweep_buy_levels() {
+    let plan = plan_sweep(&test_book(), Side::Buy, SweepLimit::Levels(2), 0.60)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(plan.levels, 2);
+    assert_eq!(plan.price, 0.53);
+    assert_eq!(plan.size, 30.0);
+    assert!((plan.expected_avg_price - (0.52 * 10.0 + 0.53 * 20.0) / 30.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_plan_sweep_sell_respects_limit_price() {
+    let plan = plan_sweep(&test_book(), Side::Sell, SweepLimit::Levels(10), 0.47)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(plan.levels, 2);
+    assert_eq!(plan.price, 0.47);
+    assert_eq!(plan.size, 30.0);
+}
+
+#[test]
+fn test_plan_sweep_partial_size() {
+    let plan = plan_sweep(&test_book(), Side::Buy, SweepLimit::Size(15.0), 1.0)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(plan.levels, 2);
+    assert_eq!(plan.price, 0.53);
+    assert_eq!(plan.size, 15.0);
+}
+
+#[test]
+fn test_plan_sweep_no_liquidity_within_limit() {
+    assert!(
+        plan_sweep(&test_book(), Side::Buy, SweepLimit::Levels(1), 0.50)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_sweep_rejects_non_positive_limit() {
+    for limit in [
+        SweepLimit::Levels(0),
+        SweepLimit::Size(0.0),
+        SweepLimit::Size(-5.0),
+        SweepLimit::Size(f64::NAN),
+    ] {
+        let err = plan_sweep(&test_book(), Side::Buy, limit, 1.0).unwrap_err();
+        assert!(
+            matches!(&err, ClobError::InvalidParameter(msg) if msg == "sweep limit must be positive")
+        );
+    }
+
+    let mock_server = MockServer::start().await;
+    let client = ClobClient::new(mock_server.uri())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        });
+    let err = client
+        .orders()
+        .unwrap()
+        .sweep("123", Side::Sell, SweepLimit::Levels(0), 0.40)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClobError::InvalidParameter(_)));
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_sweep_posts_fak_and_reports_avg_price() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/book"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(test_book()))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/fee-rate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"base_fee": 0})))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/order"))
+        .and(body_partial_json(serde_json::json!({"orderType": "FAK"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "orderID": "0xsweep",
+            "makingAmount": "5.24",
+            "takingAmount": "10",
+            "status": "MATCHED"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClobClient::new(mock_server.uri())
+        .with_signer(TEST_PRIVATE_KEY)
+        .unwrap()
+        .with_creds(ApiCreds {
+            api_key: "test-api-key".to_string(),
+            api_secret: "dGVzdC1hcGktc2VjcmV0".into(),
+            api_passphrase: "test-passphrase".into(),
+        });
+
+    let result = client
+        .orders()
+        .unwrap()
+        .sweep("123", Side::Buy, SweepLimit::Levels(2), 0.60)
+        .await
+        .unwrap();
+
+    assert_eq!(result.plan.size, 30.0);
+    assert_eq!(result.order.taker_amount, "30000000");
+    assert_eq!(result.response.order_id, "0xsweep");
+    assert!((result.avg_price.unwrap() - 0.524).abs() < 1e-12);
+}
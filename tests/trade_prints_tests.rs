@@ -0,0 +1,91 @@
+#![cfg(feature = "http")]
+
+use clob_rs::{MarketDataApi, Side, TradePrint, TradePrints, Transport};
+use futures::{stream, StreamExt};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn last_trade(token_id: &str, price: &str, timestamp: &str) -> String {
+    serde_json::json!({
+        "event_type": "last_trade_price",
+        "asset_id": token_id,
+        "market": "0xmarket",
+        "price": price,
+        "size": "5",
+        "side": "BUY",
+        "fee_rate_bps": "0",
+        "timestamp": timestamp
+    })
+    .to_string()
+}
+
+#[test]
+fn test_parse_market_message() {
+    let frame = format!(
+        r#"[{{"event_type":"price_change","asset_id":"1"}},{}]"#,
+        last_trade("1", "0.45", "1700000000123")
+    );
+    let prints = TradePrint::parse_market_message(&frame).unwrap();
+
+    assert_eq!(
+        prints,
+        vec![TradePrint {
+            token_id: "1".to_string(),
+            price: 0.45,
+            size: 5.0,
+            side: Side::Buy,
+            timestamp: 1_700_000_000_123,
+        }]
+    );
+    assert!(TradePrint::parse_market_message("PONG").unwrap().is_empty());
+    assert!(TradePrint::parse_market_message("{").is_err());
+}
+
+#[tokio::test]
+async fn test_subscribe_backfills_then_streams_live_prints() {
+    let mock_server = MockServer::start().await;
+    let event = |token_id: &str, price: f64, timestamp: &str| {
+        serde_json::json!({
+            "id": timestamp,
+            "type": "TRADE",
+            "timestamp": timestamp,
+            "token_id": token_id,
+            "side": "BUY",
+            "price": price,
+            "size": 5
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/live-activity/events/0xmarket"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+            event("1", 0.5, "1700000002"),
+            event("2", 0.5, "1700000001"),
+            event("1", 0.4, "1700000001"),
+        ]))
+        .mount(&mock_server)
+        .await;
+
+    let frames = stream::iter(vec![
+        // already delivered by the backfill
+        last_trade("1", "0.5", "1700000002500"),
+        last_trade("2", "0.55", "1700000003000"),
+        last_trade("1", "0.6", "1700000003000"),
+    ]);
+    let market_data = MarketDataApi::new(Transport::new(mock_server.uri()));
+    let prints: Vec<TradePrint> = TradePrints::new(market_data, ["1"])
+        .with_backfill("0xmarket")
+        .subscribe(frames)
+        .map(|p| p.unwrap())
+        .collect()
+        .await;
+
+    let summary: Vec<(f64, u64)> = prints.iter().map(|p| (p.price, p.timestamp)).collect();
+    assert_eq!(
+        summary,
+        vec![
+            (0.4, 1_700_000_001_000),
+            (0.5, 1_700_000_002_000),
+            (0.6, 1_700_000_003_000)
+        ]
+    );
+}
@@ -0,0 +1,80 @@
+use clob_rs::{OrderArgs, Price, Side, Size, TickSize};
+
+#[test]
+fn test_price_must_be_on_tick_and_in_range() {
+    assert_eq!(Price::new(0.57, TickSize::Size0_01).unwrap().value(), 0.57);
+    assert_eq!(Price::new(0.57, TickSize::Size0_01).unwrap().ticks(), 57);
+    assert!(Price::new(0.575, TickSize::Size0_01).is_err());
+    assert!(Price::new(0.0, TickSize::Size0_01).is_err());
+    assert!(Price::new(1.0, TickSize::Size0_01).is_err());
+    assert!(Price::new(f64::NAN, TickSize::Size0_01).is_err());
+
+    let rounded = Price::round_to_tick(0.5749, TickSize::Size0_01).unwrap();
+    assert_eq!(rounded.value(), 0.57);
+    assert!(Price::round_to_tick(0.996, TickSize::Size0_01).is_err());
+
+    assert_eq!(Price::clamped(1.2, TickSize::Size0_01).value(), 0.99);
+    assert_eq!(Price::clamped(-3.0, TickSize::Size0_001).value(), 0.001);
+    assert_eq!(Price::clamped(f64::NAN, TickSize::Size0_1).value(), 0.1);
+}
+
+#[test]
+fn test_price_arithmetic_and_ordering() {
+    let price = Price::new(0.5, TickSize::Size0_01).unwrap();
+    assert_eq!(price.offset_ticks(3).unwrap().value(), 0.53);
+    assert_eq!(price.offset_ticks(-49).unwrap().value(), 0.01);
+    assert!(price.offset_ticks(-50).is_none());
+    assert_eq!(
+        Price::new(0.37, TickSize::Size0_01)
+            .unwrap()
+            .complement()
+            .value(),
+        0.63
+    );
+
+    let coarse = Price::new(0.5, TickSize::Size0_1).unwrap();
+    assert_eq!(coarse, price);
+    let low = Price::new(0.2, TickSize::Size0_1).unwrap();
+    let high = Price::new(0.8, TickSize::Size0_1).unwrap();
+    let fine = Price::new(0.951, TickSize::Size0_001).unwrap();
+    assert_eq!(fine.clamp(low, high), high);
+    assert_eq!(
+        fine.with_tick_size(TickSize::Size0_01).unwrap().value(),
+        0.95
+    );
+    assert_eq!(fine.to_string(), "0.951");
+
+    let size = Size::new(12.5).unwrap();
+    assert!((price * size - 6.25).abs() < 1e-12);
+}
+
+#[test]
+fn test_size_precision() {
+    assert_eq!(Size::new(10.25).unwrap().value(), 10.25);
+    assert!(Size::new(10.255).is_err());
+    assert!(Size::new(0.0).is_err());
+    assert!(Size::new(-1.0).is_err());
+    assert_eq!(Size::round_down(10.259).unwrap().value(), 10.25);
+    assert!(Size::round_down(0.009).is_err());
+
+    let a = Size::new(3.0).unwrap();
+    let b = Size::new(1.25).unwrap();
+    assert_eq!((a + b).value(), 4.25);
+    assert_eq!(a.checked_sub(b).unwrap().value(), 1.75);
+    assert!(b.checked_sub(a).is_none());
+    assert!(a.checked_sub(a).is_none());
+}
+
+#[test]
+fn test_order_args_from_units() {
+    let args = OrderArgs::from_units(
+        "123",
+        Price::new(0.42, TickSize::Size0_01).unwrap(),
+        Size::new(15.0).unwrap(),
+        Side::Buy,
+    );
+    assert_eq!(args.price, 0.42);
+    assert_eq!(args.size, 15.0);
+    assert!(args.checked_price(TickSize::Size0_01).is_ok());
+    assert!(args.checked_price(TickSize::Size0_1).is_err());
+}
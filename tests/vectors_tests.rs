@@ -0,0 +1,137 @@
+use std::path::Path;
+
+use alloy_primitives::PrimitiveSignature;
+use alloy_sol_types::SolStruct;
+use clob_rs::order_builder::exchange_domain;
+use clob_rs::vectors::{load_vectors, ExpectedOrder, OrderVector, VectorFile};
+use clob_rs::Side;
+use futures::executor::block_on;
+use proptest::prelude::*;
+
+const TEST_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const EXPECTED_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+fn vector(side: Side, price: f64, size: f64) -> OrderVector {
+    OrderVector {
+        name: format!("{} {} @ {}", side, size, price),
+        private_key: TEST_PRIVATE_KEY.to_string(),
+        chain_id: 137,
+        signature_type: 0,
+        funder: None,
+        tick_size: "0.01".to_string(),
+        neg_risk: false,
+        token_id: "123".to_string(),
+        side,
+        price,
+        size,
+        market: false,
+        salt: 1234,
+        nonce: 0,
+        expiration: 0,
+        fee_rate_bps: 0,
+        expected: ExpectedOrder::default(),
+    }
+}
+
+// every file in tests/vectors, so a generated file is picked up by
+// dropping it in
+#[test]
+fn test_vector_files() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+    let mut checked = 0;
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let file = load_vectors(&path).unwrap();
+        let mismatches: Vec<String> = file
+            .vectors
+            .iter()
+            .flat_map(|vector| block_on(vector.check()).unwrap())
+            .map(|mismatch| mismatch.to_string())
+            .collect();
+        assert!(
+            mismatches.is_empty(),
+            "{}:\n{}",
+            path.display(),
+            mismatches.join("\n")
+        );
+        checked += file.vectors.len();
+    }
+    assert!(checked > 0);
+}
+
+// the reference vectors can only be written where py-clob-client installs:
+// python scripts/generate_vectors.py -n 2000 > tests/vectors/py_clob_client.json
+#[test]
+#[ignore = "needs tests/vectors/py_clob_client.json from scripts/generate_vectors.py"]
+fn test_py_clob_client_vectors_present() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors/py_clob_client.json");
+    let file = load_vectors(&path).unwrap();
+    let source = file.source.unwrap_or_default();
+    assert!(source.starts_with("py-clob-client "), "{}", source);
+    assert!(file.vectors.len() >= 1000);
+}
+
+#[test]
+fn test_vector_mismatches_are_reported() {
+    let mut vector = vector(Side::Buy, 0.5, 10.0);
+    vector.expected = ExpectedOrder {
+        maker_amount: Some("5000000".to_string()),
+        taker_amount: Some("10000001".to_string()),
+        hash: None,
+        signature: None,
+    };
+    let mismatches = block_on(vector.check()).unwrap();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].field, "taker_amount");
+    assert_eq!(mismatches[0].actual, "10000000");
+
+    vector.tick_size = "0.5".to_string();
+    assert!(block_on(vector.check()).is_err());
+}
+
+fn side() -> impl Strategy<Value = Side> {
+    prop_oneof![Just(Side::Buy), Just(Side::Sell)]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    // the hand-rolled EIP-712 encoding agrees with alloy's, the signature
+    // recovers the signer, and the order round-trips as a vector
+    #[test]
+    fn test_signed_orders_are_self_consistent(
+        side in side(),
+        ticks in 1u32..100,
+        cents in 1u64..10_000_000,
+        neg_risk in any::<bool>(),
+        market in any::<bool>(),
+        salt in any::<u32>(),
+    ) {
+        let mut vector = vector(side, ticks as f64 / 100.0, cents as f64 / 100.0);
+        vector.neg_risk = neg_risk;
+        vector.market = market;
+        vector.salt = salt as u64;
+        let order = block_on(vector.build()).unwrap();
+
+        let hash = order.hash(137, neg_risk).unwrap();
+        let domain = exchange_domain(137, neg_risk).unwrap();
+        prop_assert_eq!(order.typed_data().unwrap().eip712_signing_hash(&domain), hash);
+        let signature: PrimitiveSignature = order.signature.parse().unwrap();
+        let recovered = signature.recover_address_from_prehash(&hash).unwrap();
+        prop_assert_eq!(recovered.to_string(), EXPECTED_ADDRESS);
+
+        vector.expected = ExpectedOrder {
+            maker_amount: Some(order.maker_amount.clone()),
+            taker_amount: Some(order.taker_amount.clone()),
+            hash: Some(hash.to_string()),
+            signature: Some(order.signature.clone()),
+        };
+        let file = VectorFile { source: None, vectors: vec![vector] };
+        let json = serde_json::to_string(&file).unwrap();
+        let parsed: VectorFile = serde_json::from_str(&json).unwrap();
+        prop_assert!(block_on(parsed.vectors[0].check()).unwrap().is_empty());
+    }
+}